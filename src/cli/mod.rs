@@ -0,0 +1,299 @@
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use tokio::fs::File;
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+
+use crate::command::export_command::ExportCommand;
+use crate::command::import_command::ImportCommand;
+use crate::model::export_model::{ExportDataset, ExportFormat, ExportStream};
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::import_model::{ImportFormat, ImportKind};
+use crate::service::export_service::{ExportService, ExportServiceInterface};
+use crate::service::graph_sync_service::GraphSyncService;
+use crate::service::import_service::ImportService;
+use crate::service::reconciliation_service::ReconciliationService;
+use crate::shared::configuration::AppConfig;
+use crate::shared::database::{mongodb_migration, neo4j_schema};
+use crate::shared::logging::log;
+use crate::shared::state::AppState;
+
+
+pub const USAGE: &str = "usage: booknet-api-rust [serve | reconcile [--repair] | neo4j-schema | migrate [--dry-run] \
+| import <books|authors|ratings> <file> [--provider=good_reads|kaggle|amazon|google_books] [--format=csv|jsonl] \
+| export <books|authors|metadata|users|reviews|graph> [--format=jsonl|csv|graphml|cypher] [--output=<file>]]";
+
+
+/// What the binary was asked to do: run the servers, or a one-off maintenance job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Serve,
+    /// Reports drift between MongoDB and Neo4j, and with `--repair` fixes it through the graph-sync outbox.
+    Reconcile { repair: bool },
+    /// Applies the pending Neo4j schema versions, first merging nodes whose keys are duplicated.
+    /// The server applies them on startup too, but leaves a step with duplicated keys pending.
+    Neo4jSchema,
+    /// Runs the pending MongoDB migrations, or with `--dry-run` only reports what they would do.
+    Migrate { dry_run: bool },
+    /// Imports a Goodreads or Kaggle dump, then applies the graph changes it queued.
+    Import {
+        kind: ImportKind,
+        path: String,
+        provider: ExternalProvider,
+        format: Option<ImportFormat>,
+    },
+    /// Writes a dataset to `output`, or to stdout without one.
+    Export {
+        dataset: ExportDataset,
+        format: Option<ExportFormat>,
+        output: Option<String>,
+    },
+}
+
+impl CliCommand {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let (name, flags) = match args.split_first() {
+            Some((name, flags)) => (name.as_str(), flags),
+            None => return Ok(Self::Serve),
+        };
+
+        match name {
+            "serve" => {
+                expect_flags(flags, &[])?;
+                Ok(Self::Serve)
+            },
+            "reconcile" => {
+                expect_flags(flags, &["--repair"])?;
+                Ok(Self::Reconcile { repair: has_flag(flags, "--repair") })
+            },
+            "neo4j-schema" => {
+                expect_flags(flags, &[])?;
+                Ok(Self::Neo4jSchema)
+            },
+            "migrate" => {
+                expect_flags(flags, &["--dry-run"])?;
+                Ok(Self::Migrate { dry_run: has_flag(flags, "--dry-run") })
+            },
+            "import" => Self::parse_import(flags),
+            "export" => Self::parse_export(flags),
+            other => Err(anyhow!("Unknown command: {}\n{}", other, USAGE)),
+        }
+    }
+
+    fn parse_import(args: &[String]) -> Result<Self> {
+        let (options, positional): (Vec<&String>, Vec<&String>) = args.iter().partition(|arg| arg.starts_with("--"));
+        let (kind, path) = match positional.as_slice() {
+            [kind, path] => (kind.as_str(), path.to_string()),
+            _ => return Err(anyhow!("import expects a kind and a file\n{}", USAGE)),
+        };
+        let kind = ImportKind::from_name(kind).ok_or_else(|| anyhow!("Unknown import kind: {}\n{}", kind, USAGE))?;
+
+        let mut provider = ExternalProvider::GoodReads;
+        let mut format = None;
+        for option in options {
+            match option.split_once('=') {
+                Some(("--provider", name)) => {
+                    provider = ExternalProvider::from_field(name).ok_or_else(|| anyhow!("Unknown provider: {}\n{}", name, USAGE))?;
+                },
+                Some(("--format", name)) => {
+                    format = Some(ImportFormat::from_name(name).ok_or_else(|| anyhow!("Unknown format: {}\n{}", name, USAGE))?);
+                },
+                _ => return Err(anyhow!("Unknown option: {}\n{}", option, USAGE)),
+            }
+        }
+
+        Ok(Self::Import { kind, path, provider, format })
+    }
+
+    fn parse_export(args: &[String]) -> Result<Self> {
+        let (options, positional): (Vec<&String>, Vec<&String>) = args.iter().partition(|arg| arg.starts_with("--"));
+        let dataset = match positional.as_slice() {
+            [dataset] => dataset.as_str(),
+            _ => return Err(anyhow!("export expects a dataset\n{}", USAGE)),
+        };
+        let dataset = ExportDataset::from_name(dataset).ok_or_else(|| anyhow!("Unknown export dataset: {}\n{}", dataset, USAGE))?;
+
+        let mut format = None;
+        let mut output = None;
+        for option in options {
+            match option.split_once('=') {
+                Some(("--format", name)) => {
+                    format = Some(ExportFormat::from_name(name).ok_or_else(|| anyhow!("Unknown format: {}\n{}", name, USAGE))?);
+                },
+                Some(("--output", path)) if !path.is_empty() => output = Some(path.to_string()),
+                _ => return Err(anyhow!("Unknown option: {}\n{}", option, USAGE)),
+            }
+        }
+
+        Ok(Self::Export { dataset, format, output })
+    }
+}
+
+fn expect_flags(flags: &[String], allowed: &[&str]) -> Result<()> {
+    match flags.iter().find(|flag| !allowed.contains(&flag.as_str())) {
+        Some(flag) => Err(anyhow!("Unknown option: {}\n{}", flag, USAGE)),
+        None => Ok(()),
+    }
+}
+
+fn has_flag(flags: &[String], flag: &str) -> bool {
+    flags.iter().any(|f| f == flag)
+}
+
+
+/// Writes every chunk of the export, returning the number of bytes written.
+async fn write_export<W: AsyncWrite + Unpin>(export: ExportStream, writer: W) -> Result<u64> {
+    let mut writer = BufWriter::new(writer);
+    let mut chunks = export.chunks;
+    let mut written = 0;
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        writer.write_all(chunk.as_bytes()).await?;
+        written += chunk.len() as u64;
+    }
+    writer.flush().await?;
+    Ok(written)
+}
+
+
+/// Runs a maintenance command to completion, printing its report as JSON on stdout.
+/// Logs go to stderr, so the report can be piped.
+pub async fn run(command: CliCommand, config: AppConfig) -> Result<()> {
+    let app_state = AppState::new(config).await?;
+
+    match command {
+        CliCommand::Serve => return Err(anyhow!("serve starts the servers and is not a maintenance command\n{}", USAGE)),
+        CliCommand::Reconcile { repair } => {
+            let report = ReconciliationService::from(&app_state).reconcile(repair).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+
+            if repair {
+                let applied = GraphSyncService::from(&app_state).drain().await?;
+                log::info2(&format!("Applied {} graph sync events", applied));
+            }
+        },
+        CliCommand::Neo4jSchema => {
+            let applied = neo4j_schema::migrate(&app_state.neo4j_client, true).await?;
+            println!("{}", serde_json::json!({ "applied_versions": applied }));
+        },
+        CliCommand::Migrate { dry_run } => {
+            let database = app_state.mongo_client.database("booknet");
+            let reports = mongodb_migration::migrate(&database, dry_run).await?;
+            println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "dry_run": dry_run, "migrations": reports }))?);
+        },
+        CliCommand::Import { kind, path, provider, format } => {
+            let cmd = ImportCommand { path, kind, provider, format };
+            let report = ImportService::from(&app_state).import(&cmd, None).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+
+            let applied = GraphSyncService::from(&app_state).drain().await?;
+            log::info2(&format!("Applied {} graph sync events", applied));
+        },
+        CliCommand::Export { dataset, format, output } => {
+            let cmd = ExportCommand { dataset, format };
+            let export = ExportService::from(&app_state).export(cmd).await?;
+            let written = match &output {
+                Some(path) => write_export(export, File::create(path).await?).await?,
+                None => write_export(export, tokio::io::stdout()).await?,
+            };
+            log::info2(&format!("Exported {} ({} bytes)", dataset.name(), written));
+        },
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliCommand> {
+        CliCommand::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn no_arguments_serve() {
+        assert_eq!(parse(&[]).unwrap(), CliCommand::Serve);
+        assert_eq!(parse(&["serve"]).unwrap(), CliCommand::Serve);
+    }
+
+    #[test]
+    fn reconcile_only_repairs_when_asked() {
+        assert_eq!(parse(&["reconcile"]).unwrap(), CliCommand::Reconcile { repair: false });
+        assert_eq!(parse(&["reconcile", "--repair"]).unwrap(), CliCommand::Reconcile { repair: true });
+    }
+
+    #[test]
+    fn unknown_commands_and_options_are_rejected_with_the_usage() {
+        let error = parse(&["rebuild"]).unwrap_err().to_string();
+        assert!(error.starts_with("Unknown command: rebuild"));
+        assert!(error.contains(USAGE));
+
+        assert!(parse(&["reconcile", "--force"]).is_err());
+        assert!(parse(&["serve", "--repair"]).is_err());
+    }
+
+    #[test]
+    fn neo4j_schema_takes_no_options() {
+        assert_eq!(parse(&["neo4j-schema"]).unwrap(), CliCommand::Neo4jSchema);
+        assert!(parse(&["neo4j-schema", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn migrate_only_dry_runs_when_asked() {
+        assert_eq!(parse(&["migrate"]).unwrap(), CliCommand::Migrate { dry_run: false });
+        assert_eq!(parse(&["migrate", "--dry-run"]).unwrap(), CliCommand::Migrate { dry_run: true });
+    }
+
+    #[test]
+    fn import_defaults_to_goodreads_and_the_file_extension() {
+        let command = parse(&["import", "books", "dump/books.csv"]).unwrap();
+
+        assert_eq!(command, CliCommand::Import {
+            kind: ImportKind::Books,
+            path: "dump/books.csv".to_string(),
+            provider: ExternalProvider::GoodReads,
+            format: None,
+        });
+    }
+
+    #[test]
+    fn import_options_may_come_before_the_file() {
+        let command = parse(&["import", "--provider=kaggle", "ratings", "--format=jsonl", "ratings.json"]).unwrap();
+
+        assert_eq!(command, CliCommand::Import {
+            kind: ImportKind::Ratings,
+            path: "ratings.json".to_string(),
+            provider: ExternalProvider::Kaggle,
+            format: Some(ImportFormat::JsonLines),
+        });
+    }
+
+    #[test]
+    fn import_rejects_unknown_kinds_providers_and_missing_files() {
+        assert!(parse(&["import", "shelves", "shelves.csv"]).is_err());
+        assert!(parse(&["import", "books", "books.csv", "--provider=library"]).is_err());
+        assert!(parse(&["import", "books"]).is_err());
+    }
+
+    #[test]
+    fn export_writes_to_stdout_in_the_default_format_unless_told_otherwise() {
+        assert_eq!(parse(&["export", "users"]).unwrap(), CliCommand::Export {
+            dataset: ExportDataset::Users,
+            format: None,
+            output: None,
+        });
+        assert_eq!(parse(&["export", "--format=cypher", "graph", "--output=graph.cypher"]).unwrap(), CliCommand::Export {
+            dataset: ExportDataset::Graph,
+            format: Some(ExportFormat::Cypher),
+            output: Some("graph.cypher".to_string()),
+        });
+    }
+
+    #[test]
+    fn export_rejects_unknown_datasets_and_formats() {
+        assert!(parse(&["export", "shelves"]).is_err());
+        assert!(parse(&["export", "books", "--format=xlsx"]).is_err());
+        assert!(parse(&["export"]).is_err());
+    }
+}
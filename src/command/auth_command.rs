@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LoginCommand {
+    pub username: String,
+    pub password: String,
+}
@@ -0,0 +1,59 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::external_id_model::ExternalProvider;
+use crate::shared::models::response::PaginationRequest;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorGetCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorCreateCommand {
+    pub name: String,
+    pub image_url: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorUpdateCommand {
+    pub id: String,
+    pub image_url: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorDeleteCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorListCommand {
+    pub pagination: Option<PaginationRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorBooksCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorExternalIdGetCommand {
+    pub provider: ExternalProvider,
+    pub external_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorExternalIdAttachCommand {
+    pub id: String,
+    pub provider: ExternalProvider,
+    pub external_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorExternalIdDetachCommand {
+    pub id: String,
+    pub provider: ExternalProvider,
+}
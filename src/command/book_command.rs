@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::book_model::BookFormat;
+use crate::model::external_id_model::ExternalProvider;
+use crate::shared::models::response::PaginationRequest;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookMediaCommand {
+    pub url: String,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookGetCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookCreateCommand {
+    pub isbn: String,
+    pub isbn13: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub num_pages: Option<i32>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub format: BookFormat,
+    pub images: Vec<BookMediaCommand>,
+    pub preview: Vec<BookMediaCommand>,
+    pub genres: Vec<String>,
+    pub author_ids: Vec<String>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookUpdateCommand {
+    pub id: String,
+    pub isbn: String,
+    pub isbn13: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub num_pages: Option<i32>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub format: BookFormat,
+    pub images: Vec<BookMediaCommand>,
+    pub preview: Vec<BookMediaCommand>,
+    pub genres: Vec<String>,
+    pub author_ids: Vec<String>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookDeleteCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct BookFilterCommand {
+    pub genres: Vec<String>,
+    pub author_ids: Vec<String>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+    pub formats: Vec<BookFormat>,
+    pub pages_min: Option<i32>,
+    pub pages_max: Option<i32>,
+    pub published_from: Option<DateTime<Utc>>,
+    pub published_to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookListCommand {
+    pub pagination: Option<PaginationRequest>,
+    pub filter: BookFilterCommand,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookExternalIdGetCommand {
+    pub provider: ExternalProvider,
+    pub external_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookExternalIdAttachCommand {
+    pub id: String,
+    pub provider: ExternalProvider,
+    pub external_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookExternalIdDetachCommand {
+    pub id: String,
+    pub provider: ExternalProvider,
+}
@@ -0,0 +1,22 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateListCommand {
+    /// Name similarity from which two records are proposed, between 0 and 1
+    pub min_score: f64,
+    pub limit: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthorMergeCommand {
+    pub survivor_id: String,
+    pub merged_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BookMergeCommand {
+    pub survivor_id: String,
+    pub merged_ids: Vec<String>,
+}
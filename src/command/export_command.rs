@@ -0,0 +1,12 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::export_model::{ExportDataset, ExportFormat};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportCommand {
+    pub dataset: ExportDataset,
+    /// The dataset's default format when absent
+    pub format: Option<ExportFormat>,
+}
@@ -0,0 +1,29 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::shared::models::response::PaginationRequest;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FollowCommand {
+    pub follower_id: String,
+    pub followee_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnfollowCommand {
+    pub follower_id: String,
+    pub followee_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FollowListCommand {
+    pub user_id: String,
+    pub pagination: Option<PaginationRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeedCommand {
+    pub user_id: String,
+    pub pagination: Option<PaginationRequest>,
+}
@@ -0,0 +1,21 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::import_model::{ImportFormat, ImportKind};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportCommand {
+    /// Dump file on the server's filesystem
+    pub path: String,
+    pub kind: ImportKind,
+    pub provider: ExternalProvider,
+    /// Guessed from the file extension when absent
+    pub format: Option<ImportFormat>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportJobGetCommand {
+    pub id: String,
+}
@@ -1,4 +1,18 @@
 pub mod genre_command;
 pub mod language_command;
 pub mod source_command;
-pub mod publisher_command;
\ No newline at end of file
+pub mod publisher_command;
+pub mod auth_command;
+pub mod user_command;
+pub mod book_command;
+pub mod author_command;
+pub mod review_command;
+pub mod shelf_command;
+pub mod recommendation_command;
+pub mod similarity_command;
+pub mod follow_command;
+pub mod search_command;
+pub mod import_command;
+pub mod export_command;
+pub mod dedup_command;
+pub mod work_command;
\ No newline at end of file
@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecommendationListCommand {
+    pub user_id: String,
+    pub limit: u64,
+}
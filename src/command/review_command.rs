@@ -0,0 +1,51 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::shared::models::response::PaginationRequest;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewGetCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewCreateCommand {
+    pub book_id: String,
+    pub user_id: String,
+    pub content: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewUpdateCommand {
+    pub id: String,
+    pub user_id: String,
+    pub content: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewDeleteCommand {
+    pub id: String,
+    pub user_id: String,
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewBookListCommand {
+    pub book_id: String,
+    pub pagination: Option<PaginationRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewUserListCommand {
+    pub user_id: String,
+    pub pagination: Option<PaginationRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewWorkListCommand {
+    pub work_id: String,
+    pub pagination: Option<PaginationRequest>,
+}
@@ -0,0 +1,14 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::book_model::BookFormat;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchCommand {
+    pub text: String,
+    pub language: Option<String>,
+    pub format: Option<BookFormat>,
+    pub genre: Option<String>,
+    pub limit: u64,
+}
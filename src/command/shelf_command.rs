@@ -0,0 +1,31 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::book_model::BookReadStatus;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfListCommand {
+    pub user_id: String,
+    pub status: Option<BookReadStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfAddCommand {
+    pub user_id: String,
+    pub book_id: String,
+    pub status: BookReadStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfStatusUpdateCommand {
+    pub user_id: String,
+    pub book_id: String,
+    pub status: BookReadStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfRemoveCommand {
+    pub user_id: String,
+    pub book_id: String,
+}
@@ -0,0 +1,15 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SimilarBooksCommand {
+    pub book_id: String,
+    pub limit: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SimilarAuthorsCommand {
+    pub author_id: String,
+    pub limit: u64,
+}
@@ -0,0 +1,43 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserGetCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserCreateCommand {
+    pub username: String,
+    pub password: String,
+    pub name: String,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserUpdateCommand {
+    pub id: String,
+    pub name: Option<String>,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserPasswordUpdateCommand {
+    pub id: String,
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserPreferenceUpdateCommand {
+    pub id: String,
+    pub authors: Vec<String>,
+    pub genres: Vec<String>,
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserDeleteCommand {
+    pub id: String,
+}
@@ -0,0 +1,48 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::shared::models::response::PaginationRequest;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkGetCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkCreateCommand {
+    pub title: String,
+    pub description: Option<String>,
+    pub original_language: Option<String>,
+    pub edition_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkUpdateCommand {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub original_language: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkDeleteCommand {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkListCommand {
+    pub pagination: Option<PaginationRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkEditionAttachCommand {
+    pub id: String,
+    pub book_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkEditionDetachCommand {
+    pub id: String,
+    pub book_id: String,
+}
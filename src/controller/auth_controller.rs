@@ -0,0 +1,37 @@
+use axum::{Router, routing::post, extract::State, Json};
+
+use crate::command::auth_command::LoginCommand;
+use crate::dto::auth_dto::{LoginRequest, TokenResponse};
+use crate::service::auth_service::{AuthService, AuthServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::ValidationErrorResponse;
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/login", post(post_login))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = StatusCode::OK, description = "Access token issued", body = TokenResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Invalid credentials"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn post_login(State(state): State<AppState>, ValidatedJson(request): ValidatedJson<LoginRequest>) -> Result<Json<TokenResponse>, AppError> {
+    let cmd = LoginCommand { username: request.username, password: request.password };
+    let service = AuthService::from(&state);
+    let token = service.login(cmd).await?;
+    Ok(Json(token))
+}
+
@@ -0,0 +1,287 @@
+use axum::{Router, routing::{get, put}, extract::{Path, Query, State}, Json, http::StatusCode};
+
+use crate::command::author_command::{
+    AuthorBooksCommand, AuthorCreateCommand, AuthorDeleteCommand, AuthorExternalIdAttachCommand, AuthorExternalIdDetachCommand,
+    AuthorExternalIdGetCommand, AuthorGetCommand, AuthorListCommand, AuthorUpdateCommand,
+};
+use crate::dto::author_dto::{AuthorBookResponse, AuthorCreateRequest, AuthorResponse, AuthorUpdateRequest};
+use crate::command::similarity_command::SimilarAuthorsCommand;
+use crate::dto::external_id_dto::ExternalIdAttachRequest;
+use crate::dto::similarity_dto::{SimilarAuthorResponse, SimilarQuery};
+use crate::model::external_id_model::ExternalProvider;
+use crate::service::author_service::{AuthorService, AuthorServiceInterface};
+use crate::service::similarity_service::{SimilarityService, SimilarityServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_authors).post(post_author))
+        .route("/{author_id}", get(get_author).put(put_author).delete(delete_author))
+        .route("/{author_id}/books", get(get_author_books))
+        .route("/{author_id}/similar", get(get_similar_authors))
+        .route("/by-external/{provider}/{external_id}", get(get_author_by_external_id))
+        .route("/{author_id}/external-id/{provider}", put(put_author_external_id).delete(delete_author_external_id))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/author",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "List of authors", body = PaginatedResponse<AuthorResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Author"
+)]
+pub async fn get_authors(
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<AuthorResponse>>, AppError> {
+    let cmd = AuthorListCommand { pagination: Some(pagination) };
+    let service = AuthorService::from(&state);
+    let authors = service.list(cmd).await?;
+    Ok(Json(authors))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/author",
+    request_body = AuthorCreateRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Author created", body = AuthorResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Author"
+)]
+pub async fn post_author(
+    _: RequireRole<Admin>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<AuthorCreateRequest>
+) -> Result<(StatusCode, Json<AuthorResponse>), AppError> {
+    let cmd = AuthorCreateCommand {
+        name: request.name,
+        image_url: request.image_url,
+        description: request.description,
+    };
+    let service = AuthorService::from(&state);
+    let author = service.create(cmd).await?;
+    Ok((StatusCode::CREATED, Json(author)))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/author/{author_id}",
+    responses(
+        (status = StatusCode::OK, description = "Author retrieved", body = AuthorResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Author"
+)]
+pub async fn get_author(
+    Path(author_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<Json<AuthorResponse>, AppError> {
+    let cmd = AuthorGetCommand { id: author_id };
+    let service = AuthorService::from(&state);
+    let author = service.get(cmd).await?;
+    Ok(Json(author))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/author/{author_id}",
+    request_body = AuthorUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Author updated", body = AuthorResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Author"
+)]
+pub async fn put_author(
+    _: RequireRole<Admin>,
+    Path(author_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<AuthorUpdateRequest>
+) -> Result<Json<AuthorResponse>, AppError> {
+    let cmd = AuthorUpdateCommand {
+        id: author_id,
+        image_url: request.image_url,
+        description: request.description,
+    };
+    let service = AuthorService::from(&state);
+    let author = service.update(cmd).await?;
+    Ok(Json(author))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/author/{author_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Author deleted"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Author"
+)]
+pub async fn delete_author(
+    _: RequireRole<Admin>,
+    Path(author_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = AuthorDeleteCommand { id: author_id };
+    let service = AuthorService::from(&state);
+    service.delete(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/author/{author_id}/books",
+    responses(
+        (status = StatusCode::OK, description = "Books written by the author", body = Vec<AuthorBookResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Author"
+)]
+pub async fn get_author_books(
+    Path(author_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<AuthorBookResponse>>, AppError> {
+    let cmd = AuthorBooksCommand { id: author_id };
+    let service = AuthorService::from(&state);
+    let books = service.books(cmd).await?;
+    Ok(Json(books))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/author/{author_id}/similar",
+    params(SimilarQuery),
+    responses(
+        (status = StatusCode::OK, description = "Authors read by the same readers or writing in the same genres, most similar first", body = Vec<SimilarAuthorResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Author"
+)]
+pub async fn get_similar_authors(
+    Path(author_id): Path<String>,
+    Query(query): Query<SimilarQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<SimilarAuthorResponse>>, AppError> {
+    let cmd = SimilarAuthorsCommand { author_id, limit: query.limit() };
+    let service = SimilarityService::from(&state);
+    let authors = service.similar_authors(cmd).await?;
+    Ok(Json(authors))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/author/by-external/{provider}/{external_id}",
+    responses(
+        (status = StatusCode::OK, description = "Author carrying the provider's id", body = AuthorResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "No author carries this id"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Author"
+)]
+pub async fn get_author_by_external_id(
+    Path((provider, external_id)): Path<(ExternalProvider, String)>,
+    State(state): State<AppState>
+) -> Result<Json<AuthorResponse>, AppError> {
+    let cmd = AuthorExternalIdGetCommand { provider, external_id };
+    let service = AuthorService::from(&state);
+    let author = service.get_by_external_id(cmd).await?;
+    Ok(Json(author))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/author/{author_id}/external-id/{provider}",
+    request_body = ExternalIdAttachRequest,
+    responses(
+        (status = StatusCode::OK, description = "External id attached", body = AuthorResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::CONFLICT, description = "The id is attached to another author"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Author"
+)]
+pub async fn put_author_external_id(
+    _: RequireRole<Admin>,
+    Path((author_id, provider)): Path<(String, ExternalProvider)>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ExternalIdAttachRequest>
+) -> Result<Json<AuthorResponse>, AppError> {
+    let cmd = AuthorExternalIdAttachCommand { id: author_id, provider, external_id: request.id };
+    let service = AuthorService::from(&state);
+    let author = service.attach_external_id(cmd).await?;
+    Ok(Json(author))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/author/{author_id}/external-id/{provider}",
+    responses(
+        (status = StatusCode::OK, description = "External id detached", body = AuthorResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Author not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Author"
+)]
+pub async fn delete_author_external_id(
+    _: RequireRole<Admin>,
+    Path((author_id, provider)): Path<(String, ExternalProvider)>,
+    State(state): State<AppState>
+) -> Result<Json<AuthorResponse>, AppError> {
+    let cmd = AuthorExternalIdDetachCommand { id: author_id, provider };
+    let service = AuthorService::from(&state);
+    let author = service.detach_external_id(cmd).await?;
+    Ok(Json(author))
+}
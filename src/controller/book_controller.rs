@@ -0,0 +1,315 @@
+use axum::{Router, routing::{get, put}, extract::{Path, Query, State}, Json, http::StatusCode};
+
+use crate::command::book_command::{
+    BookCreateCommand, BookDeleteCommand, BookExternalIdAttachCommand, BookExternalIdDetachCommand, BookExternalIdGetCommand,
+    BookGetCommand, BookListCommand, BookMediaCommand, BookUpdateCommand,
+};
+use crate::dto::book_dto::{BookCatalogResponse, BookCreateRequest, BookFilterQuery, BookMediaRequest, BookResponse, BookUpdateRequest};
+use crate::command::similarity_command::SimilarBooksCommand;
+use crate::dto::external_id_dto::ExternalIdAttachRequest;
+use crate::dto::similarity_dto::{SimilarBookResponse, SimilarQuery};
+use crate::model::external_id_model::ExternalProvider;
+use crate::service::book_service::{BookService, BookServiceInterface};
+use crate::service::similarity_service::{SimilarityService, SimilarityServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_books).post(post_book))
+        .route("/{book_id}", get(get_book).put(put_book).delete(delete_book))
+        .route("/{book_id}/similar", get(get_similar_books))
+        .route("/by-external/{provider}/{external_id}", get(get_book_by_external_id))
+        .route("/{book_id}/external-id/{provider}", put(put_book_external_id).delete(delete_book_external_id))
+}
+
+fn media_commands(media: Vec<BookMediaRequest>) -> Vec<BookMediaCommand> {
+    media
+        .into_iter()
+        .map(|m| BookMediaCommand { url: m.url, source: m.source })
+        .collect()
+}
+
+/// ISBNs are stored as bare digits with an uppercase `X` check digit, the form imports write.
+fn normalize_isbn(isbn: &str) -> String {
+    isbn.chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/book",
+    params(PaginationRequest, BookFilterQuery),
+    responses(
+        (status = StatusCode::OK, description = "Filtered page of books with facet counts", body = BookCatalogResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Book"
+)]
+pub async fn get_books(
+    Query(pagination): Query<PaginationRequest>,
+    Query(filter): Query<BookFilterQuery>,
+    State(state): State<AppState>
+) -> Result<Json<BookCatalogResponse>, AppError> {
+    let cmd = BookListCommand { pagination: Some(pagination), filter: filter.try_into()? };
+    let service = BookService::from(&state);
+    let books = service.list(cmd).await?;
+    Ok(Json(books))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/book",
+    request_body = BookCreateRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Book created", body = BookResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Book"
+)]
+pub async fn post_book(
+    _: RequireRole<Admin>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<BookCreateRequest>
+) -> Result<(StatusCode, Json<BookResponse>), AppError> {
+    let cmd = BookCreateCommand {
+        isbn: request.isbn.as_deref().map(normalize_isbn).unwrap_or_default(),
+        isbn13: request.isbn13.as_deref().map(normalize_isbn).unwrap_or_default(),
+        title: request.title,
+        subtitle: request.subtitle,
+        description: request.description,
+        num_pages: request.num_pages,
+        published_date: request.published_date,
+        format: request.format,
+        images: media_commands(request.images),
+        preview: media_commands(request.preview),
+        genres: request.genres,
+        author_ids: request.author_ids,
+        publishers: request.publishers,
+        languages: request.languages,
+    };
+    let service = BookService::from(&state);
+    let book = service.create(cmd).await?;
+    Ok((StatusCode::CREATED, Json(book)))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/book/{book_id}",
+    responses(
+        (status = StatusCode::OK, description = "Book retrieved", body = BookResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Book"
+)]
+pub async fn get_book(
+    Path(book_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<Json<BookResponse>, AppError> {
+    let cmd = BookGetCommand { id: book_id };
+    let service = BookService::from(&state);
+    let book = service.get(cmd).await?;
+    Ok(Json(book))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/book/{book_id}",
+    request_body = BookUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Book updated", body = BookResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Book"
+)]
+pub async fn put_book(
+    _: RequireRole<Admin>,
+    Path(book_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<BookUpdateRequest>
+) -> Result<Json<BookResponse>, AppError> {
+    let cmd = BookUpdateCommand {
+        id: book_id,
+        isbn: request.isbn.as_deref().map(normalize_isbn).unwrap_or_default(),
+        isbn13: request.isbn13.as_deref().map(normalize_isbn).unwrap_or_default(),
+        title: request.title,
+        subtitle: request.subtitle,
+        description: request.description,
+        num_pages: request.num_pages,
+        published_date: request.published_date,
+        format: request.format,
+        images: media_commands(request.images),
+        preview: media_commands(request.preview),
+        genres: request.genres,
+        author_ids: request.author_ids,
+        publishers: request.publishers,
+        languages: request.languages,
+    };
+    let service = BookService::from(&state);
+    let book = service.update(cmd).await?;
+    Ok(Json(book))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/book/{book_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Book deleted"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Book"
+)]
+pub async fn delete_book(
+    _: RequireRole<Admin>,
+    Path(book_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = BookDeleteCommand { id: book_id };
+    let service = BookService::from(&state);
+    service.delete(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/book/{book_id}/similar",
+    params(SimilarQuery),
+    responses(
+        (status = StatusCode::OK, description = "Books read by the same readers or sharing genres, most similar first", body = Vec<SimilarBookResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Book"
+)]
+pub async fn get_similar_books(
+    Path(book_id): Path<String>,
+    Query(query): Query<SimilarQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<SimilarBookResponse>>, AppError> {
+    let cmd = SimilarBooksCommand { book_id, limit: query.limit() };
+    let service = SimilarityService::from(&state);
+    let books = service.similar_books(cmd).await?;
+    Ok(Json(books))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/book/by-external/{provider}/{external_id}",
+    responses(
+        (status = StatusCode::OK, description = "Book carrying the provider's id", body = BookResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "No book carries this id"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Book"
+)]
+pub async fn get_book_by_external_id(
+    Path((provider, external_id)): Path<(ExternalProvider, String)>,
+    State(state): State<AppState>
+) -> Result<Json<BookResponse>, AppError> {
+    let cmd = BookExternalIdGetCommand { provider, external_id };
+    let service = BookService::from(&state);
+    let book = service.get_by_external_id(cmd).await?;
+    Ok(Json(book))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/book/{book_id}/external-id/{provider}",
+    request_body = ExternalIdAttachRequest,
+    responses(
+        (status = StatusCode::OK, description = "External id attached", body = BookResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::CONFLICT, description = "The id is attached to another book"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Book"
+)]
+pub async fn put_book_external_id(
+    _: RequireRole<Admin>,
+    Path((book_id, provider)): Path<(String, ExternalProvider)>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ExternalIdAttachRequest>
+) -> Result<Json<BookResponse>, AppError> {
+    let cmd = BookExternalIdAttachCommand { id: book_id, provider, external_id: request.id };
+    let service = BookService::from(&state);
+    let book = service.attach_external_id(cmd).await?;
+    Ok(Json(book))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/book/{book_id}/external-id/{provider}",
+    responses(
+        (status = StatusCode::OK, description = "External id detached", body = BookResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Book"
+)]
+pub async fn delete_book_external_id(
+    _: RequireRole<Admin>,
+    Path((book_id, provider)): Path<(String, ExternalProvider)>,
+    State(state): State<AppState>
+) -> Result<Json<BookResponse>, AppError> {
+    let cmd = BookExternalIdDetachCommand { id: book_id, provider };
+    let service = BookService::from(&state);
+    let book = service.detach_external_id(cmd).await?;
+    Ok(Json(book))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isbn_separators_are_stripped_and_the_check_letter_uppercased() {
+        assert_eq!(normalize_isbn("0-8044-2957-x"), "080442957X");
+        assert_eq!(normalize_isbn("978 0 306 40615 7"), "9780306406157");
+    }
+}
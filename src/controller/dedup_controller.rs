@@ -0,0 +1,130 @@
+use axum::{Router, routing::{get, post}, extract::{Path, Query, State}, Json};
+
+use crate::command::dedup_command::{AuthorMergeCommand, BookMergeCommand, DuplicateListCommand};
+use crate::dto::dedup_dto::{DuplicateCandidateResponse, DuplicateQuery, MergeReportResponse, MergeRequest};
+use crate::service::dedup_service::{DedupService, DedupServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::ValidationErrorResponse;
+use crate::shared::security::role_guard::{Admin, RequireRole};
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/authors", get(get_author_duplicates))
+        .route("/authors/{author_id}/merge", post(post_author_merge))
+        .route("/books", get(get_book_duplicates))
+        .route("/books/{book_id}/merge", post(post_book_merge))
+}
+
+
+fn list_command(query: &DuplicateQuery) -> DuplicateListCommand {
+    DuplicateListCommand { min_score: query.min_score(), limit: query.limit() }
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/dedup/authors",
+    params(DuplicateQuery),
+    responses(
+        (status = StatusCode::OK, description = "Pairs of authors that may be the same person, best first", body = [DuplicateCandidateResponse]),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Dedup"
+)]
+pub async fn get_author_duplicates(
+    _: RequireRole<Admin>,
+    Query(query): Query<DuplicateQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<DuplicateCandidateResponse>>, AppError> {
+    let service = DedupService::from(&state);
+    let candidates = service.author_candidates(list_command(&query)).await?;
+    Ok(Json(candidates))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/dedup/authors/{author_id}/merge",
+    request_body = MergeRequest,
+    responses(
+        (status = StatusCode::OK, description = "Authors merged into this one and deleted", body = MergeReportResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Invalid id, or the author merged into itself"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::NOT_FOUND, description = "One of the authors not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Dedup"
+)]
+pub async fn post_author_merge(
+    _: RequireRole<Admin>,
+    Path(author_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<MergeRequest>
+) -> Result<Json<MergeReportResponse>, AppError> {
+    let cmd = AuthorMergeCommand { survivor_id: author_id, merged_ids: request.merged_ids };
+    let service = DedupService::from(&state);
+    let report = service.merge_authors(cmd).await?;
+    Ok(Json(report))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/dedup/books",
+    params(DuplicateQuery),
+    responses(
+        (status = StatusCode::OK, description = "Pairs of books that may be the same book, best first", body = [DuplicateCandidateResponse]),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Dedup"
+)]
+pub async fn get_book_duplicates(
+    _: RequireRole<Admin>,
+    Query(query): Query<DuplicateQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<DuplicateCandidateResponse>>, AppError> {
+    let service = DedupService::from(&state);
+    let candidates = service.book_candidates(list_command(&query)).await?;
+    Ok(Json(candidates))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/dedup/books/{book_id}/merge",
+    request_body = MergeRequest,
+    responses(
+        (status = StatusCode::OK, description = "Books merged into this one and deleted, with their shelves and reviews", body = MergeReportResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Invalid id, or the book merged into itself"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::NOT_FOUND, description = "One of the books not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Dedup"
+)]
+pub async fn post_book_merge(
+    _: RequireRole<Admin>,
+    Path(book_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<MergeRequest>
+) -> Result<Json<MergeReportResponse>, AppError> {
+    let cmd = BookMergeCommand { survivor_id: book_id, merged_ids: request.merged_ids };
+    let service = DedupService::from(&state);
+    let report = service.merge_books(cmd).await?;
+    Ok(Json(report))
+}
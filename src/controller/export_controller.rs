@@ -0,0 +1,58 @@
+use axum::{
+    Router, routing::get, extract::{Path, Query, State},
+    body::Body, http::header, response::IntoResponse,
+};
+use futures::TryStreamExt;
+
+use crate::command::export_command::ExportCommand;
+use crate::dto::export_dto::ExportQuery;
+use crate::model::export_model::ExportDataset;
+use crate::service::export_service::{ExportService, ExportServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::logging::log;
+use crate::shared::security::role_guard::{Admin, RequireRole};
+use crate::shared::state::AppState;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/{dataset}", get(get_export))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/export/{dataset}",
+    params(ExportQuery),
+    responses(
+        (status = StatusCode::OK, description = "Export streamed as an attachment, in the requested format"),
+        (status = StatusCode::BAD_REQUEST, description = "Unknown dataset or format not available for it"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Export"
+)]
+pub async fn get_export(
+    _: RequireRole<Admin>,
+    Path(dataset): Path<ExportDataset>,
+    Query(query): Query<ExportQuery>,
+    State(state): State<AppState>
+) -> Result<impl IntoResponse, AppError> {
+    let cmd = ExportCommand { dataset, format: query.format };
+    let service = ExportService::from(&state);
+    let export = service.export(cmd).await?;
+
+    // Headers are already sent when a later read fails: the response is cut short and the cause logged
+    let chunks = export.chunks.inspect_err(move |e| {
+        log::error(&format!("[EXPORT] {} export interrupted: {:#}", dataset.name(), e));
+    });
+    Ok((
+        [
+            (header::CONTENT_TYPE, export.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", export.filename)),
+        ],
+        Body::from_stream(chunks),
+    ))
+}
@@ -0,0 +1,198 @@
+use axum::{Router, routing::{get, put}, extract::{Path, Query, State}, Json, http::StatusCode};
+
+use crate::command::follow_command::{FeedCommand, FollowCommand, FollowListCommand, UnfollowCommand};
+use crate::dto::follow_dto::{FeedItemResponse, FollowResponse};
+use crate::service::follow_service::{FollowService, FollowServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest};
+use crate::shared::security::auth_middleware::AuthUser;
+use crate::shared::state::AppState;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/me/followers", get(get_my_followers))
+        .route("/me/following", get(get_my_following))
+        .route("/me/following/{user_id}", put(put_following).delete(delete_following))
+        .route("/me/feed", get(get_my_feed))
+        .route("/{user_id}/followers", get(get_user_followers))
+        .route("/{user_id}/following", get(get_user_following))
+}
+
+async fn list_followers(state: &AppState, user_id: String, pagination: PaginationRequest) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    let cmd = FollowListCommand { user_id, pagination: Some(pagination) };
+    let service = FollowService::from(state);
+    let followers = service.followers(cmd).await?;
+    Ok(Json(followers))
+}
+
+async fn list_following(state: &AppState, user_id: String, pagination: PaginationRequest) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    let cmd = FollowListCommand { user_id, pagination: Some(pagination) };
+    let service = FollowService::from(state);
+    let following = service.following(cmd).await?;
+    Ok(Json(following))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/user/me/followers",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Readers following the current user, newest first", body = PaginatedResponse<FollowResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Current user is not a reader"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Follow"
+)]
+pub async fn get_my_followers(
+    AuthUser(claims): AuthUser,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    list_followers(&state, claims.sub, pagination).await
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/user/me/following",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Readers the current user follows, newest first", body = PaginatedResponse<FollowResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Current user is not a reader"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Follow"
+)]
+pub async fn get_my_following(
+    AuthUser(claims): AuthUser,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    list_following(&state, claims.sub, pagination).await
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/user/me/following/{user_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Reader followed"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Follow"
+)]
+pub async fn put_following(
+    AuthUser(claims): AuthUser,
+    Path(user_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = FollowCommand { follower_id: claims.sub, followee_id: user_id };
+    let service = FollowService::from(&state);
+    service.follow(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/user/me/following/{user_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Reader unfollowed"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "Not following this user"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Follow"
+)]
+pub async fn delete_following(
+    AuthUser(claims): AuthUser,
+    Path(user_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = UnfollowCommand { follower_id: claims.sub, followee_id: user_id };
+    let service = FollowService::from(&state);
+    service.unfollow(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/user/me/feed",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Reviews, ratings and shelf changes of followed readers, newest first", body = PaginatedResponse<FeedItemResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Current user is not a reader"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Follow"
+)]
+pub async fn get_my_feed(
+    AuthUser(claims): AuthUser,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<FeedItemResponse>>, AppError> {
+    let cmd = FeedCommand { user_id: claims.sub, pagination: Some(pagination) };
+    let service = FollowService::from(&state);
+    let feed = service.feed(cmd).await?;
+    Ok(Json(feed))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/user/{user_id}/followers",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Readers following the user, newest first", body = PaginatedResponse<FollowResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Follow"
+)]
+pub async fn get_user_followers(
+    Path(user_id): Path<String>,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    list_followers(&state, user_id, pagination).await
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/user/{user_id}/following",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Readers the user follows, newest first", body = PaginatedResponse<FollowResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Follow"
+)]
+pub async fn get_user_following(
+    Path(user_id): Path<String>,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    list_following(&state, user_id, pagination).await
+}
@@ -1,9 +1,13 @@
-use axum::{Router, routing::{get}, extract::{Path, State}, Json, http::StatusCode};
+use axum::{Router, routing::{get}, extract::{Path, Query, State}, Json};
 
 use crate::command::genre_command::{GenreCreateCommand, GenreDeleteCommand, GenreGetCommand, GenreListCommand, GenreUpdateCommand};
 use crate::dto::genre_dto::{GenreCreateRequest, GenreResponse, GenreUpdateRequest};
 use crate::service::genre_service::{GenreService, GenreServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
 use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
 
 
 pub fn routes() -> Router<AppState> {
@@ -17,42 +21,46 @@ pub fn routes() -> Router<AppState> {
 #[utoipa::path(
     get,
     path = "/api/services/genre",
+    params(PaginationRequest),
     responses(
-        (status = StatusCode::OK, description = "List of genres", body = Vec<GenreResponse>),
+        (status = StatusCode::OK, description = "List of genres", body = PaginatedResponse<GenreResponse>),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
     tag = "Genre"
 )]
-pub async fn get_genres(State(state): State<AppState>) -> Result<Json<Vec<GenreResponse>>, StatusCode> {
-    let cmd = GenreListCommand { pagination: None };
+pub async fn get_genres(
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<GenreResponse>>, AppError> {
+    let cmd = GenreListCommand { pagination: Some(pagination) };
     let service = GenreService::from(&state);
-    let genres = service.list(cmd).await;
-    match genres {
-        Ok(genres) => Ok(Json(genres)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let genres = service.list(cmd).await?;
+    Ok(Json(genres))
 }
 
 
 #[utoipa::path(
     post,
     path = "/api/services/genre",
+    request_body = GenreCreateRequest,
     responses(
         (status = StatusCode::CREATED, description = "Genre created", body = GenreResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::CONFLICT, description = "Genre already exists"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Genre"
 )]
-pub async fn post_genre(State(state): State<AppState>, Json(request): Json<GenreCreateRequest>) -> Result<Json<GenreResponse>, StatusCode> {
+pub async fn post_genre(_: RequireRole<Admin>, State(state): State<AppState>, ValidatedJson(request): ValidatedJson<GenreCreateRequest>) -> Result<Json<GenreResponse>, AppError> {
     let cmd = GenreCreateCommand { name: request.name, description: request.description };
     let service = GenreService::from(&state);
-    let genre = service.create(cmd).await;
-    match genre {
-        Ok(genre) => Ok(Json(genre)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let genre = service.create(cmd).await?;
+    Ok(Json(genre))
 }
 
 
@@ -70,50 +78,40 @@ get,
 pub async fn get_genre(
     Path(genre_id): Path<String>,
     State(state): State<AppState>
-) -> Result<Json<GenreResponse>, StatusCode> {
+) -> Result<Json<GenreResponse>, AppError> {
     let cmd = GenreGetCommand { id: genre_id };
     let service = GenreService::from(&state);
-    let genre = service.get(cmd).await;
-    match genre {
-        Ok(genre) => {
-            match genre {
-                Some(genre) => Ok(Json(genre)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let genre = service.get(cmd).await?;
+    Ok(Json(genre))
 }
 
 
 #[utoipa::path(
     put,
     path = "/api/services/genre/{genre_id}",
+    request_body = GenreUpdateRequest,
     responses(
         (status = StatusCode::OK, description = "Genre updated", body = GenreResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
         (status = StatusCode::NOT_FOUND, description = "Genre not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Genre"
 )]
 pub async fn put_genre(
+    _: RequireRole<Admin>,
     Path(genre_id): Path<String>,
     State(state): State<AppState>,
-    Json(request): Json<GenreUpdateRequest>
-) -> Result<Json<GenreResponse>, StatusCode> {
+    ValidatedJson(request): ValidatedJson<GenreUpdateRequest>
+) -> Result<Json<GenreResponse>, AppError> {
     let cmd = GenreUpdateCommand { name: genre_id, description: request.description };
     let service = GenreService::from(&state);
-    let genre = service.update(cmd).await;
-    match genre {
-        Ok(genre) => {
-            match genre {
-                Some(genre) => Ok(Json(genre)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let genre = service.update(cmd).await?;
+    Ok(Json(genre))
 }
 
 
@@ -124,19 +122,19 @@ pub async fn put_genre(
         (status = StatusCode::NO_CONTENT, description = "Genre deleted"),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::NOT_FOUND, description = "Genre not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Genre"
 )]
 pub async fn delete_genre(
+    _: RequireRole<Admin>,
     Path(genre_id): Path<String>,
     State(state): State<AppState>
-) -> Result<(), StatusCode> {
+) -> Result<(), AppError> {
     let cmd = GenreDeleteCommand { id: genre_id };
     let service = GenreService::from(&state);
-    let result = service.delete(cmd).await;
-    match result {
-        Ok(_) => Ok(()),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    service.delete(cmd).await
 }
@@ -0,0 +1,76 @@
+use axum::{Router, routing::{get, post}, extract::{Path, State}, Json, http::StatusCode};
+
+use crate::command::import_command::{ImportCommand, ImportJobGetCommand};
+use crate::dto::import_dto::{ImportJobResponse, ImportRequest};
+use crate::model::external_id_model::ExternalProvider;
+use crate::service::import_service::{ImportService, ImportServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::ValidationErrorResponse;
+use crate::shared::security::role_guard::{Admin, RequireRole};
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(post_import))
+        .route("/{job_id}", get(get_import))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/import",
+    request_body = ImportRequest,
+    responses(
+        (status = StatusCode::ACCEPTED, description = "Import started, follow it through its job", body = ImportJobResponse),
+        (status = StatusCode::BAD_REQUEST, description = "File not found in the import directory, or unknown format"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Import"
+)]
+pub async fn post_import(
+    _: RequireRole<Admin>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ImportRequest>
+) -> Result<(StatusCode, Json<ImportJobResponse>), AppError> {
+    let cmd = ImportCommand {
+        path: request.path,
+        kind: request.kind,
+        provider: request.provider.unwrap_or(ExternalProvider::GoodReads),
+        format: request.format,
+    };
+    let service = ImportService::from(&state);
+    let job = service.start(cmd).await?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/import/{job_id}",
+    responses(
+        (status = StatusCode::OK, description = "Import job with its progress and error report", body = ImportJobResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Invalid id"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::NOT_FOUND, description = "Import job not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Import"
+)]
+pub async fn get_import(
+    _: RequireRole<Admin>,
+    Path(job_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<Json<ImportJobResponse>, AppError> {
+    let cmd = ImportJobGetCommand { id: job_id };
+    let service = ImportService::from(&state);
+    let job = service.get_job(cmd).await?;
+    Ok(Json(job))
+}
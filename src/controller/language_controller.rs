@@ -1,4 +1,4 @@
-use axum::{Router, routing::{get}, extract::{Path, State}, Json, http::StatusCode};
+use axum::{Router, routing::{get}, extract::{Path, Query, State}, Json};
 
 use crate::command::language_command::{
     LanguageCreateCommand,
@@ -9,7 +9,11 @@ use crate::command::language_command::{
 };
 use crate::dto::language_dto::{LanguageCreateRequest, LanguageResponse, LanguageUpdateRequest};
 use crate::service::language_service::{LanguageService, LanguageServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
 use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
 
 
 pub fn routes() -> Router<AppState> {
@@ -22,42 +26,46 @@ pub fn routes() -> Router<AppState> {
 #[utoipa::path(
     get,
     path = "/api/services/language",
+    params(PaginationRequest),
     responses(
-        (status = StatusCode::OK, description = "List of languages", body = Vec<LanguageResponse>),
+        (status = StatusCode::OK, description = "List of languages", body = PaginatedResponse<LanguageResponse>),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
     tag = "Language"
 )]
-pub async fn get_languages(State(state): State<AppState>) -> Result<Json<Vec<LanguageResponse>>, StatusCode> {
-    let cmd = LanguageListCommand { pagination: None };
+pub async fn get_languages(
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<LanguageResponse>>, AppError> {
+    let cmd = LanguageListCommand { pagination: Some(pagination) };
     let service = LanguageService::from(&state);
-    let languages = service.list(cmd).await;
-    match languages {
-        Ok(languages) => Ok(Json(languages)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let languages = service.list(cmd).await?;
+    Ok(Json(languages))
 }
 
 
 #[utoipa::path(
     post,
     path = "/api/services/language",
+    request_body = LanguageCreateRequest,
     responses(
         (status = StatusCode::CREATED, description = "Language created", body = LanguageResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::CONFLICT, description = "Language already exists"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Language"
 )]
-pub async fn post_language(State(state): State<AppState>, Json(language_create_request): Json<LanguageCreateRequest>) -> Result<Json<LanguageResponse>, StatusCode> {
+pub async fn post_language(_: RequireRole<Admin>, State(state): State<AppState>, ValidatedJson(language_create_request): ValidatedJson<LanguageCreateRequest>) -> Result<Json<LanguageResponse>, AppError> {
     let cmd = LanguageCreateCommand { code: language_create_request.code, name: language_create_request.name };
     let service = LanguageService::from(&state);
-    let language = service.create(cmd).await;
-    match language {
-        Ok(language) => Ok(Json(language)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let language = service.create(cmd).await?;
+    Ok(Json(language))
 }
 
 
@@ -75,50 +83,40 @@ pub async fn post_language(State(state): State<AppState>, Json(language_create_r
 pub async fn get_language(
     Path(language_id): Path<String>,
     State(state): State<AppState>
-) -> Result<Json<LanguageResponse>, StatusCode> {
+) -> Result<Json<LanguageResponse>, AppError> {
     let cmd = LanguageGetCommand { id: language_id };
     let service = LanguageService::from(&state);
-    let language = service.get(cmd).await;
-    match language {
-        Ok(language) => {
-            match language {
-                Some(language) => Ok(Json(language)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let language = service.get(cmd).await?;
+    Ok(Json(language))
 }
 
 
 #[utoipa::path(
     put,
     path = "/api/services/language/{language_id}",
+    request_body = LanguageUpdateRequest,
     responses(
         (status = StatusCode::OK, description = "Language updated", body = LanguageResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
         (status = StatusCode::NOT_FOUND, description = "Language not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Language"
 )]
 pub async fn put_language(
+    _: RequireRole<Admin>,
     Path(language_id): Path<String>,
     State(state): State<AppState>,
-    Json(language_update_request): Json<LanguageUpdateRequest>
-) -> Result<Json<LanguageResponse>, StatusCode> {
+    ValidatedJson(language_update_request): ValidatedJson<LanguageUpdateRequest>
+) -> Result<Json<LanguageResponse>, AppError> {
     let cmd = LanguageUpdateCommand { code: language_id, name: language_update_request.name };
     let service = LanguageService::from(&state);
-    let language = service.update(cmd).await;
-    match language {
-        Ok(language) => {
-            match language {
-                Some(language) => Ok(Json(language)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let language = service.update(cmd).await?;
+    Ok(Json(language))
 }
 
 
@@ -129,19 +127,19 @@ pub async fn put_language(
         (status = StatusCode::NO_CONTENT, description = "Language deleted"),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::NOT_FOUND, description = "Language not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Language"
 )]
 pub async fn delete_language(
+    _: RequireRole<Admin>,
     Path(language_id): Path<String>,
     State(state): State<AppState>
-) -> Result<(), StatusCode> {
+) -> Result<(), AppError> {
     let cmd = LanguageDeleteCommand { id: language_id };
     let service = LanguageService::from(&state);
-    let result = service.delete(cmd).await;
-    match result {
-        Ok(_) => Ok(()),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    service.delete(cmd).await
 }
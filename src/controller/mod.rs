@@ -1,4 +1,17 @@
 pub mod source_controller;
 pub mod language_controller;
 pub mod genre_controller;
-pub mod publisher_controller;
\ No newline at end of file
+pub mod publisher_controller;
+pub mod auth_controller;
+pub mod user_controller;
+pub mod book_controller;
+pub mod author_controller;
+pub mod review_controller;
+pub mod shelf_controller;
+pub mod recommendation_controller;
+pub mod follow_controller;
+pub mod search_controller;
+pub mod import_controller;
+pub mod export_controller;
+pub mod dedup_controller;
+pub mod work_controller;
\ No newline at end of file
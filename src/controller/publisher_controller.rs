@@ -1,11 +1,15 @@
-use axum::{Router, routing::{get}, extract::{Path, State}, Json, http::StatusCode};
+use axum::{Router, routing::{get}, extract::{Path, Query, State}, Json};
 
 use crate::command::publisher_command::{
     PublisherCreateCommand, PublisherDeleteCommand, PublisherGetCommand, PublisherListCommand, PublisherUpdateCommand
 };
 use crate::dto::publisher_dto::{PublisherCreateRequest, PublisherResponse, PublisherUpdateRequest};
 use crate::service::publisher_service::{PublisherService, PublisherServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
 use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
 
 
 pub fn routes() -> Router<AppState> {
@@ -18,42 +22,46 @@ pub fn routes() -> Router<AppState> {
 #[utoipa::path(
     get,
     path = "/api/services/publisher",
+    params(PaginationRequest),
     responses(
-        (status = StatusCode::OK, description = "List of publishers", body = Vec<PublisherResponse>),
+        (status = StatusCode::OK, description = "List of publishers", body = PaginatedResponse<PublisherResponse>),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
     tag = "Publisher"
 )]
-pub async fn list_publishers(State(state): State<AppState>) -> Result<Json<Vec<PublisherResponse>>, StatusCode> {
-    let cmd = PublisherListCommand { pagination: None };
+pub async fn list_publishers(
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<PublisherResponse>>, AppError> {
+    let cmd = PublisherListCommand { pagination: Some(pagination) };
     let service = PublisherService::from(&state);
-    let publishers = service.list(cmd).await;
-    match publishers {
-        Ok(publishers) => Ok(Json(publishers)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let publishers = service.list(cmd).await?;
+    Ok(Json(publishers))
 }
 
 
 #[utoipa::path(
     post,
     path = "/api/services/publisher",
+    request_body = PublisherCreateRequest,
     responses(
         (status = StatusCode::CREATED, description = "Publisher created", body = PublisherResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::CONFLICT, description = "Publisher already exists"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Publisher"
 )]
-pub async fn post_publisher(State(state): State<AppState>, Json(publisher_create_request): Json<PublisherCreateRequest>) -> Result<Json<PublisherResponse>, StatusCode> {
+pub async fn post_publisher(_: RequireRole<Admin>, State(state): State<AppState>, ValidatedJson(publisher_create_request): ValidatedJson<PublisherCreateRequest>) -> Result<Json<PublisherResponse>, AppError> {
     let cmd = PublisherCreateCommand { name: publisher_create_request.name, website: publisher_create_request.website };
     let service = PublisherService::from(&state);
-    let publisher = service.create(cmd).await;
-    match publisher {
-        Ok(publisher) => Ok(Json(publisher)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let publisher = service.create(cmd).await?;
+    Ok(Json(publisher))
 }
 
 
@@ -71,50 +79,40 @@ pub async fn post_publisher(State(state): State<AppState>, Json(publisher_create
 pub async fn get_publisher(
     Path(publisher_id): Path<String>,
     State(state): State<AppState>
-) -> Result<Json<PublisherResponse>, StatusCode> {
+) -> Result<Json<PublisherResponse>, AppError> {
     let cmd = PublisherGetCommand { id: publisher_id };
     let service = PublisherService::from(&state);
-    let publisher = service.get(cmd).await;
-    match publisher {
-        Ok(publisher) => {
-            match publisher {
-                Some(publisher) => Ok(Json(publisher)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let publisher = service.get(cmd).await?;
+    Ok(Json(publisher))
 }
 
 
 #[utoipa::path(
     put,
     path = "/api/services/publisher/{publisher_id}",
+    request_body = PublisherUpdateRequest,
     responses(
         (status = StatusCode::OK, description = "Publisher updated", body = PublisherResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
         (status = StatusCode::NOT_FOUND, description = "Publisher not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Publisher"
 )]
 pub async fn put_publisher(
+    _: RequireRole<Admin>,
     Path(publisher_id): Path<String>,
     State(state): State<AppState>,
-    Json(publisher_update_request): Json<PublisherUpdateRequest>
-) -> Result<Json<PublisherResponse>, StatusCode> {
+    ValidatedJson(publisher_update_request): ValidatedJson<PublisherUpdateRequest>
+) -> Result<Json<PublisherResponse>, AppError> {
     let cmd = PublisherUpdateCommand { name: publisher_id, website: publisher_update_request.website };
     let service = PublisherService::from(&state);
-    let publisher = service.update(cmd).await;
-    match publisher {
-        Ok(publisher) => {
-            match publisher {
-                Some(publisher) => Ok(Json(publisher)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let publisher = service.update(cmd).await?;
+    Ok(Json(publisher))
 }
 
 
@@ -125,20 +123,20 @@ pub async fn put_publisher(
         (status = StatusCode::NO_CONTENT, description = "Publisher deleted"),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::NOT_FOUND, description = "Publisher not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Publisher"
 )]
 pub async fn delete_publisher(
+    _: RequireRole<Admin>,
     Path(publisher_id): Path<String>,
     State(state): State<AppState>
-) -> Result<(), StatusCode> {
+) -> Result<(), AppError> {
     let cmd = PublisherDeleteCommand { id: publisher_id };
     let service = PublisherService::from(&state);
-    let result = service.delete(cmd).await;
-    match result {
-        Ok(_) => Ok(()),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    service.delete(cmd).await
 }
 
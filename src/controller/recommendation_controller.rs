@@ -0,0 +1,39 @@
+use axum::{Router, routing::get, extract::{Query, State}, Json};
+
+use crate::command::recommendation_command::RecommendationListCommand;
+use crate::dto::recommendation_dto::{RecommendationQuery, RecommendationResponse};
+use crate::service::recommendation_service::{RecommendationService, RecommendationServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::security::auth_middleware::AuthUser;
+use crate::shared::state::AppState;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/me", get(get_my_recommendations))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/recommendation/me",
+    params(RecommendationQuery),
+    responses(
+        (status = StatusCode::OK, description = "Books recommended to the current user, best first", body = Vec<RecommendationResponse>),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Recommendation"
+)]
+pub async fn get_my_recommendations(
+    AuthUser(claims): AuthUser,
+    Query(query): Query<RecommendationQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<RecommendationResponse>>, AppError> {
+    let cmd = RecommendationListCommand { user_id: claims.sub, limit: query.limit() };
+    let service = RecommendationService::from(&state);
+    let recommendations = service.list(cmd).await?;
+    Ok(Json(recommendations))
+}
@@ -0,0 +1,213 @@
+use axum::{Router, routing::{get}, extract::{Path, Query, State}, Json, http::StatusCode};
+
+use crate::command::review_command::{
+    ReviewBookListCommand, ReviewCreateCommand, ReviewDeleteCommand, ReviewGetCommand, ReviewUpdateCommand, ReviewUserListCommand,
+    ReviewWorkListCommand,
+};
+use crate::dto::review_dto::{ReviewCreateRequest, ReviewResponse, ReviewUpdateRequest};
+use crate::service::review_service::{ReviewService, ReviewServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::auth_middleware::AuthUser;
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/book/{book_id}", get(get_book_reviews).post(post_review))
+        .route("/user/{user_id}", get(get_user_reviews))
+        .route("/work/{work_id}", get(get_work_reviews))
+        .route("/{review_id}", get(get_review).put(put_review).delete(delete_review))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/review/book/{book_id}",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Reviews of the book, newest first", body = PaginatedResponse<ReviewResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Review"
+)]
+pub async fn get_book_reviews(
+    Path(book_id): Path<String>,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<ReviewResponse>>, AppError> {
+    let cmd = ReviewBookListCommand { book_id, pagination: Some(pagination) };
+    let service = ReviewService::from(&state);
+    let reviews = service.list_by_book(cmd).await?;
+    Ok(Json(reviews))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/review/book/{book_id}",
+    request_body = ReviewCreateRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Review created", body = ReviewResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Score is not a whole number from 1 to 5"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::CONFLICT, description = "Book already reviewed by the user"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Review"
+)]
+pub async fn post_review(
+    AuthUser(claims): AuthUser,
+    Path(book_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ReviewCreateRequest>
+) -> Result<(StatusCode, Json<ReviewResponse>), AppError> {
+    let cmd = ReviewCreateCommand {
+        book_id,
+        user_id: claims.sub,
+        content: request.content,
+        score: request.score,
+    };
+    let service = ReviewService::from(&state);
+    let review = service.create(cmd).await?;
+    Ok((StatusCode::CREATED, Json(review)))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/review/user/{user_id}",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Reviews written by the user, newest first", body = PaginatedResponse<ReviewResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Review"
+)]
+pub async fn get_user_reviews(
+    Path(user_id): Path<String>,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<ReviewResponse>>, AppError> {
+    let cmd = ReviewUserListCommand { user_id, pagination: Some(pagination) };
+    let service = ReviewService::from(&state);
+    let reviews = service.list_by_user(cmd).await?;
+    Ok(Json(reviews))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/review/work/{work_id}",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "Reviews of every edition of the work, newest first", body = PaginatedResponse<ReviewResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Work not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Review"
+)]
+pub async fn get_work_reviews(
+    Path(work_id): Path<String>,
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<ReviewResponse>>, AppError> {
+    let cmd = ReviewWorkListCommand { work_id, pagination: Some(pagination) };
+    let service = ReviewService::from(&state);
+    let reviews = service.list_by_work(cmd).await?;
+    Ok(Json(reviews))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/review/{review_id}",
+    responses(
+        (status = StatusCode::OK, description = "Review retrieved", body = ReviewResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Review not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Review"
+)]
+pub async fn get_review(
+    Path(review_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<Json<ReviewResponse>, AppError> {
+    let cmd = ReviewGetCommand { id: review_id };
+    let service = ReviewService::from(&state);
+    let review = service.get(cmd).await?;
+    Ok(Json(review))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/review/{review_id}",
+    request_body = ReviewUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Review updated", body = ReviewResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Score is not a whole number from 1 to 5"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Review written by another user"),
+        (status = StatusCode::NOT_FOUND, description = "Review not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Review"
+)]
+pub async fn put_review(
+    AuthUser(claims): AuthUser,
+    Path(review_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ReviewUpdateRequest>
+) -> Result<Json<ReviewResponse>, AppError> {
+    let cmd = ReviewUpdateCommand {
+        id: review_id,
+        user_id: claims.sub,
+        content: request.content,
+        score: request.score,
+    };
+    let service = ReviewService::from(&state);
+    let review = service.update(cmd).await?;
+    Ok(Json(review))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/review/{review_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Review deleted"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Review written by another user"),
+        (status = StatusCode::NOT_FOUND, description = "Review not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Review"
+)]
+pub async fn delete_review(
+    AuthUser(claims): AuthUser,
+    Path(review_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = ReviewDeleteCommand {
+        id: review_id,
+        is_admin: claims.role.is_admin(),
+        user_id: claims.sub,
+    };
+    let service = ReviewService::from(&state);
+    service.delete(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,42 @@
+use axum::{Router, routing::get, extract::{Query, State}, Json};
+
+use crate::command::search_command::SearchCommand;
+use crate::dto::search_dto::{SearchQuery, SearchResponse};
+use crate::service::search_service::{SearchService, SearchServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::state::AppState;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_search))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/search",
+    params(SearchQuery),
+    responses(
+        (status = StatusCode::OK, description = "Books, authors, genres and publishers matching the text, grouped by type", body = SearchResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Empty or too long search text"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Search"
+)]
+pub async fn get_search(
+    Query(query): Query<SearchQuery>,
+    State(state): State<AppState>
+) -> Result<Json<SearchResponse>, AppError> {
+    let limit = query.limit();
+    let cmd = SearchCommand {
+        text: query.q,
+        language: query.language,
+        format: query.format,
+        genre: query.genre,
+        limit,
+    };
+    let service = SearchService::from(&state);
+    let results = service.search(cmd).await?;
+    Ok(Json(results))
+}
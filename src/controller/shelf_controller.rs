@@ -0,0 +1,158 @@
+use axum::{Router, routing::{get, put}, extract::{Path, Query, State}, Json, http::StatusCode};
+
+use crate::command::shelf_command::{ShelfAddCommand, ShelfListCommand, ShelfRemoveCommand, ShelfStatusUpdateCommand};
+use crate::dto::shelf_dto::{ShelfAddRequest, ShelfEntryResponse, ShelfQuery, ShelfStatusUpdateRequest};
+use crate::service::shelf_service::{ShelfService, ShelfServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::ValidationErrorResponse;
+use crate::shared::security::auth_middleware::AuthUser;
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/me", get(get_my_shelf).post(post_shelf_book))
+        .route("/me/{book_id}", put(put_shelf_status).delete(delete_shelf_book))
+        .route("/user/{user_id}", get(get_user_shelf))
+}
+
+async fn list_shelf(state: &AppState, user_id: String, query: ShelfQuery) -> Result<Json<Vec<ShelfEntryResponse>>, AppError> {
+    let cmd = ShelfListCommand { user_id, status: query.status };
+    let service = ShelfService::from(state);
+    let shelf = service.list(cmd).await?;
+    Ok(Json(shelf))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/shelf/me",
+    params(ShelfQuery),
+    responses(
+        (status = StatusCode::OK, description = "Books on the current user's shelf", body = Vec<ShelfEntryResponse>),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Shelf"
+)]
+pub async fn get_my_shelf(
+    AuthUser(claims): AuthUser,
+    Query(query): Query<ShelfQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<ShelfEntryResponse>>, AppError> {
+    list_shelf(&state, claims.sub, query).await
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/shelf/user/{user_id}",
+    params(ShelfQuery),
+    responses(
+        (status = StatusCode::OK, description = "Books on the user's shelf", body = Vec<ShelfEntryResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Shelf"
+)]
+pub async fn get_user_shelf(
+    Path(user_id): Path<String>,
+    Query(query): Query<ShelfQuery>,
+    State(state): State<AppState>
+) -> Result<Json<Vec<ShelfEntryResponse>>, AppError> {
+    list_shelf(&state, user_id, query).await
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/shelf/me",
+    request_body = ShelfAddRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Book added to the shelf", body = ShelfEntryResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "Book not found"),
+        (status = StatusCode::CONFLICT, description = "Book already on the shelf"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Shelf"
+)]
+pub async fn post_shelf_book(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ShelfAddRequest>
+) -> Result<(StatusCode, Json<ShelfEntryResponse>), AppError> {
+    let cmd = ShelfAddCommand {
+        user_id: claims.sub,
+        book_id: request.book_id,
+        status: request.status,
+    };
+    let service = ShelfService::from(&state);
+    let entry = service.add(cmd).await?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/shelf/me/{book_id}",
+    request_body = ShelfStatusUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Reading status updated", body = ShelfEntryResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "Book not on the shelf"),
+        (status = StatusCode::CONFLICT, description = "Status cannot move from the current one to the requested one, or was moved meanwhile"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Shelf"
+)]
+pub async fn put_shelf_status(
+    AuthUser(claims): AuthUser,
+    Path(book_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ShelfStatusUpdateRequest>
+) -> Result<Json<ShelfEntryResponse>, AppError> {
+    let cmd = ShelfStatusUpdateCommand {
+        user_id: claims.sub,
+        book_id,
+        status: request.status,
+    };
+    let service = ShelfService::from(&state);
+    let entry = service.update_status(cmd).await?;
+    Ok(Json(entry))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/shelf/me/{book_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Book removed from the shelf"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "Book not on the shelf"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Shelf"
+)]
+pub async fn delete_shelf_book(
+    AuthUser(claims): AuthUser,
+    Path(book_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = ShelfRemoveCommand { user_id: claims.sub, book_id };
+    let service = ShelfService::from(&state);
+    service.remove(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,4 +1,4 @@
-use axum::{Router, routing::{get}, extract::{Path, State}, Json, http::StatusCode};
+use axum::{Router, routing::{get}, extract::{Path, Query, State}, Json};
 
 use crate::command::source_command::{
     SourceCreateCommand,
@@ -9,7 +9,11 @@ use crate::command::source_command::{
 };
 use crate::dto::source_dto::{SourceCreateRequest, SourceResponse, SourceUpdateRequest};
 use crate::service::source_service::{SourceService, SourceServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
 use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
 
 
 pub fn routes() -> Router<AppState> {
@@ -22,42 +26,46 @@ pub fn routes() -> Router<AppState> {
 #[utoipa::path(
     get,
     path = "/api/services/source",
+    params(PaginationRequest),
     responses(
-        (status = StatusCode::OK, description = "List of sources", body = Vec<SourceResponse>),
+        (status = StatusCode::OK, description = "List of sources", body = PaginatedResponse<SourceResponse>),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
     tag = "Source"
 )]
-pub async fn get_sources(State(state): State<AppState>) -> Result<Json<Vec<SourceResponse>>, StatusCode> {
-    let cmd = SourceListCommand { pagination: None };
+pub async fn get_sources(
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<SourceResponse>>, AppError> {
+    let cmd = SourceListCommand { pagination: Some(pagination) };
     let service = SourceService::from(&state);
-    let sources = service.list(cmd).await;
-    match sources {
-        Ok(sources) => Ok(Json(sources)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let sources = service.list(cmd).await?;
+    Ok(Json(sources))
 }
 
 
 #[utoipa::path(
     post,
     path = "/api/services/source",
+    request_body = SourceCreateRequest,
     responses(
         (status = StatusCode::CREATED, description = "Source created", body = SourceResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::CONFLICT, description = "Source already exists"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Source"
 )]
-pub async fn post_source(State(state): State<AppState>, Json(source_create_request): Json<SourceCreateRequest>) -> Result<Json<SourceResponse>, StatusCode> {
+pub async fn post_source(_: RequireRole<Admin>, State(state): State<AppState>, ValidatedJson(source_create_request): ValidatedJson<SourceCreateRequest>) -> Result<Json<SourceResponse>, AppError> {
     let cmd = SourceCreateCommand { name: source_create_request.name, website: source_create_request.website };
     let service = SourceService::from(&state);
-    let source = service.create(cmd).await;
-    match source {
-        Ok(source) => Ok(Json(source)),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let source = service.create(cmd).await?;
+    Ok(Json(source))
 }
 
 
@@ -75,50 +83,40 @@ pub async fn post_source(State(state): State<AppState>, Json(source_create_reque
 pub async fn get_source(
     Path(source_id): Path<String>,
     State(state): State<AppState>
-) -> Result<Json<SourceResponse>, StatusCode> {
+) -> Result<Json<SourceResponse>, AppError> {
     let cmd = SourceGetCommand { id: source_id };
     let service = SourceService::from(&state);
-    let source = service.get(cmd).await;
-    match source {
-        Ok(source) => {
-            match source {
-                Some(source) => Ok(Json(source)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let source = service.get(cmd).await?;
+    Ok(Json(source))
 }
 
 
 #[utoipa::path(
     put,
     path = "/api/services/source/{source_id}",
+    request_body = SourceUpdateRequest,
     responses(
         (status = StatusCode::OK, description = "Source updated", body = SourceResponse),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
         (status = StatusCode::NOT_FOUND, description = "Source not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Source"
 )]
 pub async fn put_source(
+    _: RequireRole<Admin>,
     Path(source_id): Path<String>,
     State(state): State<AppState>,
-    Json(source_update_request): Json<SourceUpdateRequest>
-) -> Result<Json<SourceResponse>, StatusCode> {
+    ValidatedJson(source_update_request): ValidatedJson<SourceUpdateRequest>
+) -> Result<Json<SourceResponse>, AppError> {
     let cmd = SourceUpdateCommand { name: source_id, website: source_update_request.website };
     let service = SourceService::from(&state);
-    let source = service.update(cmd).await;
-    match source {
-        Ok(source) => {
-            match source {
-                Some(source) => Ok(Json(source)),
-                None => Err(StatusCode::NOT_FOUND)
-            }
-        },
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    let source = service.update(cmd).await?;
+    Ok(Json(source))
 }
 
 
@@ -129,19 +127,19 @@ pub async fn put_source(
         (status = StatusCode::NO_CONTENT, description = "Source deleted"),
         (status = StatusCode::BAD_REQUEST, description = "Bad request"),
         (status = StatusCode::NOT_FOUND, description = "Source not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
+    security(("bearer_auth" = [])),
     tag = "Source"
 )]
 pub async fn delete_source(
+    _: RequireRole<Admin>,
     Path(source_id): Path<String>,
     State(state): State<AppState>
-) -> Result<(), StatusCode> {
+) -> Result<(), AppError> {
     let cmd = SourceDeleteCommand { id: source_id };
     let service = SourceService::from(&state);
-    let result = service.delete(cmd).await;
-    match result {
-        Ok(_) => Ok(()),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR)
-    }
+    service.delete(cmd).await
 }
@@ -0,0 +1,189 @@
+use axum::{Router, routing::{get, post, put}, extract::State, Json, http::StatusCode};
+
+use crate::command::user_command::{
+    UserCreateCommand, UserDeleteCommand, UserGetCommand, UserPasswordUpdateCommand, UserPreferenceUpdateCommand,
+    UserUpdateCommand,
+};
+use crate::dto::user_dto::{
+    UserCreateRequest, UserPasswordUpdateRequest, UserPreferenceUpdateRequest, UserResponse, UserUpdateRequest,
+};
+use crate::service::user_service::{UserService, UserServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::ValidationErrorResponse;
+use crate::shared::security::auth_middleware::AuthUser;
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(post_user))
+        .route("/me", get(get_me).put(put_me).delete(delete_me))
+        .route("/me/password", put(put_me_password))
+        .route("/me/preference", put(put_me_preference))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/user",
+    request_body = UserCreateRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "User registered", body = UserResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::CONFLICT, description = "Username already taken"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "User"
+)]
+pub async fn post_user(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<UserCreateRequest>
+) -> Result<(StatusCode, Json<UserResponse>), AppError> {
+    let cmd = UserCreateCommand {
+        username: request.username,
+        password: request.password,
+        name: request.name,
+        image_url: request.image_url,
+    };
+    let service = UserService::from(&state);
+    let user = service.create(cmd).await?;
+    Ok((StatusCode::CREATED, Json(user)))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/user/me",
+    responses(
+        (status = StatusCode::OK, description = "Current user", body = UserResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "User"
+)]
+pub async fn get_me(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>
+) -> Result<Json<UserResponse>, AppError> {
+    let cmd = UserGetCommand { id: claims.sub };
+    let service = UserService::from(&state);
+    let user = service.get(cmd).await?;
+    Ok(Json(user))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/user/me",
+    request_body = UserUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Profile updated", body = UserResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "User"
+)]
+pub async fn put_me(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<UserUpdateRequest>
+) -> Result<Json<UserResponse>, AppError> {
+    let cmd = UserUpdateCommand { id: claims.sub, name: request.name, image_url: request.image_url };
+    let service = UserService::from(&state);
+    let user = service.update(cmd).await?;
+    Ok(Json(user))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/user/me/password",
+    request_body = UserPasswordUpdateRequest,
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Password changed"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing token or wrong current password"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "User"
+)]
+pub async fn put_me_password(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<UserPasswordUpdateRequest>
+) -> Result<StatusCode, AppError> {
+    let cmd = UserPasswordUpdateCommand {
+        id: claims.sub,
+        current_password: request.current_password,
+        new_password: request.new_password,
+    };
+    let service = UserService::from(&state);
+    service.update_password(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/user/me/preference",
+    request_body = UserPreferenceUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Preferences updated", body = UserResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "User"
+)]
+pub async fn put_me_preference(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<UserPreferenceUpdateRequest>
+) -> Result<Json<UserResponse>, AppError> {
+    let cmd = UserPreferenceUpdateCommand {
+        id: claims.sub,
+        authors: request.authors,
+        genres: request.genres,
+        languages: request.languages,
+    };
+    let service = UserService::from(&state);
+    let user = service.update_preference(cmd).await?;
+    Ok(Json(user))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/user/me",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Account deleted"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::NOT_FOUND, description = "User not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "User"
+)]
+pub async fn delete_me(
+    AuthUser(claims): AuthUser,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = UserDeleteCommand { id: claims.sub };
+    let service = UserService::from(&state);
+    service.delete(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,212 @@
+use axum::{Router, routing::{get, put}, extract::{Path, Query, State}, Json, http::StatusCode};
+
+use crate::command::work_command::{
+    WorkCreateCommand, WorkDeleteCommand, WorkEditionAttachCommand, WorkEditionDetachCommand, WorkGetCommand, WorkListCommand,
+    WorkUpdateCommand,
+};
+use crate::dto::work_dto::{WorkCreateRequest, WorkDetailResponse, WorkResponse, WorkUpdateRequest};
+use crate::service::work_service::{WorkService, WorkServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest, ValidationErrorResponse};
+use crate::shared::security::role_guard::{Admin, RequireRole};
+use crate::shared::state::AppState;
+use crate::shared::validation::validated_json::ValidatedJson;
+
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_works).post(post_work))
+        .route("/{work_id}", get(get_work).put(put_work).delete(delete_work))
+        .route("/{work_id}/editions/{book_id}", put(put_work_edition).delete(delete_work_edition))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/work",
+    params(PaginationRequest),
+    responses(
+        (status = StatusCode::OK, description = "List of works", body = PaginatedResponse<WorkResponse>),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Work"
+)]
+pub async fn get_works(
+    Query(pagination): Query<PaginationRequest>,
+    State(state): State<AppState>
+) -> Result<Json<PaginatedResponse<WorkResponse>>, AppError> {
+    let cmd = WorkListCommand { pagination: Some(pagination) };
+    let service = WorkService::from(&state);
+    let works = service.list(cmd).await?;
+    Ok(Json(works))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/api/services/work",
+    request_body = WorkCreateRequest,
+    responses(
+        (status = StatusCode::CREATED, description = "Work created", body = WorkDetailResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::NOT_FOUND, description = "Edition not found"),
+        (status = StatusCode::CONFLICT, description = "An edition belongs to another work"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Work"
+)]
+pub async fn post_work(
+    _: RequireRole<Admin>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<WorkCreateRequest>
+) -> Result<(StatusCode, Json<WorkDetailResponse>), AppError> {
+    let cmd = WorkCreateCommand {
+        title: request.title,
+        description: request.description,
+        original_language: request.original_language,
+        edition_ids: request.edition_ids,
+    };
+    let service = WorkService::from(&state);
+    let work = service.create(cmd).await?;
+    Ok((StatusCode::CREATED, Json(work)))
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/services/work/{work_id}",
+    responses(
+        (status = StatusCode::OK, description = "Work with its editions, availability and rating", body = WorkDetailResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Work not found"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    tag = "Work"
+)]
+pub async fn get_work(
+    Path(work_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<Json<WorkDetailResponse>, AppError> {
+    let cmd = WorkGetCommand { id: work_id };
+    let service = WorkService::from(&state);
+    let work = service.get(cmd).await?;
+    Ok(Json(work))
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/work/{work_id}",
+    request_body = WorkUpdateRequest,
+    responses(
+        (status = StatusCode::OK, description = "Work updated", body = WorkDetailResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = StatusCode::NOT_FOUND, description = "Work not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Work"
+)]
+pub async fn put_work(
+    _: RequireRole<Admin>,
+    Path(work_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<WorkUpdateRequest>
+) -> Result<Json<WorkDetailResponse>, AppError> {
+    let cmd = WorkUpdateCommand {
+        id: work_id,
+        title: request.title,
+        description: request.description,
+        original_language: request.original_language,
+    };
+    let service = WorkService::from(&state);
+    let work = service.update(cmd).await?;
+    Ok(Json(work))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/work/{work_id}",
+    responses(
+        (status = StatusCode::NO_CONTENT, description = "Work deleted, its editions stay in the catalog"),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Work not found"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Work"
+)]
+pub async fn delete_work(
+    _: RequireRole<Admin>,
+    Path(work_id): Path<String>,
+    State(state): State<AppState>
+) -> Result<StatusCode, AppError> {
+    let cmd = WorkDeleteCommand { id: work_id };
+    let service = WorkService::from(&state);
+    service.delete(cmd).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+
+#[utoipa::path(
+    put,
+    path = "/api/services/work/{work_id}/editions/{book_id}",
+    responses(
+        (status = StatusCode::OK, description = "Book attached as an edition of the work", body = WorkDetailResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Work or book not found"),
+        (status = StatusCode::CONFLICT, description = "The book is an edition of another work"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Work"
+)]
+pub async fn put_work_edition(
+    _: RequireRole<Admin>,
+    Path((work_id, book_id)): Path<(String, String)>,
+    State(state): State<AppState>
+) -> Result<Json<WorkDetailResponse>, AppError> {
+    let cmd = WorkEditionAttachCommand { id: work_id, book_id };
+    let service = WorkService::from(&state);
+    let work = service.attach_edition(cmd).await?;
+    Ok(Json(work))
+}
+
+
+#[utoipa::path(
+    delete,
+    path = "/api/services/work/{work_id}/editions/{book_id}",
+    responses(
+        (status = StatusCode::OK, description = "Edition detached, the book stays in the catalog", body = WorkDetailResponse),
+        (status = StatusCode::BAD_REQUEST, description = "Bad request"),
+        (status = StatusCode::NOT_FOUND, description = "Work not found or the book is not one of its editions"),
+        (status = StatusCode::UNAUTHORIZED, description = "Missing or invalid token"),
+        (status = StatusCode::FORBIDDEN, description = "Admin role required"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Work"
+)]
+pub async fn delete_work_edition(
+    _: RequireRole<Admin>,
+    Path((work_id, book_id)): Path<(String, String)>,
+    State(state): State<AppState>
+) -> Result<Json<WorkDetailResponse>, AppError> {
+    let cmd = WorkEditionDetachCommand { id: work_id, book_id };
+    let service = WorkService::from(&state);
+    let work = service.detach_edition(cmd).await?;
+    Ok(Json(work))
+}
@@ -0,0 +1,39 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::shared::constant::PASSWORD_LENGTH_MAX;
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+}
+
+impl TokenResponse {
+    pub fn bearer(access_token: String, expires_in: i64) -> Self {
+        Self {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+        }
+    }
+}
+
+
+impl Validate for LoginRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("username", rules::length(&self.username, 1, 32))
+            .field("password", rules::length(&self.password, 1, PASSWORD_LENGTH_MAX))
+            .finish()
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::dto::external_id_dto::ExternalIdResponse;
+use crate::model::author_model::Author;
+use crate::model::book_model::BookEmbed;
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorResponse {
+    pub id: String,
+    pub name: String,
+    pub image_url: String,
+    pub description: String,
+    pub external_ids: Vec<ExternalIdResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Author> for AuthorResponse {
+    fn from(author: Author) -> Self {
+        Self {
+            id: author.id.map(|id| id.to_hex()).unwrap_or_default(),
+            name: author.name,
+            image_url: author.image_url,
+            description: author.description,
+            external_ids: ExternalIdResponse::list(author.external_id),
+            created_at: author.created_at,
+            updated_at: author.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorBookResponse {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+}
+
+impl From<BookEmbed> for AuthorBookResponse {
+    fn from(book: BookEmbed) -> Self {
+        Self {
+            id: book.book_id.to_hex(),
+            title: book.title,
+            description: book.description,
+            image: book.image,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorCreateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub image_url: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorUpdateRequest {
+    pub image_url: Option<String>,
+    pub description: Option<String>,
+}
+
+
+impl Validate for AuthorCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("name", rules::length(&self.name, 1, 256))
+            .field("image_url", rules::optional(Some(self.image_url.as_str()).filter(|url| !url.is_empty()), rules::url))
+            .field("description", rules::length(&self.description, 0, 10000))
+            .finish()
+    }
+}
+
+impl Validate for AuthorUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("image_url", rules::optional(self.image_url.as_deref(), rules::url))
+            .field("description", rules::optional(self.description.as_deref(), |description| rules::length(description, 0, 10000)))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+
+    #[test]
+    fn create_request_only_needs_a_name() {
+        let request: AuthorCreateRequest = serde_json::from_str(r#"{ "name": "Ursula K. Le Guin" }"#).unwrap();
+
+        assert_eq!(request.image_url, "");
+        assert_eq!(request.description, "");
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn create_request_rejects_an_empty_name_and_a_bad_image_url() {
+        let request = AuthorCreateRequest {
+            name: String::new(),
+            image_url: "not a url".to_string(),
+            description: String::new(),
+        };
+
+        let fields = request.validate().unwrap_err().into_fields();
+        assert!(fields.contains_key("name"));
+        assert!(fields.contains_key("image_url"));
+    }
+
+    #[test]
+    fn author_book_response_renders_the_book_id() {
+        let response = AuthorBookResponse::from(BookEmbed {
+            book_id: ObjectId::parse_str("65f0c0ffee0000000000b001").unwrap(),
+            title: "A Wizard of Earthsea".to_string(),
+            description: None,
+            image: None,
+        });
+
+        assert_eq!(response.id, "65f0c0ffee0000000000b001");
+        assert_eq!(response.title, "A Wizard of Earthsea");
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::command::book_command::BookFilterCommand;
+use crate::dto::external_id_dto::ExternalIdResponse;
+use crate::model::author_model::AuthorEmbed;
+use crate::model::book_model::{Book, BookFacets, BookFormat, BookImageSource, BookPreviewSource, BookRating, FacetCount};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
+use crate::shared::constant::{REVIEW_SCORE_MAX, REVIEW_SCORE_MIN};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookMediaResponse {
+    pub url: String,
+    pub source: String,
+}
+
+impl From<BookImageSource> for BookMediaResponse {
+    fn from(image: BookImageSource) -> Self {
+        Self { url: image.url, source: image.source.name }
+    }
+}
+
+impl From<BookPreviewSource> for BookMediaResponse {
+    fn from(preview: BookPreviewSource) -> Self {
+        Self { url: preview.url, source: preview.source.name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookAuthorResponse {
+    pub id: String,
+    pub name: String,
+    pub image_url: String,
+}
+
+impl From<AuthorEmbed> for BookAuthorResponse {
+    fn from(author: AuthorEmbed) -> Self {
+        Self { id: author.id.to_hex(), name: author.name, image_url: author.image_url }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookRatingResponse {
+    pub average: f64,
+    pub count: i64,
+    /// Number of reviews per score, every score from 1 to 5 is present.
+    pub histogram: BTreeMap<String, i64>,
+}
+
+impl From<BookRating> for BookRatingResponse {
+    fn from(rating: BookRating) -> Self {
+        let histogram = (REVIEW_SCORE_MIN..=REVIEW_SCORE_MAX)
+            .map(|score| {
+                let key = score.to_string();
+                let count = rating.histogram.get(&key).copied().unwrap_or(0);
+                (key, count)
+            })
+            .collect();
+
+        Self { average: rating.average, count: rating.count, histogram }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookResponse {
+    pub id: String,
+    pub isbn: String,
+    pub isbn13: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub num_pages: Option<i32>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub format: BookFormat,
+    pub images: Vec<BookMediaResponse>,
+    pub preview: Vec<BookMediaResponse>,
+    pub genres: Vec<String>,
+    pub authors: Vec<BookAuthorResponse>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+    pub rating: BookRatingResponse,
+    pub external_ids: Vec<ExternalIdResponse>,
+    /// Work grouping this edition with the other editions and translations
+    pub work_id: Option<String>,
+}
+
+impl From<Book> for BookResponse {
+    fn from(book: Book) -> Self {
+        Self {
+            id: book.id.map(|id| id.to_hex()).unwrap_or_default(),
+            isbn: book.isbn,
+            isbn13: book.isbn13,
+            title: book.title,
+            subtitle: book.subtitle,
+            description: book.description,
+            num_pages: book.num_pages,
+            published_date: book.published_date,
+            format: book.format,
+            images: book.images.into_iter().map(BookMediaResponse::from).collect(),
+            preview: book.preview.into_iter().map(BookMediaResponse::from).collect(),
+            genres: book.genres.into_iter().map(|g| g.name).collect(),
+            authors: book.authors.into_iter().map(BookAuthorResponse::from).collect(),
+            publishers: book.publishers.into_iter().map(|p| p.name).collect(),
+            languages: book.languages,
+            rating: BookRatingResponse::from(book.rating),
+            external_ids: ExternalIdResponse::list(book.external_id),
+            work_id: book.work_id.map(|id| id.to_hex()),
+        }
+    }
+}
+
+/// Catalog filters of `GET /book`. List values are comma-separated alternatives.
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+pub struct BookFilterQuery {
+    /// Genre names
+    #[param(example = "fantasy,science-fiction")]
+    pub genres: Option<String>,
+    /// Author ids
+    pub authors: Option<String>,
+    /// Publisher names
+    pub publishers: Option<String>,
+    /// Language codes
+    #[param(example = "en,fr")]
+    pub languages: Option<String>,
+    /// Formats: Paperback, Hardcover, EBook or Audiobook
+    #[param(example = "EBook,Audiobook")]
+    pub formats: Option<String>,
+    pub pages_min: Option<i32>,
+    pub pages_max: Option<i32>,
+    pub published_from: Option<DateTime<Utc>>,
+    pub published_to: Option<DateTime<Utc>>,
+}
+
+fn split_list(list: Option<String>) -> Vec<String> {
+    list.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl TryFrom<BookFilterQuery> for BookFilterCommand {
+    type Error = AppError;
+
+    fn try_from(query: BookFilterQuery) -> Result<Self, AppError> {
+        let formats = split_list(query.formats)
+            .iter()
+            .map(|name| BookFormat::from_name(name).ok_or_else(|| AppError::Validation(format!("Unknown book format: {}", name))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            genres: split_list(query.genres),
+            author_ids: split_list(query.authors),
+            publishers: split_list(query.publishers),
+            languages: split_list(query.languages),
+            formats,
+            pages_min: query.pages_min,
+            pages_max: query.pages_max,
+            published_from: query.published_from,
+            published_to: query.published_to,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FacetCountResponse {
+    pub value: String,
+    /// Display name when the value is an id
+    pub label: Option<String>,
+    pub count: u64,
+}
+
+impl From<FacetCount> for FacetCountResponse {
+    fn from(facet: FacetCount) -> Self {
+        Self { value: facet.value, label: facet.label, count: facet.count }
+    }
+}
+
+/// Book counts per value, each facet counted under every filter but its own.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookFacetsResponse {
+    pub genres: Vec<FacetCountResponse>,
+    pub authors: Vec<FacetCountResponse>,
+    pub publishers: Vec<FacetCountResponse>,
+    pub languages: Vec<FacetCountResponse>,
+    pub formats: Vec<FacetCountResponse>,
+}
+
+impl From<BookFacets> for BookFacetsResponse {
+    fn from(facets: BookFacets) -> Self {
+        let convert = |counts: Vec<FacetCount>| counts.into_iter().map(FacetCountResponse::from).collect();
+        Self {
+            genres: convert(facets.genres),
+            authors: convert(facets.authors),
+            publishers: convert(facets.publishers),
+            languages: convert(facets.languages),
+            formats: convert(facets.formats),
+        }
+    }
+}
+
+/// A page of the catalog with the facet counts of the whole filtered list.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookCatalogResponse {
+    #[serde(flatten)]
+    pub page: PaginatedResponse<BookResponse>,
+    pub facets: BookFacetsResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookMediaRequest {
+    pub url: String,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookCreateRequest {
+    /// Either ISBN may be left out, but not both
+    #[serde(default)]
+    pub isbn: Option<String>,
+    #[serde(default)]
+    pub isbn13: Option<String>,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub num_pages: Option<i32>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub format: BookFormat,
+    #[serde(default)]
+    pub images: Vec<BookMediaRequest>,
+    #[serde(default)]
+    pub preview: Vec<BookMediaRequest>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    pub author_ids: Vec<String>,
+    #[serde(default)]
+    pub publishers: Vec<String>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookUpdateRequest {
+    /// Either ISBN may be left out, but not both
+    #[serde(default)]
+    pub isbn: Option<String>,
+    #[serde(default)]
+    pub isbn13: Option<String>,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub num_pages: Option<i32>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub format: BookFormat,
+    #[serde(default)]
+    pub images: Vec<BookMediaRequest>,
+    #[serde(default)]
+    pub preview: Vec<BookMediaRequest>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    pub author_ids: Vec<String>,
+    #[serde(default)]
+    pub publishers: Vec<String>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+
+/// Books are identified by at least one of their ISBNs.
+fn isbn_present(isbn: &Option<String>, isbn13: &Option<String>) -> Result<(), String> {
+    if isbn.is_some() || isbn13.is_some() {
+        Ok(())
+    } else {
+        Err("isbn or isbn13 is required".to_string())
+    }
+}
+
+impl Validate for BookCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("isbn", rules::optional(self.isbn.as_deref(), rules::isbn10))
+            .field("isbn13", rules::optional(self.isbn13.as_deref(), rules::isbn13))
+            .field("isbn", isbn_present(&self.isbn, &self.isbn13))
+            .field("title", rules::length(&self.title, 1, 512))
+            .field("subtitle", rules::optional(self.subtitle.as_deref(), |subtitle| rules::length(subtitle, 0, 512)))
+            .field("num_pages", rules::optional(self.num_pages.as_ref(), |pages| rules::range(*pages, 1, 100_000)))
+            .each("images", &self.images, |image| rules::url(&image.url))
+            .each("preview", &self.preview, |preview| rules::url(&preview.url))
+            .each("genres", &self.genres, |genre| rules::slug(genre))
+            .each("author_ids", &self.author_ids, |id| rules::object_id(id))
+            .each("publishers", &self.publishers, |publisher| rules::slug(publisher))
+            .each("languages", &self.languages, |code| rules::iso639(code))
+            .finish()
+    }
+}
+
+impl Validate for BookUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("isbn", rules::optional(self.isbn.as_deref(), rules::isbn10))
+            .field("isbn13", rules::optional(self.isbn13.as_deref(), rules::isbn13))
+            .field("isbn", isbn_present(&self.isbn, &self.isbn13))
+            .field("title", rules::length(&self.title, 1, 512))
+            .field("subtitle", rules::optional(self.subtitle.as_deref(), |subtitle| rules::length(subtitle, 0, 512)))
+            .field("num_pages", rules::optional(self.num_pages.as_ref(), |pages| rules::range(*pages, 1, 100_000)))
+            .each("images", &self.images, |image| rules::url(&image.url))
+            .each("preview", &self.preview, |preview| rules::url(&preview.url))
+            .each("genres", &self.genres, |genre| rules::slug(genre))
+            .each("author_ids", &self.author_ids, |id| rules::object_id(id))
+            .each("publishers", &self.publishers, |publisher| rules::slug(publisher))
+            .each("languages", &self.languages, |code| rules::iso639(code))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+    use crate::model::genre_model::GenreEmbed;
+    use crate::model::publisher_model::PublisherEmbed;
+    use crate::model::source_model::SourceEmbed;
+
+    fn book() -> Book {
+        Book {
+            id: Some(ObjectId::parse_str("65f0c0ffee0000000000b001").unwrap()),
+            isbn: "0306406152".to_string(),
+            isbn13: "9780306406157".to_string(),
+            title: "A Book".to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: Some(320),
+            published_date: None,
+            format: BookFormat::Hardcover,
+            images: vec![BookImageSource {
+                url: "https://covers.example.org/b001.jpg".to_string(),
+                source: SourceEmbed { name: "openlibrary".to_string() },
+            }],
+            preview: vec![],
+            genres: vec![GenreEmbed { name: "fantasy".to_string() }],
+            authors: vec![AuthorEmbed {
+                id: ObjectId::parse_str("65f0c0ffee0000000000a001").unwrap(),
+                name: "An Author".to_string(),
+                image_url: String::new(),
+            }],
+            publishers: vec![PublisherEmbed { name: "tor".to_string() }],
+            languages: vec!["en".to_string()],
+            reviews: vec![],
+            rating: BookRating::default(),
+            external_id: None,
+            work_id: None,
+        }
+    }
+
+    #[test]
+    fn response_flattens_the_embedded_documents() {
+        let response = BookResponse::from(book());
+
+        assert_eq!(response.id, "65f0c0ffee0000000000b001");
+        assert_eq!(response.genres, vec!["fantasy".to_string()]);
+        assert_eq!(response.publishers, vec!["tor".to_string()]);
+        assert_eq!(response.authors[0].id, "65f0c0ffee0000000000a001");
+        assert_eq!(response.images[0].source, "openlibrary");
+    }
+
+    #[test]
+    fn rating_histogram_lists_every_score() {
+        let mut rating = BookRating { average: 4.0, count: 2, sum: 8.0, histogram: BTreeMap::new() };
+        rating.histogram.insert("3".to_string(), 1);
+        rating.histogram.insert("5".to_string(), 1);
+
+        let response = BookRatingResponse::from(rating);
+        let counts: Vec<(&str, i64)> = response.histogram.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        assert_eq!(counts, vec![("1", 0), ("2", 0), ("3", 1), ("4", 0), ("5", 1)]);
+    }
+
+    #[test]
+    fn format_names_round_trip() {
+        for format in BookFormat::ALL {
+            assert_eq!(BookFormat::from_name(format.name()).map(|f| f.name()), Some(format.name()));
+        }
+        assert!(BookFormat::from_name("Scroll").is_none());
+    }
+
+    fn create_request(json: serde_json::Value) -> BookCreateRequest {
+        let mut body = serde_json::json!({ "title": "A Book", "format": "Paperback" });
+        body.as_object_mut().unwrap().extend(json.as_object().unwrap().clone());
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn either_isbn_is_enough() {
+        assert!(create_request(serde_json::json!({ "isbn": "0-306-40615-2" })).validate().is_ok());
+        assert!(create_request(serde_json::json!({ "isbn13": "978-0-306-40615-7" })).validate().is_ok());
+    }
+
+    #[test]
+    fn a_book_without_any_isbn_is_rejected() {
+        let fields = create_request(serde_json::json!({})).validate().unwrap_err().into_fields();
+
+        assert_eq!(fields["isbn"], vec!["isbn or isbn13 is required"]);
+    }
+
+    #[test]
+    fn invalid_fields_are_reported_by_path() {
+        let request = create_request(serde_json::json!({
+            "isbn": "0306406153",
+            "title": " ",
+            "num_pages": 0,
+            "genres": ["fantasy", "science fiction"],
+            "languages": ["en", "english"],
+            "images": [{ "url": "ftp://example.com/cover.jpg", "source": "upload" }],
+        }));
+
+        let fields = request.validate().unwrap_err().into_fields();
+        let paths: Vec<&str> = fields.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["genres[1]", "images[0]", "isbn", "languages[1]", "num_pages", "title"]);
+    }
+
+    #[test]
+    fn filter_lists_are_split_on_commas_and_trimmed() {
+        let query = BookFilterQuery {
+            genres: Some("fantasy, science-fiction,,".to_string()),
+            formats: Some("EBook,Audiobook".to_string()),
+            pages_min: Some(100),
+            ..Default::default()
+        };
+
+        let cmd = BookFilterCommand::try_from(query).unwrap();
+
+        assert_eq!(cmd.genres, vec!["fantasy", "science-fiction"]);
+        assert_eq!(cmd.formats.iter().map(BookFormat::name).collect::<Vec<_>>(), vec!["EBook", "Audiobook"]);
+        assert!(cmd.author_ids.is_empty());
+        assert_eq!(cmd.pages_min, Some(100));
+    }
+
+    #[test]
+    fn unknown_formats_are_rejected() {
+        let query = BookFilterQuery { formats: Some("Scroll".to_string()), ..Default::default() };
+
+        assert!(matches!(BookFilterCommand::try_from(query), Err(AppError::Validation(_))));
+    }
+}
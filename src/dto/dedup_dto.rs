@@ -0,0 +1,133 @@
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::model::dedup_model::{DuplicateCandidate, DuplicateReason, MergeReport};
+use crate::shared::constant::{DEDUP_LIMIT_DEFAULT, DEDUP_LIMIT_MAX, DEDUP_MERGE_MAX, DEDUP_MIN_SCORE_DEFAULT};
+use crate::shared::models::response::limit;
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+pub struct DuplicateQuery {
+    /// Name similarity from which two records are proposed, between 0 and 1
+    #[param(example = 0.85)]
+    pub min_score: Option<f64>,
+    /// Candidates returned, best first, capped at `DEDUP_LIMIT_MAX`
+    #[param(example = 50)]
+    pub limit: Option<u32>,
+}
+
+impl DuplicateQuery {
+    pub fn min_score(&self) -> f64 {
+        self.min_score.filter(|score| score.is_finite()).unwrap_or(DEDUP_MIN_SCORE_DEFAULT).clamp(0.0, 1.0)
+    }
+
+    pub fn limit(&self) -> u64 {
+        limit(self.limit, DEDUP_LIMIT_DEFAULT, DEDUP_LIMIT_MAX)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateRecordResponse {
+    pub id: String,
+    /// Author name or book title
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateCandidateResponse {
+    pub records: Vec<DuplicateRecordResponse>,
+    /// 1 for a shared ISBN or external id, the name similarity otherwise
+    pub score: f64,
+    pub reasons: Vec<DuplicateReason>,
+}
+
+impl From<DuplicateCandidate> for DuplicateCandidateResponse {
+    fn from(candidate: DuplicateCandidate) -> Self {
+        Self {
+            records: candidate.ids
+                .into_iter()
+                .zip(candidate.names)
+                .map(|(id, name)| DuplicateRecordResponse { id: id.to_hex(), name })
+                .collect(),
+            score: candidate.score,
+            reasons: candidate.reasons,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MergeRequest {
+    /// Records merged into the one in the path, then deleted
+    pub merged_ids: Vec<String>,
+}
+
+impl Validate for MergeRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let count = if self.merged_ids.is_empty() || self.merged_ids.len() > DEDUP_MERGE_MAX {
+            Err(format!("must hold between 1 and {} ids", DEDUP_MERGE_MAX))
+        } else {
+            Ok(())
+        };
+        Validator::new()
+            .field("merged_ids", count)
+            .each("merged_ids", &self.merged_ids, |id| rules::object_id(id))
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MergeReportResponse {
+    pub survivor_id: String,
+    /// Records merged into the survivor, now deleted
+    pub merged_ids: Vec<String>,
+    /// Books whose author list now names the survivor
+    pub books_updated: u64,
+    /// Shelves whose entry moved to the surviving book
+    pub shelves_updated: u64,
+    /// Readers whose preferred authors now name the surviving author
+    pub preferences_updated: u64,
+    pub reviews_moved: u64,
+    /// Reviews dropped because their author also reviewed the surviving book
+    pub reviews_dropped: u64,
+}
+
+impl From<MergeReport> for MergeReportResponse {
+    fn from(report: MergeReport) -> Self {
+        Self {
+            survivor_id: report.survivor_id.to_hex(),
+            merged_ids: report.merged_ids.iter().map(|id| id.to_hex()).collect(),
+            books_updated: report.books_updated,
+            shelves_updated: report.shelves_updated,
+            preferences_updated: report.preferences_updated,
+            reviews_moved: report.reviews_moved,
+            reviews_dropped: report.reviews_dropped,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_defaults_and_clamps_its_bounds() {
+        let query = DuplicateQuery { min_score: Some(1.5), limit: Some(10_000) };
+
+        assert_eq!(query.min_score(), 1.0);
+        assert_eq!(query.limit(), DEDUP_LIMIT_MAX);
+        assert_eq!(DuplicateQuery::default().min_score(), DEDUP_MIN_SCORE_DEFAULT);
+        assert_eq!(DuplicateQuery { min_score: Some(f64::NAN), limit: None }.min_score(), DEDUP_MIN_SCORE_DEFAULT);
+    }
+
+    #[test]
+    fn merge_requests_need_valid_ids() {
+        let valid = MergeRequest { merged_ids: vec!["65f0c0ffee0000000000b001".to_string()] };
+        let invalid = MergeRequest { merged_ids: vec!["65f0c0ffee0000000000b001".to_string(), "nope".to_string()] };
+
+        assert!(valid.validate().is_ok());
+        assert!(invalid.validate().is_err());
+        assert!(MergeRequest { merged_ids: vec![] }.validate().is_err());
+    }
+}
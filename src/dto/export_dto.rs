@@ -0,0 +1,12 @@
+use serde::{Serialize, Deserialize};
+use utoipa::IntoParams;
+
+use crate::model::export_model::ExportFormat;
+
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// `json_lines` or `csv` for the collections, `graphml` or `cypher` for the graph.
+    /// Defaults to the first of them.
+    pub format: Option<ExportFormat>,
+}
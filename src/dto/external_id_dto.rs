@@ -0,0 +1,68 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::external_id_model::{ExternalId, ExternalProvider};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExternalIdResponse {
+    pub provider: ExternalProvider,
+    pub id: String,
+}
+
+impl ExternalIdResponse {
+    pub fn list(external_id: Option<ExternalId>) -> Vec<Self> {
+        external_id
+            .map(|external_id| {
+                external_id
+                    .entries()
+                    .into_iter()
+                    .map(|(provider, id)| Self { provider, id: id.to_string() })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExternalIdAttachRequest {
+    /// Id of the record in the provider's catalog
+    pub id: String,
+}
+
+
+impl Validate for ExternalIdAttachRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("id", rules::length(self.id.trim(), 1, 256))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_keeps_the_set_ids_in_provider_order() {
+        let mut external_id = ExternalId::from_provider(ExternalProvider::Kaggle, "k-42");
+        external_id.set(ExternalProvider::GoodReads, "1234");
+
+        let ids = ExternalIdResponse::list(Some(external_id));
+
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0].provider, ExternalProvider::GoodReads);
+        assert_eq!(ids[0].id, "1234");
+        assert_eq!(ids[1].provider, ExternalProvider::Kaggle);
+        assert!(ExternalIdResponse::list(None).is_empty());
+    }
+
+    #[test]
+    fn attach_request_rejects_a_blank_id() {
+        let request = ExternalIdAttachRequest { id: "  ".to_string() };
+
+        assert!(request.validate().unwrap_err().into_fields().contains_key("id"));
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::dto::review_dto::ReviewUserResponse;
+use crate::model::book_model::BookReadStatus;
+use crate::model::follow_model::{FeedItem, FeedItemKind, FollowedUser};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FollowResponse {
+    pub user: ReviewUserResponse,
+    pub followed_at: DateTime<Utc>,
+}
+
+impl From<FollowedUser> for FollowResponse {
+    fn from(followed: FollowedUser) -> Self {
+        Self {
+            user: ReviewUserResponse::from(followed.user),
+            followed_at: followed.followed_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeedItemResponse {
+    pub kind: FeedItemKind,
+    pub user: ReviewUserResponse,
+    pub book_id: String,
+    pub title: Option<String>,
+    /// Set on reviews and ratings
+    pub score: Option<f32>,
+    /// Set on reviews
+    pub content: Option<String>,
+    /// Set on shelf changes
+    pub status: Option<BookReadStatus>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl From<FeedItem> for FeedItemResponse {
+    fn from(item: FeedItem) -> Self {
+        Self {
+            kind: item.kind,
+            user: ReviewUserResponse::from(item.user),
+            book_id: item.book_id.to_hex(),
+            title: item.title,
+            score: item.score,
+            content: item.content,
+            status: item.status,
+            occurred_at: item.occurred_at,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+    use crate::model::user_model::UserEmbed;
+
+    fn reader() -> UserEmbed {
+        UserEmbed {
+            id: ObjectId::parse_str("65f0c0ffee0000000000c001").unwrap(),
+            name: "A Reader".to_string(),
+            image_url: None,
+        }
+    }
+
+    #[test]
+    fn follow_response_shows_the_other_reader() {
+        let followed_at = Utc::now();
+
+        let response = FollowResponse::from(FollowedUser { user: reader(), followed_at });
+
+        assert_eq!(response.user.id, "65f0c0ffee0000000000c001");
+        assert_eq!(response.user.name, "A Reader");
+        assert_eq!(response.followed_at, followed_at);
+    }
+
+    #[test]
+    fn shelf_feed_item_carries_its_status() {
+        let item = FeedItem {
+            kind: FeedItemKind::Shelf,
+            user: reader(),
+            book_id: ObjectId::parse_str("65f0c0ffee0000000000b001").unwrap(),
+            title: Some("A Book".to_string()),
+            score: None,
+            content: None,
+            status: Some(BookReadStatus::InProgress),
+            occurred_at: Utc::now(),
+        };
+
+        let response = serde_json::to_value(FeedItemResponse::from(item)).unwrap();
+
+        assert_eq!(response["kind"], "Shelf");
+        assert_eq!(response["book_id"], "65f0c0ffee0000000000b001");
+        assert_eq!(response["status"], "InProgress");
+        assert!(response["score"].is_null());
+    }
+}
@@ -3,6 +3,7 @@ use utoipa::ToSchema;
 
 use crate::model::genre_model::Genre;
 use crate::model::metadata_model::{Metadata};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
 
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -57,3 +58,22 @@ pub struct GenreCreateRequest {
 pub struct GenreUpdateRequest {
     pub description: String,
 }
+
+
+impl Validate for GenreCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("name", rules::length(&self.name, 1, 64))
+            .field("name", rules::slug(&self.name))
+            .field("description", rules::length(&self.description, 0, 2000))
+            .finish()
+    }
+}
+
+impl Validate for GenreUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("description", rules::length(&self.description, 0, 2000))
+            .finish()
+    }
+}
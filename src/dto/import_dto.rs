@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::import_model::{ImportFormat, ImportJob, ImportJobStatus, ImportKind, ImportReport, ImportRowError};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportRequest {
+    /// Dump file, relative to the server's import directory (`IMPORT_DIR`)
+    #[schema(example = "goodreads_books.json")]
+    pub path: String,
+    pub kind: ImportKind,
+    /// Provider whose ids the dump uses, `good_reads` when absent
+    pub provider: Option<ExternalProvider>,
+    /// Guessed from the file extension when absent
+    pub format: Option<ImportFormat>,
+}
+
+impl Validate for ImportRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("path", rules::length(&self.path, 1, 4096))
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportRowErrorResponse {
+    pub line: u64,
+    pub message: String,
+}
+
+impl From<ImportRowError> for ImportRowErrorResponse {
+    fn from(error: ImportRowError) -> Self {
+        Self { line: error.line, message: error.message }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportReportResponse {
+    pub rows_read: u64,
+    pub rows_imported: u64,
+    /// Rows repeating an external id or ISBN seen earlier in the dump
+    pub duplicates: u64,
+    pub rows_failed: u64,
+    /// Records of the imported kind
+    pub created: u64,
+    pub updated: u64,
+    pub authors_created: u64,
+    pub genres_created: u64,
+    pub publishers_created: u64,
+    pub readers_created: u64,
+    pub error_count: u64,
+    /// The first errors, by dump line
+    pub errors: Vec<ImportRowErrorResponse>,
+}
+
+impl From<ImportReport> for ImportReportResponse {
+    fn from(report: ImportReport) -> Self {
+        Self {
+            rows_read: report.rows_read,
+            rows_imported: report.rows_imported,
+            duplicates: report.duplicates,
+            rows_failed: report.rows_failed,
+            created: report.created,
+            updated: report.updated,
+            authors_created: report.authors_created,
+            genres_created: report.genres_created,
+            publishers_created: report.publishers_created,
+            readers_created: report.readers_created,
+            error_count: report.error_count,
+            errors: report.errors.into_iter().map(ImportRowErrorResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportJobResponse {
+    pub id: String,
+    pub path: String,
+    pub kind: ImportKind,
+    pub provider: ExternalProvider,
+    pub format: ImportFormat,
+    pub status: ImportJobStatus,
+    pub report: ImportReportResponse,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ImportJob> for ImportJobResponse {
+    fn from(job: ImportJob) -> Self {
+        Self {
+            id: job.id.map(|id| id.to_hex()).unwrap_or_default(),
+            path: job.path,
+            kind: job.kind,
+            provider: job.provider,
+            format: job.format,
+            status: job.status,
+            report: job.report.into(),
+            error: job.error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
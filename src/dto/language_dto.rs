@@ -3,6 +3,7 @@ use utoipa::ToSchema;
 
 use crate::model::language_model::Language;
 use crate::model::metadata_model::Metadata;
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LanguageResponse {
@@ -40,3 +41,21 @@ pub struct LanguageCreateRequest {
 pub struct LanguageUpdateRequest {
     pub name: String,
 }
+
+
+impl Validate for LanguageCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("code", rules::iso639(&self.code))
+            .field("name", rules::length(&self.name, 1, 64))
+            .finish()
+    }
+}
+
+impl Validate for LanguageUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("name", rules::length(&self.name, 1, 64))
+            .finish()
+    }
+}
@@ -1,4 +1,19 @@
 pub mod genre_dto;
 pub mod source_dto;
 pub mod language_dto;
-pub mod publisher_dto;
\ No newline at end of file
+pub mod publisher_dto;
+pub mod auth_dto;
+pub mod user_dto;
+pub mod book_dto;
+pub mod author_dto;
+pub mod review_dto;
+pub mod shelf_dto;
+pub mod recommendation_dto;
+pub mod similarity_dto;
+pub mod follow_dto;
+pub mod search_dto;
+pub mod import_dto;
+pub mod external_id_dto;
+pub mod export_dto;
+pub mod dedup_dto;
+pub mod work_dto;
\ No newline at end of file
@@ -2,6 +2,7 @@ use serde::{Serialize, Deserialize};
 use utoipa::ToSchema;
 
 use crate::model::{publisher_model::Publisher, metadata_model::Metadata};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
 
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -52,3 +53,20 @@ pub struct PublisherUpdateRequest {
 }
 
 
+impl Validate for PublisherCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("name", rules::length(&self.name, 1, 128))
+            .field("name", rules::slug(&self.name))
+            .field("website", rules::url(&self.website))
+            .finish()
+    }
+}
+
+impl Validate for PublisherUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("website", rules::url(&self.website))
+            .finish()
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::dto::book_dto::BookResponse;
+use crate::shared::constant::{RECOMMENDATION_LIMIT_DEFAULT, RECOMMENDATION_LIMIT_MAX};
+use crate::shared::models::response::limit;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecommendationResponse {
+    pub book: BookResponse,
+    /// Weighted score in `[0, 1]`, boosted for preferred languages
+    pub score: f64,
+    /// Why the book was recommended, strongest signal first
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+pub struct RecommendationQuery {
+    /// Number of books to return, capped at `RECOMMENDATION_LIMIT_MAX`
+    #[param(example = 20)]
+    pub limit: Option<u32>,
+}
+
+impl RecommendationQuery {
+    pub fn limit(&self) -> u64 {
+        limit(self.limit, RECOMMENDATION_LIMIT_DEFAULT, RECOMMENDATION_LIMIT_MAX)
+    }
+}
+
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::review_model::Review;
+use crate::model::user_model::UserEmbed;
+use crate::shared::constant::{REVIEW_SCORE_MAX, REVIEW_SCORE_MIN};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewUserResponse {
+    pub id: String,
+    pub name: String,
+    pub image_url: Option<String>,
+}
+
+impl From<UserEmbed> for ReviewUserResponse {
+    fn from(user: UserEmbed) -> Self {
+        Self { id: user.id.to_hex(), name: user.name, image_url: user.image_url }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewResponse {
+    pub id: String,
+    pub book_id: String,
+    pub user: ReviewUserResponse,
+    pub content: String,
+    pub score: f32,
+    pub date_added: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<Review> for ReviewResponse {
+    fn from(review: Review) -> Self {
+        Self {
+            id: review.id.map(|id| id.to_hex()).unwrap_or_default(),
+            book_id: review.book_id.to_hex(),
+            user: ReviewUserResponse::from(review.user),
+            content: review.content,
+            score: review.score,
+            date_added: review.date_added,
+            updated_at: review.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewCreateRequest {
+    pub content: String,
+    /// Whole score from 1 to 5
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewUpdateRequest {
+    pub content: String,
+    /// Whole score from 1 to 5
+    pub score: f32,
+}
+
+
+impl Validate for ReviewCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("content", rules::length(&self.content, 0, 10000))
+            .field("score", Review::score_bucket(self.score).map(|_| ()).ok_or_else(|| {
+                format!("must be a whole number from {} to {}", REVIEW_SCORE_MIN, REVIEW_SCORE_MAX)
+            }))
+            .finish()
+    }
+}
+
+impl Validate for ReviewUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("content", rules::length(&self.content, 0, 10000))
+            .field("score", Review::score_bucket(self.score).map(|_| ()).ok_or_else(|| {
+                format!("must be a whole number from {} to {}", REVIEW_SCORE_MIN, REVIEW_SCORE_MAX)
+            }))
+            .finish()
+    }
+}
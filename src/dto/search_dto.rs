@@ -0,0 +1,68 @@
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::dto::author_dto::AuthorResponse;
+use crate::dto::book_dto::BookResponse;
+use crate::dto::genre_dto::GenreResponse;
+use crate::dto::publisher_dto::PublisherResponse;
+use crate::model::book_model::BookFormat;
+use crate::shared::constant::{SEARCH_LIMIT_DEFAULT, SEARCH_LIMIT_MAX};
+use crate::shared::models::response::limit;
+
+
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    /// Text matched against book titles and descriptions, author names, genres and publishers
+    #[param(example = "dune")]
+    pub q: String,
+    /// Only return books available in this language code
+    pub language: Option<String>,
+    /// Only return books in this format
+    pub format: Option<BookFormat>,
+    /// Only return books of this genre
+    pub genre: Option<String>,
+    /// Results per group, capped at `SEARCH_LIMIT_MAX`
+    #[param(example = 10)]
+    pub limit: Option<u32>,
+}
+
+impl SearchQuery {
+    pub fn limit(&self) -> u64 {
+        limit(self.limit, SEARCH_LIMIT_DEFAULT, SEARCH_LIMIT_MAX)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookSearchHitResponse {
+    pub book: BookResponse,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorSearchHitResponse {
+    pub author: AuthorResponse,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GenreSearchHitResponse {
+    pub genre: GenreResponse,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PublisherSearchHitResponse {
+    pub publisher: PublisherResponse,
+    pub score: f64,
+}
+
+/// Matches grouped by type, best first within each group.
+/// Scores are only comparable inside a group.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResponse {
+    pub query: String,
+    pub books: Vec<BookSearchHitResponse>,
+    pub authors: Vec<AuthorSearchHitResponse>,
+    pub genres: Vec<GenreSearchHitResponse>,
+    pub publishers: Vec<PublisherSearchHitResponse>,
+}
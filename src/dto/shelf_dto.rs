@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::model::book_model::{BookReadStatus, ShelfEntry};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfEntryResponse {
+    pub book_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub status: BookReadStatus,
+    pub added_at: DateTime<Utc>,
+    pub status_updated_at: DateTime<Utc>,
+}
+
+impl From<ShelfEntry> for ShelfEntryResponse {
+    fn from(entry: ShelfEntry) -> Self {
+        Self {
+            book_id: entry.book_id.to_hex(),
+            title: entry.title,
+            description: entry.description,
+            image: entry.image,
+            status: entry.status,
+            added_at: entry.added_at,
+            status_updated_at: entry.status_updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+pub struct ShelfQuery {
+    /// Only return books with this status
+    pub status: Option<BookReadStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfAddRequest {
+    pub book_id: String,
+    /// Defaults to `Unread`
+    #[serde(default)]
+    pub status: BookReadStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ShelfStatusUpdateRequest {
+    pub status: BookReadStatus,
+}
+
+
+impl Validate for ShelfAddRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("book_id", rules::object_id(&self.book_id))
+            .finish()
+    }
+}
+
+impl Validate for ShelfStatusUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_request_defaults_to_unread() {
+        let request: ShelfAddRequest = serde_json::from_str(r#"{"book_id":"64b7f0c2a1b2c3d4e5f60718"}"#).unwrap();
+
+        assert_eq!(request.status, BookReadStatus::Unread);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn add_request_rejects_a_malformed_book_id() {
+        let request = ShelfAddRequest { book_id: "not-an-id".to_string(), status: BookReadStatus::Read };
+
+        let fields = request.validate().unwrap_err().into_fields();
+        assert!(fields.contains_key("book_id"));
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::dto::author_dto::AuthorResponse;
+use crate::dto::book_dto::BookResponse;
+use crate::model::similarity_model::SimilarityScore;
+use crate::shared::constant::{SIMILAR_LIMIT_DEFAULT, SIMILAR_LIMIT_MAX};
+use crate::shared::models::response::limit;
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SimilarBookResponse {
+    pub book: BookResponse,
+    /// Weighted Jaccard index of co-readers and genres, in `[0, 1]`
+    pub score: f64,
+    pub shared_readers: i64,
+    pub shared_genres: i64,
+}
+
+impl SimilarBookResponse {
+    pub fn new(book: BookResponse, similarity: &SimilarityScore) -> Self {
+        Self {
+            book,
+            score: similarity.score,
+            shared_readers: similarity.shared_readers,
+            shared_genres: similarity.shared_genres,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SimilarAuthorResponse {
+    pub author: AuthorResponse,
+    /// Weighted Jaccard index of co-readers and genres across the authors' books, in `[0, 1]`
+    pub score: f64,
+    pub shared_readers: i64,
+    pub shared_genres: i64,
+}
+
+impl SimilarAuthorResponse {
+    pub fn new(author: AuthorResponse, similarity: &SimilarityScore) -> Self {
+        Self {
+            author,
+            score: similarity.score,
+            shared_readers: similarity.shared_readers,
+            shared_genres: similarity.shared_genres,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+pub struct SimilarQuery {
+    /// Number of results, capped at `SIMILAR_LIMIT_MAX`
+    #[param(example = 10)]
+    pub limit: Option<u32>,
+}
+
+impl SimilarQuery {
+    pub fn limit(&self) -> u64 {
+        limit(self.limit, SIMILAR_LIMIT_DEFAULT, SIMILAR_LIMIT_MAX)
+    }
+}
@@ -3,6 +3,7 @@ use utoipa::ToSchema;
 
 use crate::model::metadata_model::Metadata;
 use crate::model::source_model::Source;
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SourceResponse {
@@ -39,4 +40,23 @@ pub struct SourceCreateRequest {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SourceUpdateRequest {
     pub website: String,
-}
\ No newline at end of file
+}
+
+
+impl Validate for SourceCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("name", rules::length(&self.name, 1, 128))
+            .field("name", rules::slug(&self.name))
+            .field("website", rules::url(&self.website))
+            .finish()
+    }
+}
+
+impl Validate for SourceUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("website", rules::url(&self.website))
+            .finish()
+    }
+}
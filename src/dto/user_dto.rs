@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::user_model::{User, UserPreference};
+use crate::shared::constant::{PASSWORD_LENGTH_MAX, PASSWORD_LENGTH_MIN};
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+/// Public view of a `User`: ids rendered as hex strings and no password hash.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserResponse {
+    pub id: String,
+    pub username: String,
+    pub name: String,
+    pub image_url: Option<String>,
+    pub role: String,
+    pub preference: Option<UserPreferenceResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id.map(|id| id.to_hex()).unwrap_or_default(),
+            username: user.username,
+            name: user.name,
+            image_url: user.image_url,
+            role: user.role.kind().to_string(),
+            preference: user.preference.map(UserPreferenceResponse::from),
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserPreferenceResponse {
+    pub authors: Vec<String>,
+    pub genres: Vec<String>,
+    pub languages: Vec<String>,
+}
+
+impl From<UserPreference> for UserPreferenceResponse {
+    fn from(preference: UserPreference) -> Self {
+        Self {
+            authors: preference.authors,
+            genres: preference.genres,
+            languages: preference.languages,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserCreateRequest {
+    pub username: String,
+    pub password: String,
+    pub name: String,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserUpdateRequest {
+    pub name: Option<String>,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserPasswordUpdateRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserPreferenceUpdateRequest {
+    pub authors: Vec<String>,
+    pub genres: Vec<String>,
+    pub languages: Vec<String>,
+}
+
+
+impl Validate for UserCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("username", rules::length(&self.username, 3, 32))
+            .field("username", rules::slug(&self.username))
+            .field("username", if ExternalProvider::is_imported_username(&self.username) {
+                Err("is reserved for imported readers".to_string())
+            } else {
+                Ok(())
+            })
+            .field("password", rules::length(&self.password, PASSWORD_LENGTH_MIN, PASSWORD_LENGTH_MAX))
+            .field("name", rules::length(&self.name, 1, 128))
+            .field("image_url", rules::optional(self.image_url.as_deref(), rules::url))
+            .finish()
+    }
+}
+
+impl Validate for UserUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("name", rules::optional(self.name.as_deref(), |name| rules::length(name, 1, 128)))
+            .field("image_url", rules::optional(self.image_url.as_deref(), rules::url))
+            .finish()
+    }
+}
+
+impl Validate for UserPasswordUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("current_password", rules::length(&self.current_password, 1, PASSWORD_LENGTH_MAX))
+            .field("new_password", rules::length(&self.new_password, PASSWORD_LENGTH_MIN, PASSWORD_LENGTH_MAX))
+            .finish()
+    }
+}
+
+impl Validate for UserPreferenceUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .each("authors", &self.authors, |id| rules::object_id(id))
+            .each("genres", &self.genres, |genre| rules::slug(genre))
+            .each("languages", &self.languages, |code| rules::iso639(code))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+    use crate::model::user_model::{HashedPassword, UserRole};
+
+    fn user() -> User {
+        let now = Utc::now();
+        User {
+            id: Some(ObjectId::parse_str("65f0c0ffee0000000000beef").unwrap()),
+            username: "reader".to_string(),
+            password: HashedPassword::new("$argon2id$v=19$m=1024,t=1,p=1$c2FsdA$aGFzaA".to_string()),
+            name: "A Reader".to_string(),
+            image_url: None,
+            role: UserRole::Reader,
+            preference: Some(UserPreference {
+                authors: vec!["65f0c0ffee0000000000a001".to_string()],
+                genres: vec!["fantasy".to_string()],
+                languages: vec!["en".to_string()],
+            }),
+            shelf: None,
+            reviews: None,
+            external_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn response_renders_the_id_and_role_as_strings() {
+        let response = UserResponse::from(user());
+
+        assert_eq!(response.id, "65f0c0ffee0000000000beef");
+        assert_eq!(response.role, "reader");
+        assert_eq!(response.preference.unwrap().genres, vec!["fantasy".to_string()]);
+    }
+
+    #[test]
+    fn response_never_carries_the_password_hash() {
+        let json = serde_json::to_string(&UserResponse::from(user())).unwrap();
+
+        assert!(!json.contains("password"));
+        assert!(!json.contains("argon2id"));
+    }
+
+    #[test]
+    fn sign_up_rejects_the_usernames_of_imported_readers() {
+        let request = |username: &str| UserCreateRequest {
+            username: username.to_string(),
+            password: "correct horse".to_string(),
+            name: "A Reader".to_string(),
+            image_url: None,
+        };
+
+        assert!(request("kaggle-42").validate().is_err());
+        assert!(request("Good_Reads-42").validate().is_err());
+        assert!(request("kaggle_fan").validate().is_ok());
+    }
+
+    #[test]
+    fn password_hash_is_redacted_from_debug_output() {
+        assert!(!format!("{:?}", user()).contains("argon2id"));
+    }
+}
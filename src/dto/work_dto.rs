@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+use crate::dto::book_dto::{BookAuthorResponse, BookRatingResponse};
+use crate::model::book_model::{Book, BookFormat};
+use crate::model::work_model::{work_authors, work_rating, Work, WorkAvailability};
+use crate::shared::constant::WORK_EDITIONS_MAX;
+use crate::shared::validation::{rules, Validate, ValidationErrors, Validator};
+
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkResponse {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub original_language: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Work> for WorkResponse {
+    fn from(work: Work) -> Self {
+        Self {
+            id: work.id.map(|id| id.to_hex()).unwrap_or_default(),
+            title: work.title,
+            description: work.description,
+            original_language: work.original_language,
+            created_at: work.created_at,
+            updated_at: work.updated_at,
+        }
+    }
+}
+
+/// One edition of a work: what can be bought or borrowed, in which format and language.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkEditionResponse {
+    pub id: String,
+    pub isbn: String,
+    pub isbn13: String,
+    pub title: String,
+    pub format: BookFormat,
+    pub languages: Vec<String>,
+    pub publishers: Vec<String>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub image: Option<String>,
+    /// In none of the work's original language
+    pub translation: bool,
+    pub rating: BookRatingResponse,
+}
+
+impl WorkEditionResponse {
+    fn new(work: &Work, edition: Book) -> Self {
+        Self {
+            translation: work.is_translation(&edition),
+            id: edition.id.map(|id| id.to_hex()).unwrap_or_default(),
+            isbn: edition.isbn,
+            isbn13: edition.isbn13,
+            title: edition.title,
+            format: edition.format,
+            languages: edition.languages,
+            publishers: edition.publishers.into_iter().map(|p| p.name).collect(),
+            published_date: edition.published_date,
+            image: edition.images.into_iter().next().map(|image| image.url),
+            rating: BookRatingResponse::from(edition.rating),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkAvailabilityResponse {
+    /// Formats of at least one edition
+    pub formats: Vec<BookFormat>,
+    /// Languages of at least one edition
+    pub languages: Vec<String>,
+}
+
+impl From<WorkAvailability> for WorkAvailabilityResponse {
+    fn from(availability: WorkAvailability) -> Self {
+        Self { formats: availability.formats, languages: availability.languages }
+    }
+}
+
+/// A work with its editions. Authors, availability and rating are gathered from the editions.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkDetailResponse {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub original_language: Option<String>,
+    pub authors: Vec<BookAuthorResponse>,
+    /// Over the reviews of every edition
+    pub rating: BookRatingResponse,
+    pub availability: WorkAvailabilityResponse,
+    pub editions: Vec<WorkEditionResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkDetailResponse {
+    pub fn new(work: Work, editions: Vec<Book>) -> Self {
+        let authors = work_authors(&editions).into_iter().map(BookAuthorResponse::from).collect();
+        let rating = BookRatingResponse::from(work_rating(&editions));
+        let availability = WorkAvailabilityResponse::from(WorkAvailability::of(&editions));
+        let editions = editions.into_iter().map(|edition| WorkEditionResponse::new(&work, edition)).collect();
+
+        Self {
+            id: work.id.map(|id| id.to_hex()).unwrap_or_default(),
+            title: work.title,
+            description: work.description,
+            original_language: work.original_language,
+            authors,
+            rating,
+            availability,
+            editions,
+            created_at: work.created_at,
+            updated_at: work.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkCreateRequest {
+    pub title: String,
+    pub description: Option<String>,
+    /// ISO 639-1 code of the language the work was written in
+    pub original_language: Option<String>,
+    /// Books to group as the first editions of the work
+    #[serde(default)]
+    pub edition_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkUpdateRequest {
+    pub title: String,
+    pub description: Option<String>,
+    /// ISO 639-1 code of the language the work was written in
+    pub original_language: Option<String>,
+}
+
+
+impl Validate for WorkCreateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let count = if self.edition_ids.len() > WORK_EDITIONS_MAX {
+            Err(format!("must hold at most {} ids", WORK_EDITIONS_MAX))
+        } else {
+            Ok(())
+        };
+        Validator::new()
+            .field("title", rules::length(&self.title, 1, 512))
+            .field("description", rules::optional(self.description.as_deref(), |description| rules::length(description, 0, 10000)))
+            .field("original_language", rules::optional(self.original_language.as_deref(), rules::iso639))
+            .field("edition_ids", count)
+            .each("edition_ids", &self.edition_ids, |id| rules::object_id(id))
+            .finish()
+    }
+}
+
+impl Validate for WorkUpdateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Validator::new()
+            .field("title", rules::length(&self.title, 1, 512))
+            .field("description", rules::optional(self.description.as_deref(), |description| rules::length(description, 0, 10000)))
+            .field("original_language", rules::optional(self.original_language.as_deref(), rules::iso639))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+    use crate::model::book_model::BookRating;
+
+    #[test]
+    fn create_request_only_needs_a_title() {
+        let request: WorkCreateRequest = serde_json::from_str(r#"{ "title": "Le Petit Prince" }"#).unwrap();
+
+        assert!(request.edition_ids.is_empty());
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn create_request_rejects_bad_languages_and_edition_ids() {
+        let request = WorkCreateRequest {
+            title: "Le Petit Prince".to_string(),
+            description: None,
+            original_language: Some("french".to_string()),
+            edition_ids: vec!["not an id".to_string()],
+        };
+
+        let fields = request.validate().unwrap_err().into_fields();
+        assert!(fields.contains_key("original_language"));
+        assert!(fields.contains_key("edition_ids[0]"));
+    }
+
+    #[test]
+    fn detail_flags_translations_and_aggregates_the_editions() {
+        let work = Work {
+            id: Some(ObjectId::parse_str("65f0c0ffee0000000000c001").unwrap()),
+            title: "Le Petit Prince".to_string(),
+            description: None,
+            original_language: Some("fr".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let edition = |format: BookFormat, language: &str, scores: &[f32]| Book {
+            id: Some(ObjectId::new()),
+            isbn: String::new(),
+            isbn13: String::new(),
+            title: "Le Petit Prince".to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: None,
+            published_date: None,
+            format,
+            images: vec![],
+            preview: vec![],
+            genres: vec![],
+            authors: vec![],
+            publishers: vec![],
+            languages: vec![language.to_string()],
+            reviews: vec![],
+            rating: BookRating::from_scores(scores.iter().copied()),
+            external_id: None,
+            work_id: work.id,
+        };
+
+        let detail = WorkDetailResponse::new(work.clone(), vec![
+            edition(BookFormat::Paperback, "fr", &[5.0]),
+            edition(BookFormat::EBook, "en", &[4.0, 3.0]),
+        ]);
+
+        assert_eq!(detail.id, "65f0c0ffee0000000000c001");
+        assert_eq!(detail.editions.iter().map(|edition| edition.translation).collect::<Vec<_>>(), vec![false, true]);
+        assert_eq!(detail.rating.count, 3);
+        assert_eq!(detail.availability.languages, vec!["fr".to_string(), "en".to_string()]);
+    }
+}
@@ -1,8 +1,10 @@
 use tokio::net::TcpListener;
+use crate::cli::CliCommand;
 use crate::main_app::build_app;
 use crate::shared::configuration::AppConfig;
 use crate::shared::logging::log;
 
+mod cli;
 mod shared;
 mod service;
 mod main_app;
@@ -42,14 +44,20 @@ async fn main() -> anyhow::Result<()> {
 
     // Initialize tracing
     // tracing_subscriber::fmt::init();
-    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).init();
+    tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).with_writer(std::io::stderr).init();
 
     // Load configuration
     let config = AppConfig::default()?;
 
     log::init_from_config(config.is_prod);
 
-    let (_main_server, _metrics_server) = tokio::join!(start_main_server(config.clone()), start_metrics_server(config.clone()));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match CliCommand::parse(&args)? {
+        CliCommand::Serve => {
+            let (_main_server, _metrics_server) = tokio::join!(start_main_server(config.clone()), start_metrics_server(config.clone()));
+        },
+        command => cli::run(command, config).await?,
+    }
 
     Ok(())
 }
@@ -12,10 +12,17 @@ use crate::shared::openapi::spec::ApiDoc;
 use crate::route::{routes as api_services_routes};
 use crate::shared::state::AppState;
 use crate::shared::metrics::metrics_logger::metrics_and_logging_middleware;
+use crate::shared::security::auth_middleware::jwt_auth_middleware;
+use crate::service::graph_sync_service::GraphSyncService;
+use crate::shared::database::neo4j_schema;
+use crate::shared::logging::log;
+
+pub fn create_api_router(app_state: &AppState) -> Router<AppState> {
+    let services = api_services_routes()
+        .layer(middleware::from_fn_with_state(app_state.clone(), jwt_auth_middleware));
 
-pub fn create_api_router() -> Router<AppState> {
     Router::new()
-        .nest("/services", api_services_routes())
+        .nest("/services", services)
 }
 
 pub struct App { pub addr: SocketAddr, pub router: Router }
@@ -27,6 +34,15 @@ pub async fn build_app(cfg: AppConfig) -> anyhow::Result<App> {
     // Create application state
     let app_state = AppState::new(cfg.clone()).await?;
 
+    // The graph-sync worker only MERGEs nodes, so it can run without the constraints;
+    // a failed step is retried on the next start, or with the `neo4j-schema` command
+    if let Err(e) = neo4j_schema::migrate(&app_state.neo4j_client, false).await {
+        log::error(&format!("Neo4j schema migration failed, starting without it: {:?}", e));
+    }
+
+    // Mirror outbox events into Neo4j in the background
+    GraphSyncService::from(&app_state).spawn();
+
     // CORS configuration
     let cors = CorsLayer::new()
         .allow_methods([Method::OPTIONS, Method::GET, Method::POST, Method::PUT, Method::DELETE])
@@ -35,7 +51,7 @@ pub async fn build_app(cfg: AppConfig) -> anyhow::Result<App> {
 
     let (router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
         // API routes
-        .nest("/api", OpenApiRouter::from(create_api_router()))
+        .nest("/api", OpenApiRouter::from(create_api_router(&app_state)))
 
         .layer(middleware::from_fn(metrics_and_logging_middleware))
 
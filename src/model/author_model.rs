@@ -1,4 +1,4 @@
-use bson::oid::ObjectId;
+use mongodb::bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -31,29 +31,34 @@ pub struct AuthorEmbed {
 }
 
 
-impl From<&Author> for AuthorEmbed {
-    fn from(author: &Author) -> Self {
-        Self {
-            id: author.id.clone().unwrap(),
-            name: author.name.clone(),
-            image_url: author.image_url.clone(),
+impl Author {
+    /// Completes this author with a duplicate merged into it: its books are added,
+    /// and its description, image and external ids fill the blanks of this one.
+    pub fn absorb(&mut self, other: &Author) {
+        if self.description.trim().is_empty() {
+            self.description = other.description.clone();
+        }
+        if self.image_url.trim().is_empty() {
+            self.image_url = other.image_url.clone();
+        }
+        for book in &other.books {
+            if !self.books.iter().any(|own| own.book_id == book.book_id) {
+                self.books.push(book.clone());
+            }
+        }
+        if let Some(external_id) = &other.external_id {
+            self.external_id.get_or_insert_with(ExternalId::default).absorb(external_id);
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AuthorNode {
-    pub id: Option<String>,
-    pub author_id: String,
-    pub name: String,
-}
 
-impl From<&Author> for AuthorNode {
+impl From<&Author> for AuthorEmbed {
     fn from(author: &Author) -> Self {
         Self {
-            id: None,
-            author_id: author.id.unwrap().to_hex(),
+            id: author.id.unwrap(),
             name: author.name.clone(),
+            image_url: author.image_url.clone(),
         }
     }
 }
@@ -1,11 +1,16 @@
-use bson::oid::ObjectId;
+use std::collections::BTreeMap;
+
+use mongodb::bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::model::{
     author_model::AuthorEmbed,
+    external_id_model::ExternalId,
     genre_model::GenreEmbed,
     publisher_model::PublisherEmbed,
+    review_model::Review,
     source_model::SourceEmbed
 };
 
@@ -21,7 +26,7 @@ pub struct BookPreviewSource {
     pub source: SourceEmbed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum BookFormat {
     Paperback,
     Hardcover,
@@ -29,6 +34,23 @@ pub enum BookFormat {
     Audiobook,
 }
 
+impl BookFormat {
+    pub const ALL: [BookFormat; 4] = [Self::Paperback, Self::Hardcover, Self::EBook, Self::Audiobook];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Paperback => "Paperback",
+            Self::Hardcover => "Hardcover",
+            Self::EBook => "EBook",
+            Self::Audiobook => "Audiobook",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.name() == name)
+    }
+}
+
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Book {
@@ -52,7 +74,102 @@ pub struct Book {
     pub publishers: Vec<PublisherEmbed>,
     pub languages: Vec<String>,
     
-    pub reviews: Vec<String>,
+    pub reviews: Vec<ObjectId>,
+
+    #[serde(default)]
+    pub rating: BookRating,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<ExternalId>,
+
+    /// Work this book is an edition of, maintained by the work repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_id: Option<ObjectId>,
+}
+
+/// Review aggregates, maintained by the review repository in the same
+/// transaction as every review write. `histogram` is keyed by score ("1".."5").
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BookRating {
+    pub average: f64,
+    pub count: i64,
+    pub sum: f64,
+    pub histogram: BTreeMap<String, i64>,
+}
+
+impl BookRating {
+    /// Aggregates of the given scores, skipping any outside the whole star range.
+    pub fn from_scores(scores: impl IntoIterator<Item = f32>) -> Self {
+        let mut rating = Self::default();
+        for bucket in scores.into_iter().filter_map(Review::score_bucket) {
+            rating.count += 1;
+            rating.sum += f64::from(bucket);
+            *rating.histogram.entry(bucket.to_string()).or_default() += 1;
+        }
+        if rating.count > 0 {
+            rating.average = rating.sum / rating.count as f64;
+        }
+        rating
+    }
+
+    /// Aggregates over all the reviews behind the given ratings, such as the editions of a work.
+    pub fn combine<'a>(ratings: impl IntoIterator<Item = &'a BookRating>) -> Self {
+        let mut combined = Self::default();
+        for rating in ratings {
+            combined.count += rating.count;
+            combined.sum += rating.sum;
+            for (score, count) in &rating.histogram {
+                *combined.histogram.entry(score.clone()).or_default() += count;
+            }
+        }
+        if combined.count > 0 {
+            combined.average = combined.sum / combined.count as f64;
+        }
+        combined
+    }
+}
+
+/// Appends the items of `other` whose key none of `own` has.
+fn extend_unique<T: Clone, K: PartialEq>(own: &mut Vec<T>, other: &[T], key: impl Fn(&T) -> K) {
+    for item in other {
+        if !own.iter().any(|existing| key(existing) == key(item)) {
+            own.push(item.clone());
+        }
+    }
+}
+
+impl Book {
+    /// Completes this book with a duplicate merged into it: its authors, genres, publishers,
+    /// languages and pictures are added, and its other fields fill the blanks of this one.
+    /// Reviews and ratings are left to the merge, which moves them.
+    pub fn absorb(&mut self, other: &Book) {
+        if self.isbn.is_empty() {
+            self.isbn = other.isbn.clone();
+        }
+        if self.isbn13.is_empty() {
+            self.isbn13 = other.isbn13.clone();
+        }
+        if self.subtitle.is_none() {
+            self.subtitle = other.subtitle.clone();
+        }
+        if self.description.is_none() {
+            self.description = other.description.clone();
+        }
+        self.num_pages = self.num_pages.or(other.num_pages);
+        self.published_date = self.published_date.or(other.published_date);
+        self.work_id = self.work_id.or(other.work_id);
+
+        extend_unique(&mut self.images, &other.images, |image| image.url.clone());
+        extend_unique(&mut self.preview, &other.preview, |preview| preview.url.clone());
+        extend_unique(&mut self.genres, &other.genres, |genre| genre.name.clone());
+        extend_unique(&mut self.authors, &other.authors, |author| author.id);
+        extend_unique(&mut self.publishers, &other.publishers, |publisher| publisher.name.clone());
+        extend_unique(&mut self.languages, &other.languages, |language| language.clone());
+
+        if let Some(external_id) = &other.external_id {
+            self.external_id.get_or_insert_with(ExternalId::default).absorb(external_id);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,7 +183,7 @@ pub struct BookEmbed {
 impl From<&Book> for BookEmbed {
     fn from(book: &Book) -> Self {
         Self {
-            book_id: book.id.unwrap().clone(),
+            book_id: book.id.unwrap(),
             title: book.title.clone(),
             description: book.description.clone(),
             image: book.images.first().map(|img| img.url.clone()),
@@ -75,33 +192,212 @@ impl From<&Book> for BookEmbed {
 }
 
 
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum BookReadStatus {
+    Read,
+    #[default]
+    Unread,
+    InProgress,
+}
+
+impl BookReadStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Read => "Read",
+            Self::Unread => "Unread",
+            Self::InProgress => "InProgress",
+        }
+    }
+
+    /// Allowed moves on the shelf: start or finish a book, give up on one
+    /// in progress, or start re-reading a finished one.
+    pub fn can_move_to(&self, next: BookReadStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Unread, Self::InProgress)
+                | (Self::Unread, Self::Read)
+                | (Self::InProgress, Self::Read)
+                | (Self::InProgress, Self::Unread)
+                | (Self::Read, Self::InProgress)
+        )
+    }
+}
+
+/// Book on a reader's shelf, embedded in `User.shelf`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BookNode {
-    pub book_id: String,
+pub struct ShelfEntry {
+    pub book_id: ObjectId,
     pub title: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+
+    #[serde(default)]
+    pub status: BookReadStatus,
+    #[serde(default)]
+    pub added_at: DateTime<Utc>,
+    #[serde(default)]
+    pub status_updated_at: DateTime<Utc>,
 }
 
-impl From<&Book> for BookNode {
-    fn from(book: &Book) -> Self {
+impl ShelfEntry {
+    pub fn new(book: &Book, status: BookReadStatus, ts: DateTime<Utc>) -> Self {
+        let embed = BookEmbed::from(book);
         Self {
-            book_id: book.id.clone().unwrap().to_hex(),
-            title: book.title.clone(),
+            book_id: embed.book_id,
+            title: embed.title,
+            description: embed.description,
+            image: embed.image,
+            status,
+            added_at: ts,
+            status_updated_at: ts,
         }
     }
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum BookReadStatus {
-    Read,
-    Unread,
-    InProgress,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn books_are_shelved_unread_by_default() {
+        assert_eq!(BookReadStatus::default(), BookReadStatus::Unread);
+    }
+
+    #[test]
+    fn shelf_status_moves_forward_or_back_to_reading() {
+        use BookReadStatus::*;
+
+        assert!(Unread.can_move_to(InProgress));
+        assert!(Unread.can_move_to(Read));
+        assert!(InProgress.can_move_to(Read));
+        assert!(InProgress.can_move_to(Unread));
+        assert!(Read.can_move_to(InProgress));
+    }
+
+    #[test]
+    fn shelf_status_rejects_resets_and_no_op_moves() {
+        use BookReadStatus::*;
+
+        assert!(!Read.can_move_to(Unread));
+        assert!(!Unread.can_move_to(Unread));
+        assert!(!InProgress.can_move_to(InProgress));
+        assert!(!Read.can_move_to(Read));
+    }
+
+    fn book(title: &str) -> Book {
+        Book {
+            id: Some(ObjectId::new()),
+            isbn: String::new(),
+            isbn13: String::new(),
+            title: title.to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: None,
+            published_date: None,
+            format: BookFormat::Paperback,
+            images: vec![],
+            preview: vec![],
+            genres: vec![],
+            authors: vec![],
+            publishers: vec![],
+            languages: vec![],
+            reviews: vec![],
+            rating: BookRating::default(),
+            external_id: None,
+            work_id: None,
+        }
+    }
+
+    #[test]
+    fn rating_is_rebuilt_from_whole_star_scores() {
+        let rating = BookRating::from_scores([5.0, 4.0, 4.0, 2.5]);
+
+        assert_eq!(rating.count, 3);
+        assert_eq!(rating.sum, 13.0);
+        assert!((rating.average - 13.0 / 3.0).abs() < 1e-9);
+        assert_eq!(rating.histogram.get("4"), Some(&2));
+        assert_eq!(BookRating::from_scores([]).average, 0.0);
+    }
+
+    #[test]
+    fn combined_rating_weighs_every_review_alike() {
+        let paperback = BookRating::from_scores([5.0, 5.0, 5.0]);
+        let ebook = BookRating::from_scores([2.0]);
+
+        let rating = BookRating::combine([&paperback, &ebook, &BookRating::default()]);
+
+        assert_eq!(rating.count, 4);
+        assert!((rating.average - 17.0 / 4.0).abs() < 1e-9);
+        assert_eq!(rating.histogram.get("5"), Some(&3));
+        assert_eq!(rating.histogram.get("2"), Some(&1));
+        assert_eq!(BookRating::combine([]).average, 0.0);
+    }
+
+    #[test]
+    fn absorbing_a_duplicate_fills_blanks_and_unions_lists() {
+        use crate::model::external_id_model::ExternalProvider;
+        use crate::model::genre_model::GenreEmbed;
+
+        let mut survivor = book("The Hobbit");
+        survivor.isbn13 = "9780306406157".to_string();
+        survivor.genres = vec![GenreEmbed { name: "fantasy".to_string() }];
+        survivor.external_id = Some(ExternalId::from_provider(ExternalProvider::GoodReads, "5907"));
+        let mut duplicate = book("Hobbit");
+        duplicate.isbn13 = "9780000000002".to_string();
+        duplicate.num_pages = Some(310);
+        duplicate.genres = vec![GenreEmbed { name: "fantasy".to_string() }, GenreEmbed { name: "classics".to_string() }];
+        duplicate.languages = vec!["en".to_string()];
+        duplicate.external_id = Some(ExternalId::from_provider(ExternalProvider::GoodReads, "1"));
+        duplicate.external_id.as_mut().unwrap().set(ExternalProvider::Kaggle, "k-7");
+
+        survivor.absorb(&duplicate);
+
+        assert_eq!(survivor.title, "The Hobbit");
+        assert_eq!(survivor.isbn13, "9780306406157");
+        assert_eq!(survivor.num_pages, Some(310));
+        assert_eq!(survivor.genres.iter().map(|genre| genre.name.as_str()).collect::<Vec<_>>(), vec!["fantasy", "classics"]);
+        assert_eq!(survivor.languages, vec!["en".to_string()]);
+        let external_id = survivor.external_id.unwrap();
+        assert_eq!(external_id.get(ExternalProvider::GoodReads), Some("5907"));
+        assert_eq!(external_id.get(ExternalProvider::Kaggle), Some("k-7"));
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AddedToShelf {
-    pub status: String,
-    pub ts: DateTime<Utc>,
+
+/// Catalog filters of a book list. Values within one field are alternatives,
+/// different fields must all match.
+#[derive(Debug, Clone, Default)]
+pub struct BookFilter {
+    pub genres: Vec<String>,
+    pub author_ids: Vec<ObjectId>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+    pub formats: Vec<BookFormat>,
+    pub pages_min: Option<i32>,
+    pub pages_max: Option<i32>,
+    pub published_from: Option<DateTime<Utc>>,
+    pub published_to: Option<DateTime<Utc>>,
 }
 
+/// Number of books carrying one facet value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetCount {
+    #[serde(rename = "_id")]
+    pub value: String,
+    /// Display name when the value is an id
+    pub label: Option<String>,
+    pub count: u64,
+}
 
+/// Facet counts of a book list. Each facet is counted under every filter except its own,
+/// so the other values of a filtered field keep showing how many books they would add.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BookFacets {
+    pub genres: Vec<FacetCount>,
+    pub authors: Vec<FacetCount>,
+    pub publishers: Vec<FacetCount>,
+    pub languages: Vec<FacetCount>,
+    pub formats: Vec<FacetCount>,
+}
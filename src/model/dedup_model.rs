@@ -0,0 +1,381 @@
+use std::collections::{HashMap, HashSet};
+
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::author_model::AuthorEmbed;
+use crate::model::book_model::{Book, ShelfEntry};
+use crate::model::external_id_model::{ExternalId, ExternalProvider};
+use crate::model::review_model::Review;
+use crate::shared::dedup::normalize::{isbn_key, name_key, title_key};
+
+
+/// Why two records are proposed as duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    /// Same ISBN, once 10 digit ISBNs are converted to 13 digits
+    SharedIsbn,
+    /// Same id from the same provider
+    SharedExternalId,
+    /// Names or titles spelled alike
+    SimilarName,
+}
+
+
+/// What duplicate detection reads of an author.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthorDedupRow {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub name: String,
+    #[serde(default)]
+    pub external_id: Option<ExternalId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DedupAuthorRef {
+    pub id: ObjectId,
+}
+
+/// What duplicate detection reads of a book.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookDedupRow {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub title: String,
+    #[serde(default)]
+    pub isbn: String,
+    #[serde(default)]
+    pub isbn13: String,
+    #[serde(default)]
+    pub authors: Vec<DedupAuthorRef>,
+    #[serde(default)]
+    pub external_id: Option<ExternalId>,
+}
+
+/// Author or book reduced to the keys duplicates are found by.
+#[derive(Debug, Clone)]
+pub struct DedupRecord {
+    pub id: ObjectId,
+    pub name: String,
+    /// Normalized name or title, see `name_key` and `title_key`
+    pub key: String,
+    pub isbns: Vec<String>,
+    pub external_ids: Vec<(ExternalProvider, String)>,
+    /// Authors of a book, for authors themselves always empty
+    pub author_ids: Vec<ObjectId>,
+}
+
+fn external_ids(external_id: Option<&ExternalId>) -> Vec<(ExternalProvider, String)> {
+    external_id
+        .map(|external_id| external_id.entries().into_iter().map(|(provider, id)| (provider, id.to_string())).collect())
+        .unwrap_or_default()
+}
+
+impl From<AuthorDedupRow> for DedupRecord {
+    fn from(row: AuthorDedupRow) -> Self {
+        Self {
+            id: row.id,
+            key: name_key(&row.name),
+            external_ids: external_ids(row.external_id.as_ref()),
+            name: row.name,
+            isbns: vec![],
+            author_ids: vec![],
+        }
+    }
+}
+
+impl From<BookDedupRow> for DedupRecord {
+    fn from(row: BookDedupRow) -> Self {
+        let mut isbns: Vec<String> = [&row.isbn, &row.isbn13].into_iter().filter_map(|isbn| isbn_key(isbn)).collect();
+        isbns.dedup();
+        Self {
+            id: row.id,
+            key: title_key(&row.title),
+            external_ids: external_ids(row.external_id.as_ref()),
+            name: row.title,
+            isbns,
+            author_ids: row.authors.into_iter().map(|author| author.id).collect(),
+        }
+    }
+}
+
+
+/// Two records that may be the same author or book.
+#[derive(Debug, Clone)]
+pub struct DuplicateCandidate {
+    pub ids: [ObjectId; 2],
+    pub names: [String; 2],
+    /// 1 for a shared ISBN or external id, the name similarity otherwise
+    pub score: f64,
+    pub reasons: Vec<DuplicateReason>,
+}
+
+
+/// What a merge changed besides deleting the merged records.
+#[derive(Debug, Clone, Default)]
+pub struct MergeReport {
+    pub survivor_id: ObjectId,
+    pub merged_ids: Vec<ObjectId>,
+    /// Books whose author list was rewritten
+    pub books_updated: u64,
+    /// Shelves whose entry was moved to the surviving book
+    pub shelves_updated: u64,
+    /// Readers whose preferred authors now name the surviving author
+    pub preferences_updated: u64,
+    pub reviews_moved: u64,
+    /// Reviews dropped because their author also reviewed the surviving book
+    pub reviews_dropped: u64,
+}
+
+
+/// Reviews of the merged books split between the ones kept on the surviving book and the rest.
+/// The reviews unique index allows one review per reader and book, so a reader keeps their review
+/// of the surviving book, or else their latest one.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewMergePlan {
+    pub kept: Vec<Review>,
+    pub dropped: Vec<Review>,
+}
+
+impl ReviewMergePlan {
+    pub fn new(survivor_id: &ObjectId, reviews: Vec<Review>) -> Self {
+        let rank = |review: &Review| (review.book_id == *survivor_id, review.updated_at.or(review.date_added));
+
+        let mut by_reader: HashMap<ObjectId, Review> = HashMap::new();
+        let mut dropped = Vec::new();
+        for review in reviews {
+            match by_reader.remove(&review.user.id) {
+                Some(kept) if rank(&kept) >= rank(&review) => {
+                    dropped.push(review);
+                    by_reader.insert(kept.user.id, kept);
+                },
+                Some(kept) => {
+                    dropped.push(kept);
+                    by_reader.insert(review.user.id, review);
+                },
+                None => {
+                    by_reader.insert(review.user.id, review);
+                },
+            }
+        }
+
+        let mut kept: Vec<Review> = by_reader.into_values().collect();
+        kept.sort_by_key(|review| review.id);
+        Self { kept, dropped }
+    }
+
+    /// Kept reviews that still point at a merged book.
+    pub fn moved(&self, survivor_id: &ObjectId) -> Vec<&Review> {
+        self.kept.iter().filter(|review| review.book_id != *survivor_id).collect()
+    }
+}
+
+
+/// Authors of a book with the merged authors replaced by the surviving one, listed once
+/// where the first of them was.
+pub fn merged_authors(authors: &[AuthorEmbed], survivor: &AuthorEmbed, merged_ids: &[ObjectId]) -> Vec<AuthorEmbed> {
+    let mut merged = Vec::with_capacity(authors.len());
+    let mut listed = false;
+    for author in authors {
+        if author.id != survivor.id && !merged_ids.contains(&author.id) {
+            merged.push(author.clone());
+        } else if !listed {
+            merged.push(survivor.clone());
+            listed = true;
+        }
+    }
+    merged
+}
+
+
+/// Preferred author ids of a reader with the merged authors replaced by the surviving one, listed
+/// once where the first of them was, or `None` when it names none of them.
+pub fn merged_preference_authors(author_ids: &[String], survivor_id: &str, merged_ids: &[String]) -> Option<Vec<String>> {
+    if !author_ids.iter().any(|id| merged_ids.contains(id)) {
+        return None;
+    }
+    let mut merged = Vec::with_capacity(author_ids.len());
+    for id in author_ids {
+        let id = if merged_ids.contains(id) { survivor_id } else { id.as_str() };
+        if !merged.iter().any(|listed: &String| listed == id) {
+            merged.push(id.to_string());
+        }
+    }
+    Some(merged)
+}
+
+
+/// Shelf with the merged books replaced by the surviving one, or `None` when it holds none of them.
+/// The surviving book's entry is kept when it is already shelved, otherwise the first merged
+/// book shelved takes its place with its status and dates.
+pub fn merged_shelf(shelf: &[ShelfEntry], survivor: &Book, merged_ids: &[ObjectId]) -> Option<Vec<ShelfEntry>> {
+    let merged: HashSet<&ObjectId> = merged_ids.iter().collect();
+    if !shelf.iter().any(|entry| merged.contains(&entry.book_id)) {
+        return None;
+    }
+
+    let survivor_id = survivor.id?;
+    let mut shelved = shelf.iter().any(|entry| entry.book_id == survivor_id);
+    let mut entries = Vec::with_capacity(shelf.len());
+    for entry in shelf {
+        if !merged.contains(&entry.book_id) {
+            entries.push(entry.clone());
+        } else if !shelved {
+            let mut moved = ShelfEntry::new(survivor, entry.status, entry.added_at);
+            moved.status_updated_at = entry.status_updated_at;
+            entries.push(moved);
+            shelved = true;
+        }
+    }
+    Some(entries)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+    use crate::model::book_model::{BookFormat, BookRating, BookReadStatus};
+    use crate::model::user_model::UserEmbed;
+
+    fn book(id: ObjectId, title: &str) -> Book {
+        Book {
+            id: Some(id),
+            isbn: String::new(),
+            isbn13: String::new(),
+            title: title.to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: None,
+            published_date: None,
+            format: BookFormat::Paperback,
+            images: vec![],
+            preview: vec![],
+            genres: vec![],
+            authors: vec![],
+            publishers: vec![],
+            languages: vec![],
+            reviews: vec![],
+            rating: BookRating::default(),
+            external_id: None,
+            work_id: None,
+        }
+    }
+
+    fn review(book_id: ObjectId, user_id: ObjectId, days_ago: i64) -> Review {
+        Review {
+            id: Some(ObjectId::new()),
+            book_id,
+            user: UserEmbed { id: user_id, name: "reader".to_string(), image_url: None },
+            content: String::new(),
+            score: 4.0,
+            date_added: Some(Utc::now() - Duration::days(days_ago)),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn book_records_compare_both_isbns_as_isbn13() {
+        let record = DedupRecord::from(BookDedupRow {
+            id: ObjectId::new(),
+            title: "The Hobbit".to_string(),
+            isbn: "0306406152".to_string(),
+            isbn13: "9780306406157".to_string(),
+            authors: vec![],
+            external_id: Some(ExternalId::from_provider(ExternalProvider::GoodReads, "5907")),
+        });
+
+        assert_eq!(record.key, "hobbit");
+        assert_eq!(record.isbns, vec!["9780306406157".to_string()]);
+        assert_eq!(record.external_ids, vec![(ExternalProvider::GoodReads, "5907".to_string())]);
+    }
+
+    #[test]
+    fn merged_authors_are_replaced_once_in_place() {
+        let embed = |name: &str| AuthorEmbed { id: ObjectId::new(), name: name.to_string(), image_url: String::new() };
+        let (coauthor, merged, survivor) = (embed("Terry Pratchett"), embed("Neil Gayman"), embed("Neil Gaiman"));
+
+        let authors = merged_authors(&[merged.clone(), coauthor.clone(), survivor.clone()], &survivor, &[merged.id]);
+
+        assert_eq!(authors.iter().map(|author| author.id).collect::<Vec<_>>(), vec![survivor.id, coauthor.id]);
+        assert_eq!(authors[0].name, "Neil Gaiman");
+    }
+
+    #[test]
+    fn preferred_authors_name_the_survivor_once() {
+        let merged = ["m1".to_string(), "m2".to_string()];
+        let preferred = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(merged_preference_authors(&preferred(&["a", "b"]), "s", &merged), None);
+        assert_eq!(merged_preference_authors(&preferred(&["a", "m1", "m2"]), "s", &merged), Some(preferred(&["a", "s"])));
+        assert_eq!(merged_preference_authors(&preferred(&["s", "m2", "a"]), "s", &merged), Some(preferred(&["s", "a"])));
+    }
+
+    #[test]
+    fn readers_keep_their_review_of_the_survivor_over_newer_ones() {
+        let (survivor, merged, reader) = (ObjectId::new(), ObjectId::new(), ObjectId::new());
+        let on_survivor = review(survivor, reader, 30);
+        let newer = review(merged, reader, 1);
+
+        let plan = ReviewMergePlan::new(&survivor, vec![newer.clone(), on_survivor.clone()]);
+
+        assert_eq!(plan.kept.iter().map(|review| review.id).collect::<Vec<_>>(), vec![on_survivor.id]);
+        assert_eq!(plan.dropped.iter().map(|review| review.id).collect::<Vec<_>>(), vec![newer.id]);
+        assert!(plan.moved(&survivor).is_empty());
+    }
+
+    #[test]
+    fn readers_without_a_survivor_review_keep_their_latest_one() {
+        let (survivor, first, second, reader) = (ObjectId::new(), ObjectId::new(), ObjectId::new(), ObjectId::new());
+        let older = review(first, reader, 10);
+        let newer = review(second, reader, 2);
+        let other_reader = review(first, ObjectId::new(), 5);
+
+        let plan = ReviewMergePlan::new(&survivor, vec![older.clone(), newer.clone(), other_reader.clone()]);
+
+        assert_eq!(plan.kept.len(), 2);
+        assert!(plan.kept.iter().any(|review| review.id == newer.id));
+        assert!(plan.kept.iter().any(|review| review.id == other_reader.id));
+        assert_eq!(plan.dropped.iter().map(|review| review.id).collect::<Vec<_>>(), vec![older.id]);
+        assert_eq!(plan.moved(&survivor).len(), 2);
+    }
+
+    #[test]
+    fn shelves_get_the_survivor_in_place_of_the_first_merged_book() {
+        let (survivor_id, merged_id, other_id) = (ObjectId::new(), ObjectId::new(), ObjectId::new());
+        let survivor = book(survivor_id, "The Hobbit");
+        let added = Utc::now() - Duration::days(3);
+        let shelf = vec![
+            ShelfEntry::new(&book(other_id, "Dune"), BookReadStatus::Unread, added),
+            ShelfEntry::new(&book(merged_id, "Hobbit"), BookReadStatus::Read, added),
+        ];
+
+        let shelf = merged_shelf(&shelf, &survivor, &[merged_id]).unwrap();
+
+        assert_eq!(shelf.len(), 2);
+        assert_eq!(shelf[1].book_id, survivor_id);
+        assert_eq!(shelf[1].title, "The Hobbit");
+        assert_eq!(shelf[1].status, BookReadStatus::Read);
+        assert_eq!(shelf[1].added_at, added);
+    }
+
+    #[test]
+    fn shelves_already_holding_the_survivor_only_lose_the_merged_books() {
+        let (survivor_id, merged_id) = (ObjectId::new(), ObjectId::new());
+        let survivor = book(survivor_id, "The Hobbit");
+        let shelf = vec![
+            ShelfEntry::new(&book(merged_id, "Hobbit"), BookReadStatus::Read, Utc::now()),
+            ShelfEntry::new(&survivor, BookReadStatus::InProgress, Utc::now()),
+        ];
+
+        let merged = merged_shelf(&shelf, &survivor, &[merged_id]).unwrap();
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].status, BookReadStatus::InProgress);
+        assert!(merged_shelf(&merged, &survivor, &[merged_id]).is_none());
+    }
+}
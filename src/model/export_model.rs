@@ -0,0 +1,221 @@
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::error::AppError;
+
+
+/// Snapshot handed to the data science team: a MongoDB collection, or the Neo4j reading graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportDataset {
+    Books,
+    Authors,
+    Metadata,
+    /// Without their passwords
+    Users,
+    Reviews,
+    /// `Reader`, `Book`, `Author` and `Genre` nodes with the relationships between them
+    Graph,
+}
+
+impl ExportDataset {
+    pub const ALL: [ExportDataset; 6] = [Self::Books, Self::Authors, Self::Metadata, Self::Users, Self::Reviews, Self::Graph];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Books => "books",
+            Self::Authors => "authors",
+            Self::Metadata => "metadata",
+            Self::Users => "users",
+            Self::Reviews => "reviews",
+            Self::Graph => "graph",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|dataset| dataset.name() == name)
+    }
+
+    /// Formats the dataset can be written in, the first one being the default.
+    pub fn formats(&self) -> &'static [ExportFormat] {
+        match self {
+            Self::Graph => &[ExportFormat::GraphMl, ExportFormat::Cypher],
+            _ => &[ExportFormat::JsonLines, ExportFormat::Csv],
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One JSON object per line
+    JsonLines,
+    /// Comma-separated values with a header line, lists joined with `|`
+    Csv,
+    /// GraphML document, readable by Gephi, NetworkX or igraph
+    #[serde(rename = "graphml")]
+    GraphMl,
+    /// Cypher script recreating the graph with `MERGE` statements
+    Cypher,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [Self::JsonLines, Self::Csv, Self::GraphMl, Self::Cypher];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::JsonLines => "jsonl",
+            Self::Csv => "csv",
+            Self::GraphMl => "graphml",
+            Self::Cypher => "cypher",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(Self::JsonLines),
+            _ => Self::ALL.into_iter().find(|format| format.name() == name),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::JsonLines => "application/x-ndjson",
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::GraphMl => "application/graphml+xml",
+            Self::Cypher => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+
+/// Labels of the exported graph nodes, each identified by its key property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphNodeKind {
+    Reader,
+    Book,
+    Author,
+    Genre,
+    Work,
+}
+
+impl GraphNodeKind {
+    pub const ALL: [GraphNodeKind; 5] = [Self::Reader, Self::Book, Self::Author, Self::Genre, Self::Work];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Reader => "Reader",
+            Self::Book => "Book",
+            Self::Author => "Author",
+            Self::Genre => "Genre",
+            Self::Work => "Work",
+        }
+    }
+
+    pub fn key_property(&self) -> &'static str {
+        match self {
+            Self::Reader => "user_id",
+            Self::Book => "book_id",
+            Self::Author => "author_id",
+            Self::Genre => "name",
+            Self::Work => "work_id",
+        }
+    }
+
+    /// Property shown as the node's name: the book or work title, or the name of everything else.
+    pub fn name_property(&self) -> &'static str {
+        match self {
+            Self::Book | Self::Work => "title",
+            _ => "name",
+        }
+    }
+
+    /// Id of the node in the exported document, unique across labels.
+    pub fn node_id(&self, key: &str) -> String {
+        format!("{}:{}", self.label().to_ascii_lowercase(), key)
+    }
+}
+
+
+/// Relationships exported between the `GraphNodeKind` nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphRelationship {
+    Wrote,
+    HasGenre,
+    AddedToShelf,
+    Rated,
+    Follows,
+    EditionOf,
+}
+
+impl GraphRelationship {
+    pub const ALL: [GraphRelationship; 6] = [
+        Self::Wrote, Self::HasGenre, Self::AddedToShelf, Self::Rated, Self::Follows, Self::EditionOf,
+    ];
+
+    pub fn rel_type(&self) -> &'static str {
+        match self {
+            Self::Wrote => "WROTE",
+            Self::HasGenre => "HAS_GENRE",
+            Self::AddedToShelf => "ADDED_TO_SHELF",
+            Self::Rated => "RATED",
+            Self::Follows => "FOLLOWS",
+            Self::EditionOf => "EDITION_OF",
+        }
+    }
+
+    pub fn source(&self) -> GraphNodeKind {
+        match self {
+            Self::Wrote => GraphNodeKind::Author,
+            Self::HasGenre | Self::EditionOf => GraphNodeKind::Book,
+            Self::AddedToShelf | Self::Rated | Self::Follows => GraphNodeKind::Reader,
+        }
+    }
+
+    pub fn target(&self) -> GraphNodeKind {
+        match self {
+            Self::Wrote | Self::AddedToShelf | Self::Rated => GraphNodeKind::Book,
+            Self::HasGenre => GraphNodeKind::Genre,
+            Self::Follows => GraphNodeKind::Reader,
+            Self::EditionOf => GraphNodeKind::Work,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub kind: GraphNodeKind,
+    pub key: String,
+    pub name: Option<String>,
+}
+
+/// Relationship between two nodes, by their key. Only shelf, rating and follow
+/// relationships carry properties.
+#[derive(Debug, Clone)]
+pub struct GraphEdge {
+    pub relationship: GraphRelationship,
+    pub source: String,
+    pub target: String,
+    pub status: Option<String>,
+    pub rating: Option<f64>,
+    pub ts: Option<i64>,
+}
+
+/// Element of a graph export, nodes first.
+#[derive(Debug, Clone)]
+pub enum GraphElement {
+    Node(GraphNode),
+    Edge(GraphEdge),
+}
+
+
+/// Export being written: chunks of text, produced as the records are read.
+pub struct ExportStream {
+    pub format: ExportFormat,
+    /// Suggested name of the file holding the export
+    pub filename: String,
+    pub chunks: BoxStream<'static, Result<String, AppError>>,
+}
@@ -1,6 +1,52 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+
+/// Catalog an external id comes from, named after its `ExternalId` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalProvider {
+    GoodReads,
+    Amazon,
+    GoogleBooks,
+    Kaggle,
+}
+
+impl ExternalProvider {
+    pub const ALL: [ExternalProvider; 4] = [Self::GoodReads, Self::Amazon, Self::GoogleBooks, Self::Kaggle];
+
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::GoodReads => "good_reads",
+            Self::Amazon => "amazon",
+            Self::GoogleBooks => "google_books",
+            Self::Kaggle => "kaggle",
+        }
+    }
+
+    pub fn from_field(field: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|provider| provider.field() == field)
+    }
+
+    /// Dotted path of the provider's id in a document embedding an `external_id`.
+    pub fn path(&self) -> String {
+        format!("external_id.{}", self.field())
+    }
+
+    /// Username given to a reader imported from the provider, see `is_imported_username`.
+    pub fn reader_username(&self, external_id: &str) -> String {
+        format!("{}-{}", self.field(), external_id)
+    }
+
+    /// Whether the username has the form given to imported readers, which sign-up keeps for them.
+    pub fn is_imported_username(username: &str) -> bool {
+        let username = username.to_ascii_lowercase();
+        Self::ALL.iter().any(|provider| username.starts_with(&format!("{}-", provider.field())))
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExternalId {
     pub good_reads: Option<String>,
     pub amazon: Option<String>,
@@ -45,4 +91,49 @@ impl ExternalId {
             kaggle: Some(external_id.to_string()),
         }
     }
+
+    pub fn from_provider(provider: ExternalProvider, external_id: &str) -> Self {
+        match provider {
+            ExternalProvider::GoodReads => Self::from_good_reads(external_id),
+            ExternalProvider::Amazon => Self::from_amazon(external_id),
+            ExternalProvider::GoogleBooks => Self::from_google_books(external_id),
+            ExternalProvider::Kaggle => Self::from_kaggle(external_id),
+        }
+    }
+
+    pub fn set(&mut self, provider: ExternalProvider, external_id: &str) {
+        let id = Some(external_id.to_string());
+        match provider {
+            ExternalProvider::GoodReads => self.good_reads = id,
+            ExternalProvider::Amazon => self.amazon = id,
+            ExternalProvider::GoogleBooks => self.google_books = id,
+            ExternalProvider::Kaggle => self.kaggle = id,
+        }
+    }
+
+    pub fn get(&self, provider: ExternalProvider) -> Option<&str> {
+        match provider {
+            ExternalProvider::GoodReads => self.good_reads.as_deref(),
+            ExternalProvider::Amazon => self.amazon.as_deref(),
+            ExternalProvider::GoogleBooks => self.google_books.as_deref(),
+            ExternalProvider::Kaggle => self.kaggle.as_deref(),
+        }
+    }
+
+    /// The ids that are set, in `ExternalProvider::ALL` order.
+    pub fn entries(&self) -> Vec<(ExternalProvider, &str)> {
+        ExternalProvider::ALL
+            .into_iter()
+            .filter_map(|provider| Some((provider, self.get(provider)?)))
+            .collect()
+    }
+
+    /// Takes the ids of `other` for the providers this one has none from.
+    pub fn absorb(&mut self, other: &ExternalId) {
+        for (provider, id) in other.entries() {
+            if self.get(provider).is_none() {
+                self.set(provider, id);
+            }
+        }
+    }
 }
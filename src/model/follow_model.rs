@@ -0,0 +1,53 @@
+use mongodb::bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::book_model::BookReadStatus;
+use crate::model::user_model::UserEmbed;
+
+
+pub const FOLLOW_COLLECTION: &str = "follows";
+
+
+/// `follower_id` follows `followee_id`, mirrored as `(:Reader)-[:FOLLOWS]->(:Reader)` in Neo4j.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Follow {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub follower_id: ObjectId,
+    pub followee_id: ObjectId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The other side of a follow, as listed among someone's followers or followees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowedUser {
+    pub user: UserEmbed,
+    pub followed_at: DateTime<Utc>,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum FeedItemKind {
+    /// A review with text
+    Review,
+    /// A review without text, only the score
+    Rating,
+    /// A book added to the shelf or moved to another status
+    Shelf,
+}
+
+/// One entry of the activity feed, built from a review or a shelf entry of a followed reader.
+/// Shelf entries keep no history, so each appears once, at its latest change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedItem {
+    pub kind: FeedItemKind,
+    pub user: UserEmbed,
+    pub book_id: ObjectId,
+    pub title: Option<String>,
+    pub score: Option<f32>,
+    pub content: Option<String>,
+    pub status: Option<BookReadStatus>,
+    pub occurred_at: DateTime<Utc>,
+}
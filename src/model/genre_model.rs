@@ -28,29 +28,3 @@ impl From<&MetadataDoc> for GenreEmbed {
         }
     }
 }
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GenreNode {
-    pub id: Option<String>,
-    pub genre_id: String,
-    pub name: String,
-}
-
-impl From<&Genre> for GenreNode {
-    fn from(genre: &Genre) -> Self {
-        Self {
-            id: None,
-            genre_id: genre.name.clone(),
-            name: genre.name.clone(),
-        }
-    }
-}
-
-impl From<&MetadataDoc> for GenreNode {
-    fn from(doc: &MetadataDoc) -> Self {
-        match &doc.meta {
-            Metadata::Genre { name, .. } => Self { id: None, genre_id: doc.id.clone(), name: name.clone()},
-            _ => unreachable!(),
-        }
-    }
-}
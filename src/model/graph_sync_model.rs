@@ -0,0 +1,296 @@
+use mongodb::bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::book_model::Book;
+
+
+pub const GRAPH_SYNC_OUTBOX_COLLECTION: &str = "graph_sync_outbox";
+pub const GRAPH_SYNC_DEAD_LETTER_COLLECTION: &str = "graph_sync_dead_letters";
+/// Counter handing out the `seq` of outbox events.
+pub const GRAPH_SYNC_SEQUENCE_COLLECTION: &str = "graph_sync_sequence";
+
+
+/// A change to apply to the Neo4j graph.
+/// Every operation is idempotent (`MERGE`/`SET` or `MATCH ... DELETE`), so replaying one is harmless.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GraphSyncOp {
+    UpsertGenre {
+        name: String,
+        description: String,
+    },
+    DeleteGenre {
+        name: String,
+    },
+    UpsertReader {
+        user_id: String,
+        name: String,
+    },
+    DeleteReaders {
+        user_ids: Vec<String>,
+    },
+    AddToShelf {
+        user_id: String,
+        book_id: String,
+        status: String,
+        ts: i64,
+    },
+    UpdateShelfStatus {
+        user_id: String,
+        book_id: String,
+        status: String,
+        ts: i64,
+    },
+    RemoveFromShelf {
+        user_id: String,
+        book_id: String,
+    },
+    UpsertRating {
+        user_id: String,
+        book_id: String,
+        rating: f64,
+        ts: i64,
+    },
+    RemoveRating {
+        user_id: String,
+        book_id: String,
+    },
+    UpsertAuthor {
+        author_id: String,
+        name: String,
+    },
+    DeleteAuthors {
+        author_ids: Vec<String>,
+    },
+    /// Refreshes the Book node and rebuilds its `WROTE`, `HAS_GENRE` and `PUBLISHED_BY` links.
+    UpsertBook {
+        book_id: String,
+        title: String,
+        author_ids: Vec<String>,
+        genres: Vec<String>,
+        publishers: Vec<String>,
+    },
+    DeleteBooks {
+        book_ids: Vec<String>,
+    },
+    UpsertWork {
+        work_id: String,
+        title: String,
+    },
+    DeleteWorks {
+        work_ids: Vec<String>,
+    },
+    /// Makes the book an edition of the work, replacing any `EDITION_OF` link to another work.
+    LinkEdition {
+        book_id: String,
+        work_id: String,
+    },
+    UnlinkEdition {
+        book_id: String,
+    },
+    Follow {
+        follower_id: String,
+        followee_id: String,
+        ts: i64,
+    },
+    Unfollow {
+        follower_id: String,
+        followee_id: String,
+    },
+}
+
+impl GraphSyncOp {
+    /// Refreshes the book node and its author, genre and publisher links from the stored book.
+    pub fn upsert_book(book: &Book) -> Self {
+        Self::UpsertBook {
+            book_id: book.id.map(|id| id.to_hex()).unwrap_or_default(),
+            title: book.title.clone(),
+            author_ids: book.authors.iter().map(|author| author.id.to_hex()).collect(),
+            genres: book.genres.iter().map(|genre| genre.name.clone()).collect(),
+            publishers: book.publishers.iter().map(|publisher| publisher.name.clone()).collect(),
+        }
+    }
+
+    /// The graph records the op reads or writes, such as `book:<id>`.
+    /// Relationship ops name both ends, so they wait for the events creating either node.
+    pub fn keys(&self) -> Vec<String> {
+        fn keys<'a>(kind: &str, ids: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+            ids.into_iter().map(|id| format!("{}:{}", kind, id)).collect()
+        }
+
+        match self {
+            GraphSyncOp::UpsertGenre { name, .. } | GraphSyncOp::DeleteGenre { name } => keys("genre", [name]),
+            GraphSyncOp::UpsertReader { user_id, .. } => keys("reader", [user_id]),
+            GraphSyncOp::DeleteReaders { user_ids } => keys("reader", user_ids),
+            GraphSyncOp::AddToShelf { user_id, book_id, .. }
+            | GraphSyncOp::UpdateShelfStatus { user_id, book_id, .. }
+            | GraphSyncOp::RemoveFromShelf { user_id, book_id }
+            | GraphSyncOp::UpsertRating { user_id, book_id, .. }
+            | GraphSyncOp::RemoveRating { user_id, book_id } => {
+                [keys("reader", [user_id]), keys("book", [book_id])].concat()
+            },
+            GraphSyncOp::UpsertAuthor { author_id, .. } => keys("author", [author_id]),
+            GraphSyncOp::DeleteAuthors { author_ids } => keys("author", author_ids),
+            // Publisher nodes are only ever written by book upserts, so they need no key of their own
+            GraphSyncOp::UpsertBook { book_id, author_ids, genres, .. } => {
+                [keys("book", [book_id]), keys("author", author_ids), keys("genre", genres)].concat()
+            },
+            GraphSyncOp::DeleteBooks { book_ids } => keys("book", book_ids),
+            GraphSyncOp::UpsertWork { work_id, .. } => keys("work", [work_id]),
+            GraphSyncOp::DeleteWorks { work_ids } => keys("work", work_ids),
+            GraphSyncOp::LinkEdition { book_id, work_id } => [keys("book", [book_id]), keys("work", [work_id])].concat(),
+            GraphSyncOp::UnlinkEdition { book_id } => keys("book", [book_id]),
+            GraphSyncOp::Follow { follower_id, followee_id, .. } | GraphSyncOp::Unfollow { follower_id, followee_id } => {
+                keys("reader", [follower_id, followee_id])
+            },
+        }
+    }
+}
+
+
+/// Outbox entry written in the same Mongo transaction as the change it mirrors.
+/// Entries sharing a key are applied in `seq` order and removed once Neo4j has them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphSyncEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    /// Position in the outbox, see `graph_sync_repository::enqueue`
+    #[serde(default)]
+    pub seq: i64,
+    pub op: GraphSyncOp,
+    /// `op.keys()`, stored to find the events waiting behind this one
+    #[serde(default)]
+    pub keys: Vec<String>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Unix millis before which no worker may pick the event up: the retry backoff,
+    /// or the lease of the worker currently applying it
+    pub available_at: i64,
+}
+
+impl GraphSyncEvent {
+    pub fn new(seq: i64, op: GraphSyncOp) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            seq,
+            keys: op.keys(),
+            op,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            available_at: now.timestamp_millis(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::author_model::AuthorEmbed;
+    use crate::model::book_model::{BookFormat, BookRating};
+    use crate::model::genre_model::GenreEmbed;
+    use crate::model::publisher_model::PublisherEmbed;
+
+    #[test]
+    fn new_events_are_due_immediately_at_their_sequence() {
+        let event = GraphSyncEvent::new(7, GraphSyncOp::DeleteGenre { name: "fantasy".to_string() });
+
+        assert_eq!(event.seq, 7);
+        assert_eq!(event.keys, vec!["genre:fantasy"]);
+        assert_eq!(event.attempts, 0);
+        assert!(event.last_error.is_none());
+        assert_eq!(event.available_at, event.created_at.timestamp_millis());
+    }
+
+    #[test]
+    fn ops_are_tagged_by_their_snake_case_name() {
+        let op = serde_json::to_value(GraphSyncOp::RemoveRating { user_id: "u".into(), book_id: "b".into() }).unwrap();
+
+        assert_eq!(op, serde_json::json!({ "op": "remove_rating", "user_id": "u", "book_id": "b" }));
+
+        let op = serde_json::to_value(GraphSyncOp::LinkEdition { book_id: "b".into(), work_id: "w".into() }).unwrap();
+
+        assert_eq!(op, serde_json::json!({ "op": "link_edition", "book_id": "b", "work_id": "w" }));
+    }
+
+    #[test]
+    fn events_queued_before_sequencing_read_as_seq_zero() {
+        let event: GraphSyncEvent = serde_json::from_value(serde_json::json!({
+            "op": { "op": "delete_genre", "name": "fantasy" },
+            "attempts": 1,
+            "last_error": null,
+            "created_at": "2024-01-01T00:00:00Z",
+            "available_at": 0,
+        })).unwrap();
+
+        assert_eq!(event.seq, 0);
+        assert!(event.keys.is_empty());
+    }
+
+    #[test]
+    fn relationship_ops_are_keyed_by_both_ends() {
+        let shelf = GraphSyncOp::AddToShelf { user_id: "u".into(), book_id: "b".into(), status: "read".into(), ts: 0 };
+        assert_eq!(shelf.keys(), vec!["reader:u", "book:b"]);
+
+        let follow = GraphSyncOp::Unfollow { follower_id: "u".into(), followee_id: "v".into() };
+        assert_eq!(follow.keys(), vec!["reader:u", "reader:v"]);
+
+        let edition = GraphSyncOp::LinkEdition { book_id: "b".into(), work_id: "w".into() };
+        assert_eq!(edition.keys(), vec!["book:b", "work:w"]);
+    }
+
+    #[test]
+    fn book_upserts_wait_for_their_authors_and_genres() {
+        let op = GraphSyncOp::UpsertBook {
+            book_id: "b".into(),
+            title: "A Book".into(),
+            author_ids: vec!["a1".into(), "a2".into()],
+            genres: vec!["fantasy".into()],
+            publishers: vec!["tor".into()],
+        };
+
+        assert_eq!(op.keys(), vec!["book:b", "author:a1", "author:a2", "genre:fantasy"]);
+        assert_eq!(GraphSyncOp::DeleteBooks { book_ids: vec!["b".into(), "c".into()] }.keys(), vec!["book:b", "book:c"]);
+    }
+
+    #[test]
+    fn book_upsert_carries_its_links() {
+        let author_id = ObjectId::new();
+        let book = Book {
+            id: Some(ObjectId::parse_str("65f0c0ffee0000000000b001").unwrap()),
+            isbn: String::new(),
+            isbn13: "9780306406157".to_string(),
+            title: "A Book".to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: None,
+            published_date: None,
+            format: BookFormat::Paperback,
+            images: vec![],
+            preview: vec![],
+            genres: vec![GenreEmbed { name: "fantasy".to_string() }],
+            authors: vec![AuthorEmbed { id: author_id, name: "An Author".to_string(), image_url: String::new() }],
+            publishers: vec![PublisherEmbed { name: "tor".to_string() }],
+            languages: vec![],
+            reviews: vec![],
+            rating: BookRating::default(),
+            external_id: None,
+            work_id: None,
+        };
+
+        match GraphSyncOp::upsert_book(&book) {
+            GraphSyncOp::UpsertBook { book_id, title, author_ids, genres, publishers } => {
+                assert_eq!(book_id, "65f0c0ffee0000000000b001");
+                assert_eq!(title, "A Book");
+                assert_eq!(author_ids, vec![author_id.to_hex()]);
+                assert_eq!(genres, vec!["fantasy"]);
+                assert_eq!(publishers, vec!["tor"]);
+            },
+            other => panic!("unexpected op {:?}", other),
+        }
+    }
+}
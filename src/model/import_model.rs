@@ -0,0 +1,254 @@
+use std::path::Path;
+
+use mongodb::bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::book_model::BookFormat;
+use crate::model::external_id_model::ExternalProvider;
+use crate::shared::constant::IMPORT_ERROR_SAMPLE_SIZE;
+
+
+pub const IMPORT_JOB_COLLECTION: &str = "import_jobs";
+
+
+/// Records held by a dump file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    Books,
+    Authors,
+    /// One reader's rating of one book
+    Ratings,
+}
+
+impl ImportKind {
+    pub const ALL: [ImportKind; 3] = [Self::Books, Self::Authors, Self::Ratings];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Books => "books",
+            Self::Authors => "authors",
+            Self::Ratings => "ratings",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// Comma-separated values with a header line
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl ImportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "json" => Some(Self::JsonLines),
+            _ => None,
+        }
+    }
+
+    /// Guessed from the file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| Self::from_name(&extension.to_ascii_lowercase()))
+    }
+}
+
+
+/// Author named by an imported book, by external id, by name or both.
+#[derive(Debug, Clone)]
+pub struct ImportedAuthorRef {
+    pub external_id: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedAuthor {
+    pub line: u64,
+    pub external_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedBook {
+    pub line: u64,
+    pub external_id: Option<String>,
+    pub isbn: Option<String>,
+    pub isbn13: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub num_pages: Option<i32>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub format: BookFormat,
+    pub authors: Vec<ImportedAuthorRef>,
+    pub genres: Vec<String>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedRating {
+    pub line: u64,
+    pub reader_external_id: String,
+    pub reader_name: Option<String>,
+    /// The book is found by its external id, or by ISBN when the dump has none
+    pub book_external_id: Option<String>,
+    pub isbn: Option<String>,
+    pub score: f32,
+    pub rated_at: Option<DateTime<Utc>>,
+}
+
+/// One parsed row of a dump.
+#[derive(Debug, Clone)]
+pub enum ImportRecord {
+    Author(ImportedAuthor),
+    Book(ImportedBook),
+    Rating(ImportedRating),
+}
+
+impl ImportRecord {
+    pub fn line(&self) -> u64 {
+        match self {
+            Self::Author(author) => author.line,
+            Self::Book(book) => book.line,
+            Self::Rating(rating) => rating.line,
+        }
+    }
+
+    /// Keys identifying the record within a dump: a row sharing any of them with an earlier one is a duplicate.
+    /// Ratings have none, a repeated rating updates the review the first one left, and keying
+    /// every rating of a dump would hold one key per row in memory.
+    pub fn dedup_keys(&self) -> Vec<String> {
+        match self {
+            Self::Author(author) => vec![format!("author:{}", author.external_id)],
+            Self::Book(book) => {
+                let mut keys = Vec::new();
+                if let Some(id) = &book.external_id {
+                    keys.push(format!("book:{}", id));
+                }
+                if let Some(isbn) = &book.isbn {
+                    keys.push(format!("isbn:{}", isbn));
+                }
+                if let Some(isbn13) = &book.isbn13 {
+                    keys.push(format!("isbn13:{}", isbn13));
+                }
+                keys
+            },
+            Self::Rating(_) => vec![],
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowError {
+    /// Line of the dump where the row starts
+    pub line: u64,
+    pub message: String,
+}
+
+/// What one batch wrote.
+#[derive(Debug, Default)]
+pub struct ImportBatchResult {
+    pub created: u64,
+    pub updated: u64,
+    pub authors_created: u64,
+    pub genres_created: u64,
+    pub publishers_created: u64,
+    pub readers_created: u64,
+    /// Rows of the batch that could not be imported
+    pub rejected: Vec<ImportRowError>,
+    /// Problems that did not stop a row from being imported
+    pub warnings: Vec<ImportRowError>,
+}
+
+
+/// Progress and outcome of an import. Only the first `IMPORT_ERROR_SAMPLE_SIZE` errors are kept.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub rows_read: u64,
+    pub rows_imported: u64,
+    /// Rows repeating an external id or ISBN seen earlier in the dump
+    pub duplicates: u64,
+    pub rows_failed: u64,
+    /// Records of the imported kind
+    pub created: u64,
+    pub updated: u64,
+    pub authors_created: u64,
+    pub genres_created: u64,
+    pub publishers_created: u64,
+    pub readers_created: u64,
+    pub error_count: u64,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    pub fn error(&mut self, line: u64, message: impl Into<String>) {
+        self.error_count += 1;
+        if self.errors.len() < IMPORT_ERROR_SAMPLE_SIZE {
+            self.errors.push(ImportRowError { line, message: message.into() });
+        }
+    }
+
+    pub fn reject(&mut self, line: u64, message: impl Into<String>) {
+        self.rows_failed += 1;
+        self.error(line, message);
+    }
+
+    /// Adds a written batch of `rows` rows.
+    pub fn absorb(&mut self, rows: u64, batch: ImportBatchResult) {
+        self.rows_imported += rows - batch.rejected.len() as u64;
+        self.created += batch.created;
+        self.updated += batch.updated;
+        self.authors_created += batch.authors_created;
+        self.genres_created += batch.genres_created;
+        self.publishers_created += batch.publishers_created;
+        self.readers_created += batch.readers_created;
+        for rejected in batch.rejected {
+            self.reject(rejected.line, rejected.message);
+        }
+        for warning in batch.warnings {
+            self.error(warning.line, warning.message);
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ImportJobStatus {
+    Running,
+    Completed,
+    /// Stopped before the end of the dump, see `error`
+    Failed,
+}
+
+/// An import started from the API, with its report refreshed after every batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportJob {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub path: String,
+    pub kind: ImportKind,
+    pub provider: ExternalProvider,
+    pub format: ImportFormat,
+    pub status: ImportJobStatus,
+    pub report: ImportReport,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -52,13 +52,6 @@ impl Metadata {
         Self::Publisher { name, website }
     }
 
-    pub fn save_in_noe4j(&self) -> bool {
-        match self {
-            Metadata::Genre { .. } => true,
-            _ => false,
-        }
-    }
-
     pub fn key(&self) -> &str {
         match self {
             Metadata::Source { name, .. } => name,
@@ -89,10 +82,6 @@ impl Metadata {
             meta: self.clone(),
         }
     }
-
-    pub fn id_from(kind: &str, key: &str) -> String {
-        format!("{kind}:{key}")
-    }
 }
 
 
@@ -106,13 +95,6 @@ pub enum MetadataKey {
 }
 
 impl MetadataKey {
-    pub fn save_in_noe4j(&self) -> bool {
-        match self {
-            MetadataKey::Genre { .. } => true,
-            _ => false,
-        }
-    }
-
     pub fn kind(&self) -> &'static str {
         match self {
             MetadataKey::Source { .. } => "source",
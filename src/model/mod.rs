@@ -7,4 +7,14 @@ pub mod source_model;
 pub mod language_model;
 pub mod genre_model;
 pub mod author_model;
-pub mod external_id_model;
\ No newline at end of file
+pub mod external_id_model;
+pub mod graph_sync_model;
+pub mod reconciliation_model;
+pub mod recommendation_model;
+pub mod similarity_model;
+pub mod follow_model;
+pub mod search_model;
+pub mod import_model;
+pub mod export_model;
+pub mod dedup_model;
+pub mod work_model;
\ No newline at end of file
@@ -0,0 +1,30 @@
+/// Signal that proposed a recommended book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecommendationSignal {
+    /// Liked by readers who rated the same books alike
+    Collaborative,
+    /// Shares genres or authors with books the reader liked
+    Content,
+    /// Matches the reader's preferred genres or authors
+    Preference,
+}
+
+
+/// A book proposed by one signal.
+#[derive(Debug, Clone)]
+pub struct RecommendationCandidate {
+    pub book_id: String,
+    /// Work the book is an edition of, if any
+    pub work_id: Option<String>,
+    /// Raw strength, only comparable between candidates of the same signal
+    pub score: f64,
+    /// Ids of the similar readers, or names of the shared genres and authors
+    pub evidence: Vec<String>,
+}
+
+impl RecommendationCandidate {
+    /// Key under which candidates are merged: editions of one work are a single recommendation.
+    pub fn group_key(&self) -> &str {
+        self.work_id.as_deref().unwrap_or(&self.book_id)
+    }
+}
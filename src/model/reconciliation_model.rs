@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::Serialize;
+
+use crate::shared::constant::RECONCILE_SAMPLE_SIZE;
+
+
+/// Keys present in only one store, or in both with different properties.
+#[derive(Debug, Clone)]
+pub struct Drift<K> {
+    /// In MongoDB, absent from Neo4j
+    pub missing: Vec<K>,
+    /// In Neo4j, absent from MongoDB
+    pub orphaned: Vec<K>,
+    /// In both, with different properties
+    pub mismatched: Vec<K>,
+}
+
+impl<K: Clone + Ord + Hash> Drift<K> {
+    /// Compares the expected graph, derived from MongoDB, with what Neo4j actually holds.
+    pub fn between<V: PartialEq>(source: &HashMap<K, V>, graph: &HashMap<K, V>) -> Self {
+        let mut missing = Vec::new();
+        let mut mismatched = Vec::new();
+        for (key, value) in source {
+            match graph.get(key) {
+                None => missing.push(key.clone()),
+                Some(actual) if actual != value => mismatched.push(key.clone()),
+                Some(_) => {},
+            }
+        }
+        let mut orphaned: Vec<K> = graph.keys().filter(|key| !source.contains_key(*key)).cloned().collect();
+
+        missing.sort();
+        mismatched.sort();
+        orphaned.sort();
+        Self { missing, orphaned, mismatched }
+    }
+}
+
+
+/// Drift of one kind of node or relationship, with a few sample keys of each sort.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftCategory {
+    pub category: String,
+    pub source_count: usize,
+    pub graph_count: usize,
+    pub missing: usize,
+    pub orphaned: usize,
+    pub mismatched: usize,
+    pub missing_samples: Vec<String>,
+    pub orphaned_samples: Vec<String>,
+    pub mismatched_samples: Vec<String>,
+}
+
+impl DriftCategory {
+    pub fn new<K>(category: &str, source_count: usize, graph_count: usize, drift: &Drift<K>, label: impl Fn(&K) -> String) -> Self {
+        let samples = |keys: &[K]| keys.iter().take(RECONCILE_SAMPLE_SIZE).map(&label).collect();
+        Self {
+            category: category.to_string(),
+            source_count,
+            graph_count,
+            missing: drift.missing.len(),
+            orphaned: drift.orphaned.len(),
+            mismatched: drift.mismatched.len(),
+            missing_samples: samples(&drift.missing),
+            orphaned_samples: samples(&drift.orphaned),
+            mismatched_samples: samples(&drift.mismatched),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciliationReport {
+    pub categories: Vec<DriftCategory>,
+    /// Whether repairs were requested
+    pub repair: bool,
+    /// Graph-sync events enqueued to repair the drift
+    pub repair_events: usize,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(entries: &[(&'static str, u32)]) -> HashMap<&'static str, u32> {
+        entries.iter().copied().collect()
+    }
+
+    #[test]
+    fn identical_stores_have_no_drift() {
+        let drift = Drift::between(&store(&[("a", 1), ("b", 2)]), &store(&[("a", 1), ("b", 2)]));
+
+        assert!(drift.missing.is_empty() && drift.orphaned.is_empty() && drift.mismatched.is_empty());
+    }
+
+    #[test]
+    fn drift_is_sorted_by_key() {
+        let source = store(&[("c", 1), ("a", 1), ("b", 1), ("d", 1), ("e", 1)]);
+        let graph = store(&[("e", 1), ("d", 2), ("b", 2), ("y", 1), ("x", 1)]);
+
+        let drift = Drift::between(&source, &graph);
+
+        assert_eq!(drift.missing, vec!["a", "c"]);
+        assert_eq!(drift.mismatched, vec!["b", "d"]);
+        assert_eq!(drift.orphaned, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn category_counts_everything_but_samples_a_few() {
+        let source: HashMap<u32, ()> = (0..RECONCILE_SAMPLE_SIZE as u32 + 3).map(|key| (key, ())).collect();
+        let drift = Drift::between(&source, &HashMap::new());
+
+        let category = DriftCategory::new("books", source.len(), 0, &drift, |key| format!("book:{}", key));
+
+        assert_eq!(category.missing, RECONCILE_SAMPLE_SIZE + 3);
+        assert_eq!(category.missing_samples.len(), RECONCILE_SAMPLE_SIZE);
+        assert_eq!(category.missing_samples[0], "book:0");
+    }
+}
@@ -1,7 +1,8 @@
-use bson::oid::ObjectId;
+use mongodb::bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::model::user_model::UserEmbed;
+use crate::shared::constant::{REVIEW_SCORE_MAX, REVIEW_SCORE_MIN};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Review {
@@ -14,11 +15,38 @@ pub struct Review {
     pub content: String,
     pub score: f32,
     pub date_added: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
+impl Review {
+    /// Histogram bucket of `score`: scores are whole stars between
+    /// `REVIEW_SCORE_MIN` and `REVIEW_SCORE_MAX`, anything else is rejected.
+    pub fn score_bucket(score: f32) -> Option<u8> {
+        if score.fract() != 0.0 || score < REVIEW_SCORE_MIN as f32 || score > REVIEW_SCORE_MAX as f32 {
+            return None;
+        }
+        Some(score as u8)
+    }
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RaterRelationShip {
-    pub rating: f32,
-    pub ts: i64,
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_stars_within_range_have_a_bucket() {
+        assert_eq!(Review::score_bucket(1.0), Some(1));
+        assert_eq!(Review::score_bucket(5.0), Some(5));
+    }
+
+    #[test]
+    fn fractional_or_out_of_range_scores_have_none() {
+        assert_eq!(Review::score_bucket(3.5), None);
+        assert_eq!(Review::score_bucket(0.0), None);
+        assert_eq!(Review::score_bucket(6.0), None);
+        assert_eq!(Review::score_bucket(f32::NAN), None);
+    }
 }
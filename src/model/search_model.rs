@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+use crate::model::book_model::BookFormat;
+
+
+/// A document matched by a search, with its relevance within its own collection.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchHit<T> {
+    pub item: T,
+    pub score: f64,
+}
+
+/// Narrows the book results of a search.
+#[derive(Debug, Clone, Default)]
+pub struct BookSearchFilter {
+    pub language: Option<String>,
+    pub format: Option<BookFormat>,
+    pub genre: Option<String>,
+}
@@ -0,0 +1,26 @@
+/// Kind of node compared by the similarity queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityKind {
+    Book,
+    Author,
+}
+
+impl SimilarityKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Book => "book",
+            Self::Author => "author",
+        }
+    }
+}
+
+
+/// Another node of the same kind, scored against the compared one.
+#[derive(Debug, Clone)]
+pub struct SimilarityScore {
+    pub id: String,
+    /// Weighted Jaccard index of readers and genres, in `[0, 1]`
+    pub score: f64,
+    pub shared_readers: i64,
+    pub shared_genres: i64,
+}
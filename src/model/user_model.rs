@@ -1,14 +1,17 @@
-use bson::oid::ObjectId;
+use mongodb::bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::model::book_model::BookEmbed;
+use crate::model::book_model::ShelfEntry;
+use crate::model::external_id_model::ExternalId;
+use crate::shared::security::password::UNUSABLE_PASSWORD;
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum UserRole {
     Admin,
+    #[default]
     Reader,
 }
 
@@ -29,24 +32,48 @@ impl UserRole {
     }
 }
 
-impl Default for UserRole {
-    fn default() -> Self { Self::Reader }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserPreference {
     pub authors: Vec<String>,
     pub genres: Vec<String>,
     pub languages: Vec<String>,
 }
 
+/// Argon2 PHC string of a user's password.
+/// Stored as-is in Mongo, but redacted from `Debug` output so it never ends up in logs,
+/// and deliberately absent from every response DTO.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HashedPassword(String);
+
+impl HashedPassword {
+    pub fn new(hash: String) -> Self {
+        Self(hash)
+    }
+
+    /// Marker of an account that cannot log in.
+    pub fn unusable() -> Self {
+        Self(UNUSABLE_PASSWORD.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for HashedPassword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HashedPassword(***)")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
     pub id: Option<ObjectId>,
 
     pub username: String,
-    pub password: String,
+    pub password: HashedPassword,
 
     pub name: String,
     pub image_url: Option<String>,
@@ -57,11 +84,15 @@ pub struct User {
     pub preference: Option<UserPreference>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub shelf: Option<Vec<BookEmbed>>,
+    pub shelf: Option<Vec<ShelfEntry>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reviews: Option<Vec<ObjectId>>,
 
+    /// Set on readers brought in by a catalog import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<ExternalId>,
+
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -76,7 +107,7 @@ pub struct ReaderNode {
 
 impl From<&User> for ReaderNode {
     fn from(user: &User) -> Self {
-        Self::new(user.id.clone().unwrap().to_hex(), user.name.clone())
+        Self::new(user.id.unwrap().to_hex(), user.name.clone())
     }
 }
 
@@ -101,7 +132,7 @@ pub struct UserEmbed {
 impl From<&User> for UserEmbed {
     fn from(user: &User) -> Self {
         Self {
-            id: user.id.clone().unwrap(),
+            id: user.id.unwrap(),
             name: user.name.clone(),
             image_url: user.image_url.clone(),
         }
@@ -0,0 +1,154 @@
+use mongodb::bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::author_model::AuthorEmbed;
+use crate::model::book_model::{Book, BookFormat, BookRating};
+
+
+/// A book as written, grouping its editions and translations. The editions are the books
+/// whose `work_id` points at it, so formats, languages, authors and ratings are all read from them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Work {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub title: String,
+    pub description: Option<String>,
+    /// Language the work was written in, editions in other languages are translations
+    pub original_language: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Work {
+    /// An edition is a translation when none of its languages is the original one.
+    /// Without a known original language no edition is.
+    pub fn is_translation(&self, edition: &Book) -> bool {
+        match &self.original_language {
+            Some(original) => !edition.languages.is_empty() && !edition.languages.contains(original),
+            None => false,
+        }
+    }
+}
+
+
+/// Formats and languages a work can be read in, over all its editions.
+#[derive(Debug, Clone, Default)]
+pub struct WorkAvailability {
+    pub formats: Vec<BookFormat>,
+    pub languages: Vec<String>,
+}
+
+impl WorkAvailability {
+    /// Formats in `BookFormat::ALL` order, languages in order of first appearance.
+    pub fn of(editions: &[Book]) -> Self {
+        let formats = BookFormat::ALL
+            .into_iter()
+            .filter(|format| editions.iter().any(|edition| edition.format.name() == format.name()))
+            .collect();
+
+        let mut languages: Vec<String> = Vec::new();
+        for language in editions.iter().flat_map(|edition| &edition.languages) {
+            if !languages.contains(language) {
+                languages.push(language.clone());
+            }
+        }
+        Self { formats, languages }
+    }
+}
+
+/// Authors of the editions, each once, in order of first appearance.
+pub fn work_authors(editions: &[Book]) -> Vec<AuthorEmbed> {
+    let mut authors: Vec<AuthorEmbed> = Vec::new();
+    for author in editions.iter().flat_map(|edition| &edition.authors) {
+        if !authors.iter().any(|known| known.id == author.id) {
+            authors.push(author.clone());
+        }
+    }
+    authors
+}
+
+/// Rating of the work: every review of every edition counts once.
+pub fn work_rating(editions: &[Book]) -> BookRating {
+    BookRating::combine(editions.iter().map(|edition| &edition.rating))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edition(format: BookFormat, languages: &[&str], scores: &[f32]) -> Book {
+        Book {
+            id: Some(ObjectId::new()),
+            isbn: String::new(),
+            isbn13: String::new(),
+            title: "Le Petit Prince".to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: None,
+            published_date: None,
+            format,
+            images: vec![],
+            preview: vec![],
+            genres: vec![],
+            authors: vec![],
+            publishers: vec![],
+            languages: languages.iter().map(|language| language.to_string()).collect(),
+            reviews: vec![],
+            rating: BookRating::from_scores(scores.iter().copied()),
+            external_id: None,
+            work_id: None,
+        }
+    }
+
+    fn work(original_language: Option<&str>) -> Work {
+        Work {
+            id: Some(ObjectId::new()),
+            title: "Le Petit Prince".to_string(),
+            description: None,
+            original_language: original_language.map(str::to_string),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn availability_lists_each_format_and_language_once() {
+        let editions = vec![
+            edition(BookFormat::Audiobook, &["en"], &[]),
+            edition(BookFormat::Paperback, &["fr"], &[]),
+            edition(BookFormat::Paperback, &["en", "fr"], &[]),
+        ];
+
+        let availability = WorkAvailability::of(&editions);
+
+        assert_eq!(availability.formats.iter().map(BookFormat::name).collect::<Vec<_>>(), vec!["Paperback", "Audiobook"]);
+        assert_eq!(availability.languages, vec!["en".to_string(), "fr".to_string()]);
+    }
+
+    #[test]
+    fn editions_without_the_original_language_are_translations() {
+        let french = work(Some("fr"));
+
+        assert!(!french.is_translation(&edition(BookFormat::Hardcover, &["fr"], &[])));
+        assert!(french.is_translation(&edition(BookFormat::Hardcover, &["en"], &[])));
+        assert!(!french.is_translation(&edition(BookFormat::Hardcover, &[], &[])));
+        assert!(!work(None).is_translation(&edition(BookFormat::EBook, &["en"], &[])));
+    }
+
+    #[test]
+    fn work_rating_counts_the_reviews_of_every_edition() {
+        let editions = vec![
+            edition(BookFormat::Paperback, &["fr"], &[5.0, 4.0]),
+            edition(BookFormat::EBook, &["en"], &[3.0]),
+        ];
+
+        let rating = work_rating(&editions);
+
+        assert_eq!(rating.count, 3);
+        assert!((rating.average - 4.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,704 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    Client, ClientSession, Database, Collection,
+};
+use mongodb::bson::{to_bson, to_document};
+use serde::Deserialize;
+use neo4rs::{query, Query};
+
+use crate::model::author_model::Author;
+use crate::model::book_model::{Book, BookEmbed, BookFacets, BookFilter};
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::model::review_model::Review;
+use crate::model::user_model::User;
+use crate::repository::graph_sync_repository::enqueue;
+use crate::shared::constant::BOOK_FACET_LIMIT;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginationRequest;
+use crate::shared::repository::repository_utils::{external_id_update, insert_many_in_session, sort_document, with_search};
+
+
+impl Book {
+    fn author_object_ids(&self) -> Vec<ObjectId> {
+        self.authors.iter().map(|author| author.id).collect()
+    }
+
+    pub fn neo4j_node_queries(
+        book_id: &str,
+        title: &str,
+        author_ids: Vec<String>,
+        genres: Vec<String>,
+        publishers: Vec<String>,
+    ) -> Vec<Query> {
+        vec![
+            query("MERGE (b:Book {book_id:$book_id}) SET b.title = $title")
+                .param("book_id", book_id)
+                .param("title", title),
+
+            query(
+                "MATCH (b:Book {book_id:$book_id})
+                 UNWIND $author_ids AS author_id
+                 MATCH (a:Author {author_id:author_id})
+                 MERGE (a)-[:WROTE]->(b)"
+            ).param("book_id", book_id).param("author_ids", author_ids),
+
+            query(
+                "MATCH (b:Book {book_id:$book_id})
+                 UNWIND $genres AS name
+                 MERGE (g:Genre {name:name})
+                 MERGE (b)-[:HAS_GENRE]->(g)"
+            ).param("book_id", book_id).param("genres", genres),
+
+            query(
+                "MATCH (b:Book {book_id:$book_id})
+                 UNWIND $publishers AS name
+                 MERGE (p:Publisher {name:name})
+                 MERGE (b)-[:PUBLISHED_BY]->(p)"
+            ).param("book_id", book_id).param("publishers", publishers),
+        ]
+    }
+
+    /// Drops the catalog relationships so they can be rebuilt from the updated embeds.
+    pub fn neo4j_unlink_queries(book_id: &str) -> Vec<Query> {
+        vec![
+            query("MATCH (b:Book {book_id:$book_id})-[rel:HAS_GENRE|PUBLISHED_BY]->() DELETE rel")
+                .param("book_id", book_id),
+            query("MATCH (:Author)-[rel:WROTE]->(b:Book {book_id:$book_id}) DELETE rel")
+                .param("book_id", book_id),
+        ]
+    }
+}
+
+
+#[async_trait]
+pub trait BookRepositoryInterface {
+    async fn insert(&self, book: Book) -> Result<String, AppError>;
+    async fn update(&self, book: Book) -> Result<bool, AppError>;
+    async fn delete(&self, book_id: &str) -> Result<bool, AppError>;
+    async fn find_by_id(&self, book_id: &str) -> Result<Option<Book>, AppError>;
+    async fn find_by_ids(&self, book_ids: Vec<&str>) -> Result<Vec<Book>, AppError>;
+    /// Best rated books, leaving out `book_ids`, the books holding `review_ids` and every other
+    /// edition of their works. A work is proposed once, through its best rated edition.
+    async fn find_best_rated_excluding(&self, book_ids: Vec<ObjectId>, review_ids: Vec<ObjectId>, limit: u64) -> Result<Vec<Book>, AppError>;
+    async fn find_filtered(&self, filter: &BookFilter, pagination: &PaginationRequest) -> Result<(Vec<Book>, u64, BookFacets), AppError>;
+    async fn find_by_external_id(&self, provider: ExternalProvider, external_id: &str) -> Result<Option<Book>, AppError>;
+    /// Sets the provider's id of the book, or clears it with `None`. False when the book does not exist.
+    async fn set_external_id(&self, book_id: &str, provider: ExternalProvider, external_id: Option<&str>) -> Result<bool, AppError>;
+}
+
+
+const SEARCH_FIELDS: &[&str] = &["title", "subtitle", "isbn", "isbn13", "authors.name"];
+const SORT_FIELDS: &[&str] = &["title", "published_date", "num_pages", "rating.average", "rating.count"];
+
+/// Output of the catalog's `$facet` stage.
+#[derive(Debug, Default, Deserialize)]
+struct CatalogPage {
+    items: Vec<Book>,
+    total: Vec<CatalogCount>,
+    #[serde(flatten)]
+    facets: BookFacets,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogCount {
+    count: u64,
+}
+
+/// One `$match` clause per filtered field, named after the facet it narrows.
+fn filter_clauses(filter: &BookFilter) -> Result<Vec<(&'static str, Document)>, AppError> {
+    let mut clauses = Vec::new();
+    if !filter.genres.is_empty() {
+        clauses.push(("genres", doc! { "genres.name": { "$in": &filter.genres } }));
+    }
+    if !filter.author_ids.is_empty() {
+        clauses.push(("authors", doc! { "authors.id": { "$in": &filter.author_ids } }));
+    }
+    if !filter.publishers.is_empty() {
+        clauses.push(("publishers", doc! { "publishers.name": { "$in": &filter.publishers } }));
+    }
+    if !filter.languages.is_empty() {
+        clauses.push(("languages", doc! { "languages": { "$in": &filter.languages } }));
+    }
+    if !filter.formats.is_empty() {
+        clauses.push(("formats", doc! { "format": { "$in": to_bson(&filter.formats)? } }));
+    }
+
+    let mut pages = doc! {};
+    if let Some(min) = filter.pages_min {
+        pages.insert("$gte", min);
+    }
+    if let Some(max) = filter.pages_max {
+        pages.insert("$lte", max);
+    }
+    if !pages.is_empty() {
+        clauses.push(("pages", doc! { "num_pages": pages }));
+    }
+
+    let mut published = doc! {};
+    if let Some(from) = &filter.published_from {
+        published.insert("$gte", to_bson(from)?);
+    }
+    if let Some(to) = &filter.published_to {
+        published.insert("$lte", to_bson(to)?);
+    }
+    if !published.is_empty() {
+        clauses.push(("published", doc! { "published_date": published }));
+    }
+    Ok(clauses)
+}
+
+/// `$and` of every clause, or of all but the one narrowing the `except` facet.
+fn match_clauses(clauses: &[(&'static str, Document)], except: Option<&str>) -> Document {
+    let others: Vec<&Document> = clauses
+        .iter()
+        .filter(|(name, _)| Some(*name) != except)
+        .map(|(_, clause)| clause)
+        .collect();
+    if others.is_empty() {
+        doc! {}
+    } else {
+        doc! { "$and": others }
+    }
+}
+
+/// Counts books per `$group` value, under every filter except the one narrowing `facet`.
+fn facet_pipeline(clauses: &[(&'static str, Document)], facet: &str, unwind: Option<&str>, value: Document) -> Vec<Document> {
+    let mut pipeline = vec![doc! { "$match": match_clauses(clauses, Some(facet)) }];
+    if let Some(path) = unwind {
+        pipeline.push(doc! { "$unwind": path });
+    }
+    pipeline.push(doc! { "$group": value });
+    pipeline.push(doc! { "$sort": { "count": -1, "_id": 1 } });
+    pipeline.push(doc! { "$limit": BOOK_FACET_LIMIT });
+    pipeline
+}
+
+
+#[derive(Clone)]
+pub struct BookRepository {
+    pub mongo_client: Client,
+    pub book_collection: Collection<Book>,
+    pub author_collection: Collection<Author>,
+    pub review_collection: Collection<Review>,
+    pub user_collection: Collection<User>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+}
+
+impl BookRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
+        let book_collection = mongo_database.collection::<Book>("books");
+        let author_collection = mongo_database.collection::<Author>("authors");
+        let review_collection = mongo_database.collection::<Review>("reviews");
+        let user_collection = mongo_database.collection::<User>("users");
+        let outbox_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION);
+        BookRepository {
+            mongo_client,
+            book_collection,
+            author_collection,
+            review_collection,
+            user_collection,
+            outbox_collection,
+        }
+    }
+
+    /// Records the graph changes mirroring this write in the outbox, inside the caller's transaction.
+    async fn record_graph_sync(&self, session: &mut ClientSession, ops: Vec<GraphSyncOp>) -> Result<(), AppError> {
+        enqueue(&self.outbox_collection, session, ops).await
+    }
+
+    /// Inserts the books, adds them to their authors' book lists and records their nodes
+    /// inside the caller's transaction, returning their ids in order.
+    pub async fn insert_many_in_session(&self, session: &mut ClientSession, books: &[Book]) -> Result<Vec<ObjectId>, AppError> {
+        let book_ids = insert_many_in_session(&self.book_collection, session, books).await?;
+
+        let mut ops = Vec::with_capacity(books.len());
+        for (book_id, book) in book_ids.iter().zip(books) {
+            let mut book = book.clone();
+            book.id = Some(*book_id);
+            let author_ids = book.author_object_ids();
+            if !author_ids.is_empty() {
+                self.author_collection
+                    .update_many(
+                        doc! { "_id": { "$in": &author_ids } },
+                        doc! { "$push": { "books": to_document(&BookEmbed::from(&book))? } },
+                    )
+                    .session(&mut *session)
+                    .await?;
+            }
+            ops.push(GraphSyncOp::upsert_book(&book));
+        }
+        self.record_graph_sync(session, ops).await?;
+        Ok(book_ids)
+    }
+}
+
+
+#[async_trait]
+impl BookRepositoryInterface for BookRepository {
+    async fn insert(&self, book: Book) -> Result<String, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [INSERT] data: {:?}",
+            book
+        ));
+
+        let author_ids = book.author_object_ids();
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_insert = self.book_collection
+            .insert_one(&book)
+            .session(&mut mongo_session)
+            .await;
+
+        let inserted_id = match result_insert {
+            Ok(result_insert) => match result_insert.inserted_id.as_object_id() {
+                Some(oid) => oid,
+                None => {
+                    let _ = mongo_session.abort_transaction().await;
+                    timer.error_with_message("Error adding book: inserted id is not an ObjectId");
+                    return Err(AppError::Upstream(anyhow!("Inserted book id is not an ObjectId")));
+                }
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error adding book: {}", e));
+                return Err(e.into());
+            }
+        };
+
+        let mut book = book;
+        book.id = Some(inserted_id);
+
+        // Keep the authors' embedded book list in the same transaction
+        if !author_ids.is_empty() {
+            let book_doc = to_document(&BookEmbed::from(&book))?;
+            if let Err(e) = self.author_collection
+                .update_many(doc! { "_id": { "$in": &author_ids } }, doc! { "$push": { "books": book_doc } })
+                .session(&mut mongo_session)
+                .await
+            {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error adding book to authors: {}", e));
+                return Err(e.into());
+            }
+        }
+
+        match self.record_graph_sync(&mut mongo_session, vec![GraphSyncOp::upsert_book(&book)]).await {
+            Ok(()) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(inserted_id.to_hex())
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error adding book: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn update(&self, book: Book) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [UPDATE] data: {:?}",
+            book
+        ));
+
+        let book_id = match &book.id {
+            Some(id) => id.to_hex(),
+            None => {
+                timer.error_with_message("Book without id");
+                return Err(AppError::Validation("Book id is required for update".to_string()));
+            }
+        };
+        let id = ObjectId::parse_str(&book_id)?;
+        let author_ids = book.author_object_ids();
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        // Reviews and rating aggregates belong to the review repository and the work to the work
+        // repository, never overwrite them here
+        let mut book_fields = to_document(&book)?;
+        book_fields.remove("_id");
+        book_fields.remove("reviews");
+        book_fields.remove("rating");
+        book_fields.remove("work_id");
+
+        let result_update = self.book_collection
+            .update_one(doc! { "_id": &id }, doc! { "$set": book_fields })
+            .session(&mut mongo_session)
+            .await;
+
+        let result_update = match result_update {
+            Ok(result_update) => result_update,
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error updating book: {}", e));
+                return Err(e.into());
+            }
+        };
+
+        if result_update.matched_count == 0 {
+            let _ = mongo_session.abort_transaction().await;
+            timer.warning_with_message(&format!("Book not found: {}", book_id));
+            return Ok(false);
+        }
+
+        // Authors may have changed: drop the old embeds, then push the fresh one
+        let book_doc = to_document(&BookEmbed::from(&book))?;
+        let result_authors = async {
+            self.author_collection
+                .update_many(doc! { "books.book_id": &id }, doc! { "$pull": { "books": { "book_id": &id } } })
+                .session(&mut mongo_session)
+                .await?;
+            if !author_ids.is_empty() {
+                self.author_collection
+                    .update_many(doc! { "_id": { "$in": &author_ids } }, doc! { "$push": { "books": book_doc } })
+                    .session(&mut mongo_session)
+                    .await?;
+            }
+            Ok::<(), mongodb::error::Error>(())
+        }.await;
+
+        if let Err(e) = result_authors {
+            let _ = mongo_session.abort_transaction().await;
+            timer.error_with_message(&format!("Error updating book authors: {}", e));
+            return Err(e.into());
+        }
+
+        match self.record_graph_sync(&mut mongo_session, vec![GraphSyncOp::upsert_book(&book)]).await {
+            Ok(()) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(true)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error updating book: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete(&self, book_id: &str) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [DELETE] book_id: {:?}",
+            book_id
+        ));
+
+        let id = ObjectId::parse_str(book_id);
+        match id {
+            Ok(id) => {
+                let mut mongo_session = self.mongo_client.start_session().await?;
+                mongo_session.start_transaction().await?;
+
+                let result_delete = async {
+                    let result_delete = self.book_collection
+                        .delete_one(doc! { "_id": &id })
+                        .session(&mut mongo_session)
+                        .await?;
+                    self.author_collection
+                        .update_many(doc! { "books.book_id": &id }, doc! { "$pull": { "books": { "book_id": &id } } })
+                        .session(&mut mongo_session)
+                        .await?;
+
+                    self.user_collection
+                        .update_many(doc! { "shelf.book_id": &id }, doc! { "$pull": { "shelf": { "book_id": &id } } })
+                        .session(&mut mongo_session)
+                        .await?;
+
+                    // Reviews of the book go with it, including their reference on the reviewers
+                    let review_ids: Vec<ObjectId> = self.review_collection
+                        .distinct("_id", doc! { "book_id": &id })
+                        .session(&mut mongo_session)
+                        .await?
+                        .into_iter()
+                        .filter_map(|review_id| review_id.as_object_id())
+                        .collect();
+                    if !review_ids.is_empty() {
+                        self.user_collection
+                            .update_many(doc! { "reviews": { "$in": &review_ids } }, doc! { "$pull": { "reviews": { "$in": &review_ids } } })
+                            .session(&mut mongo_session)
+                            .await?;
+                        self.review_collection
+                            .delete_many(doc! { "book_id": &id })
+                            .session(&mut mongo_session)
+                            .await?;
+                    }
+
+                    let op = GraphSyncOp::DeleteBooks { book_ids: vec![id.to_hex()] };
+                    self.record_graph_sync(&mut mongo_session, vec![op]).await?;
+                    Ok::<_, AppError>(result_delete)
+                }.await;
+
+                match result_delete {
+                    Ok(result_delete) => {
+                        mongo_session.commit_transaction().await?;
+                        timer.log();
+                        Ok(result_delete.deleted_count > 0)
+                    },
+                    Err(e) => {
+                        let _ = mongo_session.abort_transaction().await;
+                        timer.error_with_message(&format!("Error deleting book: {}", e));
+                        Err(e)
+                    },
+                }
+            },
+            Err(_) => {
+                timer.error_with_message(&format!("Invalid book id: {}", book_id));
+                Err(AppError::invalid_id("book", book_id))
+            }
+        }
+    }
+
+    async fn find_by_id(&self, book_id: &str) -> Result<Option<Book>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [FIND BY ID] book_id: {:?}",
+            book_id
+        ));
+
+        let id = ObjectId::parse_str(book_id);
+        match id {
+            Ok(id) => {
+                let result = self.book_collection.find_one(doc! {"_id": &id }).await;
+                match result {
+                    Ok(result) => {
+                        timer.log();
+                        Ok(result)
+                    },
+                    Err(e) => {
+                        timer.error_with_message(&format!("Error finding book: {}", e));
+                        Err(e.into())
+                    },
+                }
+            },
+            Err(_) => {
+                timer.error_with_message(&format!("Invalid book id: {}", book_id));
+                Err(AppError::invalid_id("book", book_id))
+            }
+        }
+    }
+
+    async fn find_by_ids(&self, book_ids: Vec<&str>) -> Result<Vec<Book>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [FIND BY IDS] book_ids: {:?}",
+            book_ids
+        ));
+
+        let ids: Vec<_> = book_ids
+            .iter()
+            .filter_map(|&id| ObjectId::parse_str(id).ok())
+            .collect();
+
+        let result_find = self.book_collection.find(doc! {"_id": {"$in": ids }}).await;
+        match result_find {
+            Ok(result_find) => {
+                timer.log();
+                Ok(result_find.try_collect().await?)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding books: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn find_best_rated_excluding(&self, book_ids: Vec<ObjectId>, review_ids: Vec<ObjectId>, limit: u64) -> Result<Vec<Book>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [FIND BEST RATED EXCLUDING] books: {:?}, reviews: {:?}, limit: {:?}",
+            book_ids.len(), review_ids.len(), limit
+        ));
+
+        // Other editions of the excluded books' works are excluded with them
+        let work_ids: Vec<ObjectId> = self.book_collection
+            .distinct("work_id", doc! { "$or": [{ "_id": { "$in": &book_ids } }, { "reviews": { "$in": &review_ids } }] })
+            .await?
+            .into_iter()
+            .filter_map(|work_id| work_id.as_object_id())
+            .collect();
+
+        // The best rated edition stands for its work
+        let pipeline = vec![
+            doc! { "$match": { "_id": { "$nin": book_ids }, "reviews": { "$nin": review_ids }, "work_id": { "$nin": work_ids } } },
+            doc! { "$sort": { "rating.average": -1, "_id": 1 } },
+            doc! { "$group": { "_id": { "$ifNull": ["$work_id", "$_id"] }, "book": { "$first": "$$ROOT" } } },
+            doc! { "$replaceWith": "$book" },
+            doc! { "$sort": { "rating.average": -1, "_id": 1 } },
+            doc! { "$limit": limit as i64 },
+        ];
+
+        let result_aggregate = self.book_collection
+            .aggregate(pipeline)
+            .with_type::<Book>()
+            .await;
+
+        match result_aggregate {
+            Ok(cursor) => {
+                timer.log();
+                Ok(cursor.try_collect().await?)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding books: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn find_filtered(&self, filter: &BookFilter, pagination: &PaginationRequest) -> Result<(Vec<Book>, u64, BookFacets), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [FIND FILTERED] filter: {:?}, pagination: {:?}",
+            filter, pagination
+        ));
+
+        let search = with_search(doc! {}, pagination, SEARCH_FIELDS);
+        let sort = sort_document(pagination, SORT_FIELDS, doc! { "title": 1 })?;
+        let clauses = filter_clauses(filter)?;
+        let all = match_clauses(&clauses, None);
+
+        let pipeline = vec![
+            doc! { "$match": search },
+            doc! { "$facet": {
+                "items": [
+                    { "$match": all.clone() },
+                    { "$sort": sort },
+                    { "$skip": pagination.skip() as i64 },
+                    { "$limit": pagination.page_size() as i64 },
+                ],
+                "total": [
+                    { "$match": all },
+                    { "$count": "count" },
+                ],
+                "genres": facet_pipeline(&clauses, "genres", Some("$genres"),
+                    doc! { "_id": "$genres.name", "count": { "$sum": 1 } }),
+                "authors": facet_pipeline(&clauses, "authors", Some("$authors"),
+                    doc! { "_id": { "$toString": "$authors.id" }, "label": { "$first": "$authors.name" }, "count": { "$sum": 1 } }),
+                "publishers": facet_pipeline(&clauses, "publishers", Some("$publishers"),
+                    doc! { "_id": "$publishers.name", "count": { "$sum": 1 } }),
+                "languages": facet_pipeline(&clauses, "languages", Some("$languages"),
+                    doc! { "_id": "$languages", "count": { "$sum": 1 } }),
+                "formats": facet_pipeline(&clauses, "formats", None,
+                    doc! { "_id": "$format", "count": { "$sum": 1 } }),
+            } },
+        ];
+
+        let result_aggregate = self.book_collection
+            .aggregate(pipeline)
+            .with_type::<CatalogPage>()
+            .await;
+        let page = match result_aggregate {
+            Ok(cursor) => cursor.try_collect::<Vec<CatalogPage>>().await?.pop().unwrap_or_default(),
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding books: {}", e));
+                return Err(e.into());
+            },
+        };
+        let total = page.total.first().map_or(0, |total| total.count);
+
+        timer.log();
+        Ok((page.items, total, page.facets))
+    }
+
+    async fn find_by_external_id(&self, provider: ExternalProvider, external_id: &str) -> Result<Option<Book>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [FIND BY EXTERNAL ID] provider: {:?}, external_id: {:?}",
+            provider, external_id
+        ));
+
+        let result = self.book_collection.find_one(doc! { provider.path(): external_id }).await;
+        match result {
+            Ok(result) => {
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding book: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn set_external_id(&self, book_id: &str, provider: ExternalProvider, external_id: Option<&str>) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [BOOK] [SET EXTERNAL ID] book_id: {:?}, provider: {:?}, external_id: {:?}",
+            book_id, provider, external_id
+        ));
+
+        let id = ObjectId::parse_str(book_id);
+        match id {
+            Ok(id) => {
+                let result_update = self.book_collection
+                    .update_one(doc! { "_id": &id }, external_id_update(provider, external_id))
+                    .await;
+                match result_update {
+                    Ok(result_update) => {
+                        timer.log();
+                        Ok(result_update.matched_count > 0)
+                    },
+                    Err(e) => {
+                        timer.error_with_message(&format!("Error updating book: {}", e));
+                        Err(e.into())
+                    },
+                }
+            },
+            Err(_) => {
+                timer.error_with_message(&format!("Invalid book id: {}", book_id));
+                Err(AppError::invalid_id("book", book_id))
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::Bson;
+
+    use super::*;
+    use crate::model::book_model::BookFormat;
+
+    fn filter() -> BookFilter {
+        BookFilter {
+            genres: vec!["fantasy".to_string()],
+            languages: vec!["en".to_string(), "fr".to_string()],
+            formats: vec![BookFormat::EBook],
+            pages_max: Some(400),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn no_filter_has_no_clauses() {
+        assert!(filter_clauses(&BookFilter::default()).unwrap().is_empty());
+        assert_eq!(match_clauses(&[], None), doc! {});
+    }
+
+    #[test]
+    fn each_filtered_field_gets_its_own_clause() {
+        let clauses = filter_clauses(&filter()).unwrap();
+
+        assert_eq!(clauses, vec![
+            ("genres", doc! { "genres.name": { "$in": ["fantasy"] } }),
+            ("languages", doc! { "languages": { "$in": ["en", "fr"] } }),
+            ("formats", doc! { "format": { "$in": ["EBook"] } }),
+            ("pages", doc! { "num_pages": { "$lte": 400 } }),
+        ]);
+    }
+
+    #[test]
+    fn facets_are_counted_without_their_own_filter() {
+        let clauses = filter_clauses(&filter()).unwrap();
+
+        let pipeline = facet_pipeline(&clauses, "genres", Some("$genres"), doc! { "_id": "$genres.name", "count": { "$sum": 1 } });
+
+        let matched = pipeline[0].get_document("$match").unwrap().get_array("$and").unwrap();
+        assert_eq!(matched.len(), clauses.len() - 1);
+        assert!(!matched.contains(&Bson::Document(clauses[0].1.clone())));
+        assert_eq!(pipeline[1], doc! { "$unwind": "$genres" });
+    }
+}
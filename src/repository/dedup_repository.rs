@@ -0,0 +1,419 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, to_document},
+    Client, ClientSession, Collection, Database,
+};
+use serde::de::DeserializeOwned;
+
+use crate::model::author_model::{Author, AuthorEmbed};
+use crate::model::book_model::{Book, BookEmbed, BookRating};
+use crate::model::dedup_model::{
+    merged_authors, merged_preference_authors, merged_shelf, AuthorDedupRow, BookDedupRow, DedupRecord, MergeReport, ReviewMergePlan,
+};
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::model::review_model::Review;
+use crate::model::user_model::User;
+use crate::repository::graph_sync_repository::enqueue;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+#[async_trait]
+pub trait DedupRepositoryInterface {
+    /// Every author, reduced to what duplicates are found by.
+    async fn author_records(&self) -> Result<Vec<DedupRecord>, AppError>;
+    /// Every book, reduced to what duplicates are found by.
+    async fn book_records(&self) -> Result<Vec<DedupRecord>, AppError>;
+    /// Merges the authors into the survivor and deletes them, in one transaction.
+    async fn merge_authors(&self, survivor_id: &ObjectId, merged_ids: &[ObjectId]) -> Result<MergeReport, AppError>;
+    /// Merges the books into the survivor and deletes them, in one transaction.
+    async fn merge_books(&self, survivor_id: &ObjectId, merged_ids: &[ObjectId]) -> Result<MergeReport, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct DedupRepository {
+    pub mongo_client: Client,
+    pub author_collection: Collection<Author>,
+    pub book_collection: Collection<Book>,
+    pub review_collection: Collection<Review>,
+    pub user_collection: Collection<User>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+}
+
+impl DedupRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
+        let author_collection = mongo_database.collection::<Author>("authors");
+        let book_collection = mongo_database.collection::<Book>("books");
+        let review_collection = mongo_database.collection::<Review>("reviews");
+        let user_collection = mongo_database.collection::<User>("users");
+        let outbox_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION);
+        DedupRepository {
+            mongo_client,
+            author_collection,
+            book_collection,
+            review_collection,
+            user_collection,
+            outbox_collection,
+        }
+    }
+
+    /// Projection of `collection` read as `R`, in `_id` order.
+    async fn rows<T, R>(collection: &Collection<T>, projection: mongodb::bson::Document) -> Result<Vec<R>, AppError>
+    where
+        T: Send + Sync,
+        R: DeserializeOwned + Unpin + Send + Sync,
+    {
+        let rows = collection
+            .clone_with_type::<R>()
+            .find(doc! {})
+            .projection(projection)
+            .sort(doc! { "_id": 1 })
+            .await?
+            .try_collect()
+            .await?;
+        Ok(rows)
+    }
+
+    /// Loads the records to merge, failing on the first one missing.
+    async fn find_merged<T>(
+        collection: &Collection<T>,
+        session: &mut ClientSession,
+        kind: &str,
+        merged_ids: &[ObjectId],
+        id_of: impl Fn(&T) -> Option<ObjectId>,
+    ) -> Result<Vec<T>, AppError>
+    where
+        T: DeserializeOwned + Unpin + Send + Sync,
+    {
+        let found: Vec<T> = collection
+            .find(doc! { "_id": { "$in": merged_ids } })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .try_collect()
+            .await?;
+        match merged_ids.iter().find(|id| !found.iter().any(|record| id_of(record) == Some(**id))) {
+            Some(missing) => Err(AppError::not_found(kind, &missing.to_hex())),
+            None => Ok(found),
+        }
+    }
+
+    async fn merge_authors_in_session(
+        &self,
+        session: &mut ClientSession,
+        survivor_id: &ObjectId,
+        merged_ids: &[ObjectId],
+    ) -> Result<MergeReport, AppError> {
+        let mut survivor = self.author_collection
+            .find_one(doc! { "_id": survivor_id })
+            .session(&mut *session)
+            .await?
+            .ok_or_else(|| AppError::not_found("Author", &survivor_id.to_hex()))?;
+        let merged = Self::find_merged(&self.author_collection, session, "Author", merged_ids, |author| author.id).await?;
+        for author in &merged {
+            survivor.absorb(author);
+        }
+        survivor.updated_at = Utc::now();
+
+        // The merged authors go first: the survivor takes over their external ids, which are unique
+        self.author_collection
+            .delete_many(doc! { "_id": { "$in": merged_ids } })
+            .session(&mut *session)
+            .await?;
+        let mut author_fields = to_document(&survivor)?;
+        author_fields.remove("_id");
+        self.author_collection
+            .update_one(doc! { "_id": survivor_id }, doc! { "$set": author_fields })
+            .session(&mut *session)
+            .await?;
+
+        // Books of any of them get the survivor's embed, refreshed with what it took from the others
+        let author_ids: Vec<ObjectId> = std::iter::once(*survivor_id).chain(merged_ids.iter().copied()).collect();
+        let books: Vec<Book> = self.book_collection
+            .find(doc! { "authors.id": { "$in": &author_ids } })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .try_collect()
+            .await?;
+
+        let embed = AuthorEmbed::from(&survivor);
+        let mut ops = vec![GraphSyncOp::UpsertAuthor { author_id: survivor_id.to_hex(), name: survivor.name.clone() }];
+        let mut books_updated = 0;
+        for mut book in books {
+            if book.authors.iter().any(|author| merged_ids.contains(&author.id)) {
+                books_updated += 1;
+            }
+            book.authors = merged_authors(&book.authors, &embed, merged_ids);
+            self.book_collection
+                .update_one(doc! { "_id": book.id }, doc! { "$set": { "authors": to_bson(&book.authors)? } })
+                .session(&mut *session)
+                .await?;
+            ops.push(GraphSyncOp::upsert_book(&book));
+        }
+        ops.push(GraphSyncOp::DeleteAuthors { author_ids: merged_ids.iter().map(|id| id.to_hex()).collect() });
+        enqueue(&self.outbox_collection, session, ops).await?;
+
+        // Preferences hold author ids as strings, read by the recommendations' preference signal
+        let survivor_hex = survivor_id.to_hex();
+        let merged_hex: Vec<String> = merged_ids.iter().map(|id| id.to_hex()).collect();
+        let readers: Vec<User> = self.user_collection
+            .find(doc! { "preference.authors": { "$in": &merged_hex } })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .try_collect()
+            .await?;
+        let mut preferences_updated = 0;
+        for reader in readers {
+            let (Some(user_id), Some(preference)) = (reader.id, reader.preference.as_ref()) else {
+                continue;
+            };
+            let Some(authors) = merged_preference_authors(&preference.authors, &survivor_hex, &merged_hex) else {
+                continue;
+            };
+            self.user_collection
+                .update_one(doc! { "_id": user_id }, doc! { "$set": { "preference.authors": authors } })
+                .session(&mut *session)
+                .await?;
+            preferences_updated += 1;
+        }
+
+        Ok(MergeReport {
+            survivor_id: *survivor_id,
+            merged_ids: merged_ids.to_vec(),
+            books_updated,
+            preferences_updated,
+            ..MergeReport::default()
+        })
+    }
+
+    async fn merge_books_in_session(
+        &self,
+        session: &mut ClientSession,
+        survivor_id: &ObjectId,
+        merged_ids: &[ObjectId],
+    ) -> Result<MergeReport, AppError> {
+        let mut survivor = self.book_collection
+            .find_one(doc! { "_id": survivor_id })
+            .session(&mut *session)
+            .await?
+            .ok_or_else(|| AppError::not_found("Book", &survivor_id.to_hex()))?;
+        let work_id = survivor.work_id;
+        let merged = Self::find_merged(&self.book_collection, session, "Book", merged_ids, |book| book.id).await?;
+        for book in &merged {
+            survivor.absorb(book);
+        }
+        let book_ids: Vec<ObjectId> = std::iter::once(*survivor_id).chain(merged_ids.iter().copied()).collect();
+
+        // Reviews move to the survivor, one per reader: the others are dropped before the move
+        // so the book and reader unique index never sees two
+        let reviews: Vec<Review> = self.review_collection
+            .find(doc! { "book_id": { "$in": &book_ids } })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .try_collect()
+            .await?;
+        let plan = ReviewMergePlan::new(survivor_id, reviews);
+        let dropped_ids: Vec<ObjectId> = plan.dropped.iter().filter_map(|review| review.id).collect();
+        if !dropped_ids.is_empty() {
+            self.review_collection
+                .delete_many(doc! { "_id": { "$in": &dropped_ids } })
+                .session(&mut *session)
+                .await?;
+            self.user_collection
+                .update_many(doc! { "reviews": { "$in": &dropped_ids } }, doc! { "$pull": { "reviews": { "$in": &dropped_ids } } })
+                .session(&mut *session)
+                .await?;
+        }
+        let moved = plan.moved(survivor_id);
+        let moved_ids: Vec<ObjectId> = moved.iter().filter_map(|review| review.id).collect();
+        if !moved_ids.is_empty() {
+            self.review_collection
+                .update_many(doc! { "_id": { "$in": &moved_ids } }, doc! { "$set": { "book_id": survivor_id } })
+                .session(&mut *session)
+                .await?;
+        }
+        survivor.reviews = plan.kept.iter().filter_map(|review| review.id).collect();
+        survivor.rating = BookRating::from_scores(plan.kept.iter().map(|review| review.score));
+
+        // The merged books go first: the survivor takes over their external ids, which are unique
+        self.book_collection
+            .delete_many(doc! { "_id": { "$in": merged_ids } })
+            .session(&mut *session)
+            .await?;
+        let mut book_fields = to_document(&survivor)?;
+        book_fields.remove("_id");
+        self.book_collection
+            .update_one(doc! { "_id": survivor_id }, doc! { "$set": book_fields })
+            .session(&mut *session)
+            .await?;
+
+        // Authors list the survivor once, under its refreshed embed
+        self.author_collection
+            .update_many(doc! { "books.book_id": { "$in": &book_ids } }, doc! { "$pull": { "books": { "book_id": { "$in": &book_ids } } } })
+            .session(&mut *session)
+            .await?;
+        let author_ids: Vec<ObjectId> = survivor.authors.iter().map(|author| author.id).collect();
+        if !author_ids.is_empty() {
+            self.author_collection
+                .update_many(doc! { "_id": { "$in": &author_ids } }, doc! { "$push": { "books": to_document(&BookEmbed::from(&survivor))? } })
+                .session(&mut *session)
+                .await?;
+        }
+
+        let mut ops = vec![GraphSyncOp::upsert_book(&survivor)];
+        // A survivor outside any work joins the work of the first merged edition having one
+        if let (None, Some(merged_work_id)) = (work_id, survivor.work_id) {
+            ops.push(GraphSyncOp::LinkEdition { book_id: survivor_id.to_hex(), work_id: merged_work_id.to_hex() });
+        }
+
+        let readers: Vec<User> = self.user_collection
+            .find(doc! { "shelf.book_id": { "$in": merged_ids } })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .try_collect()
+            .await?;
+        let mut shelves_updated = 0;
+        for reader in readers {
+            let (Some(user_id), Some(shelf)) = (reader.id, reader.shelf.as_deref()) else {
+                continue;
+            };
+            let Some(merged_shelf) = merged_shelf(shelf, &survivor, merged_ids) else {
+                continue;
+            };
+            self.user_collection
+                .update_one(doc! { "_id": user_id }, doc! { "$set": { "shelf": to_bson(&merged_shelf)? } })
+                .session(&mut *session)
+                .await?;
+            shelves_updated += 1;
+
+            if shelf.iter().any(|entry| entry.book_id == *survivor_id) {
+                continue;
+            }
+            if let Some(entry) = merged_shelf.iter().find(|entry| entry.book_id == *survivor_id) {
+                ops.push(GraphSyncOp::AddToShelf {
+                    user_id: user_id.to_hex(),
+                    book_id: survivor_id.to_hex(),
+                    status: entry.status.name().to_string(),
+                    ts: entry.added_at.timestamp_millis(),
+                });
+                if entry.status_updated_at != entry.added_at {
+                    ops.push(GraphSyncOp::UpdateShelfStatus {
+                        user_id: user_id.to_hex(),
+                        book_id: survivor_id.to_hex(),
+                        status: entry.status.name().to_string(),
+                        ts: entry.status_updated_at.timestamp_millis(),
+                    });
+                }
+            }
+        }
+
+        ops.extend(moved.iter().map(|review| GraphSyncOp::UpsertRating {
+            user_id: review.user.id.to_hex(),
+            book_id: survivor_id.to_hex(),
+            rating: f64::from(review.score),
+            ts: review.updated_at.or(review.date_added).unwrap_or_else(Utc::now).timestamp_millis(),
+        }));
+        // Deleting the merged nodes drops their shelf, rating and catalog relationships with them
+        ops.push(GraphSyncOp::DeleteBooks { book_ids: merged_ids.iter().map(|id| id.to_hex()).collect() });
+        enqueue(&self.outbox_collection, session, ops).await?;
+
+        Ok(MergeReport {
+            survivor_id: *survivor_id,
+            merged_ids: merged_ids.to_vec(),
+            books_updated: 0,
+            shelves_updated,
+            preferences_updated: 0,
+            reviews_moved: moved_ids.len() as u64,
+            reviews_dropped: dropped_ids.len() as u64,
+        })
+    }
+}
+
+
+#[async_trait]
+impl DedupRepositoryInterface for DedupRepository {
+    async fn author_records(&self) -> Result<Vec<DedupRecord>, AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [DEDUP] [AUTHOR RECORDS]");
+
+        let projection = doc! { "name": 1, "external_id": 1 };
+        match Self::rows::<_, AuthorDedupRow>(&self.author_collection, projection).await {
+            Ok(rows) => {
+                timer.log();
+                Ok(rows.into_iter().map(DedupRecord::from).collect())
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error reading authors: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn book_records(&self) -> Result<Vec<DedupRecord>, AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [DEDUP] [BOOK RECORDS]");
+
+        let projection = doc! { "title": 1, "isbn": 1, "isbn13": 1, "authors.id": 1, "external_id": 1 };
+        match Self::rows::<_, BookDedupRow>(&self.book_collection, projection).await {
+            Ok(rows) => {
+                timer.log();
+                Ok(rows.into_iter().map(DedupRecord::from).collect())
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error reading books: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn merge_authors(&self, survivor_id: &ObjectId, merged_ids: &[ObjectId]) -> Result<MergeReport, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [DEDUP] [MERGE AUTHORS] survivor_id: {:?} merged_ids: {:?}",
+            survivor_id, merged_ids
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.merge_authors_in_session(&mut mongo_session, survivor_id, merged_ids).await {
+            Ok(report) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(report)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error merging authors: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn merge_books(&self, survivor_id: &ObjectId, merged_ids: &[ObjectId]) -> Result<MergeReport, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [DEDUP] [MERGE BOOKS] survivor_id: {:?} merged_ids: {:?}",
+            survivor_id, merged_ids
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.merge_books_in_session(&mut mongo_session, survivor_id, merged_ids).await {
+            Ok(report) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(report)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error merging books: {}", e));
+                Err(e)
+            },
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use mongodb::{
+    bson::{doc, Bson, Document},
+    Database,
+};
+use neo4rs::{query, Graph};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::model::export_model::{ExportDataset, GraphEdge, GraphElement, GraphNode, GraphNodeKind, GraphRelationship};
+use crate::model::external_id_model::ExternalProvider;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+#[async_trait]
+pub trait ExportRepositoryInterface {
+    /// Records of a MongoDB dataset flattened to its `export_fields`, in `_id` order.
+    async fn records(&self, dataset: ExportDataset) -> Result<BoxStream<'static, Result<Map<String, Value>, AppError>>, AppError>;
+    /// Nodes of the reading graph, then the relationships between them.
+    fn graph(&self) -> BoxStream<'static, Result<GraphElement, AppError>>;
+}
+
+
+#[derive(Clone)]
+pub struct ExportRepository {
+    pub mongo_database: Database,
+    pub neo4j_client: Graph,
+}
+
+impl ExportRepository {
+    pub fn new(mongo_database: Database, neo4j_client: Graph) -> Self {
+        ExportRepository { mongo_database, neo4j_client }
+    }
+}
+
+
+#[derive(Debug, Deserialize)]
+struct NodeRow {
+    key: String,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EdgeRow {
+    source: String,
+    target: String,
+    status: Option<String>,
+    rating: Option<f64>,
+    ts: Option<i64>,
+}
+
+
+fn collection_name(dataset: ExportDataset) -> Option<&'static str> {
+    match dataset {
+        ExportDataset::Books => Some("books"),
+        ExportDataset::Authors => Some("authors"),
+        ExportDataset::Metadata => Some("metadata"),
+        ExportDataset::Users => Some("users"),
+        ExportDataset::Reviews => Some("reviews"),
+        ExportDataset::Graph => None,
+    }
+}
+
+fn field(path: &str) -> Bson {
+    Bson::String(format!("${}", path))
+}
+
+fn id_string(path: &str) -> Bson {
+    Bson::Document(doc! { "$toString": field(path) })
+}
+
+/// Ids of the embedded documents of the array at `path`, as strings.
+fn embedded_ids(path: &str, id: &str) -> Bson {
+    Bson::Document(doc! { "$map": { "input": field(path), "as": "item", "in": { "$toString": format!("$$item.{}", id) } } })
+}
+
+fn external_id_fields() -> Vec<(String, Bson)> {
+    ExternalProvider::ALL
+        .into_iter()
+        .map(|provider| (format!("external_id_{}", provider.field()), field(&provider.path())))
+        .collect()
+}
+
+/// Exported columns of a MongoDB dataset with the aggregation expression computing each.
+/// Ids are rendered as strings and embedded documents reduced to their ids and names;
+/// the users' password is never among them.
+pub fn export_fields(dataset: ExportDataset) -> Vec<(String, Bson)> {
+    let plain = |names: &[&str]| -> Vec<(String, Bson)> {
+        names.iter().map(|name| (name.to_string(), field(name))).collect()
+    };
+
+    let mut fields = vec![("id".to_string(), id_string("_id"))];
+    match dataset {
+        ExportDataset::Books => {
+            fields.extend(plain(&["isbn", "isbn13", "title", "subtitle", "description", "num_pages", "published_date", "format"]));
+            fields.extend([
+                ("genres".to_string(), field("genres.name")),
+                ("author_ids".to_string(), embedded_ids("authors", "id")),
+                ("authors".to_string(), field("authors.name")),
+                ("publishers".to_string(), field("publishers.name")),
+                ("languages".to_string(), field("languages")),
+                ("rating_average".to_string(), field("rating.average")),
+                ("rating_count".to_string(), field("rating.count")),
+            ]);
+            fields.extend(external_id_fields());
+        },
+        ExportDataset::Authors => {
+            fields.extend(plain(&["name", "description", "image_url"]));
+            fields.push(("book_ids".to_string(), embedded_ids("books", "book_id")));
+            fields.extend(external_id_fields());
+            fields.extend(plain(&["created_at", "updated_at"]));
+        },
+        ExportDataset::Metadata => {
+            fields.extend(plain(&["type", "key", "name", "code", "description", "website"]));
+        },
+        ExportDataset::Users => {
+            fields.extend(plain(&["username", "name", "role", "image_url"]));
+            fields.extend([
+                ("preferred_authors".to_string(), field("preference.authors")),
+                ("preferred_genres".to_string(), field("preference.genres")),
+                ("preferred_languages".to_string(), field("preference.languages")),
+                ("shelf".to_string(), Bson::Document(doc! { "$map": {
+                    "input": "$shelf",
+                    "as": "entry",
+                    "in": {
+                        "book_id": { "$toString": "$$entry.book_id" },
+                        "status": "$$entry.status",
+                        "added_at": "$$entry.added_at",
+                        "status_updated_at": "$$entry.status_updated_at",
+                    },
+                } })),
+            ]);
+            fields.extend(external_id_fields());
+            fields.extend(plain(&["created_at", "updated_at"]));
+        },
+        ExportDataset::Reviews => {
+            fields.extend([
+                ("book_id".to_string(), id_string("book_id")),
+                ("user_id".to_string(), id_string("user.id")),
+                ("user_name".to_string(), field("user.name")),
+            ]);
+            fields.extend(plain(&["score", "content", "date_added", "updated_at"]));
+        },
+        ExportDataset::Graph => return vec![],
+    }
+    fields
+}
+
+/// Column names of a MongoDB dataset, in export order.
+pub fn export_columns(dataset: ExportDataset) -> Vec<String> {
+    export_fields(dataset).into_iter().map(|(name, _)| name).collect()
+}
+
+fn export_pipeline(dataset: ExportDataset) -> Vec<Document> {
+    let mut projection = doc! { "_id": 0 };
+    for (name, expression) in export_fields(dataset) {
+        projection.insert(name, expression);
+    }
+    vec![doc! { "$sort": { "_id": 1 } }, doc! { "$project": projection }]
+}
+
+fn node_cypher(kind: GraphNodeKind) -> String {
+    format!(
+        "MATCH (n:{label}) WHERE n.{key} IS NOT NULL RETURN n.{key} AS key, n.{name} AS name ORDER BY key",
+        label = kind.label(), key = kind.key_property(), name = kind.name_property(),
+    )
+}
+
+fn edge_cypher(relationship: GraphRelationship) -> String {
+    let (source, target) = (relationship.source(), relationship.target());
+    format!(
+        "MATCH (s:{source_label})-[rel:{rel_type}]->(t:{target_label}) \
+         WHERE s.{source_key} IS NOT NULL AND t.{target_key} IS NOT NULL \
+         RETURN s.{source_key} AS source, t.{target_key} AS target, rel.status AS status, rel.rating AS rating, rel.ts AS ts",
+        source_label = source.label(), source_key = source.key_property(),
+        target_label = target.label(), target_key = target.key_property(),
+        rel_type = relationship.rel_type(),
+    )
+}
+
+
+#[async_trait]
+impl ExportRepositoryInterface for ExportRepository {
+    async fn records(&self, dataset: ExportDataset) -> Result<BoxStream<'static, Result<Map<String, Value>, AppError>>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [EXPORT] [RECORDS] dataset: {:?}",
+            dataset
+        ));
+
+        let collection_name = match collection_name(dataset) {
+            Some(collection_name) => collection_name,
+            None => {
+                timer.error_with_message(&format!("Not a MongoDB dataset: {}", dataset.name()));
+                return Err(AppError::Validation(format!("{} is not a MongoDB dataset", dataset.name())));
+            },
+        };
+
+        let result_aggregate = self.mongo_database
+            .collection::<Document>(collection_name)
+            .aggregate(export_pipeline(dataset))
+            .await;
+        match result_aggregate {
+            Ok(cursor) => {
+                timer.log();
+                Ok(cursor
+                    .map_err(AppError::from)
+                    .map_ok(|record| match Bson::Document(record).into_relaxed_extjson() {
+                        Value::Object(fields) => fields,
+                        _ => Map::new(),
+                    })
+                    .boxed())
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error exporting {}: {}", dataset.name(), e));
+                Err(e.into())
+            },
+        }
+    }
+
+    fn graph(&self) -> BoxStream<'static, Result<GraphElement, AppError>> {
+        let graph = self.neo4j_client.clone();
+        let nodes = stream::iter(GraphNodeKind::ALL)
+            .then(move |kind| {
+                let graph = graph.clone();
+                async move {
+                    let rows = graph.execute(query(&node_cypher(kind))).await?;
+                    Ok::<_, AppError>(rows
+                        .into_stream_as::<NodeRow>()
+                        .map_err(AppError::from)
+                        .map_ok(move |row| GraphElement::Node(GraphNode { kind, key: row.key, name: row.name })))
+                }
+            })
+            .try_flatten();
+
+        let graph = self.neo4j_client.clone();
+        let edges = stream::iter(GraphRelationship::ALL)
+            .then(move |relationship| {
+                let graph = graph.clone();
+                async move {
+                    let rows = graph.execute(query(&edge_cypher(relationship))).await?;
+                    Ok::<_, AppError>(rows
+                        .into_stream_as::<EdgeRow>()
+                        .map_err(AppError::from)
+                        .map_ok(move |row| GraphElement::Edge(GraphEdge {
+                            relationship,
+                            source: row.source,
+                            target: row.target,
+                            status: row.status,
+                            rating: row.rating,
+                            ts: row.ts,
+                        })))
+                }
+            })
+            .try_flatten();
+
+        nodes.chain(edges).boxed()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_collection_export_starts_with_the_id_as_a_string() {
+        for dataset in ExportDataset::ALL.into_iter().filter(|dataset| *dataset != ExportDataset::Graph) {
+            let fields = export_fields(dataset);
+
+            assert_eq!(fields[0], ("id".to_string(), Bson::Document(doc! { "$toString": "$_id" })));
+        }
+        assert!(export_fields(ExportDataset::Graph).is_empty());
+    }
+
+    #[test]
+    fn users_are_exported_without_their_password() {
+        let columns = export_columns(ExportDataset::Users);
+
+        assert!(!columns.iter().any(|column| column.contains("password")));
+        assert!(columns.contains(&"external_id_good_reads".to_string()));
+    }
+
+    #[test]
+    fn pipeline_sorts_on_id_then_projects_the_columns_only() {
+        let pipeline = export_pipeline(ExportDataset::Reviews);
+
+        assert_eq!(pipeline[0], doc! { "$sort": { "_id": 1 } });
+        let projection = pipeline[1].get_document("$project").unwrap();
+        assert_eq!(projection.get_i32("_id").unwrap(), 0);
+        assert_eq!(projection.get_document("user_id").unwrap(), &doc! { "$toString": "$user.id" });
+        assert_eq!(projection.len(), export_columns(ExportDataset::Reviews).len() + 1);
+    }
+
+    #[test]
+    fn edges_are_read_from_their_source_to_their_target() {
+        assert_eq!(
+            edge_cypher(GraphRelationship::HasGenre),
+            "MATCH (s:Book)-[rel:HAS_GENRE]->(t:Genre) WHERE s.book_id IS NOT NULL AND t.name IS NOT NULL \
+             RETURN s.book_id AS source, t.name AS target, rel.status AS status, rel.rating AS rating, rel.ts AS ts"
+        );
+    }
+}
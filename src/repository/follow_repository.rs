@@ -0,0 +1,310 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, Document},
+    Client, Database, Collection,
+};
+use serde::Deserialize;
+
+use crate::model::follow_model::{FeedItem, Follow, FollowedUser, FOLLOW_COLLECTION};
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::repository::graph_sync_repository::enqueue;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginationRequest;
+
+
+#[async_trait]
+pub trait FollowRepositoryInterface {
+    /// Returns false when `follower_id` already follows `followee_id`.
+    async fn insert(&self, follower_id: &ObjectId, followee_id: &ObjectId) -> Result<bool, AppError>;
+    async fn delete(&self, follower_id: &ObjectId, followee_id: &ObjectId) -> Result<bool, AppError>;
+    async fn find_followers(&self, user_id: &ObjectId, pagination: &PaginationRequest) -> Result<(Vec<FollowedUser>, u64), AppError>;
+    async fn find_following(&self, user_id: &ObjectId, pagination: &PaginationRequest) -> Result<(Vec<FollowedUser>, u64), AppError>;
+    async fn find_following_ids(&self, user_id: &ObjectId) -> Result<Vec<ObjectId>, AppError>;
+    async fn find_feed(&self, user_ids: Vec<ObjectId>, pagination: &PaginationRequest) -> Result<(Vec<FeedItem>, u64), AppError>;
+}
+
+
+/// Output of the feed's `$facet` stage.
+#[derive(Debug, Default, Deserialize)]
+struct FeedPage {
+    items: Vec<FeedItem>,
+    total: Vec<FacetCount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacetCount {
+    count: u64,
+}
+
+
+#[derive(Clone)]
+pub struct FollowRepository {
+    pub mongo_client: Client,
+    pub follow_collection: Collection<Follow>,
+    pub review_collection: Collection<Document>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+}
+
+impl FollowRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
+        FollowRepository {
+            mongo_client,
+            follow_collection: mongo_database.collection::<Follow>(FOLLOW_COLLECTION),
+            review_collection: mongo_database.collection::<Document>("reviews"),
+            outbox_collection: mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION),
+        }
+    }
+
+    /// One page of the follows matching `filter`, newest first, joined with the user on the `other` side.
+    async fn find_followed_users(
+        &self,
+        filter: Document,
+        other: &str,
+        pagination: &PaginationRequest,
+    ) -> Result<(Vec<FollowedUser>, u64), AppError> {
+        let total = self.follow_collection.count_documents(filter.clone()).await?;
+
+        let pipeline = vec![
+            doc! { "$match": filter },
+            doc! { "$sort": { "created_at": -1, "_id": -1 } },
+            doc! { "$skip": pagination.skip() as i64 },
+            doc! { "$limit": pagination.page_size() as i64 },
+            doc! { "$lookup": { "from": "users", "localField": other, "foreignField": "_id", "as": "user" } },
+            doc! { "$unwind": "$user" },
+            doc! { "$project": {
+                "_id": 0,
+                "user": { "id": "$user._id", "name": "$user.name", "image_url": "$user.image_url" },
+                "followed_at": "$created_at",
+            } },
+        ];
+
+        let users: Vec<FollowedUser> = self.follow_collection
+            .aggregate(pipeline)
+            .with_type::<FollowedUser>()
+            .await?
+            .try_collect()
+            .await?;
+        Ok((users, total))
+    }
+}
+
+
+#[async_trait]
+impl FollowRepositoryInterface for FollowRepository {
+    async fn insert(&self, follower_id: &ObjectId, followee_id: &ObjectId) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [FOLLOW] [INSERT] follower_id: {}, followee_id: {}",
+            follower_id, followee_id
+        ));
+
+        let now = Utc::now();
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_insert = async {
+            let result_upsert = self.follow_collection
+                .update_one(
+                    doc! { "follower_id": follower_id, "followee_id": followee_id },
+                    doc! { "$setOnInsert": { "created_at": to_bson(&now)? } },
+                )
+                .upsert(true)
+                .session(&mut mongo_session)
+                .await?;
+            if result_upsert.upserted_id.is_none() {
+                return Ok(false);
+            }
+
+            let op = GraphSyncOp::Follow {
+                follower_id: follower_id.to_hex(),
+                followee_id: followee_id.to_hex(),
+                ts: now.timestamp_millis(),
+            };
+            enqueue(&self.outbox_collection, &mut mongo_session, vec![op]).await?;
+
+            Ok::<_, AppError>(true)
+        }.await;
+
+        match result_insert {
+            Ok(true) => {},
+            Ok(false) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.warning_with_message("Already following");
+                return Ok(false);
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error inserting follow: {}", e));
+                return Err(e);
+            }
+        };
+
+        mongo_session.commit_transaction().await?;
+        timer.log();
+        Ok(true)
+    }
+
+    async fn delete(&self, follower_id: &ObjectId, followee_id: &ObjectId) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [FOLLOW] [DELETE] follower_id: {}, followee_id: {}",
+            follower_id, followee_id
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_delete = async {
+            let result_delete = self.follow_collection
+                .delete_one(doc! { "follower_id": follower_id, "followee_id": followee_id })
+                .session(&mut mongo_session)
+                .await?;
+            if result_delete.deleted_count == 0 {
+                return Ok(false);
+            }
+
+            let op = GraphSyncOp::Unfollow {
+                follower_id: follower_id.to_hex(),
+                followee_id: followee_id.to_hex(),
+            };
+            enqueue(&self.outbox_collection, &mut mongo_session, vec![op]).await?;
+
+            Ok::<_, AppError>(true)
+        }.await;
+
+        match result_delete {
+            Ok(true) => {},
+            Ok(false) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.warning_with_message("Not following");
+                return Ok(false);
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error deleting follow: {}", e));
+                return Err(e);
+            }
+        };
+
+        mongo_session.commit_transaction().await?;
+        timer.log();
+        Ok(true)
+    }
+
+    async fn find_followers(&self, user_id: &ObjectId, pagination: &PaginationRequest) -> Result<(Vec<FollowedUser>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [FOLLOW] [FIND FOLLOWERS] user_id: {}, pagination: {:?}",
+            user_id, pagination
+        ));
+
+        let result = self.find_followed_users(doc! { "followee_id": user_id }, "follower_id", pagination).await;
+        match result {
+            Ok(result) => {
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding followers: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn find_following(&self, user_id: &ObjectId, pagination: &PaginationRequest) -> Result<(Vec<FollowedUser>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [FOLLOW] [FIND FOLLOWING] user_id: {}, pagination: {:?}",
+            user_id, pagination
+        ));
+
+        let result = self.find_followed_users(doc! { "follower_id": user_id }, "followee_id", pagination).await;
+        match result {
+            Ok(result) => {
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding followees: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn find_following_ids(&self, user_id: &ObjectId) -> Result<Vec<ObjectId>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [FOLLOW] [FIND FOLLOWING IDS] user_id: {}",
+            user_id
+        ));
+
+        let follows: Vec<Follow> = self.follow_collection
+            .find(doc! { "follower_id": user_id })
+            .await?
+            .try_collect()
+            .await?;
+
+        timer.log();
+        Ok(follows.into_iter().map(|follow| follow.followee_id).collect())
+    }
+
+    async fn find_feed(&self, user_ids: Vec<ObjectId>, pagination: &PaginationRequest) -> Result<(Vec<FeedItem>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [FOLLOW] [FIND FEED] user_ids: {:?}, pagination: {:?}",
+            user_ids, pagination
+        ));
+
+        // Reviews and shelf entries share one shape, so a single sort can interleave them
+        let pipeline = vec![
+            doc! { "$match": { "user.id": { "$in": &user_ids } } },
+            doc! { "$project": {
+                "_id": 0,
+                "kind": { "$cond": [{ "$eq": [{ "$trim": { "input": "$content" } }, ""] }, "Rating", "Review"] },
+                "user": 1,
+                "book_id": 1,
+                "score": 1,
+                "content": { "$cond": [{ "$eq": [{ "$trim": { "input": "$content" } }, ""] }, null, "$content"] },
+                "occurred_at": { "$ifNull": ["$updated_at", "$date_added"] },
+            } },
+            doc! { "$unionWith": { "coll": "users", "pipeline": [
+                { "$match": { "_id": { "$in": &user_ids } } },
+                { "$unwind": "$shelf" },
+                { "$project": {
+                    "_id": 0,
+                    "kind": "Shelf",
+                    "user": { "id": "$_id", "name": "$name", "image_url": "$image_url" },
+                    "book_id": "$shelf.book_id",
+                    "title": "$shelf.title",
+                    "status": "$shelf.status",
+                    "occurred_at": "$shelf.status_updated_at",
+                } },
+            ] } },
+            doc! { "$match": { "occurred_at": { "$ne": null } } },
+            doc! { "$sort": { "occurred_at": -1, "book_id": -1 } },
+            doc! { "$facet": {
+                "items": [
+                    { "$skip": pagination.skip() as i64 },
+                    { "$limit": pagination.page_size() as i64 },
+                    { "$lookup": { "from": "books", "localField": "book_id", "foreignField": "_id", "as": "book" } },
+                    { "$set": { "title": { "$ifNull": ["$title", { "$first": "$book.title" }] } } },
+                    { "$unset": "book" },
+                ],
+                "total": [{ "$count": "count" }],
+            } },
+        ];
+
+        let result_aggregate = self.review_collection
+            .aggregate(pipeline)
+            .with_type::<FeedPage>()
+            .await;
+        let page = match result_aggregate {
+            Ok(cursor) => cursor.try_collect::<Vec<FeedPage>>().await?.pop().unwrap_or_default(),
+            Err(e) => {
+                timer.error_with_message(&format!("Error building feed: {}", e));
+                return Err(e.into());
+            },
+        };
+        let total = page.total.first().map_or(0, |total| total.count);
+
+        timer.log();
+        Ok((page.items, total))
+    }
+}
@@ -0,0 +1,459 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, from_document, oid::ObjectId, to_document, Document},
+    options::ReturnDocument,
+    Client, ClientSession, Database, Collection,
+};
+use neo4rs::{query, Graph, Query};
+
+use crate::model::book_model::Book;
+use crate::model::graph_sync_model::{
+    GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_DEAD_LETTER_COLLECTION, GRAPH_SYNC_OUTBOX_COLLECTION,
+    GRAPH_SYNC_SEQUENCE_COLLECTION,
+};
+use crate::shared::constant::GRAPH_SYNC_CLAIM_WINDOW;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+impl GraphSyncOp {
+    pub fn neo4j_queries(&self) -> Vec<Query> {
+        let single = match self {
+            GraphSyncOp::UpsertGenre { name, description } => query(
+                "MERGE (g:Genre {name:$name}) SET g.description = $description"
+            ).param("name", name.as_str()).param("description", description.as_str()),
+
+            GraphSyncOp::DeleteGenre { name } => query("MATCH (g:Genre {name:$name}) DETACH DELETE g")
+                .param("name", name.as_str()),
+
+            GraphSyncOp::UpsertReader { user_id, name } => query(
+                "MERGE (r:Reader {user_id:$user_id}) SET r.name = $name"
+            ).param("user_id", user_id.as_str()).param("name", name.as_str()),
+
+            GraphSyncOp::DeleteReaders { user_ids } => query(
+                "MATCH (r:Reader) WHERE r.user_id IN $user_ids DETACH DELETE r"
+            ).param("user_ids", user_ids.clone()),
+
+            // `added_ts` keeps the first time the book was shelved when the event is replayed
+            GraphSyncOp::AddToShelf { user_id, book_id, status, ts } => query(
+                "MATCH (r:Reader {user_id: $user_id})
+                 MATCH (b:Book {book_id: $book_id})
+                 MERGE (r)-[rel:ADDED_TO_SHELF]->(b)
+                 SET rel.status = $status, rel.added_ts = coalesce(rel.added_ts, $ts), rel.ts = $ts"
+            )
+                .param("user_id", user_id.as_str())
+                .param("book_id", book_id.as_str())
+                .param("status", status.as_str())
+                .param("ts", *ts),
+
+            GraphSyncOp::UpdateShelfStatus { user_id, book_id, status, ts } => query(
+                "MATCH (r:Reader {user_id: $user_id})-[rel:ADDED_TO_SHELF]->(b:Book {book_id: $book_id})
+                 SET rel.status = $status, rel.ts = $ts"
+            )
+                .param("user_id", user_id.as_str())
+                .param("book_id", book_id.as_str())
+                .param("status", status.as_str())
+                .param("ts", *ts),
+
+            GraphSyncOp::RemoveFromShelf { user_id, book_id } => query(
+                "MATCH (r:Reader {user_id: $user_id})-[rel:ADDED_TO_SHELF]->(b:Book {book_id: $book_id})
+                 DELETE rel"
+            ).param("user_id", user_id.as_str()).param("book_id", book_id.as_str()),
+
+            GraphSyncOp::UpsertRating { user_id, book_id, rating, ts } => query(
+                "MATCH (u:Reader {user_id: $user_id})
+                 MATCH (b:Book {book_id: $book_id})
+                 MERGE (u)-[r:RATED]->(b)
+                 SET r.rating = $rating, r.ts = $ts"
+            )
+                .param("user_id", user_id.as_str())
+                .param("book_id", book_id.as_str())
+                .param("rating", *rating)
+                .param("ts", *ts),
+
+            GraphSyncOp::RemoveRating { user_id, book_id } => query(
+                "MATCH (u:Reader {user_id: $user_id})-[r:RATED]->(b:Book {book_id: $book_id})
+                 DELETE r"
+            ).param("user_id", user_id.as_str()).param("book_id", book_id.as_str()),
+
+            GraphSyncOp::UpsertAuthor { author_id, name } => query(
+                "MERGE (a:Author {author_id:$author_id}) SET a.name = $name"
+            ).param("author_id", author_id.as_str()).param("name", name.as_str()),
+
+            GraphSyncOp::DeleteAuthors { author_ids } => query(
+                "MATCH (a:Author) WHERE a.author_id IN $author_ids DETACH DELETE a"
+            ).param("author_ids", author_ids.clone()),
+
+            GraphSyncOp::UpsertBook { book_id, title, author_ids, genres, publishers } => {
+                let mut queries = Book::neo4j_unlink_queries(book_id);
+                queries.extend(Book::neo4j_node_queries(
+                    book_id,
+                    title,
+                    author_ids.clone(),
+                    genres.clone(),
+                    publishers.clone(),
+                ));
+                return queries;
+            },
+
+            GraphSyncOp::DeleteBooks { book_ids } => query(
+                "MATCH (b:Book) WHERE b.book_id IN $book_ids DETACH DELETE b"
+            ).param("book_ids", book_ids.clone()),
+
+            GraphSyncOp::UpsertWork { work_id, title } => query(
+                "MERGE (w:Work {work_id:$work_id}) SET w.title = $title"
+            ).param("work_id", work_id.as_str()).param("title", title.as_str()),
+
+            GraphSyncOp::DeleteWorks { work_ids } => query(
+                "MATCH (w:Work) WHERE w.work_id IN $work_ids DETACH DELETE w"
+            ).param("work_ids", work_ids.clone()),
+
+            GraphSyncOp::LinkEdition { book_id, work_id } => {
+                return vec![
+                    query("MATCH (b:Book {book_id:$book_id})-[rel:EDITION_OF]->(w:Work) WHERE w.work_id <> $work_id DELETE rel")
+                        .param("book_id", book_id.as_str())
+                        .param("work_id", work_id.as_str()),
+                    query(
+                        "MATCH (b:Book {book_id:$book_id})
+                         MATCH (w:Work {work_id:$work_id})
+                         MERGE (b)-[:EDITION_OF]->(w)"
+                    ).param("book_id", book_id.as_str()).param("work_id", work_id.as_str()),
+                ];
+            },
+
+            GraphSyncOp::UnlinkEdition { book_id } => query(
+                "MATCH (b:Book {book_id:$book_id})-[rel:EDITION_OF]->(:Work) DELETE rel"
+            ).param("book_id", book_id.as_str()),
+
+            GraphSyncOp::Follow { follower_id, followee_id, ts } => query(
+                "MATCH (f:Reader {user_id: $follower_id})
+                 MATCH (t:Reader {user_id: $followee_id})
+                 MERGE (f)-[rel:FOLLOWS]->(t)
+                 SET rel.ts = $ts"
+            )
+                .param("follower_id", follower_id.as_str())
+                .param("followee_id", followee_id.as_str())
+                .param("ts", *ts),
+
+            GraphSyncOp::Unfollow { follower_id, followee_id } => query(
+                "MATCH (:Reader {user_id: $follower_id})-[rel:FOLLOWS]->(:Reader {user_id: $followee_id})
+                 DELETE rel"
+            ).param("follower_id", follower_id.as_str()).param("followee_id", followee_id.as_str()),
+        };
+        vec![single]
+    }
+}
+
+
+/// Records the graph changes mirroring a write in the outbox, inside the caller's transaction.
+///
+/// The events take their `seq` from a counter incremented outside the transaction, once the caller
+/// made its own writes. A later change to the same documents can only be written after this
+/// transaction commits, so its events always sort after these ones.
+pub async fn enqueue(
+    outbox_collection: &Collection<GraphSyncEvent>,
+    session: &mut ClientSession,
+    ops: Vec<GraphSyncOp>,
+) -> Result<(), AppError> {
+    if ops.is_empty() {
+        return Ok(());
+    }
+
+    let count = ops.len() as i64;
+    let sequence = outbox_collection
+        .client()
+        .database(&outbox_collection.namespace().db)
+        .collection::<Document>(GRAPH_SYNC_SEQUENCE_COLLECTION);
+    let counter = sequence
+        .find_one_and_update(doc! { "_id": outbox_collection.name() }, doc! { "$inc": { "seq": count } })
+        .upsert(true)
+        .return_document(ReturnDocument::After)
+        .await?
+        .ok_or_else(|| AppError::Upstream(anyhow!("Graph sync sequence was not returned")))?;
+    let last = counter
+        .get_i64("seq")
+        .map_err(|e| AppError::Upstream(anyhow!("Invalid graph sync sequence: {}", e)))?;
+
+    let events: Vec<GraphSyncEvent> = ops
+        .into_iter()
+        .zip(last - count + 1..)
+        .map(|(op, seq)| GraphSyncEvent::new(seq, op))
+        .collect();
+    outbox_collection
+        .insert_many(events)
+        .session(session)
+        .await?;
+    Ok(())
+}
+
+
+#[async_trait]
+pub trait GraphSyncRepositoryInterface {
+    async fn claim_next(&self, lease_ms: i64) -> Result<Option<GraphSyncEvent>, AppError>;
+    /// Later events sharing a key with `event`, held back while it waits for a retry.
+    async fn count_behind(&self, event: &GraphSyncEvent) -> Result<u64, AppError>;
+    /// Events that failed at least once and wait for their next attempt.
+    async fn count_retrying(&self) -> Result<u64, AppError>;
+    async fn apply(&self, op: &GraphSyncOp) -> Result<(), AppError>;
+    async fn complete(&self, event_id: &ObjectId) -> Result<(), AppError>;
+    async fn retry_later(&self, event_id: &ObjectId, error: &str, available_at: i64) -> Result<(), AppError>;
+    async fn dead_letter(&self, event: GraphSyncEvent, error: &str) -> Result<(), AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct GraphSyncRepository {
+    pub mongo_client: Client,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+    pub dead_letter_collection: Collection<GraphSyncEvent>,
+    pub neo4j_client: Graph,
+}
+
+impl GraphSyncRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database, neo4j_client: Graph) -> Self {
+        let outbox_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION);
+        let dead_letter_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_DEAD_LETTER_COLLECTION);
+        GraphSyncRepository {
+            mongo_client,
+            outbox_collection,
+            dead_letter_collection,
+            neo4j_client,
+        }
+    }
+
+    /// Dead-letters an outbox document that no longer reads as an event, such as an op removed since it was queued.
+    async fn dead_letter_unreadable(&self, mut document: Document, error: &str) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [GRAPH SYNC] [DEAD LETTER UNREADABLE] id: {:?} error: {:?} ",
+            document.get("_id"), error
+        ));
+
+        let event_id = document
+            .get_object_id("_id")
+            .map_err(|_| AppError::Validation("Graph sync event has no id".to_string()))?;
+        document.insert("last_error", error);
+
+        match self.move_to_dead_letters(&event_id, document).await {
+            Ok(()) => {
+                timer.warning();
+                Ok(())
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error dead-lettering graph sync event: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn move_to_dead_letters(&self, event_id: &ObjectId, document: Document) -> Result<(), AppError> {
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result = async {
+            self.dead_letter_collection
+                .clone_with_type::<Document>()
+                .insert_one(document)
+                .session(&mut mongo_session)
+                .await?;
+            self.outbox_collection
+                .delete_one(doc! { "_id": event_id })
+                .session(&mut mongo_session)
+                .await?;
+            Ok::<_, AppError>(())
+        }.await;
+
+        match result {
+            Ok(()) => {
+                mongo_session.commit_transaction().await?;
+                Ok(())
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                Err(e)
+            }
+        }
+    }
+}
+
+
+#[async_trait]
+impl GraphSyncRepositoryInterface for GraphSyncRepository {
+    /// Leases the first event in `seq` order whose keys no earlier event holds.
+    /// An event waiting for a retry, or leased by another worker, holds its keys, and so does every
+    /// event queued behind it on one of them. A later delete can therefore never overtake the upsert
+    /// it follows, while writes to unrelated records go on. Relationship ops carry the keys of both
+    /// nodes they match, so a shelf entry still waits for the Book upsert before it.
+    /// Only the first `GRAPH_SYNC_CLAIM_WINDOW` events are looked at.
+    async fn claim_next(&self, lease_ms: i64) -> Result<Option<GraphSyncEvent>, AppError> {
+        let mut cursor = self.outbox_collection
+            .clone_with_type::<Document>()
+            .find(doc! {})
+            .sort(doc! { "seq": 1, "_id": 1 })
+            .limit(GRAPH_SYNC_CLAIM_WINDOW)
+            .await?;
+
+        let now = Utc::now().timestamp_millis();
+        let mut held: HashSet<String> = HashSet::new();
+        while let Some(document) = cursor.try_next().await? {
+            let event = match from_document::<GraphSyncEvent>(document.clone()) {
+                Ok(event) => event,
+                Err(e) => {
+                    // No attempt can ever apply it, and its keys are unknown, so it holds nothing back
+                    self.dead_letter_unreadable(document, &e.to_string()).await?;
+                    continue;
+                }
+            };
+
+            let keys = event.op.keys();
+            let event_id = match event.id {
+                Some(id) if event.available_at <= now && !keys.iter().any(|key| held.contains(key)) => id,
+                _ => {
+                    held.extend(keys);
+                    continue;
+                }
+            };
+
+            // Another worker may have leased the same event in between, in which case nothing matches
+            let claimed = self.outbox_collection
+                .find_one_and_update(
+                    doc! { "_id": &event_id, "available_at": { "$lte": now } },
+                    doc! { "$set": { "available_at": now + lease_ms }, "$inc": { "attempts": 1 } },
+                )
+                .return_document(ReturnDocument::After)
+                .await?;
+
+            match claimed {
+                Some(event) => return Ok(Some(event)),
+                None => held.extend(keys),
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn count_behind(&self, event: &GraphSyncEvent) -> Result<u64, AppError> {
+        Ok(self.outbox_collection
+            .count_documents(doc! { "seq": { "$gt": event.seq }, "keys": { "$in": event.op.keys() } })
+            .await?)
+    }
+
+    async fn count_retrying(&self) -> Result<u64, AppError> {
+        Ok(self.outbox_collection.count_documents(doc! { "last_error": { "$ne": null } }).await?)
+    }
+
+    async fn apply(&self, op: &GraphSyncOp) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [GRAPH SYNC] [APPLY] op: {:?} ",
+            op
+        ));
+
+        let mut neo4j_tx = self.neo4j_client.start_txn().await?;
+        if let Err(e) = neo4j_tx.run_queries(op.neo4j_queries()).await {
+            let _ = neo4j_tx.rollback().await;
+            timer.error_with_message(&format!("Error applying graph sync event: {}", e));
+            return Err(e.into());
+        }
+        neo4j_tx.commit().await?;
+
+        timer.log();
+        Ok(())
+    }
+
+    async fn complete(&self, event_id: &ObjectId) -> Result<(), AppError> {
+        self.outbox_collection
+            .delete_one(doc! { "_id": event_id })
+            .await?;
+        Ok(())
+    }
+
+    async fn retry_later(&self, event_id: &ObjectId, error: &str, available_at: i64) -> Result<(), AppError> {
+        self.outbox_collection
+            .update_one(
+                doc! { "_id": event_id },
+                doc! { "$set": { "available_at": available_at, "last_error": error } },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn dead_letter(&self, mut event: GraphSyncEvent, error: &str) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [GRAPH SYNC] [DEAD LETTER] id: {:?} op: {:?} ",
+            event.id, event.op
+        ));
+
+        let event_id = event.id.ok_or_else(|| AppError::Validation("Graph sync event has no id".to_string()))?;
+        event.last_error = Some(error.to_string());
+
+        match self.move_to_dead_letters(&event_id, to_document(&event)?).await {
+            Ok(()) => {
+                timer.warning();
+                Ok(())
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error dead-lettering graph sync event: {}", e));
+                Err(e)
+            }
+        }
+    }
+}
+
+
+/// Whether applying an op failed in a way no retry can fix: Neo4j rejected the statement or its
+/// parameters, as with a Cypher syntax error or a constraint violation, or they could not be encoded.
+/// Connection, transient and database errors are retried.
+pub fn is_permanent_failure(error: &AppError) -> bool {
+    match error {
+        AppError::Upstream(e) => match e.downcast_ref::<neo4rs::Error>() {
+            Some(neo4rs::Error::Neo4j(e)) => is_permanent_code(e.code()),
+            Some(
+                neo4rs::Error::StringTooLong
+                | neo4rs::Error::MapTooBig
+                | neo4rs::Error::BytesTooBig
+                | neo4rs::Error::ListTooLong
+                | neo4rs::Error::ConversionError,
+            ) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_permanent_code(code: &str) -> bool {
+    ["Neo.ClientError.Statement.", "Neo.ClientError.Schema.", "Neo.ClientError.Request."]
+        .iter()
+        .any(|prefix| code.starts_with(prefix))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejected_statements_are_permanent() {
+        assert!(is_permanent_code("Neo.ClientError.Statement.SyntaxError"));
+        assert!(is_permanent_code("Neo.ClientError.Schema.ConstraintValidationFailed"));
+        assert!(is_permanent_code("Neo.ClientError.Request.Invalid"));
+    }
+
+    #[test]
+    fn busy_or_unreachable_servers_are_retried() {
+        assert!(!is_permanent_code("Neo.TransientError.Transaction.DeadlockDetected"));
+        assert!(!is_permanent_code("Neo.ClientError.Security.Unauthorized"));
+        assert!(!is_permanent_code("Neo.ClientError.Cluster.NotALeader"));
+        assert!(!is_permanent_code("Neo.DatabaseError.General.UnknownError"));
+    }
+
+    #[test]
+    fn driver_errors_are_classified_by_cause() {
+        assert!(is_permanent_failure(&neo4rs::Error::StringTooLong.into()));
+        assert!(!is_permanent_failure(&neo4rs::Error::ConnectionError.into()));
+        assert!(!is_permanent_failure(&AppError::Validation("Graph sync event has no id".to_string())));
+    }
+}
@@ -0,0 +1,717 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, to_document, Bson, Document},
+    Client, ClientSession, Database, Collection,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::model::author_model::{Author, AuthorEmbed};
+use crate::model::book_model::{Book, BookEmbed, BookImageSource, BookRating};
+use crate::model::external_id_model::{ExternalId, ExternalProvider};
+use crate::model::genre_model::GenreEmbed;
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::model::import_model::{
+    ImportBatchResult, ImportJob, ImportJobStatus, ImportReport, ImportRowError, ImportedAuthor, ImportedBook,
+    ImportedRating, IMPORT_JOB_COLLECTION,
+};
+use crate::model::metadata_model::{Metadata, MetadataDoc};
+use crate::model::publisher_model::PublisherEmbed;
+use crate::model::review_model::Review;
+use crate::model::source_model::SourceEmbed;
+use crate::model::user_model::{HashedPassword, User, UserEmbed, UserRole};
+use crate::repository::author_repository::AuthorRepository;
+use crate::repository::book_repository::BookRepository;
+use crate::repository::graph_sync_repository::enqueue;
+use crate::repository::user_repository::UserRepository;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+#[async_trait]
+pub trait ImportRepositoryInterface {
+    /// Creates the authors whose external id is unknown and refreshes the others.
+    async fn upsert_authors(&self, provider: ExternalProvider, authors: Vec<ImportedAuthor>) -> Result<ImportBatchResult, AppError>;
+    /// Creates or refreshes books matched on external id, then ISBN-13, then ISBN-10,
+    /// along with the authors, genres and publishers they name.
+    async fn upsert_books(&self, provider: ExternalProvider, books: Vec<ImportedBook>) -> Result<ImportBatchResult, AppError>;
+    /// Stores each rating as a review without text, creating the readers it names.
+    async fn upsert_ratings(&self, provider: ExternalProvider, ratings: Vec<ImportedRating>) -> Result<ImportBatchResult, AppError>;
+    async fn insert_job(&self, job: ImportJob) -> Result<ObjectId, AppError>;
+    async fn update_job(&self, job_id: &ObjectId, status: ImportJobStatus, report: &ImportReport, error: Option<&str>) -> Result<(), AppError>;
+    async fn find_job(&self, job_id: &str) -> Result<Option<ImportJob>, AppError>;
+}
+
+
+/// Reviews of one book with one score, counted by `refresh_ratings`.
+#[derive(Debug, Deserialize)]
+struct ScoreCount {
+    #[serde(rename = "_id")]
+    key: ScoreKey,
+    count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreKey {
+    book_id: ObjectId,
+    score: f64,
+}
+
+
+async fn find_in_session<T>(collection: &Collection<T>, session: &mut ClientSession, filter: Document) -> Result<Vec<T>, AppError>
+where
+    T: DeserializeOwned + Send + Sync,
+{
+    let mut cursor = collection.find(filter).session(&mut *session).await?;
+    Ok(cursor.stream(session).try_collect().await?)
+}
+
+fn external_key(external_id: &Option<ExternalId>, provider: ExternalProvider) -> Option<String> {
+    external_id.as_ref()?.get(provider).map(str::to_string)
+}
+
+
+#[derive(Clone)]
+pub struct ImportRepository {
+    pub mongo_client: Client,
+    pub author_collection: Collection<Author>,
+    pub book_collection: Collection<Book>,
+    pub user_collection: Collection<User>,
+    pub review_collection: Collection<Review>,
+    pub metadata_collection: Collection<MetadataDoc>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+    pub job_collection: Collection<ImportJob>,
+    pub author_repo: AuthorRepository,
+    pub book_repo: BookRepository,
+    pub user_repo: UserRepository,
+}
+
+impl ImportRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
+        ImportRepository {
+            mongo_client: mongo_client.clone(),
+            author_collection: mongo_database.collection::<Author>("authors"),
+            book_collection: mongo_database.collection::<Book>("books"),
+            user_collection: mongo_database.collection::<User>("users"),
+            review_collection: mongo_database.collection::<Review>("reviews"),
+            metadata_collection: mongo_database.collection::<MetadataDoc>("metadata"),
+            outbox_collection: mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION),
+            job_collection: mongo_database.collection::<ImportJob>(IMPORT_JOB_COLLECTION),
+            author_repo: AuthorRepository::new(mongo_client.clone(), mongo_database.clone()),
+            book_repo: BookRepository::new(mongo_client.clone(), mongo_database.clone()),
+            user_repo: UserRepository::new(mongo_client.clone(), mongo_database.clone()),
+        }
+    }
+
+    /// Records the graph changes mirroring this batch in the outbox, inside the caller's transaction.
+    async fn record_graph_sync(&self, session: &mut ClientSession, ops: Vec<GraphSyncOp>) -> Result<(), AppError> {
+        enqueue(&self.outbox_collection, session, ops).await
+    }
+
+    /// Upserts a genre or publisher, returning whether it was new.
+    async fn ensure_metadata(&self, session: &mut ClientSession, metadata: Metadata) -> Result<bool, AppError> {
+        let mut fields = to_document(&metadata.to_doc())?;
+        fields.remove("_id");
+        let result_upsert = self.metadata_collection
+            .update_one(doc! { "_id": metadata.mongo_id() }, doc! { "$setOnInsert": fields })
+            .upsert(true)
+            .session(session)
+            .await?;
+        Ok(result_upsert.upserted_id.is_some())
+    }
+
+    async fn write_authors(
+        &self,
+        session: &mut ClientSession,
+        provider: ExternalProvider,
+        authors: Vec<ImportedAuthor>,
+    ) -> Result<ImportBatchResult, AppError> {
+        let now = Utc::now();
+        let mut result = ImportBatchResult::default();
+        let mut ops = Vec::new();
+
+        let external_ids: Vec<&str> = authors.iter().map(|author| author.external_id.as_str()).collect();
+        let existing: HashMap<String, ObjectId> =
+            find_in_session(&self.author_collection, session, doc! { provider.path(): { "$in": &external_ids } })
+                .await?
+                .into_iter()
+                .filter_map(|author| Some((external_key(&author.external_id, provider)?, author.id?)))
+                .collect();
+
+        let mut created = Vec::new();
+        for author in authors {
+            let author_id = match existing.get(&author.external_id) {
+                Some(author_id) => author_id,
+                None => {
+                    created.push(Author {
+                        id: None,
+                        name: author.name,
+                        image_url: author.image_url.unwrap_or_default(),
+                        description: author.description.unwrap_or_default(),
+                        books: vec![],
+                        external_id: Some(ExternalId::from_provider(provider, &author.external_id)),
+                        created_at: now,
+                        updated_at: now,
+                    });
+                    continue;
+                },
+            };
+
+            let mut fields = doc! { "name": &author.name, "updated_at": to_bson(&now)? };
+            if let Some(description) = &author.description {
+                fields.insert("description", description);
+            }
+            if let Some(image_url) = &author.image_url {
+                fields.insert("image_url", image_url);
+            }
+            self.author_collection
+                .update_one(doc! { "_id": author_id }, doc! { "$set": fields })
+                .session(&mut *session)
+                .await?;
+            ops.push(GraphSyncOp::UpsertAuthor { author_id: author_id.to_hex(), name: author.name });
+            result.updated += 1;
+        }
+
+        let created_ids = self.author_repo.insert_many_in_session(session, &created).await?;
+        result.created += created_ids.len() as u64;
+
+        self.record_graph_sync(session, ops).await?;
+        Ok(result)
+    }
+
+    /// Embeds of the authors each book names, creating the named ones not found by external id or exact name.
+    /// Authors known only by an unknown external id cannot be created and are reported as warnings.
+    async fn resolve_authors(
+        &self,
+        session: &mut ClientSession,
+        provider: ExternalProvider,
+        books: &[ImportedBook],
+        result: &mut ImportBatchResult,
+    ) -> Result<Vec<Vec<AuthorEmbed>>, AppError> {
+        let refs = || books.iter().flat_map(|book| book.authors.iter());
+        let external_ids: Vec<&str> = refs().filter_map(|author| author.external_id.as_deref()).collect();
+        let names: Vec<&str> = refs().filter_map(|author| author.name.as_deref()).collect();
+
+        let found = find_in_session(
+            &self.author_collection,
+            session,
+            doc! { "$or": [{ provider.path(): { "$in": &external_ids } }, { "name": { "$in": &names } }] },
+        ).await?;
+        let mut by_external: HashMap<String, AuthorEmbed> = HashMap::new();
+        let mut by_name: HashMap<String, AuthorEmbed> = HashMap::new();
+        for author in &found {
+            if let Some(key) = external_key(&author.external_id, provider) {
+                by_external.insert(key, AuthorEmbed::from(author));
+            }
+            by_name.entry(author.name.clone()).or_insert_with(|| AuthorEmbed::from(author));
+        }
+
+        let now = Utc::now();
+        let mut created: Vec<Author> = Vec::new();
+        let mut pending: HashSet<String> = HashSet::new();
+        for author in refs() {
+            let known = author.external_id.as_ref().is_some_and(|id| by_external.contains_key(id))
+                || author.name.as_ref().is_some_and(|name| by_name.contains_key(name));
+            let name = match (&author.name, known) {
+                (Some(name), false) => name,
+                _ => continue,
+            };
+            if pending.insert(author.external_id.clone().unwrap_or_else(|| name.clone())) {
+                created.push(Author {
+                    id: None,
+                    name: name.clone(),
+                    image_url: String::new(),
+                    description: String::new(),
+                    books: vec![],
+                    external_id: author.external_id.as_deref().map(|id| ExternalId::from_provider(provider, id)),
+                    created_at: now,
+                    updated_at: now,
+                });
+            }
+        }
+
+        let created_ids = self.author_repo.insert_many_in_session(session, &created).await?;
+        for (author_id, mut author) in created_ids.into_iter().zip(created) {
+            author.id = Some(author_id);
+            if let Some(key) = external_key(&author.external_id, provider) {
+                by_external.insert(key, AuthorEmbed::from(&author));
+            }
+            by_name.entry(author.name.clone()).or_insert_with(|| AuthorEmbed::from(&author));
+            result.authors_created += 1;
+        }
+
+        let mut embeds = Vec::with_capacity(books.len());
+        for book in books {
+            let mut book_embeds: Vec<AuthorEmbed> = Vec::new();
+            for author in &book.authors {
+                let embed = author.external_id.as_ref().and_then(|id| by_external.get(id))
+                    .or_else(|| author.name.as_ref().and_then(|name| by_name.get(name)));
+                match embed {
+                    Some(embed) if !book_embeds.iter().any(|known| known.id == embed.id) => book_embeds.push(embed.clone()),
+                    Some(_) => {},
+                    None => result.warnings.push(ImportRowError {
+                        line: book.line,
+                        message: format!("Unknown author {}, not linked", author.external_id.as_deref().unwrap_or_default()),
+                    }),
+                }
+            }
+            embeds.push(book_embeds);
+        }
+        Ok(embeds)
+    }
+
+    async fn write_books(
+        &self,
+        session: &mut ClientSession,
+        provider: ExternalProvider,
+        books: Vec<ImportedBook>,
+    ) -> Result<ImportBatchResult, AppError> {
+        let mut result = ImportBatchResult::default();
+        let mut ops = Vec::new();
+
+        let author_embeds = self.resolve_authors(session, provider, &books, &mut result).await?;
+
+        let genres: HashSet<&String> = books.iter().flat_map(|book| &book.genres).collect();
+        for name in genres {
+            if self.ensure_metadata(session, Metadata::new_genre(name.clone(), String::new())).await? {
+                ops.push(GraphSyncOp::UpsertGenre { name: name.clone(), description: String::new() });
+                result.genres_created += 1;
+            }
+        }
+        let publishers: HashSet<&String> = books.iter().flat_map(|book| &book.publishers).collect();
+        for name in publishers {
+            if self.ensure_metadata(session, Metadata::new_publisher(name.clone(), String::new())).await? {
+                result.publishers_created += 1;
+            }
+        }
+
+        let external_ids: Vec<&str> = books.iter().filter_map(|book| book.external_id.as_deref()).collect();
+        let isbns: Vec<&str> = books.iter().filter_map(|book| book.isbn.as_deref()).collect();
+        let isbn13s: Vec<&str> = books.iter().filter_map(|book| book.isbn13.as_deref()).collect();
+        let existing = find_in_session(&self.book_collection, session, doc! { "$or": [
+            { provider.path(): { "$in": &external_ids } },
+            { "isbn": { "$in": &isbns } },
+            { "isbn13": { "$in": &isbn13s } },
+        ] }).await?;
+
+        let mut by_key: HashMap<String, &Book> = HashMap::new();
+        for book in &existing {
+            if let Some(key) = external_key(&book.external_id, provider) {
+                by_key.insert(format!("id:{}", key), book);
+            }
+            if !book.isbn13.is_empty() {
+                by_key.entry(format!("isbn13:{}", book.isbn13)).or_insert(book);
+            }
+            if !book.isbn.is_empty() {
+                by_key.entry(format!("isbn:{}", book.isbn)).or_insert(book);
+            }
+        }
+
+        let mut created: Vec<Book> = Vec::new();
+        for (imported, authors) in books.into_iter().zip(author_embeds) {
+            let matched = [
+                imported.external_id.as_ref().map(|id| format!("id:{}", id)),
+                imported.isbn13.as_ref().map(|isbn| format!("isbn13:{}", isbn)),
+                imported.isbn.as_ref().map(|isbn| format!("isbn:{}", isbn)),
+            ]
+                .into_iter()
+                .flatten()
+                .find_map(|key| by_key.get(&key).copied());
+
+            let mut book = match matched {
+                Some(existing) => existing.clone(),
+                None => Book {
+                    id: None,
+                    isbn: String::new(),
+                    isbn13: String::new(),
+                    title: String::new(),
+                    subtitle: None,
+                    description: None,
+                    num_pages: None,
+                    published_date: None,
+                    format: imported.format.clone(),
+                    images: vec![],
+                    preview: vec![],
+                    genres: vec![],
+                    authors: vec![],
+                    publishers: vec![],
+                    languages: vec![],
+                    reviews: vec![],
+                    rating: BookRating::default(),
+                    external_id: None,
+                    work_id: None,
+                },
+            };
+
+            book.title = imported.title;
+            book.format = imported.format;
+            // An empty column in a sparse dump keeps what the catalog already has
+            if !authors.is_empty() {
+                book.authors = authors;
+            }
+            if !imported.genres.is_empty() {
+                book.genres = imported.genres.into_iter().map(|name| GenreEmbed { name }).collect();
+            }
+            if !imported.publishers.is_empty() {
+                book.publishers = imported.publishers.into_iter().map(|name| PublisherEmbed { name }).collect();
+            }
+            if !imported.languages.is_empty() {
+                book.languages = imported.languages;
+            }
+            if let Some(isbn) = imported.isbn {
+                book.isbn = isbn;
+            }
+            if let Some(isbn13) = imported.isbn13 {
+                book.isbn13 = isbn13;
+            }
+            book.description = imported.description.or(book.description);
+            book.num_pages = imported.num_pages.or(book.num_pages);
+            book.published_date = imported.published_date.or(book.published_date);
+            if let (Some(url), true) = (imported.image_url, book.images.is_empty()) {
+                book.images.push(BookImageSource { url, source: SourceEmbed { name: provider.field().to_string() } });
+            }
+            if let Some(external_id) = imported.external_id {
+                book.external_id.get_or_insert_with(ExternalId::default).set(provider, &external_id);
+            }
+
+            let book_id = match book.id {
+                Some(book_id) => book_id,
+                None => {
+                    created.push(book);
+                    continue;
+                },
+            };
+            self.book_collection
+                .replace_one(doc! { "_id": &book_id }, &book)
+                .session(&mut *session)
+                .await?;
+            // The authors' embedded book lists follow the new author list
+            self.author_collection
+                .update_many(doc! { "books.book_id": &book_id }, doc! { "$pull": { "books": { "book_id": &book_id } } })
+                .session(&mut *session)
+                .await?;
+            self.link_authors(session, &book).await?;
+            ops.push(GraphSyncOp::upsert_book(&book));
+            result.updated += 1;
+        }
+
+        let created_ids = self.book_repo.insert_many_in_session(session, &created).await?;
+        result.created += created_ids.len() as u64;
+
+        self.record_graph_sync(session, ops).await?;
+        Ok(result)
+    }
+
+    async fn link_authors(&self, session: &mut ClientSession, book: &Book) -> Result<(), AppError> {
+        if book.authors.is_empty() {
+            return Ok(());
+        }
+        let author_ids: Vec<ObjectId> = book.authors.iter().map(|author| author.id).collect();
+        self.author_collection
+            .update_many(
+                doc! { "_id": { "$in": author_ids } },
+                doc! { "$push": { "books": to_document(&BookEmbed::from(book))? } },
+            )
+            .session(session)
+            .await?;
+        Ok(())
+    }
+
+    /// Readers named by the ratings, created with an unusable password when the external id is unknown:
+    /// imported readers have to set one before they can sign in.
+    async fn resolve_readers(
+        &self,
+        session: &mut ClientSession,
+        provider: ExternalProvider,
+        ratings: &[ImportedRating],
+        result: &mut ImportBatchResult,
+    ) -> Result<HashMap<String, UserEmbed>, AppError> {
+        let external_ids: Vec<&str> = ratings.iter().map(|rating| rating.reader_external_id.as_str()).collect();
+        let mut readers: HashMap<String, UserEmbed> =
+            find_in_session(&self.user_collection, session, doc! { provider.path(): { "$in": &external_ids } })
+                .await?
+                .iter()
+                .filter_map(|user| Some((external_key(&user.external_id, provider)?, UserEmbed::from(user))))
+                .collect();
+
+        let now = Utc::now();
+        let mut created: Vec<User> = Vec::new();
+        let mut pending: HashSet<&str> = HashSet::new();
+        for rating in ratings {
+            let external_id = rating.reader_external_id.as_str();
+            if readers.contains_key(external_id) || !pending.insert(external_id) {
+                continue;
+            }
+            created.push(User {
+                id: None,
+                username: provider.reader_username(external_id),
+                password: HashedPassword::unusable(),
+                name: rating.reader_name.clone().unwrap_or_else(|| format!("Reader {}", external_id)),
+                image_url: None,
+                role: UserRole::Reader,
+                preference: None,
+                shelf: None,
+                reviews: None,
+                external_id: Some(ExternalId::from_provider(provider, external_id)),
+                created_at: now,
+                updated_at: now,
+            });
+        }
+
+        // A user who registered the name before sign-up reserved it keeps it, and the reader's rows are rejected
+        let usernames: Vec<&str> = created.iter().map(|user| user.username.as_str()).collect();
+        let taken: HashSet<String> =
+            find_in_session(&self.user_collection, session, doc! { "username": { "$in": &usernames } })
+                .await?
+                .into_iter()
+                .map(|user| user.username)
+                .collect();
+        created.retain(|user| !taken.contains(&user.username));
+
+        let created_ids = self.user_repo.insert_many_in_session(session, &created).await?;
+        for (user_id, mut user) in created_ids.into_iter().zip(created) {
+            user.id = Some(user_id);
+            if let Some(key) = external_key(&user.external_id, provider) {
+                readers.insert(key, UserEmbed::from(&user));
+            }
+            result.readers_created += 1;
+        }
+        Ok(readers)
+    }
+
+    async fn write_ratings(
+        &self,
+        session: &mut ClientSession,
+        provider: ExternalProvider,
+        ratings: Vec<ImportedRating>,
+    ) -> Result<ImportBatchResult, AppError> {
+        let now = Utc::now();
+        let mut result = ImportBatchResult::default();
+        let mut ops = Vec::new();
+
+        let external_ids: Vec<&str> = ratings.iter().filter_map(|rating| rating.book_external_id.as_deref()).collect();
+        let isbns: Vec<&str> = ratings.iter().filter_map(|rating| rating.isbn.as_deref()).collect();
+        let books = find_in_session(&self.book_collection, session, doc! { "$or": [
+            { provider.path(): { "$in": &external_ids } },
+            { "isbn": { "$in": &isbns } },
+            { "isbn13": { "$in": &isbns } },
+        ] }).await?;
+        let mut book_ids: HashMap<String, ObjectId> = HashMap::new();
+        for book in &books {
+            let Some(book_id) = book.id else { continue };
+            if let Some(key) = external_key(&book.external_id, provider) {
+                book_ids.insert(format!("id:{}", key), book_id);
+            }
+            for isbn in [&book.isbn, &book.isbn13].into_iter().filter(|isbn| !isbn.is_empty()) {
+                book_ids.entry(format!("isbn:{}", isbn)).or_insert(book_id);
+            }
+        }
+
+        let readers = self.resolve_readers(session, provider, &ratings, &mut result).await?;
+
+        let mut rated_books: HashSet<ObjectId> = HashSet::new();
+        for rating in ratings {
+            let book_id = rating.book_external_id.as_ref().and_then(|id| book_ids.get(&format!("id:{}", id)))
+                .or_else(|| rating.isbn.as_ref().and_then(|isbn| book_ids.get(&format!("isbn:{}", isbn))));
+            let (book_id, reader) = match (book_id, readers.get(&rating.reader_external_id)) {
+                (Some(book_id), Some(reader)) => (*book_id, reader),
+                (None, _) => {
+                    let book = rating.book_external_id.or(rating.isbn).unwrap_or_default();
+                    result.rejected.push(ImportRowError { line: rating.line, message: format!("Unknown book {}", book) });
+                    continue;
+                },
+                (_, None) => {
+                    let message = format!(
+                        "Reader {} not created, username {} is taken",
+                        rating.reader_external_id, provider.reader_username(&rating.reader_external_id)
+                    );
+                    result.rejected.push(ImportRowError { line: rating.line, message });
+                    continue;
+                },
+            };
+
+            let rated_at: DateTime<Utc> = rating.rated_at.unwrap_or(now);
+            let result_upsert = self.review_collection
+                .update_one(
+                    doc! { "book_id": &book_id, "user.id": &reader.id },
+                    doc! {
+                        "$set": { "score": f64::from(rating.score), "updated_at": to_bson(&now)? },
+                        "$setOnInsert": {
+                            "user.name": &reader.name,
+                            "user.image_url": to_bson(&reader.image_url)?,
+                            "content": "",
+                            "date_added": to_bson(&rated_at)?,
+                        },
+                    },
+                )
+                .upsert(true)
+                .session(&mut *session)
+                .await?;
+
+            match result_upsert.upserted_id.as_ref().and_then(Bson::as_object_id) {
+                Some(review_id) => {
+                    self.book_collection
+                        .update_one(doc! { "_id": &book_id }, doc! { "$push": { "reviews": review_id } })
+                        .session(&mut *session)
+                        .await?;
+                    self.user_collection
+                        .update_one(doc! { "_id": &reader.id }, doc! { "$push": { "reviews": review_id } })
+                        .session(&mut *session)
+                        .await?;
+                    result.created += 1;
+                },
+                None => result.updated += 1,
+            }
+
+            rated_books.insert(book_id);
+            ops.push(GraphSyncOp::UpsertRating {
+                user_id: reader.id.to_hex(),
+                book_id: book_id.to_hex(),
+                rating: f64::from(rating.score),
+                ts: rated_at.timestamp_millis(),
+            });
+        }
+
+        self.refresh_ratings(session, rated_books.into_iter().collect()).await?;
+        self.record_graph_sync(session, ops).await?;
+        Ok(result)
+    }
+
+    /// Recomputes the `rating` aggregates of the books from their reviews.
+    /// Cheaper than applying every imported rating as a delta, and right even when a rating replaces an older one.
+    async fn refresh_ratings(&self, session: &mut ClientSession, book_ids: Vec<ObjectId>) -> Result<(), AppError> {
+        let pipeline = vec![
+            doc! { "$match": { "book_id": { "$in": &book_ids } } },
+            doc! { "$group": { "_id": { "book_id": "$book_id", "score": "$score" }, "count": { "$sum": 1 } } },
+        ];
+        let mut cursor = self.review_collection
+            .aggregate(pipeline)
+            .with_type::<ScoreCount>()
+            .session(&mut *session)
+            .await?;
+        let counts: Vec<ScoreCount> = cursor.stream(&mut *session).try_collect().await?;
+
+        let mut ratings: HashMap<ObjectId, BookRating> = book_ids.into_iter().map(|id| (id, BookRating::default())).collect();
+        for ScoreCount { key, count } in counts {
+            let (Some(rating), Some(bucket)) = (ratings.get_mut(&key.book_id), Review::score_bucket(key.score as f32)) else {
+                continue;
+            };
+            rating.count += count;
+            rating.sum += key.score * count as f64;
+            *rating.histogram.entry(bucket.to_string()).or_default() += count;
+        }
+
+        for (book_id, mut rating) in ratings {
+            rating.average = if rating.count > 0 { rating.sum / rating.count as f64 } else { 0.0 };
+            self.book_collection
+                .update_one(doc! { "_id": &book_id }, doc! { "$set": { "rating": to_bson(&rating)? } })
+                .session(&mut *session)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+
+#[async_trait]
+impl ImportRepositoryInterface for ImportRepository {
+    async fn upsert_authors(&self, provider: ExternalProvider, authors: Vec<ImportedAuthor>) -> Result<ImportBatchResult, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [IMPORT] [UPSERT AUTHORS] provider: {:?}, count: {}",
+            provider, authors.len()
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.write_authors(&mut mongo_session, provider, authors).await {
+            Ok(result) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error importing authors: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn upsert_books(&self, provider: ExternalProvider, books: Vec<ImportedBook>) -> Result<ImportBatchResult, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [IMPORT] [UPSERT BOOKS] provider: {:?}, count: {}",
+            provider, books.len()
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.write_books(&mut mongo_session, provider, books).await {
+            Ok(result) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error importing books: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn upsert_ratings(&self, provider: ExternalProvider, ratings: Vec<ImportedRating>) -> Result<ImportBatchResult, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [IMPORT] [UPSERT RATINGS] provider: {:?}, count: {}",
+            provider, ratings.len()
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.write_ratings(&mut mongo_session, provider, ratings).await {
+            Ok(result) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error importing ratings: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn insert_job(&self, job: ImportJob) -> Result<ObjectId, AppError> {
+        let result_insert = self.job_collection.insert_one(&job).await?;
+        result_insert.inserted_id
+            .as_object_id()
+            .ok_or_else(|| AppError::Upstream(anyhow!("Inserted import job id is not an ObjectId")))
+    }
+
+    async fn update_job(&self, job_id: &ObjectId, status: ImportJobStatus, report: &ImportReport, error: Option<&str>) -> Result<(), AppError> {
+        self.job_collection
+            .update_one(
+                doc! { "_id": job_id },
+                doc! { "$set": {
+                    "status": to_bson(&status)?,
+                    "report": to_bson(report)?,
+                    "error": error,
+                    "updated_at": to_bson(&Utc::now())?,
+                } },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn find_job(&self, job_id: &str) -> Result<Option<ImportJob>, AppError> {
+        let id = ObjectId::parse_str(job_id).map_err(|_| AppError::invalid_id("import job", job_id))?;
+        Ok(self.job_collection.find_one(doc! { "_id": id }).await?)
+    }
+}
@@ -1,62 +1,38 @@
-use anyhow::{anyhow, Error, Result};
 use async_trait::async_trait;
 use futures::StreamExt;
 use mongodb::{
-    bson::{doc, oid::ObjectId},
-    results::{DeleteResult, InsertOneResult, UpdateResult},
+    bson::doc,
     Client, Database, Collection,
 };
-use neo4rs::{query, Graph, Query, Txn};
 
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
 use crate::model::metadata_model::{Metadata, MetadataDoc, MetadataKey};
+use crate::repository::graph_sync_repository::enqueue;
 use crate::shared::logging::log::TimePrinter;
-use crate::shared::repository::repository_utils::neo4j_count;
+use crate::shared::repository::repository_utils::{sort_document, with_search};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginationRequest;
 
 impl Metadata {
-    pub fn neo4j_create_query(&self) -> Query {
+    /// Graph change mirroring an insert or update, for the kinds that also live in Neo4j.
+    pub fn graph_sync_upsert(&self) -> Option<GraphSyncOp> {
         match self {
-            Metadata::Genre { name, description } => query(
-                "CREATE (g:Genre {name:$k, description:$description})"
-            ).param("k", name.as_str()).param("description", description.as_str()),
-
-            _ => unreachable!(),
-        }
-    }
-
-    pub fn neo4j_update_query_with_count(&self) -> Query {
-        match self {
-            Metadata::Genre { name, description } => query(
-                "MATCH (g:Genre {name:$k})
-                 SET g.description = $description
-                 RETURN count(g) AS n"
-            ).param("k", name.as_str()).param("description", description.as_str()),
-
-            _ => unreachable!(),
-        }
-    }
-
-    pub fn neo4j_delete_query(&self) -> Query {
-        match self {
-            Metadata::Genre { name, .. } => query("MATCH (g:Genre {name:$id}) DETACH DELETE g")
-                .param("id", name.as_str()),
-            _ => unreachable!(),
+            Metadata::Genre { name, description } => Some(GraphSyncOp::UpsertGenre {
+                name: name.clone(),
+                description: description.clone(),
+            }),
+            _ => None,
         }
     }
 }
 
 
 impl MetadataKey {
-    pub fn neo4j_delete_query_with_count(&self) -> Query {
+    /// Graph change mirroring a delete, for the kinds that also live in Neo4j.
+    pub fn graph_sync_delete(&self) -> Option<GraphSyncOp> {
         match self {
-
-            MetadataKey::Genre { name } => query(
-                "MATCH (g:Genre {name:$k})
-                 WITH g, count(g) AS n
-                 DETACH DELETE g
-                 RETURN n"
-            ).param("k", name.as_str()),
-
-            _ => unreachable!(),
+            MetadataKey::Genre { name } => Some(GraphSyncOp::DeleteGenre { name: name.clone() }),
+            _ => None,
         }
     }
 }
@@ -64,13 +40,11 @@ impl MetadataKey {
 
 #[async_trait]
 pub trait MetadataRepositoryInterface {
-    async fn insert(&self, metadata: Metadata) -> Result<Metadata, Error>;
-    async fn update(&self, metadata: Metadata) -> Result<Option<Metadata>, Error>;
-    async fn delete(&self, key: MetadataKey) -> Result<(), Error>;
-    async fn find_by_id(&self, id: &str) -> Result<Option<Metadata>, Error>;
-    async fn find_by_key(&self, key: MetadataKey) -> Result<Option<Metadata>, Error>;
-    async fn find_all(&self) -> Result<Vec<Metadata>, Error>;
-    async fn find_all_by_type(&self, metadata_type: &str) -> Result<Vec<Metadata>, Error>;
+    async fn insert(&self, metadata: Metadata) -> Result<Metadata, AppError>;
+    async fn update(&self, metadata: Metadata) -> Result<Option<Metadata>, AppError>;
+    async fn delete(&self, key: MetadataKey) -> Result<(), AppError>;
+    async fn find_by_key(&self, key: MetadataKey) -> Result<Option<Metadata>, AppError>;
+    async fn find_page_by_type(&self, metadata_type: &str, pagination: &PaginationRequest) -> Result<(Vec<Metadata>, u64), AppError>;
 }
 
 
@@ -78,16 +52,17 @@ pub trait MetadataRepositoryInterface {
 pub struct MetadataRepository {
     pub mongo_client: Client,
     pub metadata_collection: Collection<MetadataDoc>,
-    pub neo4j_client: Graph,
+    pub outbox_collection: Collection<GraphSyncEvent>,
 }
 
 impl MetadataRepository {
-    pub fn new(mongo_client: Client, mongo_database: Database, neo4j_client: Graph) -> Self {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
         let metadata_collection = mongo_database.collection::<MetadataDoc>("metadata");
+        let outbox_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION);
         MetadataRepository {
             mongo_client,
             metadata_collection,
-            neo4j_client,
+            outbox_collection,
         }
     }
 }
@@ -95,49 +70,46 @@ impl MetadataRepository {
 
 #[async_trait]
 impl MetadataRepositoryInterface for MetadataRepository {
-    async fn insert(&self, metadata: Metadata) -> Result<Metadata, Error> {
+    async fn insert(&self, metadata: Metadata) -> Result<Metadata, AppError> {
         let timer = TimePrinter::with_message(&format!(
             "[REPOSITORY] [META DATA] [INSERT] {:?}: {:?} ",
             metadata.kind(), metadata
         ));
 
         let new_doc = metadata.to_doc();
-        let id = new_doc.id.clone();
-
-        if(metadata.save_in_noe4j()) {
-            let mut neo4j_tx = self.neo4j_client.start_txn().await?;
-            neo4j_tx.run(metadata.neo4j_create_query()).await?;
-
-            let mut mongo_session = self.mongo_client.start_session().await?;
-            mongo_session.start_transaction().await?;
-
-            if let Err(e) = self.metadata_collection
-                .insert_one(new_doc.clone())
-                .session(&mut mongo_session)
-                .await
-            {
-                let _ = mongo_session.abort_transaction().await;
-                let _ = neo4j_tx.rollback().await;
-                timer.error_with_message(&format!("Error adding metadata: {}", e));
-                return Err(e.into());
-            }
-
-            mongo_session.commit_transaction().await?;
 
-            if let Err(e) = neo4j_tx.commit().await {
-                let _ = self.metadata_collection.delete_one(doc! { "_id": &id }).await;
-                timer.error_with_message(&format!("Error adding metadata: {}", e));
-                return Err(e.into());
+        match metadata.graph_sync_upsert() {
+            Some(op) => {
+                let mut mongo_session = self.mongo_client.start_session().await?;
+                mongo_session.start_transaction().await?;
+
+                let result = async {
+                    self.metadata_collection
+                        .insert_one(new_doc.clone())
+                        .session(&mut mongo_session)
+                        .await?;
+                    enqueue(&self.outbox_collection, &mut mongo_session, vec![op]).await?;
+                    Ok::<_, AppError>(())
+                }.await;
+
+                if let Err(e) = result {
+                    let _ = mongo_session.abort_transaction().await;
+                    timer.error_with_message(&format!("Error adding metadata: {}", e));
+                    return Err(e);
+                }
+
+                mongo_session.commit_transaction().await?;
+            },
+            None => {
+                let _ = self.metadata_collection.insert_one(new_doc.clone()).await?;
             }
-        } else {
-            let _ = self.metadata_collection.insert_one(new_doc.clone()).await?;
         }
 
         timer.log();
         Ok(new_doc.meta)
     }
 
-    async fn update(&self, metadata: Metadata) -> Result<Option<Metadata>, Error> {
+    async fn update(&self, metadata: Metadata) -> Result<Option<Metadata>, AppError> {
         let timer = TimePrinter::with_message(&format!(
             "[REPOSITORY] [META DATA] [UPDATE] {:?}: {:?} ",
             metadata.kind(), metadata
@@ -152,46 +124,41 @@ impl MetadataRepositoryInterface for MetadataRepository {
             Metadata::Publisher { website, .. } => doc! { "$set": { "website": website } },
         };
 
-        if(!metadata.save_in_noe4j()) {
-            let mut neo_tx = self.neo4j_client.start_txn().await?;
-            let n = neo4j_count(&mut neo_tx, metadata.neo4j_update_query_with_count()).await?;
-            if n == 0 {
-                let _ = neo_tx.rollback().await;
-                timer.error_with_message(&format!("Neo4j node not found for {}", id));
-                return Err(anyhow!("Neo4j node not found for {}", id));
-            }
-
-            let mut session = self.mongo_client.start_session().await?;
-            session.start_transaction().await?;
-
-            let old = self
-                .metadata_collection
-                .find_one(filter.clone())
-                .session(&mut session)
-                .await?;
-
-            let old = old.ok_or_else(|| anyhow!("Mongo doc not found for {}", id))?;
-
-            self.metadata_collection
-                .update_one(filter, update)
-                .session(&mut session)
-                .await?;
-
-            session.commit_transaction().await?;
-
-            if let Err(e) = neo_tx.commit().await {
-                let _ = self.metadata_collection.replace_one(doc! { "_id": &id }, old).await;
-                timer.error_with_message(&format!("Error adding metadata: {}", e));
-                return Err(e.into());
-            }
-        } else {
-            let update_result = self.metadata_collection
-                .update_one(filter, update)
-                .await?;
-
-            if update_result.matched_count == 0 {
-                timer.error_with_message(&format!("Mongo doc not found for {}", id));
-                return Err(anyhow!("Mongo doc not found for {}", id));
+        match metadata.graph_sync_upsert() {
+            Some(op) => {
+                let mut session = self.mongo_client.start_session().await?;
+                session.start_transaction().await?;
+
+                let result = async {
+                    let update_result = self.metadata_collection
+                        .update_one(filter, update)
+                        .session(&mut session)
+                        .await?;
+                    if update_result.matched_count == 0 {
+                        return Err(AppError::NotFound(format!("Mongo doc not found for {}", id)));
+                    }
+
+                    enqueue(&self.outbox_collection, &mut session, vec![op]).await?;
+                    Ok::<_, AppError>(())
+                }.await;
+
+                if let Err(e) = result {
+                    let _ = session.abort_transaction().await;
+                    timer.error_with_message(&format!("Error updating metadata: {}", e));
+                    return Err(e);
+                }
+
+                session.commit_transaction().await?;
+            },
+            None => {
+                let update_result = self.metadata_collection
+                    .update_one(filter, update)
+                    .await?;
+
+                if update_result.matched_count == 0 {
+                    timer.error_with_message(&format!("Mongo doc not found for {}", id));
+                    return Err(AppError::NotFound(format!("Mongo doc not found for {}", id)));
+                }
             }
         }
 
@@ -199,7 +166,7 @@ impl MetadataRepositoryInterface for MetadataRepository {
         Ok(Some(metadata))
     }
 
-    async fn delete(&self, key: MetadataKey) -> Result<(), Error> {
+    async fn delete(&self, key: MetadataKey) -> Result<(), AppError> {
         let timer = TimePrinter::with_message(&format!(
             "[REPOSITORY] [META DATA] [DELETE] {:?}: {:?} ",
             key.kind(), key
@@ -208,46 +175,41 @@ impl MetadataRepositoryInterface for MetadataRepository {
         let id = key.mongo_id();
         let filter = doc! {"_id": &id };
 
-        if key.save_in_noe4j() {
-            let mut neo_tx = self.neo4j_client.start_txn().await?;
-            let n = neo4j_count(&mut neo_tx, key.neo4j_delete_query_with_count()).await?;
-            if n == 0 {
-                let _ = neo_tx.rollback().await;
-                timer.error_with_message(&format!("Neo4j node not found for {}", id));
-                return Err(anyhow!("Neo4j node not found for {}", id));
-            }
-
-            let mut session = self.mongo_client.start_session().await?;
-            session.start_transaction().await?;
-
-            let old = self
-                .metadata_collection
-                .find_one(filter.clone())
-                .session(&mut session)
-                .await?;
-
-            let old = old.ok_or_else(|| anyhow!("Mongo doc not found for {}", id))?;
-
-            self.metadata_collection
-                .delete_one(filter)
-                .session(&mut session)
-                .await?;
-
-            session.commit_transaction().await?;
-
-            if let Err(e) = neo_tx.commit().await {
-                let _ = self.metadata_collection.insert_one(old).await;
-                timer.error_with_message(&format!("Error updating metadata: {}", e));
-                return Err(e.into());
-            }
-        } else {
-            let delete_result = self.metadata_collection
-                .delete_one(filter)
-                .await?;
-
-            if delete_result.deleted_count == 0 {
-                timer.error_with_message(&format!("Mongo doc not found for {}", id));
-                return Err(anyhow!("Mongo doc not found for {}", id));
+        match key.graph_sync_delete() {
+            Some(op) => {
+                let mut session = self.mongo_client.start_session().await?;
+                session.start_transaction().await?;
+
+                let result = async {
+                    let delete_result = self.metadata_collection
+                        .delete_one(filter)
+                        .session(&mut session)
+                        .await?;
+                    if delete_result.deleted_count == 0 {
+                        return Err(AppError::NotFound(format!("Mongo doc not found for {}", id)));
+                    }
+
+                    enqueue(&self.outbox_collection, &mut session, vec![op]).await?;
+                    Ok::<_, AppError>(())
+                }.await;
+
+                if let Err(e) = result {
+                    let _ = session.abort_transaction().await;
+                    timer.error_with_message(&format!("Error deleting metadata: {}", e));
+                    return Err(e);
+                }
+
+                session.commit_transaction().await?;
+            },
+            None => {
+                let delete_result = self.metadata_collection
+                    .delete_one(filter)
+                    .await?;
+
+                if delete_result.deleted_count == 0 {
+                    timer.error_with_message(&format!("Mongo doc not found for {}", id));
+                    return Err(AppError::NotFound(format!("Mongo doc not found for {}", id)));
+                }
             }
         }
 
@@ -255,30 +217,7 @@ impl MetadataRepositoryInterface for MetadataRepository {
         Ok(())
     }
 
-    async fn find_by_id(&self, id: &str) -> Result<Option<Metadata>, Error> {
-        let timer = TimePrinter::with_message(&format!(
-            "[REPOSITORY] [META DATA] [FIND BY ID] id: {:?} ",
-            id
-        ));
-
-        let doc_opt = self.metadata_collection
-            .find_one(doc! { "_id": id })
-            .await?;
-
-        // Ok(doc_opt.map(|d| d.meta))
-        match doc_opt {
-            Some(d) => {
-                timer.log();
-                Ok(Some(d.meta))
-            },
-            None => {
-                timer.error_with_message(&format!("Mongo doc not found for {}", id));
-                Ok(None)
-            }
-        }
-    }
-
-    async fn find_by_key(&self, key: MetadataKey) -> Result<Option<Metadata>, Error> {
+    async fn find_by_key(&self, key: MetadataKey) -> Result<Option<Metadata>, AppError> {
         let timer = TimePrinter::with_message(&format!(
             "[REPOSITORY] [META DATA] [FIND BY KEY] {:?}: {:?} ",
             key.kind(), key
@@ -300,27 +239,22 @@ impl MetadataRepositoryInterface for MetadataRepository {
         }
     }
 
-    async fn find_all(&self) -> Result<Vec<Metadata>, Error> {
-        let timer = TimePrinter::with_message("[REPOSITORY] [META DATA] [FIND ALL]");
-
-        let mut cursor = self.metadata_collection.find(doc! {}).await?;
-
-        let mut out = Vec::new();
-        while let Some(item) = cursor.next().await {
-            out.push(item?);
-        }
-        timer.log();
-        Ok(out.into_iter().map(|d| d.meta).collect())
-    }
-
-    async fn find_all_by_type(&self, metadata_type: &str) -> Result<Vec<Metadata>, Error> {
+    async fn find_page_by_type(&self, metadata_type: &str, pagination: &PaginationRequest) -> Result<(Vec<Metadata>, u64), AppError> {
         let timer = TimePrinter::with_message(&format!(
-            "[REPOSITORY] [META DATA] [FIND BY TYPE] type: {:?} ",
-            metadata_type
+            "[REPOSITORY] [META DATA] [FIND PAGE BY TYPE] type: {:?} pagination: {:?} ",
+            metadata_type, pagination
         ));
 
+        // `key` is the name, or the code for languages, so both stay searchable
+        let filter = with_search(doc! { "type": metadata_type }, pagination, &["key", "name"]);
+        let sort = sort_document(pagination, &["key", "name"], doc! { "key": 1 })?;
+
+        let total = self.metadata_collection.count_documents(filter.clone()).await?;
         let mut cursor = self.metadata_collection
-            .find(doc! { "type": metadata_type })
+            .find(filter)
+            .sort(sort)
+            .skip(pagination.skip())
+            .limit(pagination.page_size() as i64)
             .await?;
 
         let mut out = Vec::new();
@@ -328,7 +262,6 @@ impl MetadataRepositoryInterface for MetadataRepository {
             out.push(item?);
         }
         timer.log();
-        Ok(out.into_iter().map(|d| d.meta).collect())
+        Ok((out.into_iter().map(|d| d.meta).collect(), total))
     }
 }
-
@@ -1,3 +1,15 @@
 pub mod metadata_repository;
 pub mod user_repository;
-pub mod author_repository;
\ No newline at end of file
+pub mod author_repository;
+pub mod book_repository;
+pub mod review_repository;
+pub mod graph_sync_repository;
+pub mod reconciliation_repository;
+pub mod recommendation_repository;
+pub mod similarity_repository;
+pub mod follow_repository;
+pub mod search_repository;
+pub mod import_repository;
+pub mod export_repository;
+pub mod dedup_repository;
+pub mod work_repository;
\ No newline at end of file
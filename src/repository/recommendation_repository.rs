@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use neo4rs::{query, Graph, Query};
+
+use crate::model::recommendation_model::{RecommendationCandidate, RecommendationSignal};
+use crate::shared::constant::RECOMMENDATION_LIKED_RATING;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+#[async_trait]
+pub trait RecommendationRepositoryInterface {
+    async fn find_candidates(
+        &self,
+        signal: RecommendationSignal,
+        user_id: &str,
+        preferred_author_ids: &[String],
+        preferred_genres: &[String],
+        limit: u64,
+    ) -> Result<Vec<RecommendationCandidate>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct RecommendationRepository {
+    pub neo4j_client: Graph,
+}
+
+impl RecommendationRepository {
+    pub fn new(neo4j_client: Graph) -> Self {
+        RecommendationRepository { neo4j_client }
+    }
+}
+
+
+/// Every query skips books the reader already rated or shelved, in any edition of their work,
+/// and returns the candidates as `book_id`, `work_id`, `score` and `evidence` rows.
+fn candidate_query(signal: RecommendationSignal) -> Query {
+    match signal {
+        // Peers rated at least one common book within a star of the reader;
+        // each of their liked books scores the number of such common books
+        RecommendationSignal::Collaborative => query(
+            "MATCH (me:Reader {user_id:$user_id})-[mine:RATED]->(:Book)<-[theirs:RATED]-(peer:Reader)
+             WHERE peer <> me AND abs(mine.rating - theirs.rating) <= 1
+             WITH me, peer, count(*) AS common
+             MATCH (peer)-[liked:RATED]->(rec:Book)
+             WHERE liked.rating >= $liked_rating AND NOT (me)-[:RATED|ADDED_TO_SHELF]->(rec)
+               AND NOT (me)-[:RATED|ADDED_TO_SHELF]->(:Book)-[:EDITION_OF]->(:Work)<-[:EDITION_OF]-(rec)
+             RETURN rec.book_id AS book_id,
+                    [(rec)-[:EDITION_OF]->(w:Work) | w.work_id][0] AS work_id,
+                    toFloat(sum(common)) AS score,
+                    collect(DISTINCT peer.user_id) AS evidence
+             ORDER BY score DESC LIMIT $limit"
+        ),
+        // Books linked through a genre or an author to the ones the reader shelved or liked
+        RecommendationSignal::Content => query(
+            "MATCH (me:Reader {user_id:$user_id})-[signal:RATED|ADDED_TO_SHELF]->(liked:Book)
+             WHERE type(signal) = 'ADDED_TO_SHELF' OR signal.rating >= $liked_rating
+             MATCH (liked)-[:HAS_GENRE|WROTE]-(link)-[:HAS_GENRE|WROTE]-(rec:Book)
+             WHERE rec <> liked AND NOT (me)-[:RATED|ADDED_TO_SHELF]->(rec)
+               AND NOT (me)-[:RATED|ADDED_TO_SHELF]->(:Book)-[:EDITION_OF]->(:Work)<-[:EDITION_OF]-(rec)
+             RETURN rec.book_id AS book_id,
+                    [(rec)-[:EDITION_OF]->(w:Work) | w.work_id][0] AS work_id,
+                    toFloat(count(*)) AS score,
+                    collect(DISTINCT link.name) AS evidence
+             ORDER BY score DESC LIMIT $limit"
+        ),
+        RecommendationSignal::Preference => query(
+            "OPTIONAL MATCH (me:Reader {user_id:$user_id})
+             MATCH (rec:Book)-[:HAS_GENRE|WROTE]-(link)
+             WHERE ((link:Genre AND link.name IN $genres) OR (link:Author AND link.author_id IN $author_ids))
+               AND (me IS NULL OR (NOT (me)-[:RATED|ADDED_TO_SHELF]->(rec)
+                    AND NOT (me)-[:RATED|ADDED_TO_SHELF]->(:Book)-[:EDITION_OF]->(:Work)<-[:EDITION_OF]-(rec)))
+             RETURN rec.book_id AS book_id,
+                    [(rec)-[:EDITION_OF]->(w:Work) | w.work_id][0] AS work_id,
+                    toFloat(count(DISTINCT link)) AS score,
+                    collect(DISTINCT link.name) AS evidence
+             ORDER BY score DESC LIMIT $limit"
+        ),
+    }
+}
+
+
+#[async_trait]
+impl RecommendationRepositoryInterface for RecommendationRepository {
+    async fn find_candidates(
+        &self,
+        signal: RecommendationSignal,
+        user_id: &str,
+        preferred_author_ids: &[String],
+        preferred_genres: &[String],
+        limit: u64,
+    ) -> Result<Vec<RecommendationCandidate>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [RECOMMENDATION] [FIND CANDIDATES] signal: {:?}, user_id: {}, limit: {}",
+            signal, user_id, limit
+        ));
+
+        let cypher = candidate_query(signal)
+            .param("user_id", user_id)
+            .param("liked_rating", RECOMMENDATION_LIKED_RATING)
+            .param("author_ids", preferred_author_ids.to_vec())
+            .param("genres", preferred_genres.to_vec())
+            .param("limit", limit as i64);
+
+        let mut stream = match self.neo4j_client.execute(cypher).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                timer.error_with_message(&format!("Error querying recommendation candidates: {}", e));
+                return Err(e.into());
+            },
+        };
+
+        let mut candidates = Vec::new();
+        while let Some(row) = stream.next().await? {
+            candidates.push(RecommendationCandidate {
+                book_id: row.get("book_id")?,
+                work_id: row.get("work_id")?,
+                score: row.get("score")?,
+                evidence: row.get("evidence")?,
+            });
+        }
+
+        timer.log();
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    Database, Collection,
+};
+use neo4rs::{query, Graph};
+
+use crate::model::author_model::Author;
+use crate::model::book_model::Book;
+use crate::model::follow_model::{Follow, FOLLOW_COLLECTION};
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::model::metadata_model::{Metadata, MetadataDoc};
+use crate::model::review_model::Review;
+use crate::model::user_model::{User, UserRole};
+use crate::model::work_model::Work;
+use crate::repository::graph_sync_repository::enqueue;
+use crate::shared::constant::RECONCILE_REPAIR_CHUNK_SIZE;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+/// `(from, to)` ids of a relationship
+pub type EdgeKey = (String, String);
+
+
+#[async_trait]
+pub trait ReconciliationRepositoryInterface {
+    async fn for_each_reader(&self, visit: &mut (dyn FnMut(User) + Send)) -> Result<(), AppError>;
+    async fn for_each_author(&self, visit: &mut (dyn FnMut(Author) + Send)) -> Result<(), AppError>;
+    async fn find_genres(&self) -> Result<HashMap<String, String>, AppError>;
+    async fn for_each_book(&self, visit: &mut (dyn FnMut(Book) + Send)) -> Result<(), AppError>;
+    async fn find_books_by_ids(&self, book_ids: &[String]) -> Result<Vec<Book>, AppError>;
+    async fn for_each_work(&self, visit: &mut (dyn FnMut(Work) + Send)) -> Result<(), AppError>;
+    async fn for_each_review(&self, visit: &mut (dyn FnMut(Review) + Send)) -> Result<(), AppError>;
+    async fn for_each_follow(&self, visit: &mut (dyn FnMut(Follow) + Send)) -> Result<(), AppError>;
+    async fn graph_nodes(&self, cypher: &str) -> Result<HashMap<String, String>, AppError>;
+    async fn graph_edges(&self, cypher: &str) -> Result<HashMap<EdgeKey, String>, AppError>;
+    async fn enqueue(&self, ops: Vec<GraphSyncOp>) -> Result<usize, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct ReconciliationRepository {
+    pub user_collection: Collection<User>,
+    pub author_collection: Collection<Author>,
+    pub metadata_collection: Collection<MetadataDoc>,
+    pub book_collection: Collection<Book>,
+    pub work_collection: Collection<Work>,
+    pub review_collection: Collection<Review>,
+    pub follow_collection: Collection<Follow>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+    pub neo4j_client: Graph,
+}
+
+impl ReconciliationRepository {
+    pub fn new(mongo_database: Database, neo4j_client: Graph) -> Self {
+        ReconciliationRepository {
+            user_collection: mongo_database.collection::<User>("users"),
+            author_collection: mongo_database.collection::<Author>("authors"),
+            metadata_collection: mongo_database.collection::<MetadataDoc>("metadata"),
+            book_collection: mongo_database.collection::<Book>("books"),
+            work_collection: mongo_database.collection::<Work>("works"),
+            review_collection: mongo_database.collection::<Review>("reviews"),
+            follow_collection: mongo_database.collection::<Follow>(FOLLOW_COLLECTION),
+            outbox_collection: mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION),
+            neo4j_client,
+        }
+    }
+}
+
+
+#[async_trait]
+impl ReconciliationRepositoryInterface for ReconciliationRepository {
+    /// Streams the readers instead of loading the whole collection, as do the other `for_each_*`.
+    async fn for_each_reader(&self, visit: &mut (dyn FnMut(User) + Send)) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FOR EACH READER]");
+
+        let role = UserRole::Reader.kind();
+        let mut cursor = self.user_collection.find(doc! { "role": role }).await?;
+        while let Some(user) = cursor.try_next().await? {
+            visit(user);
+        }
+
+        timer.log();
+        Ok(())
+    }
+
+    async fn for_each_author(&self, visit: &mut (dyn FnMut(Author) + Send)) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FOR EACH AUTHOR]");
+
+        let mut cursor = self.author_collection.find(doc! {}).await?;
+        while let Some(author) = cursor.try_next().await? {
+            visit(author);
+        }
+
+        timer.log();
+        Ok(())
+    }
+
+    async fn find_genres(&self) -> Result<HashMap<String, String>, AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FIND GENRES]");
+
+        let docs: Vec<MetadataDoc> = self.metadata_collection
+            .find(doc! { "type": "genre" })
+            .await?
+            .try_collect()
+            .await?;
+
+        let genres = docs
+            .into_iter()
+            .filter_map(|d| match d.meta {
+                Metadata::Genre { name, description } => Some((name, description)),
+                _ => None,
+            })
+            .collect();
+
+        timer.log();
+        Ok(genres)
+    }
+
+    async fn for_each_book(&self, visit: &mut (dyn FnMut(Book) + Send)) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FOR EACH BOOK]");
+
+        let mut cursor = self.book_collection.find(doc! {}).await?;
+        while let Some(book) = cursor.try_next().await? {
+            visit(book);
+        }
+
+        timer.log();
+        Ok(())
+    }
+
+    async fn find_books_by_ids(&self, book_ids: &[String]) -> Result<Vec<Book>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [RECONCILIATION] [FIND BOOKS BY IDS] count: {:?} ",
+            book_ids.len()
+        ));
+
+        let ids: Vec<ObjectId> = book_ids.iter().filter_map(|id| ObjectId::parse_str(id).ok()).collect();
+        let books: Vec<Book> = self.book_collection
+            .find(doc! { "_id": { "$in": ids } })
+            .await?
+            .try_collect()
+            .await?;
+
+        timer.log();
+        Ok(books)
+    }
+
+    async fn for_each_work(&self, visit: &mut (dyn FnMut(Work) + Send)) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FOR EACH WORK]");
+
+        let mut cursor = self.work_collection.find(doc! {}).await?;
+        while let Some(work) = cursor.try_next().await? {
+            visit(work);
+        }
+
+        timer.log();
+        Ok(())
+    }
+
+    async fn for_each_review(&self, visit: &mut (dyn FnMut(Review) + Send)) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FOR EACH REVIEW]");
+
+        let mut cursor = self.review_collection.find(doc! {}).await?;
+        while let Some(review) = cursor.try_next().await? {
+            visit(review);
+        }
+
+        timer.log();
+        Ok(())
+    }
+
+    async fn for_each_follow(&self, visit: &mut (dyn FnMut(Follow) + Send)) -> Result<(), AppError> {
+        let timer = TimePrinter::with_message("[REPOSITORY] [RECONCILIATION] [FOR EACH FOLLOW]");
+
+        let mut cursor = self.follow_collection.find(doc! {}).await?;
+        while let Some(follow) = cursor.try_next().await? {
+            visit(follow);
+        }
+
+        timer.log();
+        Ok(())
+    }
+
+    /// Runs a query returning `key` and `value` columns.
+    async fn graph_nodes(&self, cypher: &str) -> Result<HashMap<String, String>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [RECONCILIATION] [GRAPH NODES] cypher: {:?} ",
+            cypher
+        ));
+
+        let mut stream = self.neo4j_client.execute(query(cypher)).await?;
+        let mut nodes = HashMap::new();
+        while let Some(row) = stream.next().await? {
+            nodes.insert(row.get::<String>("key")?, row.get::<String>("value")?);
+        }
+
+        timer.log();
+        Ok(nodes)
+    }
+
+    /// Runs a query returning `from`, `to` and `value` columns.
+    async fn graph_edges(&self, cypher: &str) -> Result<HashMap<EdgeKey, String>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [RECONCILIATION] [GRAPH EDGES] cypher: {:?} ",
+            cypher
+        ));
+
+        let mut stream = self.neo4j_client.execute(query(cypher)).await?;
+        let mut edges = HashMap::new();
+        while let Some(row) = stream.next().await? {
+            edges.insert((row.get::<String>("from")?, row.get::<String>("to")?), row.get::<String>("value")?);
+        }
+
+        timer.log();
+        Ok(edges)
+    }
+
+    async fn enqueue(&self, ops: Vec<GraphSyncOp>) -> Result<usize, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [RECONCILIATION] [ENQUEUE] count: {:?} ",
+            ops.len()
+        ));
+
+        if ops.is_empty() {
+            return Ok(0);
+        }
+
+        // Each chunk commits on its own, a large repair never holds one huge transaction open
+        for chunk in ops.chunks(RECONCILE_REPAIR_CHUNK_SIZE) {
+            let mut mongo_session = self.outbox_collection.client().start_session().await?;
+            mongo_session.start_transaction().await?;
+            if let Err(e) = enqueue(&self.outbox_collection, &mut mongo_session, chunk.to_vec()).await {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Failed to enqueue graph sync events: {:?}", e));
+                return Err(e);
+            }
+            mongo_session.commit_transaction().await?;
+        }
+
+        timer.log();
+        Ok(ops.len())
+    }
+}
@@ -0,0 +1,547 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson, Document},
+    Client, ClientSession, Database, Collection,
+};
+
+use crate::model::book_model::Book;
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::model::review_model::Review;
+use crate::model::user_model::User;
+use crate::repository::graph_sync_repository::enqueue;
+use crate::shared::logging::log::{self, TimePrinter};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginationRequest;
+use crate::shared::repository::repository_utils::{sort_document, with_search};
+
+
+/// Pipeline update moving a book's `rating` from the `removed` score to the `added` one:
+/// a new review only adds, a deleted review only removes and an edit does both.
+/// The average is recomputed from the updated sum and count in the same statement.
+fn rating_update(removed: Option<u8>, added: Option<u8>) -> Vec<Document> {
+    let count_delta = added.is_some() as i64 - removed.is_some() as i64;
+    let sum_delta = added.map_or(0.0, f64::from) - removed.map_or(0.0, f64::from);
+
+    let mut histogram: BTreeMap<u8, i64> = BTreeMap::new();
+    if let Some(score) = removed {
+        *histogram.entry(score).or_default() -= 1;
+    }
+    if let Some(score) = added {
+        *histogram.entry(score).or_default() += 1;
+    }
+
+    let mut fields = doc! {
+        "rating.count": { "$add": [{ "$ifNull": ["$rating.count", 0_i64] }, count_delta] },
+        "rating.sum": { "$add": [{ "$ifNull": ["$rating.sum", 0.0] }, sum_delta] },
+    };
+    for (score, delta) in histogram.into_iter().filter(|(_, delta)| *delta != 0) {
+        fields.insert(
+            format!("rating.histogram.{}", score),
+            doc! { "$add": [{ "$ifNull": [format!("$rating.histogram.{}", score), 0_i64] }, delta] },
+        );
+    }
+
+    vec![
+        doc! { "$set": fields },
+        doc! { "$set": { "rating.average": {
+            "$cond": [
+                { "$gt": ["$rating.count", 0] },
+                { "$divide": ["$rating.sum", "$rating.count"] },
+                0.0
+            ]
+        } } },
+    ]
+}
+
+fn stored_bucket(review: &Review) -> Result<u8, AppError> {
+    Review::score_bucket(review.score).ok_or_else(|| AppError::Validation(format!("Invalid review score: {}", review.score)))
+}
+
+/// Deletes the reviews written by the users and takes their scores out of the reviewed books' ratings,
+/// inside the caller's transaction.
+pub async fn delete_user_reviews(
+    review_collection: &Collection<Review>,
+    book_collection: &Collection<Book>,
+    session: &mut ClientSession,
+    user_ids: &[ObjectId],
+) -> Result<(), AppError> {
+    let reviews: Vec<Review> = review_collection
+        .find(doc! { "user.id": { "$in": user_ids } })
+        .session(&mut *session)
+        .await?
+        .stream(&mut *session)
+        .try_collect()
+        .await?;
+    if reviews.is_empty() {
+        return Ok(());
+    }
+
+    for review in &reviews {
+        // A legacy review with an out-of-range score has no bucket to take it out of, and must not block the deletion
+        let Some(bucket) = Review::score_bucket(review.score) else {
+            log::warning(&format!(
+                "[REPOSITORY] [REVIEW] [DELETE USER REVIEWS] Review {:?} has invalid score {}, book {} rating left as is",
+                review.id, review.score, review.book_id
+            ));
+            continue;
+        };
+        book_collection
+            .update_one(doc! { "_id": &review.book_id }, rating_update(Some(bucket), None))
+            .session(&mut *session)
+            .await?;
+    }
+    let review_ids: Vec<ObjectId> = reviews.iter().filter_map(|review| review.id).collect();
+    let book_ids: Vec<ObjectId> = reviews.iter().map(|review| review.book_id).collect();
+    book_collection
+        .update_many(doc! { "_id": { "$in": &book_ids } }, doc! { "$pull": { "reviews": { "$in": &review_ids } } })
+        .session(&mut *session)
+        .await?;
+    review_collection
+        .delete_many(doc! { "user.id": { "$in": user_ids } })
+        .session(&mut *session)
+        .await?;
+    Ok(())
+}
+
+
+#[async_trait]
+pub trait ReviewRepositoryInterface {
+    async fn insert(&self, review: Review) -> Result<String, AppError>;
+    async fn update(&self, review_id: &str, content: &str, score: f32) -> Result<bool, AppError>;
+    async fn delete(&self, review_id: &str) -> Result<bool, AppError>;
+    async fn find_by_id(&self, review_id: &str) -> Result<Option<Review>, AppError>;
+    async fn find_by_book_and_user(&self, book_id: &str, user_id: &str) -> Result<Option<Review>, AppError>;
+    async fn find_by_book(&self, book_id: &str, pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError>;
+    /// Reviews of any of the books, such as the editions of a work.
+    async fn find_by_books(&self, book_ids: &[ObjectId], pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError>;
+    async fn find_by_user(&self, user_id: &str, pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct ReviewRepository {
+    pub mongo_client: Client,
+    pub review_collection: Collection<Review>,
+    pub book_collection: Collection<Book>,
+    pub user_collection: Collection<User>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+}
+
+impl ReviewRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
+        let review_collection = mongo_database.collection::<Review>("reviews");
+        let book_collection = mongo_database.collection::<Book>("books");
+        let user_collection = mongo_database.collection::<User>("users");
+        let outbox_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION);
+        ReviewRepository {
+            mongo_client,
+            review_collection,
+            book_collection,
+            user_collection,
+            outbox_collection,
+        }
+    }
+
+    /// One page of the reviews matching `filter`, newest first unless another sort is requested,
+    /// with the total number of matches.
+    async fn find_many(&self, filter: Document, pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError> {
+        let filter = with_search(filter, pagination, &["content", "user.name"]);
+        let sort = sort_document(pagination, &["date_added", "score"], doc! { "date_added": -1 })?;
+
+        let total = self.review_collection.count_documents(filter.clone()).await?;
+        let result_find = self.review_collection
+            .find(filter)
+            .sort(sort)
+            .skip(pagination.skip())
+            .limit(pagination.page_size() as i64)
+            .await?;
+
+        Ok((result_find.try_collect().await?, total))
+    }
+}
+
+
+#[async_trait]
+impl ReviewRepositoryInterface for ReviewRepository {
+    async fn insert(&self, review: Review) -> Result<String, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [INSERT] data: {:?}",
+            review
+        ));
+
+        let bucket = stored_bucket(&review)?;
+        let book_id = review.book_id;
+        let user_id = review.user.id;
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_insert = async {
+            let result_insert = self.review_collection
+                .insert_one(&review)
+                .session(&mut mongo_session)
+                .await?;
+            let review_id = result_insert.inserted_id
+                .as_object_id()
+                .ok_or_else(|| AppError::Upstream(anyhow!("Inserted review id is not an ObjectId")))?;
+
+            let result_book = self.book_collection
+                .update_one(doc! { "_id": &book_id }, doc! { "$push": { "reviews": review_id } })
+                .session(&mut mongo_session)
+                .await?;
+            if result_book.matched_count == 0 {
+                return Err(AppError::not_found("Book", &book_id.to_hex()));
+            }
+            self.book_collection
+                .update_one(doc! { "_id": &book_id }, rating_update(None, Some(bucket)))
+                .session(&mut mongo_session)
+                .await?;
+
+            self.user_collection
+                .update_one(doc! { "_id": &user_id }, doc! { "$push": { "reviews": review_id } })
+                .session(&mut mongo_session)
+                .await?;
+
+            let op = GraphSyncOp::UpsertRating {
+                user_id: user_id.to_hex(),
+                book_id: book_id.to_hex(),
+                rating: f64::from(review.score),
+                ts: review.date_added.unwrap_or_else(Utc::now).timestamp_millis(),
+            };
+            enqueue(&self.outbox_collection, &mut mongo_session, vec![op]).await?;
+
+            Ok::<_, AppError>(review_id)
+        }.await;
+
+        let review_id = match result_insert {
+            Ok(review_id) => review_id,
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error adding review: {}", e));
+                return Err(e);
+            }
+        };
+
+        mongo_session.commit_transaction().await?;
+        timer.log();
+        Ok(review_id.to_hex())
+    }
+
+    async fn update(&self, review_id: &str, content: &str, score: f32) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [UPDATE] review_id: {:?} score: {:?}",
+            review_id, score
+        ));
+
+        let id = match ObjectId::parse_str(review_id) {
+            Ok(id) => id,
+            Err(_) => {
+                timer.error_with_message(&format!("Invalid review id: {}", review_id));
+                return Err(AppError::invalid_id("review", review_id));
+            }
+        };
+        let bucket = Review::score_bucket(score).ok_or_else(|| AppError::Validation(format!("Invalid review score: {}", score)))?;
+        let now = Utc::now();
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_update = async {
+            let existing = match self.review_collection
+                .find_one(doc! { "_id": &id })
+                .session(&mut mongo_session)
+                .await?
+            {
+                Some(existing) => existing,
+                None => return Ok(false),
+            };
+            let previous_bucket = stored_bucket(&existing)?;
+            let book_id = existing.book_id;
+
+            self.review_collection
+                .update_one(
+                    doc! { "_id": &id },
+                    doc! { "$set": { "content": content, "score": score, "updated_at": to_bson(&now)? } }
+                )
+                .session(&mut mongo_session)
+                .await?;
+            self.book_collection
+                .update_one(doc! { "_id": &book_id }, rating_update(Some(previous_bucket), Some(bucket)))
+                .session(&mut mongo_session)
+                .await?;
+
+            let op = GraphSyncOp::UpsertRating {
+                user_id: existing.user.id.to_hex(),
+                book_id: book_id.to_hex(),
+                rating: f64::from(score),
+                ts: now.timestamp_millis(),
+            };
+            enqueue(&self.outbox_collection, &mut mongo_session, vec![op]).await?;
+
+            Ok::<_, AppError>(true)
+        }.await;
+
+        match result_update {
+            Ok(true) => {},
+            Ok(false) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.warning_with_message(&format!("Review not found: {}", review_id));
+                return Ok(false);
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error updating review: {}", e));
+                return Err(e);
+            }
+        };
+
+        mongo_session.commit_transaction().await?;
+        timer.log();
+        Ok(true)
+    }
+
+    async fn delete(&self, review_id: &str) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [DELETE] review_id: {:?}",
+            review_id
+        ));
+
+        let id = match ObjectId::parse_str(review_id) {
+            Ok(id) => id,
+            Err(_) => {
+                timer.error_with_message(&format!("Invalid review id: {}", review_id));
+                return Err(AppError::invalid_id("review", review_id));
+            }
+        };
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_delete = async {
+            let existing = match self.review_collection
+                .find_one(doc! { "_id": &id })
+                .session(&mut mongo_session)
+                .await?
+            {
+                Some(existing) => existing,
+                None => return Ok(false),
+            };
+            let bucket = stored_bucket(&existing)?;
+            let book_id = existing.book_id;
+            let user_id = existing.user.id;
+
+            self.review_collection
+                .delete_one(doc! { "_id": &id })
+                .session(&mut mongo_session)
+                .await?;
+            self.book_collection
+                .update_one(doc! { "_id": &book_id }, doc! { "$pull": { "reviews": &id } })
+                .session(&mut mongo_session)
+                .await?;
+            self.book_collection
+                .update_one(doc! { "_id": &book_id }, rating_update(Some(bucket), None))
+                .session(&mut mongo_session)
+                .await?;
+            self.user_collection
+                .update_one(doc! { "_id": &user_id }, doc! { "$pull": { "reviews": &id } })
+                .session(&mut mongo_session)
+                .await?;
+
+            let op = GraphSyncOp::RemoveRating {
+                user_id: user_id.to_hex(),
+                book_id: book_id.to_hex(),
+            };
+            enqueue(&self.outbox_collection, &mut mongo_session, vec![op]).await?;
+
+            Ok::<_, AppError>(true)
+        }.await;
+
+        match result_delete {
+            Ok(true) => {},
+            Ok(false) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.warning_with_message(&format!("Review not found: {}", review_id));
+                return Ok(false);
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error deleting review: {}", e));
+                return Err(e);
+            }
+        };
+
+        mongo_session.commit_transaction().await?;
+        timer.log();
+        Ok(true)
+    }
+
+    async fn find_by_id(&self, review_id: &str) -> Result<Option<Review>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [FIND BY ID] review_id: {:?}",
+            review_id
+        ));
+
+        let id = ObjectId::parse_str(review_id);
+        match id {
+            Ok(id) => {
+                let result = self.review_collection.find_one(doc! { "_id": &id }).await;
+                match result {
+                    Ok(result) => {
+                        timer.log();
+                        Ok(result)
+                    },
+                    Err(e) => {
+                        timer.error_with_message(&format!("Error finding review: {}", e));
+                        Err(e.into())
+                    },
+                }
+            },
+            Err(_) => {
+                timer.error_with_message(&format!("Invalid review id: {}", review_id));
+                Err(AppError::invalid_id("review", review_id))
+            }
+        }
+    }
+
+    async fn find_by_book_and_user(&self, book_id: &str, user_id: &str) -> Result<Option<Review>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [FIND BY BOOK AND USER] book_id: {:?} user_id: {:?}",
+            book_id, user_id
+        ));
+
+        let book_oid = ObjectId::parse_str(book_id).map_err(|_| AppError::invalid_id("book", book_id))?;
+        let user_oid = ObjectId::parse_str(user_id).map_err(|_| AppError::invalid_id("user", user_id))?;
+
+        let result = self.review_collection
+            .find_one(doc! { "book_id": &book_oid, "user.id": &user_oid })
+            .await;
+        match result {
+            Ok(result) => {
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding review: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn find_by_book(&self, book_id: &str, pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [FIND BY BOOK] book_id: {:?} pagination: {:?}",
+            book_id, pagination
+        ));
+
+        let id = ObjectId::parse_str(book_id).map_err(|_| AppError::invalid_id("book", book_id))?;
+        match self.find_many(doc! { "book_id": &id }, pagination).await {
+            Ok(reviews) => {
+                timer.log();
+                Ok(reviews)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding reviews: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn find_by_books(&self, book_ids: &[ObjectId], pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [FIND BY BOOKS] books: {:?} pagination: {:?}",
+            book_ids.len(), pagination
+        ));
+
+        match self.find_many(doc! { "book_id": { "$in": book_ids } }, pagination).await {
+            Ok(reviews) => {
+                timer.log();
+                Ok(reviews)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding reviews: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn find_by_user(&self, user_id: &str, pagination: &PaginationRequest) -> Result<(Vec<Review>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [REVIEW] [FIND BY USER] user_id: {:?} pagination: {:?}",
+            user_id, pagination
+        ));
+
+        let id = ObjectId::parse_str(user_id).map_err(|_| AppError::invalid_id("user", user_id))?;
+        match self.find_many(doc! { "user.id": &id }, pagination).await {
+            Ok(reviews) => {
+                timer.log();
+                Ok(reviews)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding reviews: {}", e));
+                Err(e)
+            },
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_stage(removed: Option<u8>, added: Option<u8>) -> Document {
+        rating_update(removed, added)[0].get_document("$set").unwrap().clone()
+    }
+
+    fn delta(fields: &Document, field: &str) -> mongodb::bson::Bson {
+        fields.get_document(field).unwrap().get_array("$add").unwrap()[1].clone()
+    }
+
+    #[test]
+    fn new_review_adds_to_count_sum_and_its_bucket() {
+        let fields = set_stage(None, Some(4));
+
+        assert_eq!(delta(&fields, "rating.count"), mongodb::bson::Bson::Int64(1));
+        assert_eq!(delta(&fields, "rating.sum"), mongodb::bson::Bson::Double(4.0));
+        assert_eq!(delta(&fields, "rating.histogram.4"), mongodb::bson::Bson::Int64(1));
+    }
+
+    #[test]
+    fn deleted_review_removes_from_count_sum_and_its_bucket() {
+        let fields = set_stage(Some(2), None);
+
+        assert_eq!(delta(&fields, "rating.count"), mongodb::bson::Bson::Int64(-1));
+        assert_eq!(delta(&fields, "rating.sum"), mongodb::bson::Bson::Double(-2.0));
+        assert_eq!(delta(&fields, "rating.histogram.2"), mongodb::bson::Bson::Int64(-1));
+    }
+
+    #[test]
+    fn edited_review_moves_between_buckets_without_changing_the_count() {
+        let fields = set_stage(Some(2), Some(5));
+
+        assert_eq!(delta(&fields, "rating.count"), mongodb::bson::Bson::Int64(0));
+        assert_eq!(delta(&fields, "rating.sum"), mongodb::bson::Bson::Double(3.0));
+        assert_eq!(delta(&fields, "rating.histogram.2"), mongodb::bson::Bson::Int64(-1));
+        assert_eq!(delta(&fields, "rating.histogram.5"), mongodb::bson::Bson::Int64(1));
+    }
+
+    #[test]
+    fn unchanged_score_leaves_the_histogram_alone() {
+        let fields = set_stage(Some(3), Some(3));
+
+        assert!(!fields.contains_key("rating.histogram.3"));
+    }
+
+    #[test]
+    fn average_is_recomputed_after_the_totals() {
+        let stages = rating_update(None, Some(1));
+
+        assert_eq!(stages.len(), 2);
+        assert!(stages[1].get_document("$set").unwrap().contains_key("rating.average"));
+    }
+}
@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, to_bson, Bson, Document, Regex},
+    error::ErrorKind,
+    Database, Collection,
+};
+use serde::de::DeserializeOwned;
+
+use crate::model::author_model::Author;
+use crate::model::book_model::Book;
+use crate::model::metadata_model::MetadataDoc;
+use crate::model::search_model::{BookSearchFilter, SearchHit};
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+use crate::shared::repository::repository_utils::escape_regex;
+
+
+const MONGO_INDEX_NOT_FOUND: i32 = 27;
+
+/// Field searched by the `$regex` fallback, with its weight in the collection's text index.
+type WeightedField = (&'static str, i32);
+
+const BOOK_FIELDS: &[WeightedField] = &[("title", 10), ("subtitle", 5), ("description", 1)];
+const AUTHOR_FIELDS: &[WeightedField] = &[("name", 10), ("description", 1)];
+const METADATA_FIELDS: &[WeightedField] = &[("name", 10), ("description", 1)];
+
+
+#[async_trait]
+pub trait SearchRepositoryInterface {
+    async fn search_books(&self, text: &str, filter: &BookSearchFilter, limit: u64) -> Result<Vec<SearchHit<Book>>, AppError>;
+    async fn search_authors(&self, text: &str, limit: u64) -> Result<Vec<SearchHit<Author>>, AppError>;
+    async fn search_metadata(&self, metadata_type: &str, text: &str, limit: u64) -> Result<Vec<SearchHit<MetadataDoc>>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct SearchRepository {
+    pub book_collection: Collection<Book>,
+    pub author_collection: Collection<Author>,
+    pub metadata_collection: Collection<MetadataDoc>,
+}
+
+impl SearchRepository {
+    pub fn new(mongo_database: Database) -> Self {
+        SearchRepository {
+            book_collection: mongo_database.collection::<Book>("books"),
+            author_collection: mongo_database.collection::<Author>("authors"),
+            metadata_collection: mongo_database.collection::<MetadataDoc>("metadata"),
+        }
+    }
+}
+
+
+fn is_missing_text_index(error: &mongodb::error::Error) -> bool {
+    matches!(error.kind.as_ref(), ErrorKind::Command(e) if e.code == MONGO_INDEX_NOT_FOUND)
+}
+
+fn book_filter(filter: &BookSearchFilter) -> Result<Document, AppError> {
+    let mut document = doc! {};
+    if let Some(language) = &filter.language {
+        document.insert("languages", language);
+    }
+    if let Some(format) = &filter.format {
+        document.insert("format", to_bson(format)?);
+    }
+    if let Some(genre) = &filter.genre {
+        document.insert("genres.name", genre);
+    }
+    Ok(document)
+}
+
+/// Runs `$text` search over `collection`, best matches first.
+/// Without a text index it falls back to case-insensitive `$regex` matching, scored by field weight
+/// and by whether the text is the whole field, its beginning or only somewhere inside it.
+async fn search<T>(
+    collection: &Collection<T>,
+    text: &str,
+    filter: Document,
+    fields: &[WeightedField],
+    limit: u64,
+) -> Result<Vec<SearchHit<T>>, AppError>
+where
+    T: DeserializeOwned + Send + Sync,
+{
+    let mut text_match = filter.clone();
+    text_match.insert("$text", doc! { "$search": text });
+    let pipeline = vec![
+        doc! { "$match": text_match },
+        doc! { "$sort": { "score": { "$meta": "textScore" } } },
+        doc! { "$limit": limit as i64 },
+        doc! { "$project": { "_id": 0, "item": "$$ROOT", "score": { "$meta": "textScore" } } },
+    ];
+
+    match collection.aggregate(pipeline).with_type::<SearchHit<T>>().await {
+        Ok(cursor) => return Ok(cursor.try_collect().await?),
+        Err(e) if !is_missing_text_index(&e) => return Err(e.into()),
+        Err(_) => {},
+    }
+
+    let escaped = escape_regex(text);
+    let mut regex_match = filter;
+    let any_field: Vec<Bson> = fields
+        .iter()
+        .map(|(field, _)| Bson::Document(doc! { *field: Regex { pattern: escaped.clone(), options: "i".to_string() } }))
+        .collect();
+    regex_match.insert("$or", any_field);
+
+    let field_scores: Vec<Bson> = fields
+        .iter()
+        .map(|(field, weight)| {
+            let input = doc! { "$ifNull": [format!("${}", field), ""] };
+            let matches = |pattern: String| doc! { "$regexMatch": { "input": input.clone(), "regex": pattern, "options": "i" } };
+            Bson::Document(doc! { "$multiply": [*weight, { "$switch": {
+                "branches": [
+                    { "case": matches(format!("^{}$", escaped)), "then": 1.0 },
+                    { "case": matches(format!("^{}", escaped)), "then": 0.75 },
+                    { "case": matches(escaped.clone()), "then": 0.5 },
+                ],
+                "default": 0.0,
+            } }] })
+        })
+        .collect();
+
+    let pipeline = vec![
+        doc! { "$match": regex_match },
+        doc! { "$project": { "_id": 0, "item": "$$ROOT", "score": { "$max": field_scores } } },
+        doc! { "$sort": { "score": -1 } },
+        doc! { "$limit": limit as i64 },
+    ];
+    Ok(collection.aggregate(pipeline).with_type::<SearchHit<T>>().await?.try_collect().await?)
+}
+
+
+#[async_trait]
+impl SearchRepositoryInterface for SearchRepository {
+    async fn search_books(&self, text: &str, filter: &BookSearchFilter, limit: u64) -> Result<Vec<SearchHit<Book>>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [SEARCH] [BOOKS] text: {:?}, filter: {:?}, limit: {}",
+            text, filter, limit
+        ));
+
+        let result = search(&self.book_collection, text, book_filter(filter)?, BOOK_FIELDS, limit).await;
+        match result {
+            Ok(hits) => {
+                timer.log();
+                Ok(hits)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error searching books: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn search_authors(&self, text: &str, limit: u64) -> Result<Vec<SearchHit<Author>>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [SEARCH] [AUTHORS] text: {:?}, limit: {}",
+            text, limit
+        ));
+
+        let result = search(&self.author_collection, text, doc! {}, AUTHOR_FIELDS, limit).await;
+        match result {
+            Ok(hits) => {
+                timer.log();
+                Ok(hits)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error searching authors: {}", e));
+                Err(e)
+            },
+        }
+    }
+
+    async fn search_metadata(&self, metadata_type: &str, text: &str, limit: u64) -> Result<Vec<SearchHit<MetadataDoc>>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [SEARCH] [METADATA] type: {}, text: {:?}, limit: {}",
+            metadata_type, text, limit
+        ));
+
+        let result = search(&self.metadata_collection, text, doc! { "type": metadata_type }, METADATA_FIELDS, limit).await;
+        match result {
+            Ok(hits) => {
+                timer.log();
+                Ok(hits)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error searching {}s: {}", metadata_type, e));
+                Err(e)
+            },
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::book_model::BookFormat;
+    use crate::shared::database::mongodb_migration::{mongo_migrations, MigrationStep};
+
+    fn text_index_weights(collection: &str) -> Document {
+        mongo_migrations()
+            .into_iter()
+            .flat_map(|migration| (migration.steps)())
+            .find_map(|step| match step {
+                MigrationStep::CreateIndex { collection: c, weights: Some(weights), .. } if c == collection => Some(weights),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn fallback_weights(fields: &[WeightedField]) -> Document {
+        fields.iter().map(|(field, weight)| (field.to_string(), Bson::Int32(*weight))).collect()
+    }
+
+    #[test]
+    fn empty_filter_matches_every_book() {
+        assert_eq!(book_filter(&BookSearchFilter::default()).unwrap(), doc! {});
+    }
+
+    #[test]
+    fn filter_narrows_on_language_format_and_genre() {
+        let filter = BookSearchFilter {
+            language: Some("en".to_string()),
+            format: Some(BookFormat::EBook),
+            genre: Some("fantasy".to_string()),
+        };
+
+        assert_eq!(book_filter(&filter).unwrap(), doc! { "languages": "en", "format": "EBook", "genres.name": "fantasy" });
+    }
+
+    #[test]
+    fn regex_fallback_weighs_fields_like_the_text_indexes() {
+        assert_eq!(fallback_weights(BOOK_FIELDS), text_index_weights("books"));
+        assert_eq!(fallback_weights(AUTHOR_FIELDS), text_index_weights("authors"));
+        assert_eq!(fallback_weights(METADATA_FIELDS), text_index_weights("metadata"));
+    }
+}
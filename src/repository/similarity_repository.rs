@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use neo4rs::{query, Graph};
+
+use crate::model::similarity_model::{SimilarityKind, SimilarityScore};
+use crate::shared::constant::{SIMILAR_WEIGHT_GENRES, SIMILAR_WEIGHT_READERS};
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+
+
+#[async_trait]
+pub trait SimilarityRepositoryInterface {
+    async fn find_similar(&self, kind: SimilarityKind, id: &str, limit: u64) -> Result<Vec<SimilarityScore>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct SimilarityRepository {
+    pub neo4j_client: Graph,
+}
+
+impl SimilarityRepository {
+    pub fn new(neo4j_client: Graph) -> Self {
+        SimilarityRepository { neo4j_client }
+    }
+}
+
+
+/// Collects the `readers` and `genres` of the compared node, then the `other_readers` and
+/// `other_genres` of every node sharing at least one of them.
+fn neighbourhood_cypher(kind: SimilarityKind) -> &'static str {
+    match kind {
+        SimilarityKind::Book => {
+            "MATCH (subject:Book {book_id:$id})
+             OPTIONAL MATCH (subject)<-[:RATED|ADDED_TO_SHELF]-(r:Reader)
+             WITH subject, collect(DISTINCT r) AS readers
+             OPTIONAL MATCH (subject)-[:HAS_GENRE]->(g:Genre)
+             WITH subject, readers, collect(DISTINCT g) AS genres
+             UNWIND readers + genres AS link
+             MATCH (link)-[:RATED|ADDED_TO_SHELF|HAS_GENRE]-(other:Book)
+             WHERE other <> subject
+             WITH readers, genres, collect(DISTINCT other) AS others
+             UNWIND others AS other
+             OPTIONAL MATCH (other)<-[:RATED|ADDED_TO_SHELF]-(r:Reader)
+             WITH readers, genres, other, collect(DISTINCT r) AS other_readers
+             OPTIONAL MATCH (other)-[:HAS_GENRE]->(g:Genre)
+             WITH readers, genres, other, other_readers, collect(DISTINCT g) AS other_genres,
+                  other.book_id AS other_id"
+        },
+        SimilarityKind::Author => {
+            "MATCH (subject:Author {author_id:$id})
+             OPTIONAL MATCH (subject)-[:WROTE]->(:Book)<-[:RATED|ADDED_TO_SHELF]-(r:Reader)
+             WITH subject, collect(DISTINCT r) AS readers
+             OPTIONAL MATCH (subject)-[:WROTE]->(:Book)-[:HAS_GENRE]->(g:Genre)
+             WITH subject, readers, collect(DISTINCT g) AS genres
+             UNWIND readers + genres AS link
+             MATCH (link)-[:RATED|ADDED_TO_SHELF|HAS_GENRE]-(:Book)<-[:WROTE]-(other:Author)
+             WHERE other <> subject
+             WITH readers, genres, collect(DISTINCT other) AS others
+             UNWIND others AS other
+             OPTIONAL MATCH (other)-[:WROTE]->(:Book)<-[:RATED|ADDED_TO_SHELF]-(r:Reader)
+             WITH readers, genres, other, collect(DISTINCT r) AS other_readers
+             OPTIONAL MATCH (other)-[:WROTE]->(:Book)-[:HAS_GENRE]->(g:Genre)
+             WITH readers, genres, other, other_readers, collect(DISTINCT g) AS other_genres,
+                  other.author_id AS other_id"
+        },
+    }
+}
+
+/// Jaccard index of each side, `shared / (total - shared)`, weighted into a single score.
+const SCORE_CYPHER: &str = "
+     WITH other_id,
+          size([r IN other_readers WHERE r IN readers]) AS shared_readers,
+          size(readers) + size(other_readers) AS reader_total,
+          size([g IN other_genres WHERE g IN genres]) AS shared_genres,
+          size(genres) + size(other_genres) AS genre_total
+     WITH other_id, shared_readers, shared_genres,
+          $reader_weight * CASE WHEN reader_total = shared_readers THEN 0.0
+                                ELSE toFloat(shared_readers) / (reader_total - shared_readers) END
+        + $genre_weight * CASE WHEN genre_total = shared_genres THEN 0.0
+                               ELSE toFloat(shared_genres) / (genre_total - shared_genres) END AS score
+     WHERE score > 0
+     RETURN other_id, score, shared_readers, shared_genres
+     ORDER BY score DESC, other_id LIMIT $limit";
+
+
+#[async_trait]
+impl SimilarityRepositoryInterface for SimilarityRepository {
+    async fn find_similar(&self, kind: SimilarityKind, id: &str, limit: u64) -> Result<Vec<SimilarityScore>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [SIMILARITY] [FIND SIMILAR] kind: {}, id: {}, limit: {}",
+            kind.name(), id, limit
+        ));
+
+        let cypher = query(&format!("{}{}", neighbourhood_cypher(kind), SCORE_CYPHER))
+            .param("id", id)
+            .param("reader_weight", SIMILAR_WEIGHT_READERS)
+            .param("genre_weight", SIMILAR_WEIGHT_GENRES)
+            .param("limit", limit as i64);
+
+        let mut stream = match self.neo4j_client.execute(cypher).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                timer.error_with_message(&format!("Error querying similar {}s: {}", kind.name(), e));
+                return Err(e.into());
+            },
+        };
+
+        let mut similar = Vec::new();
+        while let Some(row) = stream.next().await? {
+            similar.push(SimilarityScore {
+                id: row.get("other_id")?,
+                score: row.get("score")?,
+                shared_readers: row.get("shared_readers")?,
+                shared_genres: row.get("shared_genres")?,
+            });
+        }
+
+        timer.log();
+        Ok(similar)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_weights_keep_the_score_within_one() {
+        assert!((SIMILAR_WEIGHT_READERS + SIMILAR_WEIGHT_GENRES - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn neighbourhoods_end_with_the_columns_the_score_reads() {
+        for kind in [SimilarityKind::Book, SimilarityKind::Author] {
+            let cypher = neighbourhood_cypher(kind);
+            let last_with = cypher.rsplit("WITH ").next().unwrap();
+
+            for column in ["readers", "genres", "other_readers", "other_genres", "AS other_id"] {
+                assert!(last_with.contains(column), "{} neighbourhood lacks {}", kind.name(), column);
+            }
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, to_bson},
+    Client, ClientSession, Database, Collection,
+};
+
+use crate::model::book_model::Book;
+use crate::model::graph_sync_model::{GraphSyncEvent, GraphSyncOp, GRAPH_SYNC_OUTBOX_COLLECTION};
+use crate::model::work_model::Work;
+use crate::repository::graph_sync_repository::enqueue;
+use crate::shared::logging::log::TimePrinter;
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginationRequest;
+use crate::shared::repository::repository_utils::{sort_document, with_search};
+
+
+#[async_trait]
+pub trait WorkRepositoryInterface {
+    /// Inserts the work and makes the given books its editions.
+    async fn insert(&self, work: Work, edition_ids: Vec<ObjectId>) -> Result<String, AppError>;
+    async fn update(&self, work: Work) -> Result<bool, AppError>;
+    /// Deletes the work, its editions stay in the catalog as standalone books.
+    async fn delete(&self, work_id: &str) -> Result<bool, AppError>;
+    async fn find_by_id(&self, work_id: &str) -> Result<Option<Work>, AppError>;
+    async fn find_all(&self, pagination: &PaginationRequest) -> Result<(Vec<Work>, u64), AppError>;
+    /// Editions of the work, oldest publication first.
+    async fn find_editions(&self, work_id: &str) -> Result<Vec<Book>, AppError>;
+    /// Makes the book an edition of the work. False when the book does not exist or is already an edition,
+    /// `NotFound` when the work does not.
+    async fn attach_edition(&self, work_id: &str, book_id: &str) -> Result<bool, AppError>;
+    /// False when the book is not an edition of the work.
+    async fn detach_edition(&self, work_id: &str, book_id: &str) -> Result<bool, AppError>;
+}
+
+
+const SEARCH_FIELDS: &[&str] = &["title", "description"];
+const SORT_FIELDS: &[&str] = &["title", "created_at", "updated_at"];
+
+
+#[derive(Clone)]
+pub struct WorkRepository {
+    pub mongo_client: Client,
+    pub work_collection: Collection<Work>,
+    pub book_collection: Collection<Book>,
+    pub outbox_collection: Collection<GraphSyncEvent>,
+}
+
+impl WorkRepository {
+    pub fn new(mongo_client: Client, mongo_database: Database) -> Self {
+        let work_collection = mongo_database.collection::<Work>("works");
+        let book_collection = mongo_database.collection::<Book>("books");
+        let outbox_collection = mongo_database.collection::<GraphSyncEvent>(GRAPH_SYNC_OUTBOX_COLLECTION);
+        WorkRepository {
+            mongo_client,
+            work_collection,
+            book_collection,
+            outbox_collection,
+        }
+    }
+
+    /// Records the graph changes mirroring this write in the outbox, inside the caller's transaction.
+    async fn record_graph_sync(&self, session: &mut ClientSession, ops: Vec<GraphSyncOp>) -> Result<(), AppError> {
+        enqueue(&self.outbox_collection, session, ops).await
+    }
+
+    async fn insert_in_session(&self, session: &mut ClientSession, work: &Work, edition_ids: &[ObjectId]) -> Result<ObjectId, AppError> {
+        let work_id = self.work_collection
+            .insert_one(work)
+            .session(&mut *session)
+            .await?
+            .inserted_id
+            .as_object_id()
+            .ok_or_else(|| AppError::Upstream(anyhow!("Inserted work id is not an ObjectId")))?;
+
+        let mut ops = vec![GraphSyncOp::UpsertWork { work_id: work_id.to_hex(), title: work.title.clone() }];
+        if !edition_ids.is_empty() {
+            let result_update = self.book_collection
+                .update_many(
+                    doc! { "_id": { "$in": edition_ids }, "work_id": { "$exists": false } },
+                    doc! { "$set": { "work_id": work_id } },
+                )
+                .session(&mut *session)
+                .await?;
+            if result_update.matched_count < edition_ids.len() as u64 {
+                return Err(AppError::Conflict("An edition was removed or claimed by another work meanwhile".to_string()));
+            }
+            ops.extend(edition_ids.iter().map(|book_id| GraphSyncOp::LinkEdition {
+                book_id: book_id.to_hex(),
+                work_id: work_id.to_hex(),
+            }));
+        }
+        self.record_graph_sync(session, ops).await?;
+        Ok(work_id)
+    }
+
+    async fn delete_in_session(&self, session: &mut ClientSession, id: &ObjectId) -> Result<bool, AppError> {
+        let result_delete = self.work_collection
+            .delete_one(doc! { "_id": id })
+            .session(&mut *session)
+            .await?;
+        if result_delete.deleted_count == 0 {
+            return Ok(false);
+        }
+        self.book_collection
+            .update_many(doc! { "work_id": id }, doc! { "$unset": { "work_id": "" } })
+            .session(&mut *session)
+            .await?;
+
+        // Deleting the node drops its `EDITION_OF` relationships with it
+        self.record_graph_sync(session, vec![GraphSyncOp::DeleteWorks { work_ids: vec![id.to_hex()] }]).await?;
+        Ok(true)
+    }
+
+    /// Claims the book for the work. Touching the work first fails the transaction when it was
+    /// deleted meanwhile, and the filter leaves a book already claimed by any work untouched.
+    async fn attach_edition_in_session(&self, session: &mut ClientSession, work_id: &ObjectId, book_id: &ObjectId) -> Result<bool, AppError> {
+        let result_update = self.work_collection
+            .update_one(doc! { "_id": work_id }, doc! { "$set": { "updated_at": to_bson(&Utc::now())? } })
+            .session(&mut *session)
+            .await?;
+        if result_update.matched_count == 0 {
+            return Err(AppError::not_found("Work", &work_id.to_hex()));
+        }
+
+        let result_update = self.book_collection
+            .update_one(
+                doc! { "_id": book_id, "work_id": { "$exists": false } },
+                doc! { "$set": { "work_id": work_id } },
+            )
+            .session(&mut *session)
+            .await?;
+        if result_update.matched_count == 0 {
+            return Ok(false);
+        }
+
+        let op = GraphSyncOp::LinkEdition { book_id: book_id.to_hex(), work_id: work_id.to_hex() };
+        self.record_graph_sync(session, vec![op]).await?;
+        Ok(true)
+    }
+
+    async fn detach_edition_in_session(&self, session: &mut ClientSession, work_id: &ObjectId, book_id: &ObjectId) -> Result<bool, AppError> {
+        let result_update = self.book_collection
+            .update_one(doc! { "_id": book_id, "work_id": work_id }, doc! { "$unset": { "work_id": "" } })
+            .session(&mut *session)
+            .await?;
+        if result_update.matched_count == 0 {
+            return Ok(false);
+        }
+        self.work_collection
+            .update_one(doc! { "_id": work_id }, doc! { "$set": { "updated_at": to_bson(&Utc::now())? } })
+            .session(&mut *session)
+            .await?;
+
+        self.record_graph_sync(session, vec![GraphSyncOp::UnlinkEdition { book_id: book_id.to_hex() }]).await?;
+        Ok(true)
+    }
+}
+
+
+#[async_trait]
+impl WorkRepositoryInterface for WorkRepository {
+    async fn insert(&self, work: Work, edition_ids: Vec<ObjectId>) -> Result<String, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [INSERT] data: {:?}, editions: {:?}",
+            work, edition_ids
+        ));
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.insert_in_session(&mut mongo_session, &work, &edition_ids).await {
+            Ok(work_id) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(work_id.to_hex())
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error adding work: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn update(&self, work: Work) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [UPDATE] data: {:?}",
+            work
+        ));
+
+        let Some(id) = work.id else {
+            timer.error_with_message("Work without id");
+            return Err(AppError::Validation("Work id is required for update".to_string()));
+        };
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        let result_update = async {
+            let result_update = self.work_collection
+                .update_one(
+                    doc! { "_id": &id },
+                    doc! { "$set": {
+                        "title": &work.title,
+                        "description": &work.description,
+                        "original_language": &work.original_language,
+                        "updated_at": to_bson(&work.updated_at)?,
+                    } },
+                )
+                .session(&mut mongo_session)
+                .await?;
+            if result_update.matched_count > 0 {
+                let op = GraphSyncOp::UpsertWork { work_id: id.to_hex(), title: work.title.clone() };
+                self.record_graph_sync(&mut mongo_session, vec![op]).await?;
+            }
+            Ok::<_, AppError>(result_update.matched_count > 0)
+        }.await;
+
+        match result_update {
+            Ok(updated) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(updated)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error updating work: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete(&self, work_id: &str) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [DELETE] work_id: {:?}",
+            work_id
+        ));
+
+        let id = ObjectId::parse_str(work_id).map_err(|_| AppError::invalid_id("work", work_id))?;
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.delete_in_session(&mut mongo_session, &id).await {
+            Ok(deleted) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(deleted)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error deleting work: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn find_by_id(&self, work_id: &str) -> Result<Option<Work>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [FIND BY ID] work_id: {:?}",
+            work_id
+        ));
+
+        let id = ObjectId::parse_str(work_id).map_err(|_| AppError::invalid_id("work", work_id))?;
+        match self.work_collection.find_one(doc! { "_id": &id }).await {
+            Ok(result) => {
+                timer.log();
+                Ok(result)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding work: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn find_all(&self, pagination: &PaginationRequest) -> Result<(Vec<Work>, u64), AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [FIND ALL] pagination: {:?}",
+            pagination
+        ));
+
+        let filter = with_search(doc! {}, pagination, SEARCH_FIELDS);
+        let sort = sort_document(pagination, SORT_FIELDS, doc! { "title": 1 })?;
+
+        let total = self.work_collection.count_documents(filter.clone()).await?;
+        let result_find = self.work_collection
+            .find(filter)
+            .sort(sort)
+            .skip(pagination.skip())
+            .limit(pagination.page_size() as i64)
+            .await;
+
+        match result_find {
+            Ok(result_find) => {
+                timer.log();
+                Ok((result_find.try_collect().await?, total))
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding works: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn find_editions(&self, work_id: &str) -> Result<Vec<Book>, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [FIND EDITIONS] work_id: {:?}",
+            work_id
+        ));
+
+        let id = ObjectId::parse_str(work_id).map_err(|_| AppError::invalid_id("work", work_id))?;
+        let result_find = self.book_collection
+            .find(doc! { "work_id": &id })
+            .sort(doc! { "published_date": 1, "_id": 1 })
+            .await;
+
+        match result_find {
+            Ok(result_find) => {
+                timer.log();
+                Ok(result_find.try_collect().await?)
+            },
+            Err(e) => {
+                timer.error_with_message(&format!("Error finding editions: {}", e));
+                Err(e.into())
+            },
+        }
+    }
+
+    async fn attach_edition(&self, work_id: &str, book_id: &str) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [ATTACH EDITION] work_id: {:?} book_id: {:?}",
+            work_id, book_id
+        ));
+
+        let work_oid = ObjectId::parse_str(work_id).map_err(|_| AppError::invalid_id("work", work_id))?;
+        let book_oid = ObjectId::parse_str(book_id).map_err(|_| AppError::invalid_id("book", book_id))?;
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.attach_edition_in_session(&mut mongo_session, &work_oid, &book_oid).await {
+            Ok(true) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(true)
+            },
+            Ok(false) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.warning_with_message("Book missing or already an edition");
+                Ok(false)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error attaching edition: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    async fn detach_edition(&self, work_id: &str, book_id: &str) -> Result<bool, AppError> {
+        let timer = TimePrinter::with_message(&format!(
+            "[REPOSITORY] [WORK] [DETACH EDITION] work_id: {:?} book_id: {:?}",
+            work_id, book_id
+        ));
+
+        let work_oid = ObjectId::parse_str(work_id).map_err(|_| AppError::invalid_id("work", work_id))?;
+        let book_oid = ObjectId::parse_str(book_id).map_err(|_| AppError::invalid_id("book", book_id))?;
+
+        let mut mongo_session = self.mongo_client.start_session().await?;
+        mongo_session.start_transaction().await?;
+
+        match self.detach_edition_in_session(&mut mongo_session, &work_oid, &book_oid).await {
+            Ok(detached) => {
+                mongo_session.commit_transaction().await?;
+                timer.log();
+                Ok(detached)
+            },
+            Err(e) => {
+                let _ = mongo_session.abort_transaction().await;
+                timer.error_with_message(&format!("Error detaching edition: {}", e));
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::auth_controller::routes as auth_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(auth_routes())
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::author_controller::routes as author_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(author_routes())
+}
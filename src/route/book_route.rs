@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::book_controller::routes as book_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(book_routes())
+}
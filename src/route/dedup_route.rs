@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::dedup_controller::routes as dedup_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(dedup_routes())
+}
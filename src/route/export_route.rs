@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::export_controller::routes as export_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(export_routes())
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::import_controller::routes as import_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(import_routes())
+}
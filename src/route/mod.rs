@@ -5,6 +5,18 @@ mod genre_route;
 mod language_route;
 mod publisher_route;
 mod source_route;
+mod auth_route;
+mod user_route;
+mod book_route;
+mod author_route;
+mod review_route;
+mod shelf_route;
+mod recommendation_route;
+mod search_route;
+mod import_route;
+mod export_route;
+mod dedup_route;
+mod work_route;
 
 
 
@@ -14,5 +26,17 @@ pub fn routes() -> Router<AppState> {
         .nest("/language", language_route::routes())
         .nest("/publisher", publisher_route::routes())
         .nest("/source", source_route::routes())
+        .nest("/auth", auth_route::routes())
+        .nest("/user", user_route::routes())
+        .nest("/book", book_route::routes())
+        .nest("/work", work_route::routes())
+        .nest("/author", author_route::routes())
+        .nest("/review", review_route::routes())
+        .nest("/shelf", shelf_route::routes())
+        .nest("/recommendation", recommendation_route::routes())
+        .nest("/search", search_route::routes())
+        .nest("/import", import_route::routes())
+        .nest("/export", export_route::routes())
+        .nest("/dedup", dedup_route::routes())
 }
 
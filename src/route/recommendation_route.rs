@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::recommendation_controller::routes as recommendation_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(recommendation_routes())
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::review_controller::routes as review_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(review_routes())
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::search_controller::routes as search_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(search_routes())
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::shelf_controller::routes as shelf_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(shelf_routes())
+}
@@ -0,0 +1,8 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::user_controller::routes as user_routes;
+use crate::controller::follow_controller::routes as follow_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(user_routes()).merge(follow_routes())
+}
@@ -0,0 +1,7 @@
+use axum::Router;
+use crate::shared::state::AppState;
+use crate::controller::work_controller::routes as work_routes;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().merge(work_routes())
+}
@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+
+use crate::command::auth_command::LoginCommand;
+use crate::dto::auth_dto::TokenResponse;
+use crate::service::credential_service::{CredentialService, CredentialServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::security::jwt::JwtKeys;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait AuthServiceInterface {
+    async fn login(&self, cmd: LoginCommand) -> Result<TokenResponse, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct AuthService {
+    credential_service: CredentialService,
+    jwt_keys: JwtKeys,
+}
+
+impl From<&AppState> for AuthService {
+    fn from(app_state: &AppState) -> Self {
+        Self::new(
+            CredentialService::from(app_state),
+            app_state.jwt_keys.clone(),
+        )
+    }
+}
+
+impl AuthService {
+    pub fn new(credential_service: CredentialService, jwt_keys: JwtKeys) -> Self {
+        Self { credential_service, jwt_keys }
+    }
+}
+
+#[async_trait]
+impl AuthServiceInterface for AuthService {
+    async fn login(&self, cmd: LoginCommand) -> Result<TokenResponse, AppError> {
+        let user = match self.credential_service.verify_credentials(&cmd.username, &cmd.password).await? {
+            Some(user) => user,
+            None => return Err(AppError::Unauthorized("Invalid username or password".to_string())),
+        };
+
+        let claims = self.jwt_keys.claims_for(&user)?;
+        let token = self.jwt_keys.sign(&claims)?;
+
+        Ok(TokenResponse::bearer(token, self.jwt_keys.expires_in_seconds()))
+    }
+}
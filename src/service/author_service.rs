@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::command::author_command::{
+    AuthorBooksCommand, AuthorCreateCommand, AuthorDeleteCommand, AuthorExternalIdAttachCommand, AuthorExternalIdDetachCommand,
+    AuthorExternalIdGetCommand, AuthorGetCommand, AuthorListCommand, AuthorUpdateCommand,
+};
+use crate::dto::author_dto::{AuthorBookResponse, AuthorResponse};
+use crate::model::author_model::Author;
+use crate::repository::author_repository::{AuthorRepository, AuthorRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait AuthorServiceInterface {
+    async fn get(&self, cmd: AuthorGetCommand) -> Result<AuthorResponse, AppError>;
+    async fn create(&self, cmd: AuthorCreateCommand) -> Result<AuthorResponse, AppError>;
+    async fn update(&self, cmd: AuthorUpdateCommand) -> Result<AuthorResponse, AppError>;
+    async fn delete(&self, cmd: AuthorDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: AuthorListCommand) -> Result<PaginatedResponse<AuthorResponse>, AppError>;
+    async fn books(&self, cmd: AuthorBooksCommand) -> Result<Vec<AuthorBookResponse>, AppError>;
+    async fn get_by_external_id(&self, cmd: AuthorExternalIdGetCommand) -> Result<AuthorResponse, AppError>;
+    async fn attach_external_id(&self, cmd: AuthorExternalIdAttachCommand) -> Result<AuthorResponse, AppError>;
+    async fn detach_external_id(&self, cmd: AuthorExternalIdDetachCommand) -> Result<AuthorResponse, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct AuthorService {
+    author_repo: AuthorRepository,
+}
+
+impl From<&AppState> for AuthorService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            AuthorRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            )
+        )
+    }
+}
+
+impl AuthorService {
+    pub fn new(author_repo: AuthorRepository) -> Self {
+        Self { author_repo }
+    }
+}
+
+#[async_trait]
+impl AuthorServiceInterface for AuthorService {
+    async fn get(&self, cmd: AuthorGetCommand) -> Result<AuthorResponse, AppError> {
+        let author = self.author_repo.find_by_id(&cmd.id).await?;
+        author.map(AuthorResponse::from).ok_or_else(|| AppError::not_found("Author", &cmd.id))
+    }
+
+    async fn create(&self, cmd: AuthorCreateCommand) -> Result<AuthorResponse, AppError> {
+        let now = Utc::now();
+        let author = Author {
+            id: None,
+            name: cmd.name,
+            image_url: cmd.image_url,
+            description: cmd.description,
+            books: vec![],
+            external_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let author_id = self.author_repo.insert(author).await?;
+        let author = self.author_repo.find_by_id(&author_id).await?;
+        author.map(AuthorResponse::from).ok_or_else(|| AppError::not_found("Author", &author_id))
+    }
+
+    async fn update(&self, cmd: AuthorUpdateCommand) -> Result<AuthorResponse, AppError> {
+        if self.author_repo.find_by_id(&cmd.id).await?.is_none() {
+            return Err(AppError::not_found("Author", &cmd.id));
+        }
+
+        if let Some(description) = &cmd.description {
+            self.author_repo.update_description(&cmd.id, description).await?;
+        }
+        if let Some(image_url) = &cmd.image_url {
+            self.author_repo.update_image_url(&cmd.id, image_url).await?;
+        }
+
+        let author = self.author_repo.find_by_id(&cmd.id).await?;
+        author.map(AuthorResponse::from).ok_or_else(|| AppError::not_found("Author", &cmd.id))
+    }
+
+    async fn delete(&self, cmd: AuthorDeleteCommand) -> Result<(), AppError> {
+        if !self.author_repo.delete(&cmd.id).await? {
+            return Err(AppError::not_found("Author", &cmd.id));
+        }
+        Ok(())
+    }
+
+    async fn list(&self, cmd: AuthorListCommand) -> Result<PaginatedResponse<AuthorResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (authors, total) = self.author_repo.find_all(&pagination).await?;
+        Ok(PaginatedResponse::new(authors.into_iter().map(AuthorResponse::from).collect(), total, &pagination))
+    }
+
+    async fn books(&self, cmd: AuthorBooksCommand) -> Result<Vec<AuthorBookResponse>, AppError> {
+        let author = self.author_repo.find_by_id(&cmd.id).await?;
+        author
+            .map(|author| author.books.into_iter().map(AuthorBookResponse::from).collect())
+            .ok_or_else(|| AppError::not_found("Author", &cmd.id))
+    }
+
+    async fn get_by_external_id(&self, cmd: AuthorExternalIdGetCommand) -> Result<AuthorResponse, AppError> {
+        let author = self.author_repo.find_by_external_id(cmd.provider, &cmd.external_id).await?;
+        author.map(AuthorResponse::from)
+            .ok_or_else(|| AppError::not_found("Author", &format!("{}:{}", cmd.provider.field(), cmd.external_id)))
+    }
+
+    /// An id already attached to another author is a conflict: the two records have to be merged first.
+    async fn attach_external_id(&self, cmd: AuthorExternalIdAttachCommand) -> Result<AuthorResponse, AppError> {
+        let external_id = cmd.external_id.trim();
+        if let Some(other) = self.author_repo.find_by_external_id(cmd.provider, external_id).await? {
+            let other_id = other.id.map(|id| id.to_hex()).unwrap_or_default();
+            if other_id != cmd.id {
+                return Err(AppError::Conflict(format!(
+                    "{} id {} is already attached to author {}", cmd.provider.field(), external_id, other_id
+                )));
+            }
+        }
+
+        if !self.author_repo.set_external_id(&cmd.id, cmd.provider, Some(external_id)).await? {
+            return Err(AppError::not_found("Author", &cmd.id));
+        }
+        self.get(AuthorGetCommand { id: cmd.id }).await
+    }
+
+    async fn detach_external_id(&self, cmd: AuthorExternalIdDetachCommand) -> Result<AuthorResponse, AppError> {
+        if !self.author_repo.set_external_id(&cmd.id, cmd.provider, None).await? {
+            return Err(AppError::not_found("Author", &cmd.id));
+        }
+        self.get(AuthorGetCommand { id: cmd.id }).await
+    }
+}
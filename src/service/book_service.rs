@@ -0,0 +1,267 @@
+use async_trait::async_trait;
+use mongodb::bson::oid::ObjectId;
+
+use crate::command::book_command::{
+    BookCreateCommand, BookDeleteCommand, BookExternalIdAttachCommand, BookExternalIdDetachCommand, BookExternalIdGetCommand,
+    BookGetCommand, BookListCommand, BookMediaCommand, BookUpdateCommand,
+};
+use crate::dto::book_dto::{BookCatalogResponse, BookResponse};
+use crate::model::author_model::AuthorEmbed;
+use crate::model::book_model::{Book, BookFilter, BookImageSource, BookPreviewSource, BookRating};
+use crate::model::genre_model::GenreEmbed;
+use crate::model::metadata_model::{Metadata, MetadataKey};
+use crate::model::publisher_model::PublisherEmbed;
+use crate::model::source_model::SourceEmbed;
+use crate::repository::author_repository::{AuthorRepository, AuthorRepositoryInterface};
+use crate::repository::book_repository::{BookRepository, BookRepositoryInterface};
+use crate::repository::metadata_repository::{MetadataRepository, MetadataRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait BookServiceInterface {
+    async fn get(&self, cmd: BookGetCommand) -> Result<BookResponse, AppError>;
+    async fn create(&self, cmd: BookCreateCommand) -> Result<BookResponse, AppError>;
+    async fn update(&self, cmd: BookUpdateCommand) -> Result<BookResponse, AppError>;
+    async fn delete(&self, cmd: BookDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: BookListCommand) -> Result<BookCatalogResponse, AppError>;
+    async fn get_by_external_id(&self, cmd: BookExternalIdGetCommand) -> Result<BookResponse, AppError>;
+    async fn attach_external_id(&self, cmd: BookExternalIdAttachCommand) -> Result<BookResponse, AppError>;
+    async fn detach_external_id(&self, cmd: BookExternalIdDetachCommand) -> Result<BookResponse, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct BookService {
+    book_repo: BookRepository,
+    author_repo: AuthorRepository,
+    metadata_repo: MetadataRepository,
+}
+
+impl From<&AppState> for BookService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            BookRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            AuthorRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            MetadataRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+        )
+    }
+}
+
+/// Embeds resolved from the ids and names given by the client.
+struct BookLinks {
+    authors: Vec<AuthorEmbed>,
+    genres: Vec<GenreEmbed>,
+    publishers: Vec<PublisherEmbed>,
+}
+
+impl BookService {
+    pub fn new(book_repo: BookRepository, author_repo: AuthorRepository, metadata_repo: MetadataRepository) -> Self {
+        Self { book_repo, author_repo, metadata_repo }
+    }
+
+    /// Resolves author ids, genre names and publisher names against the stored records,
+    /// so a book can only reference authors and metadata that exist.
+    async fn resolve_links(
+        &self,
+        author_ids: &[String],
+        genres: &[String],
+        publishers: &[String],
+    ) -> Result<BookLinks, AppError> {
+        let authors = self.author_repo
+            .find_by_ids(author_ids.iter().map(String::as_str).collect())
+            .await?;
+        let found: Vec<String> = authors.iter().filter_map(|a| a.id.map(|id| id.to_hex())).collect();
+        if let Some(missing) = author_ids.iter().find(|id| !found.contains(id)) {
+            return Err(AppError::Validation(format!("Unknown author: {}", missing)));
+        }
+
+        let mut genre_embeds = Vec::with_capacity(genres.len());
+        for name in genres {
+            match self.metadata_repo.find_by_key(MetadataKey::Genre { name: name.clone() }).await? {
+                Some(Metadata::Genre { name, .. }) => genre_embeds.push(GenreEmbed { name }),
+                _ => return Err(AppError::Validation(format!("Unknown genre: {}", name))),
+            }
+        }
+
+        let mut publisher_embeds = Vec::with_capacity(publishers.len());
+        for name in publishers {
+            match self.metadata_repo.find_by_key(MetadataKey::Publisher { name: name.clone() }).await? {
+                Some(Metadata::Publisher { name, .. }) => publisher_embeds.push(PublisherEmbed { name }),
+                _ => return Err(AppError::Validation(format!("Unknown publisher: {}", name))),
+            }
+        }
+
+        Ok(BookLinks {
+            authors: authors.iter().map(AuthorEmbed::from).collect(),
+            genres: genre_embeds,
+            publishers: publisher_embeds,
+        })
+    }
+}
+
+fn images_from(images: Vec<BookMediaCommand>) -> Vec<BookImageSource> {
+    images
+        .into_iter()
+        .map(|image| BookImageSource { url: image.url, source: SourceEmbed { name: image.source } })
+        .collect()
+}
+
+fn previews_from(previews: Vec<BookMediaCommand>) -> Vec<BookPreviewSource> {
+    previews
+        .into_iter()
+        .map(|preview| BookPreviewSource { url: preview.url, source: SourceEmbed { name: preview.source } })
+        .collect()
+}
+
+#[async_trait]
+impl BookServiceInterface for BookService {
+    async fn get(&self, cmd: BookGetCommand) -> Result<BookResponse, AppError> {
+        let book = self.book_repo.find_by_id(&cmd.id).await?;
+        book.map(BookResponse::from).ok_or_else(|| AppError::not_found("Book", &cmd.id))
+    }
+
+    async fn create(&self, cmd: BookCreateCommand) -> Result<BookResponse, AppError> {
+        let links = self.resolve_links(&cmd.author_ids, &cmd.genres, &cmd.publishers).await?;
+
+        let book = Book {
+            id: None,
+            isbn: cmd.isbn,
+            isbn13: cmd.isbn13,
+            title: cmd.title,
+            subtitle: cmd.subtitle,
+            description: cmd.description,
+            num_pages: cmd.num_pages,
+            published_date: cmd.published_date,
+            format: cmd.format,
+            images: images_from(cmd.images),
+            preview: previews_from(cmd.preview),
+            genres: links.genres,
+            authors: links.authors,
+            publishers: links.publishers,
+            languages: cmd.languages,
+            reviews: vec![],
+            rating: BookRating::default(),
+            external_id: None,
+            work_id: None,
+        };
+
+        let book_id = self.book_repo.insert(book).await?;
+        let book = self.book_repo.find_by_id(&book_id).await?;
+        book.map(BookResponse::from).ok_or_else(|| AppError::not_found("Book", &book_id))
+    }
+
+    async fn update(&self, cmd: BookUpdateCommand) -> Result<BookResponse, AppError> {
+        let existing = match self.book_repo.find_by_id(&cmd.id).await? {
+            Some(book) => book,
+            None => return Err(AppError::not_found("Book", &cmd.id)),
+        };
+
+        let links = self.resolve_links(&cmd.author_ids, &cmd.genres, &cmd.publishers).await?;
+
+        let book = Book {
+            id: existing.id,
+            isbn: cmd.isbn,
+            isbn13: cmd.isbn13,
+            title: cmd.title,
+            subtitle: cmd.subtitle,
+            description: cmd.description,
+            num_pages: cmd.num_pages,
+            published_date: cmd.published_date,
+            format: cmd.format,
+            images: images_from(cmd.images),
+            preview: previews_from(cmd.preview),
+            genres: links.genres,
+            authors: links.authors,
+            publishers: links.publishers,
+            languages: cmd.languages,
+            reviews: existing.reviews,
+            rating: existing.rating,
+            external_id: existing.external_id,
+            work_id: existing.work_id,
+        };
+
+        if !self.book_repo.update(book).await? {
+            return Err(AppError::not_found("Book", &cmd.id));
+        }
+
+        let book = self.book_repo.find_by_id(&cmd.id).await?;
+        book.map(BookResponse::from).ok_or_else(|| AppError::not_found("Book", &cmd.id))
+    }
+
+    async fn delete(&self, cmd: BookDeleteCommand) -> Result<(), AppError> {
+        if !self.book_repo.delete(&cmd.id).await? {
+            return Err(AppError::not_found("Book", &cmd.id));
+        }
+        Ok(())
+    }
+
+    async fn list(&self, cmd: BookListCommand) -> Result<BookCatalogResponse, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let author_ids = cmd.filter.author_ids
+            .iter()
+            .map(|id| ObjectId::parse_str(id).map_err(|_| AppError::invalid_id("author", id)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let filter = BookFilter {
+            genres: cmd.filter.genres,
+            author_ids,
+            publishers: cmd.filter.publishers,
+            languages: cmd.filter.languages,
+            formats: cmd.filter.formats,
+            pages_min: cmd.filter.pages_min,
+            pages_max: cmd.filter.pages_max,
+            published_from: cmd.filter.published_from,
+            published_to: cmd.filter.published_to,
+        };
+
+        let (books, total, facets) = self.book_repo.find_filtered(&filter, &pagination).await?;
+        Ok(BookCatalogResponse {
+            page: PaginatedResponse::new(books.into_iter().map(BookResponse::from).collect(), total, &pagination),
+            facets: facets.into(),
+        })
+    }
+
+    async fn get_by_external_id(&self, cmd: BookExternalIdGetCommand) -> Result<BookResponse, AppError> {
+        let book = self.book_repo.find_by_external_id(cmd.provider, &cmd.external_id).await?;
+        book.map(BookResponse::from)
+            .ok_or_else(|| AppError::not_found("Book", &format!("{}:{}", cmd.provider.field(), cmd.external_id)))
+    }
+
+    /// An id already attached to another book is a conflict: the two records have to be merged first.
+    async fn attach_external_id(&self, cmd: BookExternalIdAttachCommand) -> Result<BookResponse, AppError> {
+        let external_id = cmd.external_id.trim();
+        if let Some(other) = self.book_repo.find_by_external_id(cmd.provider, external_id).await? {
+            let other_id = other.id.map(|id| id.to_hex()).unwrap_or_default();
+            if other_id != cmd.id {
+                return Err(AppError::Conflict(format!(
+                    "{} id {} is already attached to book {}", cmd.provider.field(), external_id, other_id
+                )));
+            }
+        }
+
+        if !self.book_repo.set_external_id(&cmd.id, cmd.provider, Some(external_id)).await? {
+            return Err(AppError::not_found("Book", &cmd.id));
+        }
+        self.get(BookGetCommand { id: cmd.id }).await
+    }
+
+    async fn detach_external_id(&self, cmd: BookExternalIdDetachCommand) -> Result<BookResponse, AppError> {
+        if !self.book_repo.set_external_id(&cmd.id, cmd.provider, None).await? {
+            return Err(AppError::not_found("Book", &cmd.id));
+        }
+        self.get(BookGetCommand { id: cmd.id }).await
+    }
+}
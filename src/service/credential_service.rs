@@ -0,0 +1,110 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+
+use crate::model::user_model::{HashedPassword, User};
+use crate::repository::user_repository::{UserRepository, UserRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::logging::log;
+use crate::shared::security::password::{PasswordCheck, PasswordHasher};
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait CredentialServiceInterface {
+    async fn hash_password(&self, password: &str) -> Result<HashedPassword, AppError>;
+    async fn verify_password(&self, user: &User, password: &str) -> Result<bool, AppError>;
+    async fn verify_credentials(&self, username: &str, password: &str) -> Result<Option<User>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct CredentialService {
+    user_repo: UserRepository,
+    hasher: PasswordHasher,
+}
+
+impl From<&AppState> for CredentialService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            UserRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+            app_state.password_hasher.clone(),
+        )
+    }
+}
+
+impl CredentialService {
+    pub fn new(user_repo: UserRepository, hasher: PasswordHasher) -> Self {
+        Self { user_repo, hasher }
+    }
+
+    // Argon2 is deliberately slow, keep it off the async workers
+    async fn check(&self, password: &str, stored: Option<&str>) -> Result<PasswordCheck, AppError> {
+        let hasher = self.hasher.clone();
+        let password = password.to_string();
+        let stored = stored.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || match stored {
+            Some(stored) => hasher.verify(&password, &stored),
+            None => {
+                hasher.verify_dummy(&password);
+                PasswordCheck::Invalid
+            }
+        })
+            .await
+            .map_err(|e| AppError::Upstream(anyhow!("Password verification task failed: {}", e)))
+    }
+}
+
+#[async_trait]
+impl CredentialServiceInterface for CredentialService {
+    async fn hash_password(&self, password: &str) -> Result<HashedPassword, AppError> {
+        let hasher = self.hasher.clone();
+        let password = password.to_string();
+
+        let hash = tokio::task::spawn_blocking(move || hasher.hash(&password))
+            .await
+            .map_err(|e| AppError::Upstream(anyhow!("Password hashing task failed: {}", e)))??;
+
+        Ok(HashedPassword::new(hash))
+    }
+
+    /// Checks `password` against the user's stored hash and upgrades legacy or
+    /// weaker hashes in place once the password is known to be correct.
+    async fn verify_password(&self, user: &User, password: &str) -> Result<bool, AppError> {
+        match self.check(password, Some(user.password.as_str())).await? {
+            PasswordCheck::Invalid => Ok(false),
+            PasswordCheck::Valid { needs_rehash } => {
+                if needs_rehash && let Some(user_id) = &user.id {
+                    let rehashed = self.hash_password(password).await?;
+                    // A failed upgrade must not block the login, it is retried on the next one
+                    if let Err(e) = self.user_repo.update_password(&user_id.to_hex(), &rehashed).await {
+                        log::warning(&format!("[CREDENTIAL] Unable to rehash password for {}: {}", user_id.to_hex(), e));
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    async fn verify_credentials(&self, username: &str, password: &str) -> Result<Option<User>, AppError> {
+        let user = match self.user_repo.find_by_username(username).await? {
+            Some(user) => user,
+            None => {
+                // Unknown usernames cost a full verification too, or timing would reveal them
+                self.check(password, None).await?;
+                return Ok(None);
+            }
+        };
+
+        if self.verify_password(&user, password).await? {
+            Ok(Some(user))
+        } else {
+            Ok(None)
+        }
+    }
+}
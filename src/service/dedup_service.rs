@@ -0,0 +1,121 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use mongodb::bson::oid::ObjectId;
+
+use crate::command::dedup_command::{AuthorMergeCommand, BookMergeCommand, DuplicateListCommand};
+use crate::dto::dedup_dto::{DuplicateCandidateResponse, MergeReportResponse};
+use crate::model::dedup_model::DedupRecord;
+use crate::repository::dedup_repository::{DedupRepository, DedupRepositoryInterface};
+use crate::shared::dedup::candidates::duplicate_candidates;
+use crate::shared::error::AppError;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait DedupServiceInterface {
+    async fn author_candidates(&self, cmd: DuplicateListCommand) -> Result<Vec<DuplicateCandidateResponse>, AppError>;
+    async fn book_candidates(&self, cmd: DuplicateListCommand) -> Result<Vec<DuplicateCandidateResponse>, AppError>;
+    async fn merge_authors(&self, cmd: AuthorMergeCommand) -> Result<MergeReportResponse, AppError>;
+    async fn merge_books(&self, cmd: BookMergeCommand) -> Result<MergeReportResponse, AppError>;
+}
+
+
+/// Proposes authors and books that imports created twice, and merges them into one record.
+/// Candidates are only proposed: nothing is merged until an admin picks the surviving record.
+#[derive(Clone)]
+pub struct DedupService {
+    dedup_repo: DedupRepository,
+}
+
+impl From<&AppState> for DedupService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(DedupRepository::new(app_state.mongo_client.clone(), database))
+    }
+}
+
+impl DedupService {
+    pub fn new(dedup_repo: DedupRepository) -> Self {
+        Self { dedup_repo }
+    }
+
+    // Comparing names is CPU bound over the whole collection, keep it off the async workers
+    async fn candidates(records: Vec<DedupRecord>, cmd: DuplicateListCommand) -> Result<Vec<DuplicateCandidateResponse>, AppError> {
+        let candidates = tokio::task::spawn_blocking(move || duplicate_candidates(&records, cmd.min_score, cmd.limit as usize))
+            .await
+            .map_err(|e| AppError::Upstream(anyhow!("Duplicate detection task failed: {}", e)))?;
+
+        Ok(candidates.into_iter().map(DuplicateCandidateResponse::from).collect())
+    }
+
+    /// Parses the ids of a merge, dropping repeated ones.
+    fn merge_ids(kind: &str, survivor_id: &str, merged_ids: &[String]) -> Result<(ObjectId, Vec<ObjectId>), AppError> {
+        let survivor = ObjectId::parse_str(survivor_id).map_err(|_| AppError::invalid_id(kind, survivor_id))?;
+
+        let mut merged: Vec<ObjectId> = Vec::with_capacity(merged_ids.len());
+        for merged_id in merged_ids {
+            let id = ObjectId::parse_str(merged_id).map_err(|_| AppError::invalid_id(kind, merged_id))?;
+            if id == survivor {
+                return Err(AppError::Validation(format!("Cannot merge {} {} into itself", kind, survivor_id)));
+            }
+            if !merged.contains(&id) {
+                merged.push(id);
+            }
+        }
+        if merged.is_empty() {
+            return Err(AppError::Validation(format!("No {} to merge into {}", kind, survivor_id)));
+        }
+        Ok((survivor, merged))
+    }
+}
+
+#[async_trait]
+impl DedupServiceInterface for DedupService {
+    async fn author_candidates(&self, cmd: DuplicateListCommand) -> Result<Vec<DuplicateCandidateResponse>, AppError> {
+        let records = self.dedup_repo.author_records().await?;
+        Self::candidates(records, cmd).await
+    }
+
+    async fn book_candidates(&self, cmd: DuplicateListCommand) -> Result<Vec<DuplicateCandidateResponse>, AppError> {
+        let records = self.dedup_repo.book_records().await?;
+        Self::candidates(records, cmd).await
+    }
+
+    async fn merge_authors(&self, cmd: AuthorMergeCommand) -> Result<MergeReportResponse, AppError> {
+        let (survivor_id, merged_ids) = Self::merge_ids("author", &cmd.survivor_id, &cmd.merged_ids)?;
+        let report = self.dedup_repo.merge_authors(&survivor_id, &merged_ids).await?;
+        Ok(report.into())
+    }
+
+    async fn merge_books(&self, cmd: BookMergeCommand) -> Result<MergeReportResponse, AppError> {
+        let (survivor_id, merged_ids) = Self::merge_ids("book", &cmd.survivor_id, &cmd.merged_ids)?;
+        let report = self.dedup_repo.merge_books(&survivor_id, &merged_ids).await?;
+        Ok(report.into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SURVIVOR: &str = "65f0c0ffee0000000000b001";
+    const MERGED: &str = "65f0c0ffee0000000000b002";
+
+    #[test]
+    fn repeated_merged_ids_are_merged_once() {
+        let (survivor, merged) = DedupService::merge_ids("book", SURVIVOR, &[MERGED.to_string(), MERGED.to_string()]).unwrap();
+
+        assert_eq!(survivor.to_hex(), SURVIVOR);
+        assert_eq!(merged.len(), 1);
+    }
+
+    #[test]
+    fn a_record_cannot_be_merged_into_itself() {
+        let result = DedupService::merge_ids("author", SURVIVOR, &[MERGED.to_string(), SURVIVOR.to_string()]);
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(DedupService::merge_ids("author", "nope", &[MERGED.to_string()]).is_err());
+    }
+}
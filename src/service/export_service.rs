@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::command::export_command::ExportCommand;
+use crate::model::export_model::{ExportDataset, ExportFormat, ExportStream, GraphElement};
+use crate::repository::export_repository::{export_columns, ExportRepository, ExportRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::export::graph_format::{cypher_edge, cypher_node, graphml_edge, graphml_footer, graphml_header, graphml_node};
+use crate::shared::export::record_format::{csv_line, csv_record, json_line};
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait ExportServiceInterface {
+    /// Checks the format suits the dataset and starts reading it.
+    async fn export(&self, cmd: ExportCommand) -> Result<ExportStream, AppError>;
+}
+
+
+/// Streams catalog and reading graph snapshots record by record, so an export
+/// never holds more than a cursor batch in memory.
+#[derive(Clone)]
+pub struct ExportService {
+    export_repo: ExportRepository,
+}
+
+impl From<&AppState> for ExportService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(ExportRepository::new(database, app_state.neo4j_client.clone()))
+    }
+}
+
+impl ExportService {
+    pub fn new(export_repo: ExportRepository) -> Self {
+        Self { export_repo }
+    }
+
+    fn format(cmd: &ExportCommand) -> Result<ExportFormat, AppError> {
+        let formats = cmd.dataset.formats();
+        match cmd.format {
+            None => Ok(formats[0]),
+            Some(format) if formats.contains(&format) => Ok(format),
+            Some(format) => Err(AppError::Validation(format!(
+                "{} cannot be exported as {}, expected one of: {}",
+                cmd.dataset.name(),
+                format.name(),
+                formats.iter().map(ExportFormat::name).collect::<Vec<_>>().join(", "),
+            ))),
+        }
+    }
+
+    fn filename(dataset: ExportDataset, format: ExportFormat) -> String {
+        format!("booknet-{}-{}.{}", dataset.name(), Utc::now().format("%Y%m%d"), format.name())
+    }
+}
+
+#[async_trait]
+impl ExportServiceInterface for ExportService {
+    async fn export(&self, cmd: ExportCommand) -> Result<ExportStream, AppError> {
+        let format = Self::format(&cmd)?;
+
+        let chunks = match format {
+            ExportFormat::JsonLines => {
+                let records = self.export_repo.records(cmd.dataset).await?;
+                records.map_ok(|record| json_line(&record)).boxed()
+            },
+            ExportFormat::Csv => {
+                let records = self.export_repo.records(cmd.dataset).await?;
+                let columns = export_columns(cmd.dataset);
+                let header = csv_line(&columns);
+                stream::once(async move { Ok(header) })
+                    .chain(records.map_ok(move |record| csv_record(&record, &columns)))
+                    .boxed()
+            },
+            ExportFormat::GraphMl => {
+                let elements = self.export_repo.graph().map_ok(|element| match element {
+                    GraphElement::Node(node) => graphml_node(&node),
+                    GraphElement::Edge(edge) => graphml_edge(&edge),
+                });
+                stream::once(async { Ok(graphml_header()) })
+                    .chain(elements)
+                    .chain(stream::once(async { Ok(graphml_footer()) }))
+                    .boxed()
+            },
+            ExportFormat::Cypher => {
+                self.export_repo.graph()
+                    .map_ok(|element| match element {
+                        GraphElement::Node(node) => cypher_node(&node),
+                        GraphElement::Edge(edge) => cypher_edge(&edge),
+                    })
+                    .boxed()
+            },
+        };
+
+        Ok(ExportStream { format, filename: Self::filename(cmd.dataset, format), chunks })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_dataset_defaults_to_its_first_format() {
+        let records = ExportCommand { dataset: ExportDataset::Users, format: None };
+        let graph = ExportCommand { dataset: ExportDataset::Graph, format: None };
+
+        assert_eq!(ExportService::format(&records).unwrap(), ExportFormat::JsonLines);
+        assert_eq!(ExportService::format(&graph).unwrap(), ExportFormat::GraphMl);
+    }
+
+    #[test]
+    fn graph_formats_are_rejected_for_collections_and_back() {
+        let records = ExportCommand { dataset: ExportDataset::Books, format: Some(ExportFormat::Cypher) };
+        let graph = ExportCommand { dataset: ExportDataset::Graph, format: Some(ExportFormat::Csv) };
+
+        assert!(matches!(ExportService::format(&records), Err(AppError::Validation(_))));
+        assert!(matches!(ExportService::format(&graph), Err(AppError::Validation(_))));
+    }
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use mongodb::bson::oid::ObjectId;
+
+use crate::command::follow_command::{FeedCommand, FollowCommand, FollowListCommand, UnfollowCommand};
+use crate::dto::follow_dto::{FeedItemResponse, FollowResponse};
+use crate::model::user_model::UserRole;
+use crate::repository::follow_repository::{FollowRepository, FollowRepositoryInterface};
+use crate::repository::user_repository::{UserRepository, UserRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait FollowServiceInterface {
+    /// Following someone already followed is a no-op.
+    async fn follow(&self, cmd: FollowCommand) -> Result<(), AppError>;
+    async fn unfollow(&self, cmd: UnfollowCommand) -> Result<(), AppError>;
+    async fn followers(&self, cmd: FollowListCommand) -> Result<PaginatedResponse<FollowResponse>, AppError>;
+    async fn following(&self, cmd: FollowListCommand) -> Result<PaginatedResponse<FollowResponse>, AppError>;
+    /// Reviews, ratings and shelf changes of the followed readers, newest first.
+    async fn feed(&self, cmd: FeedCommand) -> Result<PaginatedResponse<FeedItemResponse>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct FollowService {
+    follow_repo: FollowRepository,
+    user_repo: UserRepository,
+}
+
+impl From<&AppState> for FollowService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            FollowRepository::new(app_state.mongo_client.clone(), database.clone()),
+            UserRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+        )
+    }
+}
+
+impl FollowService {
+    pub fn new(follow_repo: FollowRepository, user_repo: UserRepository) -> Self {
+        Self { follow_repo, user_repo }
+    }
+
+    /// Id of an existing reader; only readers have a node to hang `FOLLOWS` on.
+    async fn reader_id(&self, user_id: &str) -> Result<ObjectId, AppError> {
+        let user = match self.user_repo.find_by_id(user_id).await? {
+            Some(user) => user,
+            None => return Err(AppError::not_found("User", user_id)),
+        };
+        if !matches!(user.role, UserRole::Reader) {
+            return Err(AppError::Validation(format!("User {} is not a reader", user_id)));
+        }
+        user.id.ok_or_else(|| AppError::invalid_id("user", user_id))
+    }
+}
+
+#[async_trait]
+impl FollowServiceInterface for FollowService {
+    async fn follow(&self, cmd: FollowCommand) -> Result<(), AppError> {
+        if cmd.follower_id == cmd.followee_id {
+            return Err(AppError::Validation("Readers cannot follow themselves".to_string()));
+        }
+        let follower_id = self.reader_id(&cmd.follower_id).await?;
+        let followee_id = self.reader_id(&cmd.followee_id).await?;
+
+        self.follow_repo.insert(&follower_id, &followee_id).await?;
+        Ok(())
+    }
+
+    async fn unfollow(&self, cmd: UnfollowCommand) -> Result<(), AppError> {
+        let follower_id = ObjectId::parse_str(&cmd.follower_id)
+            .map_err(|_| AppError::invalid_id("user", &cmd.follower_id))?;
+        let followee_id = ObjectId::parse_str(&cmd.followee_id)
+            .map_err(|_| AppError::invalid_id("user", &cmd.followee_id))?;
+
+        if !self.follow_repo.delete(&follower_id, &followee_id).await? {
+            return Err(AppError::not_found("Follow", &cmd.followee_id));
+        }
+        Ok(())
+    }
+
+    async fn followers(&self, cmd: FollowListCommand) -> Result<PaginatedResponse<FollowResponse>, AppError> {
+        let user_id = self.reader_id(&cmd.user_id).await?;
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (followers, total) = self.follow_repo.find_followers(&user_id, &pagination).await?;
+        Ok(PaginatedResponse::new(followers.into_iter().map(FollowResponse::from).collect(), total, &pagination))
+    }
+
+    async fn following(&self, cmd: FollowListCommand) -> Result<PaginatedResponse<FollowResponse>, AppError> {
+        let user_id = self.reader_id(&cmd.user_id).await?;
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (following, total) = self.follow_repo.find_following(&user_id, &pagination).await?;
+        Ok(PaginatedResponse::new(following.into_iter().map(FollowResponse::from).collect(), total, &pagination))
+    }
+
+    async fn feed(&self, cmd: FeedCommand) -> Result<PaginatedResponse<FeedItemResponse>, AppError> {
+        let user_id = self.reader_id(&cmd.user_id).await?;
+        let pagination = cmd.pagination.unwrap_or_default();
+
+        let following = self.follow_repo.find_following_ids(&user_id).await?;
+        if following.is_empty() {
+            return Ok(PaginatedResponse::new(Vec::new(), 0, &pagination));
+        }
+
+        let (items, total) = self.follow_repo.find_feed(following, &pagination).await?;
+        Ok(PaginatedResponse::new(items.into_iter().map(FeedItemResponse::from).collect(), total, &pagination))
+    }
+}
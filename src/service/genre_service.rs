@@ -1,4 +1,3 @@
-use anyhow::{Error, Result};
 use async_trait::async_trait;
 
 use crate::command::genre_command::{
@@ -6,16 +5,18 @@ use crate::command::genre_command::{
 };
 use crate::dto::genre_dto::GenreResponse;
 use crate::service::metadata_service::{MetadataService, MetadataServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
 use crate::shared::state::AppState;
 
 
 #[async_trait]
 pub trait GenreServiceInterface {
-    async fn get(&self, cmd: GenreGetCommand) -> Result<Option<GenreResponse>, Error>;
-    async fn create(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, Error>;
-    async fn update(&self, cmd: GenreUpdateCommand) -> Result<Option<GenreResponse>, Error>;
-    async fn delete(&self, cmd: GenreDeleteCommand) -> Result<(), Error>;
-    async fn list(&self, cmd: GenreListCommand) -> Result<Vec<GenreResponse>, Error>;
+    async fn get(&self, cmd: GenreGetCommand) -> Result<GenreResponse, AppError>;
+    async fn create(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, AppError>;
+    async fn update(&self, cmd: GenreUpdateCommand) -> Result<GenreResponse, AppError>;
+    async fn delete(&self, cmd: GenreDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: GenreListCommand) -> Result<PaginatedResponse<GenreResponse>, AppError>;
 }
 
 
@@ -40,23 +41,23 @@ impl GenreService {
 
 #[async_trait]
 impl GenreServiceInterface for GenreService {
-    async fn get(&self, cmd: GenreGetCommand) -> Result<Option<GenreResponse>, Error> {
+    async fn get(&self, cmd: GenreGetCommand) -> Result<GenreResponse, AppError> {
         self.metadata_service.get_genre(cmd).await
     }
     
-    async fn create(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, Error> {
+    async fn create(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, AppError> {
         self.metadata_service.create_genre(cmd).await
     }
 
-    async fn update(&self, cmd: GenreUpdateCommand) -> Result<Option<GenreResponse>, Error> {
+    async fn update(&self, cmd: GenreUpdateCommand) -> Result<GenreResponse, AppError> {
         self.metadata_service.update_genre(cmd).await
     }
 
-    async fn delete(&self, cmd: GenreDeleteCommand) -> Result<(), Error> {
+    async fn delete(&self, cmd: GenreDeleteCommand) -> Result<(), AppError> {
         self.metadata_service.delete_genre(cmd).await
     }
 
-    async fn list(&self, cmd: GenreListCommand) -> Result<Vec<GenreResponse>, Error> {
+    async fn list(&self, cmd: GenreListCommand) -> Result<PaginatedResponse<GenreResponse>, AppError> {
         self.metadata_service.list_genres(cmd).await
     }
 }
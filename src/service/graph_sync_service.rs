@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use metrics::{counter, describe_counter, describe_gauge, gauge};
+use tokio::task::JoinHandle;
+
+use crate::repository::graph_sync_repository::{is_permanent_failure, GraphSyncRepository, GraphSyncRepositoryInterface};
+use crate::shared::constant::{
+    GRAPH_SYNC_BACKOFF_BASE_MS, GRAPH_SYNC_BACKOFF_MAX_MS, GRAPH_SYNC_LEASE_MS, GRAPH_SYNC_MAX_ATTEMPTS,
+    GRAPH_SYNC_POLL_INTERVAL_MS,
+};
+use crate::shared::error::AppError;
+use crate::shared::logging::log;
+use crate::shared::state::AppState;
+
+
+const EVENTS_METRIC: &str = "graph_sync_events_total";
+const BLOCKED_METRIC: &str = "graph_sync_blocked_events";
+const RETRYING_METRIC: &str = "graph_sync_retrying_events";
+
+
+/// Background worker draining the graph-sync outbox into Neo4j.
+/// Failed events are retried with exponential backoff and moved to the dead-letter collection
+/// after `GRAPH_SYNC_MAX_ATTEMPTS`, or at once when Neo4j rejects the statement itself.
+///
+/// Events touching the same records are applied in order, so a failing event holds back the later
+/// writes to its records for the sum of its backoffs, about two minutes (see `max_stall_ms`), before
+/// it is dead-lettered. Writes to other records go on meanwhile. Each retry logs how many events
+/// wait behind it, and `graph_sync_retrying_events` counts the events waiting for a retry.
+#[derive(Clone)]
+pub struct GraphSyncService {
+    graph_sync_repo: GraphSyncRepository,
+}
+
+impl From<&AppState> for GraphSyncService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            GraphSyncRepository::new(
+                app_state.mongo_client.clone(),
+                database,
+                app_state.neo4j_client.clone()
+            ),
+        )
+    }
+}
+
+impl GraphSyncService {
+    pub fn new(graph_sync_repo: GraphSyncRepository) -> Self {
+        Self { graph_sync_repo }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        describe_counter!(EVENTS_METRIC, "Graph sync events processed, by outcome");
+        describe_gauge!(BLOCKED_METRIC, "Graph sync events held back by the last event that failed, on the same records");
+        describe_gauge!(RETRYING_METRIC, "Graph sync events waiting for a retry");
+        log::info2(&format!(
+            "[GRAPH SYNC] Worker started, a failing event holds later writes to its records back for up to {}s",
+            max_stall_ms() / 1000
+        ));
+        tokio::spawn(async move { self.run().await })
+    }
+
+    /// Applies every event that is due, for one-off commands running without the server's worker.
+    pub async fn drain(&self) -> Result<usize, AppError> {
+        let mut processed = 0;
+        while self.process_next().await? {
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    async fn run(&self) {
+        let idle = Duration::from_millis(GRAPH_SYNC_POLL_INTERVAL_MS);
+        loop {
+            match self.process_next().await {
+                Ok(true) => continue,
+                Ok(false) => tokio::time::sleep(idle).await,
+                Err(e) => {
+                    log::error(&format!("[GRAPH SYNC] Outbox unavailable: {}", e));
+                    tokio::time::sleep(idle).await;
+                }
+            }
+        }
+    }
+
+    /// Applies the next due event, returning whether there was one.
+    async fn process_next(&self) -> Result<bool, AppError> {
+        let event = match self.graph_sync_repo.claim_next(GRAPH_SYNC_LEASE_MS).await? {
+            Some(event) => event,
+            None => return Ok(false),
+        };
+        let event_id = event.id.ok_or_else(|| AppError::Validation("Graph sync event has no id".to_string()))?;
+
+        match self.graph_sync_repo.apply(&event.op).await {
+            Ok(()) => {
+                self.graph_sync_repo.complete(&event_id).await?;
+                counter!(EVENTS_METRIC, "outcome" => "applied").increment(1);
+                if event.attempts > 1 {
+                    self.update_retrying().await?;
+                }
+            },
+            Err(e) if is_permanent_failure(&e) || event.attempts >= GRAPH_SYNC_MAX_ATTEMPTS => {
+                let reason = if is_permanent_failure(&e) {
+                    "rejected by Neo4j".to_string()
+                } else {
+                    format!("after {} attempts", event.attempts)
+                };
+                log::error(&format!(
+                    "[GRAPH SYNC] Event {} (seq {}) dead-lettered {}: {}",
+                    event_id, event.seq, reason, e
+                ));
+                self.graph_sync_repo.dead_letter(event, &e.to_string()).await?;
+                counter!(EVENTS_METRIC, "outcome" => "dead_lettered").increment(1);
+                self.update_retrying().await?;
+            },
+            Err(e) => {
+                let delay_ms = backoff_ms(event.attempts);
+                self.graph_sync_repo.retry_later(&event_id, &e.to_string(), Utc::now().timestamp_millis() + delay_ms).await?;
+                let blocked = self.graph_sync_repo.count_behind(&event).await?;
+                log::warning(&format!(
+                    "[GRAPH SYNC] Event {} (seq {}) failed attempt {}/{}, retrying in {}ms with {} events on the same records waiting behind it: {}",
+                    event_id, event.seq, event.attempts, GRAPH_SYNC_MAX_ATTEMPTS, delay_ms, blocked, e
+                ));
+                counter!(EVENTS_METRIC, "outcome" => "retried").increment(1);
+                gauge!(BLOCKED_METRIC).set(blocked as f64);
+                self.update_retrying().await?;
+            },
+        }
+        Ok(true)
+    }
+
+    async fn update_retrying(&self) -> Result<(), AppError> {
+        let retrying = self.graph_sync_repo.count_retrying().await?;
+        gauge!(RETRYING_METRIC).set(retrying as f64);
+        Ok(())
+    }
+}
+
+fn backoff_ms(attempts: u32) -> i64 {
+    GRAPH_SYNC_BACKOFF_BASE_MS
+        .saturating_mul(1_i64 << attempts.saturating_sub(1).min(20))
+        .min(GRAPH_SYNC_BACKOFF_MAX_MS)
+}
+
+
+/// Longest time a failing event holds its records back: the backoffs between its attempts,
+/// not counting the attempts themselves.
+fn max_stall_ms() -> i64 {
+    (1..GRAPH_SYNC_MAX_ATTEMPTS).map(backoff_ms).sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_from_the_base_delay() {
+        assert_eq!(backoff_ms(0), GRAPH_SYNC_BACKOFF_BASE_MS);
+        assert_eq!(backoff_ms(1), GRAPH_SYNC_BACKOFF_BASE_MS);
+        assert_eq!(backoff_ms(2), GRAPH_SYNC_BACKOFF_BASE_MS * 2);
+        assert_eq!(backoff_ms(4), GRAPH_SYNC_BACKOFF_BASE_MS * 8);
+    }
+
+    #[test]
+    fn a_failing_event_stalls_its_records_for_about_two_minutes() {
+        assert_eq!(max_stall_ms(), 127_000);
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        assert_eq!(backoff_ms(GRAPH_SYNC_MAX_ATTEMPTS * 10), GRAPH_SYNC_BACKOFF_MAX_MS);
+        assert_eq!(backoff_ms(u32::MAX), GRAPH_SYNC_BACKOFF_MAX_MS);
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use mongodb::bson::oid::ObjectId;
+use tokio::fs::{self, File};
+use tokio::io::BufReader;
+
+use crate::command::import_command::{ImportCommand, ImportJobGetCommand};
+use crate::dto::import_dto::ImportJobResponse;
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::import_model::{ImportBatchResult, ImportFormat, ImportJob, ImportJobStatus, ImportRecord, ImportReport};
+use crate::repository::import_repository::{ImportRepository, ImportRepositoryInterface};
+use crate::shared::constant::IMPORT_BATCH_SIZE;
+use crate::shared::error::AppError;
+use crate::shared::import::dump_mapping::parse_record;
+use crate::shared::import::dump_reader::DumpReader;
+use crate::shared::logging::log;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait ImportServiceInterface {
+    /// Checks the dump can be read and imports it in the background.
+    async fn start(&self, cmd: ImportCommand) -> Result<ImportJobResponse, AppError>;
+    async fn get_job(&self, cmd: ImportJobGetCommand) -> Result<ImportJobResponse, AppError>;
+}
+
+
+/// Streams Goodreads and Kaggle dumps into the catalog, in batches of `IMPORT_BATCH_SIZE` rows
+/// each written in one transaction. Rows repeating an external id or ISBN seen earlier in the
+/// dump are skipped, and existing records matching one are refreshed rather than duplicated.
+/// Ratings are upserted on the reviews' unique book and reader index.
+#[derive(Clone)]
+pub struct ImportService {
+    import_repo: ImportRepository,
+    /// The only directory `start` reads dumps from, the CLI reads any path it is given
+    import_dir: PathBuf,
+}
+
+impl From<&AppState> for ImportService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            ImportRepository::new(app_state.mongo_client.clone(), database),
+            PathBuf::from(&app_state.config.import_dir),
+        )
+    }
+}
+
+impl ImportService {
+    pub fn new(import_repo: ImportRepository, import_dir: PathBuf) -> Self {
+        Self { import_repo, import_dir }
+    }
+
+    fn format(cmd: &ImportCommand) -> Result<ImportFormat, AppError> {
+        cmd.format
+            .or_else(|| ImportFormat::from_path(Path::new(&cmd.path)))
+            .ok_or_else(|| AppError::Validation(format!("Cannot tell the format of {}, expected .csv or .jsonl", cmd.path)))
+    }
+
+    async fn open(path: &str) -> Result<File, AppError> {
+        File::open(path)
+            .await
+            .map_err(|e| AppError::Validation(format!("Cannot open {}: {}", path, e)))
+    }
+
+    /// Imports the whole dump, logging progress after every batch and saving it on the job when there is one.
+    pub async fn import(&self, cmd: &ImportCommand, job_id: Option<ObjectId>) -> Result<ImportReport, AppError> {
+        let format = Self::format(cmd)?;
+        let file = Self::open(&cmd.path).await?;
+        let mut reader = DumpReader::new(BufReader::new(file), format);
+
+        let mut report = ImportReport::default();
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut batch: Vec<ImportRecord> = Vec::with_capacity(IMPORT_BATCH_SIZE);
+
+        loop {
+            let next = reader
+                .next_row()
+                .await
+                .map_err(|e| AppError::Upstream(anyhow::anyhow!("Error reading {}: {}", cmd.path, e)))?;
+            let done = next.is_none();
+
+            if let Some((line, row)) = next {
+                report.rows_read += 1;
+                match row.and_then(|row| parse_record(cmd.kind, cmd.provider, line, &row)) {
+                    Ok(record) => {
+                        let keys = record.dedup_keys();
+                        if keys.iter().any(|key| seen.contains(key)) {
+                            report.duplicates += 1;
+                        } else {
+                            seen.extend(keys);
+                            batch.push(record);
+                        }
+                    },
+                    Err(message) => report.reject(line, message),
+                }
+            }
+
+            if batch.len() >= IMPORT_BATCH_SIZE || (done && !batch.is_empty()) {
+                let rows = batch.len() as u64;
+                let first_line = batch.first().map_or(0, ImportRecord::line);
+                match self.write_batch(cmd.provider, std::mem::take(&mut batch)).await {
+                    Ok(result) => report.absorb(rows, result),
+                    Err(e) => {
+                        report.rows_failed += rows;
+                        report.error(first_line, format!("Batch of {} rows failed: {}", rows, e));
+                    },
+                }
+
+                log::info2(&format!(
+                    "[IMPORT] {}: {} rows read, {} imported, {} duplicates, {} failed",
+                    cmd.path, report.rows_read, report.rows_imported, report.duplicates, report.rows_failed
+                ));
+                if let Some(job_id) = &job_id {
+                    self.import_repo.update_job(job_id, ImportJobStatus::Running, &report, None).await?;
+                }
+            }
+
+            if done {
+                break;
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn write_batch(&self, provider: ExternalProvider, batch: Vec<ImportRecord>) -> Result<ImportBatchResult, AppError> {
+        let mut authors = Vec::new();
+        let mut books = Vec::new();
+        let mut ratings = Vec::new();
+        for record in batch {
+            match record {
+                ImportRecord::Author(author) => authors.push(author),
+                ImportRecord::Book(book) => books.push(book),
+                ImportRecord::Rating(rating) => ratings.push(rating),
+            }
+        }
+
+        if !authors.is_empty() {
+            self.import_repo.upsert_authors(provider, authors).await
+        } else if !books.is_empty() {
+            self.import_repo.upsert_books(provider, books).await
+        } else {
+            self.import_repo.upsert_ratings(provider, ratings).await
+        }
+    }
+}
+
+
+#[async_trait]
+impl ImportServiceInterface for ImportService {
+    async fn start(&self, cmd: ImportCommand) -> Result<ImportJobResponse, AppError> {
+        let format = Self::format(&cmd)?;
+        let path = resolve_import_path(&self.import_dir, &cmd.path).await?;
+
+        let now = Utc::now();
+        let mut job = ImportJob {
+            id: None,
+            path: cmd.path.clone(),
+            kind: cmd.kind,
+            provider: cmd.provider,
+            format,
+            status: ImportJobStatus::Running,
+            report: ImportReport::default(),
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let job_id = self.import_repo.insert_job(job.clone()).await?;
+        job.id = Some(job_id);
+
+        let cmd = ImportCommand { path: path.to_string_lossy().into_owned(), ..cmd };
+        let service = self.clone();
+        tokio::spawn(async move {
+            let (status, report, error) = match service.import(&cmd, Some(job_id)).await {
+                Ok(report) => (ImportJobStatus::Completed, report, None),
+                Err(e) => {
+                    log::error(&format!("[IMPORT] {} failed: {}", cmd.path, e));
+                    // The progress saved after the last batch is kept
+                    let report = service.import_repo
+                        .find_job(&job_id.to_hex())
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|job| job.report)
+                        .unwrap_or_default();
+                    (ImportJobStatus::Failed, report, Some(e.to_string()))
+                },
+            };
+            if let Err(e) = service.import_repo.update_job(&job_id, status, &report, error.as_deref()).await {
+                log::error(&format!("[IMPORT] Unable to save the outcome of job {}: {}", job_id, e));
+            }
+        });
+
+        Ok(job.into())
+    }
+
+    async fn get_job(&self, cmd: ImportJobGetCommand) -> Result<ImportJobResponse, AppError> {
+        let job = self.import_repo.find_job(&cmd.id).await?;
+        job.map(ImportJobResponse::from).ok_or_else(|| AppError::not_found("Import job", &cmd.id))
+    }
+}
+
+
+/// The readable file at `path` within `import_dir`, once symlinks and `..` are resolved.
+/// A path outside the directory reads as missing, and the error tells nothing about the server's files.
+async fn resolve_import_path(import_dir: &Path, path: &str) -> Result<PathBuf, AppError> {
+    let resolved = async {
+        let dir = fs::canonicalize(import_dir).await?;
+        let file = fs::canonicalize(dir.join(path)).await?;
+        let readable = file.starts_with(&dir) && fs::metadata(&file).await?.is_file() && File::open(&file).await.is_ok();
+        Ok::<_, std::io::Error>(readable.then_some(file))
+    }.await;
+
+    match resolved {
+        Ok(Some(file)) => Ok(file),
+        _ => Err(AppError::Validation("Import file not found".to_string())),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn imports_only_read_files_inside_the_import_directory() {
+        let root = std::env::temp_dir().join(format!("booknet-import-{}", ObjectId::new().to_hex()));
+        let import_dir = root.join("imports");
+        std::fs::create_dir_all(import_dir.join("kaggle")).unwrap();
+        std::fs::write(import_dir.join("kaggle/books.csv"), "title\n").unwrap();
+        std::fs::write(root.join("secret.csv"), "title\n").unwrap();
+
+        let resolved = resolve_import_path(&import_dir, "kaggle/books.csv").await.unwrap();
+        assert!(resolved.ends_with("imports/kaggle/books.csv"));
+
+        for path in ["../secret.csv", root.join("secret.csv").to_str().unwrap(), "missing.csv", "kaggle"] {
+            match resolve_import_path(&import_dir, path).await {
+                Err(AppError::Validation(message)) => assert_eq!(message, "Import file not found"),
+                other => panic!("{} resolved to {:?}", path, other),
+            }
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
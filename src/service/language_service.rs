@@ -1,4 +1,3 @@
-use anyhow::{Error, Result};
 use async_trait::async_trait;
 
 use crate::command::language_command::{
@@ -6,16 +5,18 @@ use crate::command::language_command::{
 };
 use crate::dto::language_dto::LanguageResponse;
 use crate::service::metadata_service::{MetadataService, MetadataServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
 use crate::shared::state::AppState;
 
 
 #[async_trait]
 pub trait LanguageServiceInterface {
-    async fn get(&self, cmd: LanguageGetCommand) -> Result<Option<LanguageResponse>, Error>;
-    async fn create(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, Error>;
-    async fn update(&self, cmd: LanguageUpdateCommand) -> Result<Option<LanguageResponse>, Error>;
-    async fn delete(&self, cmd: LanguageDeleteCommand) -> Result<(), Error>;
-    async fn list(&self, cmd: LanguageListCommand) -> Result<Vec<LanguageResponse>, Error>;
+    async fn get(&self, cmd: LanguageGetCommand) -> Result<LanguageResponse, AppError>;
+    async fn create(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, AppError>;
+    async fn update(&self, cmd: LanguageUpdateCommand) -> Result<LanguageResponse, AppError>;
+    async fn delete(&self, cmd: LanguageDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: LanguageListCommand) -> Result<PaginatedResponse<LanguageResponse>, AppError>;
 }
 
 
@@ -40,23 +41,23 @@ impl LanguageService {
 
 #[async_trait]
 impl LanguageServiceInterface for LanguageService {
-    async fn get(&self, cmd: LanguageGetCommand) -> Result<Option<LanguageResponse>, Error> {
+    async fn get(&self, cmd: LanguageGetCommand) -> Result<LanguageResponse, AppError> {
         self.metadata_service.get_language(cmd).await
     }
     
-    async fn create(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, Error> {
+    async fn create(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, AppError> {
         self.metadata_service.create_language(cmd).await
     }
     
-    async fn update(&self, cmd: LanguageUpdateCommand) -> Result<Option<LanguageResponse>, Error> {
+    async fn update(&self, cmd: LanguageUpdateCommand) -> Result<LanguageResponse, AppError> {
         self.metadata_service.update_language(cmd).await
     }
     
-    async fn delete(&self, cmd: LanguageDeleteCommand) -> Result<(), Error> {
+    async fn delete(&self, cmd: LanguageDeleteCommand) -> Result<(), AppError> {
         self.metadata_service.delete_language(cmd).await
     }
     
-    async fn list(&self, cmd: LanguageListCommand) -> Result<Vec<LanguageResponse>, Error> {
+    async fn list(&self, cmd: LanguageListCommand) -> Result<PaginatedResponse<LanguageResponse>, AppError> {
         self.metadata_service.list_languages(cmd).await
     }
 }
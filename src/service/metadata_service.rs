@@ -1,4 +1,3 @@
-use anyhow::{Error, Result};
 use async_trait::async_trait;
 use bb8::Pool;
 use bb8_redis::RedisConnectionManager;
@@ -25,7 +24,9 @@ use crate::dto::{
 };
 use crate::model::metadata_model::{Metadata, MetadataKey};
 use crate::repository::metadata_repository::{MetadataRepository, MetadataRepositoryInterface};
-use crate::shared::database::redis::{delete_key, get_key, set_key};
+use crate::shared::database::redis::{delete_key, delete_keys_by_prefix, get_key, set_key};
+use crate::shared::error::AppError;
+use crate::shared::models::response::{PaginatedResponse, PaginationRequest};
 use crate::shared::state::AppState;
 
 
@@ -33,32 +34,32 @@ use crate::shared::state::AppState;
 pub trait MetadataServiceInterface {
 
     // Genre
-    async fn get_genre(&self, cmd: GenreGetCommand) -> Result<Option<GenreResponse>, Error>;
-    async fn create_genre(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, Error>;
-    async fn update_genre(&self, cmd: GenreUpdateCommand) -> Result<Option<GenreResponse>, Error>;
-    async fn delete_genre(&self, cmd: GenreDeleteCommand) -> Result<(), Error>;
-    async fn list_genres(&self, _: GenreListCommand) -> Result<Vec<GenreResponse>, Error>;
+    async fn get_genre(&self, cmd: GenreGetCommand) -> Result<GenreResponse, AppError>;
+    async fn create_genre(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, AppError>;
+    async fn update_genre(&self, cmd: GenreUpdateCommand) -> Result<GenreResponse, AppError>;
+    async fn delete_genre(&self, cmd: GenreDeleteCommand) -> Result<(), AppError>;
+    async fn list_genres(&self, cmd: GenreListCommand) -> Result<PaginatedResponse<GenreResponse>, AppError>;
 
     // Language
-    async fn get_language(&self, cmd: LanguageGetCommand) -> Result<Option<LanguageResponse>, Error>;
-    async fn create_language(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, Error>;
-    async fn update_language(&self, cmd: LanguageUpdateCommand) -> Result<Option<LanguageResponse>, Error>;
-    async fn delete_language(&self, cmd: LanguageDeleteCommand) -> Result<(), Error>;
-    async fn list_languages(&self, _: LanguageListCommand) -> Result<Vec<LanguageResponse>, Error>;
+    async fn get_language(&self, cmd: LanguageGetCommand) -> Result<LanguageResponse, AppError>;
+    async fn create_language(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, AppError>;
+    async fn update_language(&self, cmd: LanguageUpdateCommand) -> Result<LanguageResponse, AppError>;
+    async fn delete_language(&self, cmd: LanguageDeleteCommand) -> Result<(), AppError>;
+    async fn list_languages(&self, cmd: LanguageListCommand) -> Result<PaginatedResponse<LanguageResponse>, AppError>;
     
     // Publisher
-    async fn get_publisher(&self, cmd: PublisherGetCommand) -> Result<Option<PublisherResponse>, Error>;
-    async fn create_publisher(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, Error>;
-    async fn update_publisher(&self, cmd: PublisherUpdateCommand) -> Result<Option<PublisherResponse>, Error>;
-    async fn delete_publisher(&self, cmd: PublisherDeleteCommand) -> Result<(), Error>;
-    async fn list_publishers(&self, _: PublisherListCommand) -> Result<Vec<PublisherResponse>, Error>;
+    async fn get_publisher(&self, cmd: PublisherGetCommand) -> Result<PublisherResponse, AppError>;
+    async fn create_publisher(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, AppError>;
+    async fn update_publisher(&self, cmd: PublisherUpdateCommand) -> Result<PublisherResponse, AppError>;
+    async fn delete_publisher(&self, cmd: PublisherDeleteCommand) -> Result<(), AppError>;
+    async fn list_publishers(&self, cmd: PublisherListCommand) -> Result<PaginatedResponse<PublisherResponse>, AppError>;
 
     // Source
-    async fn get_source(&self, cmd: SourceGetCommand) -> Result<Option<SourceResponse>, Error>;
-    async fn create_source(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, Error>;
-    async fn update_source(&self, cmd: SourceUpdateCommand) -> Result<Option<SourceResponse>, Error>;
-    async fn delete_source(&self, cmd: SourceDeleteCommand) -> Result<(), Error>;
-    async fn list_sources(&self, _: SourceListCommand) -> Result<Vec<SourceResponse>, Error>;
+    async fn get_source(&self, cmd: SourceGetCommand) -> Result<SourceResponse, AppError>;
+    async fn create_source(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, AppError>;
+    async fn update_source(&self, cmd: SourceUpdateCommand) -> Result<SourceResponse, AppError>;
+    async fn delete_source(&self, cmd: SourceDeleteCommand) -> Result<(), AppError>;
+    async fn list_sources(&self, cmd: SourceListCommand) -> Result<PaginatedResponse<SourceResponse>, AppError>;
 }
 
 
@@ -85,8 +86,7 @@ impl From<&AppState> for MetadataService {
         Self::new(
             MetadataRepository::new(
                 app_state.mongo_client.clone(),
-                database,
-                app_state.neo4j_client.clone()
+                database
             ),
             Some(app_state.redis_pool.clone()),
             Some(space_name),
@@ -120,14 +120,19 @@ impl MetadataService {
         format!("{}{}:{}", self.redis_prefix_colon(), kind, key)
     }
 
-    // Generates: "booknet:source:list" or "booknet:genre:list"
-    fn list_cache_key(&self, kind: &str) -> String {
-        format!("{}{}:list", self.redis_prefix_colon(), kind)
+    // Generates: "booknet:source:list:" or "booknet:genre:list:", shared by every cached page
+    fn list_cache_prefix(&self, kind: &str) -> String {
+        format!("{}{}:list:", self.redis_prefix_colon(), kind)
     }
 
-    async fn clear_list_cache(&self, kind: &str) -> Result<(), Error> {
+    // Generates: "booknet:genre:list:page=1:size=10:search=:sort="
+    fn list_cache_key(&self, kind: &str, pagination: &PaginationRequest) -> String {
+        format!("{}{}", self.list_cache_prefix(kind), pagination.cache_key())
+    }
+
+    async fn clear_list_cache(&self, kind: &str) -> Result<(), AppError> {
         if let Some(pool) = &self.redis_pool {
-            let _ = delete_key(pool, &self.list_cache_key(kind)).await?;
+            delete_keys_by_prefix(pool, &self.list_cache_prefix(kind)).await?;
         }
         Ok(())
     }
@@ -136,7 +141,7 @@ impl MetadataService {
     // --- Generic Internal Logic (avoids code duplication) ---
 
 
-    async fn _get(&self, key: MetadataKey) -> Result<Option<Metadata>, Error> {
+    async fn _get(&self, key: MetadataKey) -> Result<Option<Metadata>, AppError> {
         let cache_key = self.cache_key(key.kind(), key.key());
 
         if let Some(pool) = &self.redis_pool {
@@ -148,38 +153,37 @@ impl MetadataService {
 
         let result = self.metadata_repo.find_by_key(key).await?;
 
-        if let Some(meta) = &result {
-            if let Some(pool) = &self.redis_pool {
-                let _ = set_key(pool, &cache_key, meta, Some(self.redis_ttl())).await?;
-            }
+        if let Some(meta) = &result
+            && let Some(pool) = &self.redis_pool
+        {
+            set_key(pool, &cache_key, meta, Some(self.redis_ttl())).await?;
         }
 
         Ok(result)
     }
 
 
-    async fn _create(&self, meta: Metadata) -> Result<Metadata, Error> {
+    async fn _create(&self, meta: Metadata) -> Result<Metadata, AppError> {
         let kind = meta.kind();
         let key_str = meta.key().to_string(); // clone strictly for string generation
 
         let created = self.metadata_repo.insert(meta).await?;
 
         if let Some(pool) = &self.redis_pool {
-            let _ = set_key(
+            set_key(
                 pool,
                 &self.cache_key(kind, &key_str),
                 &created,
                 Some(self.redis_ttl())
             ).await?;
-
-            let _ = delete_key(pool, &self.list_cache_key(kind)).await?;
         }
+        self.clear_list_cache(kind).await?;
 
         Ok(created)
     }
 
 
-    async fn _update(&self, meta: Metadata) -> Result<Option<Metadata>, Error> {
+    async fn _update(&self, meta: Metadata) -> Result<Option<Metadata>, AppError> {
         let kind = meta.kind();
         let key_str = meta.key().to_string();
 
@@ -187,231 +191,194 @@ impl MetadataService {
 
         if let Some(result) = &updated {
             if let Some(pool) = &self.redis_pool {
-                let _ = set_key(
+                set_key(
                     pool,
                     &self.cache_key(kind, &key_str),
                     result,
                     Some(self.redis_ttl())
                 ).await?;
-
-                let _ = delete_key(pool, &self.list_cache_key(kind)).await?;
             }
+            self.clear_list_cache(kind).await?;
         }
 
         Ok(updated)
     }
 
 
-    async fn _delete(&self, key: MetadataKey) -> Result<(), Error> {
+    async fn _delete(&self, key: MetadataKey) -> Result<(), AppError> {
         let kind = key.kind();
         let key_str = key.key().to_string();
 
         self.metadata_repo.delete(key).await?;
 
         if let Some(pool) = &self.redis_pool {
-            let _ = delete_key(pool, &self.cache_key(kind, &key_str)).await?;
-            let _ = delete_key(pool, &self.list_cache_key(kind)).await?;
+            delete_key(pool, &self.cache_key(kind, &key_str)).await?;
         }
+        self.clear_list_cache(kind).await?;
 
         Ok(())
     }
 
 
-    async fn _list(&self, kind: &str) -> Result<Vec<Metadata>, Error> {
-        let cache_key = self.list_cache_key(kind);
+    async fn _list(&self, kind: &str, pagination: &PaginationRequest) -> Result<(Vec<Metadata>, u64), AppError> {
+        let cache_key = self.list_cache_key(kind, pagination);
 
         if let Some(pool) = &self.redis_pool {
-            let cached: Option<Vec<Metadata>> = get_key(pool, &cache_key).await?;
-            if let Some(list) = cached {
-                return Ok(list);
+            let cached: Option<(Vec<Metadata>, u64)> = get_key(pool, &cache_key).await?;
+            if let Some(page) = cached {
+                return Ok(page);
             }
         }
 
-        let list = self.metadata_repo.find_all_by_type(kind).await?;
+        let page = self.metadata_repo.find_page_by_type(kind, pagination).await?;
 
         if let Some(pool) = &self.redis_pool {
-            let _ = set_key(pool, &cache_key, &list, Some(self.redis_ttl())).await?;
+            set_key(pool, &cache_key, &page, Some(self.redis_ttl())).await?;
         }
 
-        Ok(list)
+        Ok(page)
     }
 }
 
 #[async_trait]
 impl MetadataServiceInterface for MetadataService {
-    
-
-    
 
     // --- Genre Implementation ---
 
-    async fn get_genre(&self, cmd: GenreGetCommand) -> Result<Option<GenreResponse>, Error> {
-        let metadata = self._get(MetadataKey::Genre { name: cmd.id }).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(GenreResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while getting metadata from database"))
-        }
+    async fn get_genre(&self, cmd: GenreGetCommand) -> Result<GenreResponse, AppError> {
+        let metadata = self._get(MetadataKey::Genre { name: cmd.id.clone() }).await?;
+        metadata
+            .map(GenreResponse::from)
+            .ok_or_else(|| AppError::not_found("Genre", &cmd.id))
     }
 
-    async fn create_genre(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, Error> {
+    async fn create_genre(&self, cmd: GenreCreateCommand) -> Result<GenreResponse, AppError> {
         let meta = Metadata::new_genre(cmd.name, cmd.description);
-        let metadata = self._create(meta).await;
-        match metadata {
-            Ok(meta) => Ok(GenreResponse::from(meta)),
-            Err(_) => Err(Error::msg("Error while creating metadata in database"))
-        }
+        let metadata = self._create(meta).await?;
+        Ok(GenreResponse::from(metadata))
     }
 
-    async fn update_genre(&self, cmd: GenreUpdateCommand) -> Result<Option<GenreResponse>, Error> {
+    async fn update_genre(&self, cmd: GenreUpdateCommand) -> Result<GenreResponse, AppError> {
+        let key = cmd.name.clone();
         let meta = Metadata::new_genre(cmd.name, cmd.description);
-        let metadata = self._update(meta).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(GenreResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while updating metadata in database"))
-        }
+        let metadata = self._update(meta).await?;
+        metadata
+            .map(GenreResponse::from)
+            .ok_or_else(|| AppError::not_found("Genre", &key))
     }
 
-    async fn delete_genre(&self, cmd: GenreDeleteCommand) -> Result<(), Error> {
+    async fn delete_genre(&self, cmd: GenreDeleteCommand) -> Result<(), AppError> {
         self._delete(MetadataKey::Genre { name: cmd.id }).await
     }
 
-    async fn list_genres(&self, _: GenreListCommand) -> Result<Vec<GenreResponse>, Error> {
-        let genres = self._list("genre").await;
-        match genres {
-            Ok(genres) => Ok(genres.into_iter().map(GenreResponse::from).collect()),
-            Err(_) => Err(Error::msg("Error while listing genres from database"))
-        }
+    async fn list_genres(&self, cmd: GenreListCommand) -> Result<PaginatedResponse<GenreResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (genres, total) = self._list("genre", &pagination).await?;
+        Ok(PaginatedResponse::new(genres.into_iter().map(GenreResponse::from).collect(), total, &pagination))
     }
 
 
     // --- Language Implementation ---
 
-    async fn get_language(&self, cmd: LanguageGetCommand) -> Result<Option<LanguageResponse>, Error> {
-        let metadata = self._get(MetadataKey::Language { code: cmd.id.to_string() }).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(LanguageResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while getting metadata from database"))
-        }
+    async fn get_language(&self, cmd: LanguageGetCommand) -> Result<LanguageResponse, AppError> {
+        let metadata = self._get(MetadataKey::Language { code: cmd.id.clone() }).await?;
+        metadata
+            .map(LanguageResponse::from)
+            .ok_or_else(|| AppError::not_found("Language", &cmd.id))
     }
 
-    async fn create_language(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, Error> {
+    async fn create_language(&self, cmd: LanguageCreateCommand) -> Result<LanguageResponse, AppError> {
         let meta = Metadata::new_language(cmd.code, cmd.name);
-        let metadata = self._create(meta).await;
-        match metadata {
-            Ok(meta) => Ok(LanguageResponse::from(meta)),
-            Err(_) => Err(Error::msg("Error while creating metadata in database"))
-        }
+        let metadata = self._create(meta).await?;
+        Ok(LanguageResponse::from(metadata))
     }
 
-    async fn update_language(&self, cmd: LanguageUpdateCommand) -> Result<Option<LanguageResponse>, Error> {
+    async fn update_language(&self, cmd: LanguageUpdateCommand) -> Result<LanguageResponse, AppError> {
+        let key = cmd.code.clone();
         let meta = Metadata::new_language(cmd.code, cmd.name);
-        let metadata = self._update(meta).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(LanguageResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while updating metadata in database"))
-        }
+        let metadata = self._update(meta).await?;
+        metadata
+            .map(LanguageResponse::from)
+            .ok_or_else(|| AppError::not_found("Language", &key))
     }
 
-    async fn delete_language(&self, cmd: LanguageDeleteCommand) -> Result<(), Error> {
+    async fn delete_language(&self, cmd: LanguageDeleteCommand) -> Result<(), AppError> {
         self._delete(MetadataKey::Language { code: cmd.id }).await
     }
 
-    async fn list_languages(&self, _: LanguageListCommand) -> Result<Vec<LanguageResponse>, Error> {
-        let languages = self._list("language").await;
-        match languages {
-            Ok(languages) => Ok(languages.into_iter().map(LanguageResponse::from).collect()),
-            Err(_) => Err(Error::msg("Error while listing languages from database"))
-        }
+    async fn list_languages(&self, cmd: LanguageListCommand) -> Result<PaginatedResponse<LanguageResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (languages, total) = self._list("language", &pagination).await?;
+        Ok(PaginatedResponse::new(languages.into_iter().map(LanguageResponse::from).collect(), total, &pagination))
     }
-    
-    
+
+
     // --- Publisher Implementation ---
-    
-    async fn get_publisher(&self, cmd: PublisherGetCommand) -> Result<Option<PublisherResponse>, Error> {
-        let metadata = self._get(MetadataKey::Publisher { name: cmd.id }).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(PublisherResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while getting metadata from database"))
-        }
+
+    async fn get_publisher(&self, cmd: PublisherGetCommand) -> Result<PublisherResponse, AppError> {
+        let metadata = self._get(MetadataKey::Publisher { name: cmd.id.clone() }).await?;
+        metadata
+            .map(PublisherResponse::from)
+            .ok_or_else(|| AppError::not_found("Publisher", &cmd.id))
     }
 
-    async fn create_publisher(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, Error> {
+    async fn create_publisher(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, AppError> {
         let meta = Metadata::new_publisher(cmd.name, cmd.website);
-        let metadata = self._create(meta).await;
-        match metadata {
-            Ok(meta) => Ok(PublisherResponse::from(meta)),
-            Err(_) => Err(Error::msg("Error while creating metadata in database"))
-        }
+        let metadata = self._create(meta).await?;
+        Ok(PublisherResponse::from(metadata))
     }
 
-    async fn update_publisher(&self, cmd: PublisherUpdateCommand) -> Result<Option<PublisherResponse>, Error> {
+    async fn update_publisher(&self, cmd: PublisherUpdateCommand) -> Result<PublisherResponse, AppError> {
+        let key = cmd.name.clone();
         let meta = Metadata::new_publisher(cmd.name, cmd.website);
-        let metadata = self._update(meta).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(PublisherResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while updating metadata in database"))
-        }
+        let metadata = self._update(meta).await?;
+        metadata
+            .map(PublisherResponse::from)
+            .ok_or_else(|| AppError::not_found("Publisher", &key))
     }
-    
-    async fn delete_publisher(&self, cmd: PublisherDeleteCommand) -> Result<(), Error> {
+
+    async fn delete_publisher(&self, cmd: PublisherDeleteCommand) -> Result<(), AppError> {
         self._delete(MetadataKey::Publisher { name: cmd.id }).await
     }
-    
-    async fn list_publishers(&self, _: PublisherListCommand) -> Result<Vec<PublisherResponse>, Error> {
-        let publishers = self._list("publisher").await;
-        match publishers {
-            Ok(publishers) => Ok(publishers.into_iter().map(PublisherResponse::from).collect()),
-            Err(_) => Err(Error::msg("Error while listing publishers from database"))
-        }
+
+    async fn list_publishers(&self, cmd: PublisherListCommand) -> Result<PaginatedResponse<PublisherResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (publishers, total) = self._list("publisher", &pagination).await?;
+        Ok(PaginatedResponse::new(publishers.into_iter().map(PublisherResponse::from).collect(), total, &pagination))
     }
 
 
     // --- Source Implementation ---
 
-    async fn get_source(&self, cmd: SourceGetCommand) -> Result<Option<SourceResponse>, Error> {
-        let metadata = self._get(MetadataKey::Source { name: cmd.id }).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(SourceResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while getting metadata from database"))
-        }
+    async fn get_source(&self, cmd: SourceGetCommand) -> Result<SourceResponse, AppError> {
+        let metadata = self._get(MetadataKey::Source { name: cmd.id.clone() }).await?;
+        metadata
+            .map(SourceResponse::from)
+            .ok_or_else(|| AppError::not_found("Source", &cmd.id))
     }
 
-    async fn create_source(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, Error> {
+    async fn create_source(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, AppError> {
         let meta = Metadata::new_source(cmd.name, cmd.website);
-        let metadata = self._create(meta).await;
-        match metadata {
-            Ok(meta) => Ok(SourceResponse::from(meta)),
-            Err(_) => Err(Error::msg("Error while creating metadata in database"))
-        }
+        let metadata = self._create(meta).await?;
+        Ok(SourceResponse::from(metadata))
     }
 
-    async fn update_source(&self, cmd: SourceUpdateCommand) -> Result<Option<SourceResponse>, Error> {
+    async fn update_source(&self, cmd: SourceUpdateCommand) -> Result<SourceResponse, AppError> {
+        let key = cmd.name.clone();
         let meta = Metadata::new_source(cmd.name, cmd.website);
-        let metadata = self._update(meta).await;
-        match metadata {
-            Ok(Some(meta)) => Ok(Some(SourceResponse::from(meta))),
-            Ok(None) => Ok(None),
-            Err(_) => Err(Error::msg("Error while updating metadata in database"))
-        }
+        let metadata = self._update(meta).await?;
+        metadata
+            .map(SourceResponse::from)
+            .ok_or_else(|| AppError::not_found("Source", &key))
     }
 
-    async fn delete_source(&self, cmd: SourceDeleteCommand) -> Result<(), Error> {
+    async fn delete_source(&self, cmd: SourceDeleteCommand) -> Result<(), AppError> {
         self._delete(MetadataKey::Source { name: cmd.id }).await
     }
 
-    async fn list_sources(&self, _: SourceListCommand) -> Result<Vec<SourceResponse>, Error> {
-        let sources = self._list("source").await;
-        match sources {
-            Ok(sources) => Ok(sources.into_iter().map(SourceResponse::from).collect()),
-            Err(_) => Err(Error::msg("Error while listing sources from database"))
-        }
+    async fn list_sources(&self, cmd: SourceListCommand) -> Result<PaginatedResponse<SourceResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (sources, total) = self._list("source", &pagination).await?;
+        Ok(PaginatedResponse::new(sources.into_iter().map(SourceResponse::from).collect(), total, &pagination))
     }
 }
-
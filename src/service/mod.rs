@@ -2,4 +2,21 @@ pub(crate) mod metadata_service;
 pub mod source_service;
 pub mod language_service;
 pub mod genre_service;
-pub mod publisher_service;
\ No newline at end of file
+pub mod publisher_service;
+pub mod auth_service;
+pub mod credential_service;
+pub mod user_service;
+pub mod book_service;
+pub mod author_service;
+pub mod review_service;
+pub mod shelf_service;
+pub mod graph_sync_service;
+pub mod reconciliation_service;
+pub mod recommendation_service;
+pub mod similarity_service;
+pub mod follow_service;
+pub mod search_service;
+pub mod import_service;
+pub mod export_service;
+pub mod dedup_service;
+pub mod work_service;
\ No newline at end of file
@@ -1,4 +1,3 @@
-use anyhow::{Error, Result};
 use async_trait::async_trait;
 
 
@@ -7,16 +6,18 @@ use crate::command::publisher_command::{
 };
 use crate::dto::publisher_dto::PublisherResponse;
 use crate::service::metadata_service::{MetadataService, MetadataServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
 use crate::shared::state::AppState;
 
 
 #[async_trait]
 pub trait PublisherServiceInterface {
-    async fn get(&self, cmd: PublisherGetCommand) -> Result<Option<PublisherResponse>, Error>;
-    async fn create(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, Error>;
-    async fn update(&self, cmd: PublisherUpdateCommand) -> Result<Option<PublisherResponse>, Error>;
-    async fn delete(&self, cmd: PublisherDeleteCommand) -> Result<(), Error>;
-    async fn list(&self, cmd: PublisherListCommand) -> Result<Vec<PublisherResponse>, Error>;
+    async fn get(&self, cmd: PublisherGetCommand) -> Result<PublisherResponse, AppError>;
+    async fn create(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, AppError>;
+    async fn update(&self, cmd: PublisherUpdateCommand) -> Result<PublisherResponse, AppError>;
+    async fn delete(&self, cmd: PublisherDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: PublisherListCommand) -> Result<PaginatedResponse<PublisherResponse>, AppError>;
 }
 
 
@@ -41,23 +42,23 @@ impl PublisherService {
 
 #[async_trait]
 impl PublisherServiceInterface for PublisherService {
-    async fn get(&self, cmd: PublisherGetCommand) -> Result<Option<PublisherResponse>, Error> {
+    async fn get(&self, cmd: PublisherGetCommand) -> Result<PublisherResponse, AppError> {
         self.metadata_service.get_publisher(cmd).await
     }
     
-    async fn create(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, Error> {
+    async fn create(&self, cmd: PublisherCreateCommand) -> Result<PublisherResponse, AppError> {
         self.metadata_service.create_publisher(cmd).await
     }
     
-    async fn update(&self, cmd: PublisherUpdateCommand) -> Result<Option<PublisherResponse>, Error> {
+    async fn update(&self, cmd: PublisherUpdateCommand) -> Result<PublisherResponse, AppError> {
         self.metadata_service.update_publisher(cmd).await
     }
     
-    async fn delete(&self, cmd: PublisherDeleteCommand) -> Result<(), Error> {
+    async fn delete(&self, cmd: PublisherDeleteCommand) -> Result<(), AppError> {
         self.metadata_service.delete_publisher(cmd).await
     }
     
-    async fn list(&self, cmd: PublisherListCommand) -> Result<Vec<PublisherResponse>, Error> {
+    async fn list(&self, cmd: PublisherListCommand) -> Result<PaginatedResponse<PublisherResponse>, AppError> {
         self.metadata_service.list_publishers(cmd).await
     }
 }
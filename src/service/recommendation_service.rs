@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+
+use crate::command::recommendation_command::RecommendationListCommand;
+use crate::dto::book_dto::BookResponse;
+use crate::dto::recommendation_dto::RecommendationResponse;
+use crate::model::recommendation_model::{RecommendationCandidate, RecommendationSignal};
+use crate::model::user_model::User;
+use crate::repository::book_repository::{BookRepository, BookRepositoryInterface};
+use crate::repository::recommendation_repository::{RecommendationRepository, RecommendationRepositoryInterface};
+use crate::repository::user_repository::{UserRepository, UserRepositoryInterface};
+use crate::shared::constant::{
+    RECOMMENDATION_CACHE_TTL_SECS, RECOMMENDATION_LANGUAGE_BOOST, RECOMMENDATION_WEIGHT_COLLABORATIVE,
+    RECOMMENDATION_WEIGHT_CONTENT, RECOMMENDATION_WEIGHT_PREFERENCE, REVIEW_SCORE_MAX,
+};
+use crate::shared::database::redis::{get_key, set_key};
+use crate::shared::error::AppError;
+use crate::shared::logging::log;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait RecommendationServiceInterface {
+    /// Books ranked for the user, falling back to the best rated ones when the graph knows nothing about them.
+    async fn list(&self, cmd: RecommendationListCommand) -> Result<Vec<RecommendationResponse>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct RecommendationService {
+    recommendation_repo: RecommendationRepository,
+    user_repo: UserRepository,
+    book_repo: BookRepository,
+    redis_pool: Option<Pool<RedisConnectionManager>>,
+    space_name: Option<String>,
+}
+
+impl From<&AppState> for RecommendationService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+        let space_name = app_state
+            .config
+            .database
+            .redis
+            .as_ref()
+            .and_then(|r| r.app_space_name.as_deref())
+            .unwrap_or("booknet")
+            .to_string();
+
+        Self::new(
+            RecommendationRepository::new(app_state.neo4j_client.clone()),
+            UserRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            BookRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+            Some(app_state.redis_pool.clone()),
+            Some(space_name),
+        )
+    }
+}
+
+
+/// Running total of a book's weighted signals, summed over the editions of its work.
+#[derive(Default)]
+struct ScoredBook {
+    /// Edition recommended: the first one a signal proposed
+    book_id: String,
+    score: f64,
+    /// Reasons with the share of the score they contributed
+    reasons: Vec<(f64, String)>,
+}
+
+
+impl RecommendationService {
+    pub fn new(
+        recommendation_repo: RecommendationRepository,
+        user_repo: UserRepository,
+        book_repo: BookRepository,
+        redis_pool: Option<Pool<RedisConnectionManager>>,
+        space_name: Option<String>,
+    ) -> Self {
+        Self { recommendation_repo, user_repo, book_repo, redis_pool, space_name }
+    }
+
+    // Generates: "booknet:recommendation:<user_id>:limit=20"
+    // The graph is updated asynchronously through the outbox, so entries are only expired by their TTL.
+    fn cache_key(&self, user_id: &str, limit: u64) -> String {
+        let prefix = self.space_name
+            .as_deref()
+            .map(|s| format!("{s}:"))
+            .unwrap_or_default();
+        format!("{}recommendation:{}:limit={}", prefix, user_id, limit)
+    }
+
+    /// Best rated books the user has neither shelved nor rated, in any edition.
+    async fn popular(&self, user: &User, limit: u64) -> Result<Vec<RecommendationResponse>, AppError> {
+        let shelved = user.shelf.iter().flatten().map(|entry| entry.book_id).collect();
+        let reviews = user.reviews.clone().unwrap_or_default();
+        let books = self.book_repo.find_best_rated_excluding(shelved, reviews, limit).await?;
+
+        Ok(books
+            .into_iter()
+            .map(|book| {
+                let reason = format!(
+                    "Highly rated by readers ({:.1} from {} ratings)",
+                    book.rating.average, book.rating.count
+                );
+                RecommendationResponse {
+                    score: book.rating.average / f64::from(REVIEW_SCORE_MAX),
+                    reasons: vec![reason],
+                    book: BookResponse::from(book),
+                }
+            })
+            .collect())
+    }
+}
+
+fn weight(signal: RecommendationSignal) -> f64 {
+    match signal {
+        RecommendationSignal::Collaborative => RECOMMENDATION_WEIGHT_COLLABORATIVE,
+        RecommendationSignal::Content => RECOMMENDATION_WEIGHT_CONTENT,
+        RecommendationSignal::Preference => RECOMMENDATION_WEIGHT_PREFERENCE,
+    }
+}
+
+fn reason(signal: RecommendationSignal, candidate: &RecommendationCandidate) -> String {
+    let names = candidate.evidence.iter().take(3).cloned().collect::<Vec<_>>().join(", ");
+    match signal {
+        RecommendationSignal::Collaborative => match candidate.evidence.len() {
+            1 => "Liked by a reader who rates books like you".to_string(),
+            n => format!("Liked by {} readers who rate books like you", n),
+        },
+        RecommendationSignal::Content => format!("Shares {} with books you liked", names),
+        RecommendationSignal::Preference => format!("Matches your preferred {}", names),
+    }
+}
+
+
+#[async_trait]
+impl RecommendationServiceInterface for RecommendationService {
+    async fn list(&self, cmd: RecommendationListCommand) -> Result<Vec<RecommendationResponse>, AppError> {
+        let cache_key = self.cache_key(&cmd.user_id, cmd.limit);
+        if let Some(pool) = &self.redis_pool {
+            let cached: Option<Vec<RecommendationResponse>> = get_key(pool, &cache_key).await?;
+            if let Some(cached) = cached {
+                return Ok(cached);
+            }
+        }
+
+        let user = match self.user_repo.find_by_id(&cmd.user_id).await? {
+            Some(user) => user,
+            None => return Err(AppError::not_found("User", &cmd.user_id)),
+        };
+        let preference = user.preference.clone().unwrap_or_default();
+
+        // Every signal over-fetches so that books proposed by several of them can rise to the top
+        let mut scored: HashMap<String, ScoredBook> = HashMap::new();
+        for signal in [RecommendationSignal::Collaborative, RecommendationSignal::Content, RecommendationSignal::Preference] {
+            if signal == RecommendationSignal::Preference && preference.authors.is_empty() && preference.genres.is_empty() {
+                continue;
+            }
+
+            let candidates = self.recommendation_repo
+                .find_candidates(signal, &cmd.user_id, &preference.authors, &preference.genres, cmd.limit * 3)
+                .await?;
+            let max_score = candidates.iter().map(|c| c.score).fold(0.0, f64::max);
+            if max_score <= 0.0 {
+                continue;
+            }
+
+            for candidate in candidates {
+                let share = weight(signal) * candidate.score / max_score;
+                let entry = scored
+                    .entry(candidate.group_key().to_string())
+                    .or_insert_with(|| ScoredBook { book_id: candidate.book_id.clone(), ..ScoredBook::default() });
+                entry.score += share;
+                entry.reasons.push((share, reason(signal, &candidate)));
+            }
+        }
+
+        let recommendations = if scored.is_empty() {
+            self.popular(&user, cmd.limit).await?
+        } else {
+            let mut scored: HashMap<String, ScoredBook> = scored.into_values().map(|book| (book.book_id.clone(), book)).collect();
+            let books = self.book_repo.find_by_ids(scored.keys().map(String::as_str).collect()).await?;
+
+            let mut recommendations: Vec<RecommendationResponse> = books
+                .into_iter()
+                .filter_map(|book| {
+                    let ScoredBook { mut score, mut reasons, .. } = scored.remove(&book.id?.to_hex())?;
+                    reasons.sort_by(|a, b| b.0.total_cmp(&a.0));
+                    let mut reasons: Vec<String> = reasons.into_iter().map(|(_, reason)| reason).collect();
+
+                    if let Some(language) = book.languages.iter().find(|l| preference.languages.contains(l)) {
+                        score *= RECOMMENDATION_LANGUAGE_BOOST;
+                        reasons.push(format!("Available in your preferred language {}", language));
+                    }
+                    Some(RecommendationResponse { book: BookResponse::from(book), score, reasons })
+                })
+                .collect();
+
+            recommendations.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.book.id.cmp(&b.book.id)));
+            recommendations.truncate(cmd.limit as usize);
+            recommendations
+        };
+
+        if let Some(pool) = &self.redis_pool {
+            // A miss only costs the next request a recomputation
+            if let Err(e) = set_key(pool, &cache_key, &recommendations, Some(RECOMMENDATION_CACHE_TTL_SECS)).await {
+                log::warning(&format!("[RECOMMENDATION] Unable to cache {}: {}", cache_key, e));
+            }
+        }
+        Ok(recommendations)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(evidence: &[&str]) -> RecommendationCandidate {
+        RecommendationCandidate {
+            book_id: "65f0c0ffee0000000000b001".to_string(),
+            work_id: None,
+            score: 1.0,
+            evidence: evidence.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn signal_weights_sum_to_one() {
+        let total: f64 = [RecommendationSignal::Collaborative, RecommendationSignal::Content, RecommendationSignal::Preference]
+            .into_iter()
+            .map(weight)
+            .sum();
+
+        assert!((total - 1.0).abs() < 1e-9, "{}", total);
+    }
+
+    #[test]
+    fn collaborative_reason_counts_the_readers() {
+        assert_eq!(reason(RecommendationSignal::Collaborative, &candidate(&["u1"])), "Liked by a reader who rates books like you");
+        assert_eq!(reason(RecommendationSignal::Collaborative, &candidate(&["u1", "u2"])), "Liked by 2 readers who rate books like you");
+    }
+
+    #[test]
+    fn content_and_preference_reasons_name_at_most_three_matches() {
+        let candidate = candidate(&["fantasy", "tor", "An Author", "horror"]);
+
+        assert_eq!(reason(RecommendationSignal::Content, &candidate), "Shares fantasy, tor, An Author with books you liked");
+        assert_eq!(reason(RecommendationSignal::Preference, &candidate), "Matches your preferred fantasy, tor, An Author");
+    }
+
+    #[test]
+    fn editions_of_a_work_share_a_group_key() {
+        let mut edition = candidate(&[]);
+        assert_eq!(edition.group_key(), "65f0c0ffee0000000000b001");
+
+        edition.work_id = Some("65f0c0ffee0000000000c001".to_string());
+        assert_eq!(edition.group_key(), "65f0c0ffee0000000000c001");
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::model::graph_sync_model::GraphSyncOp;
+use crate::model::reconciliation_model::{Drift, DriftCategory, ReconciliationReport};
+use crate::repository::reconciliation_repository::{EdgeKey, ReconciliationRepository, ReconciliationRepositoryInterface};
+use crate::shared::constant::RECONCILE_REPAIR_CHUNK_SIZE;
+use crate::shared::error::AppError;
+use crate::shared::state::AppState;
+
+
+fn edge_label(kind: &str) -> impl Fn(&EdgeKey) -> String + '_ {
+    move |(from, to)| format!("{} -[{}]-> {}", from, kind, to)
+}
+
+/// Reviews only hold whole stars, formatted the same way as Neo4j's `toString` of a float
+fn rating_value(score: f64) -> String {
+    format!("{:.1}", score)
+}
+
+
+/// Compares MongoDB, the source of truth, with the Neo4j graph and reports the drift by category.
+/// Repairs are enqueued as graph-sync events: upserts first, then removals of orphaned nodes and relationships.
+#[derive(Clone)]
+pub struct ReconciliationService {
+    reconciliation_repo: ReconciliationRepository,
+}
+
+impl From<&AppState> for ReconciliationService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(ReconciliationRepository::new(database, app_state.neo4j_client.clone()))
+    }
+}
+
+impl ReconciliationService {
+    pub fn new(reconciliation_repo: ReconciliationRepository) -> Self {
+        Self { reconciliation_repo }
+    }
+
+    pub async fn reconcile(&self, repair: bool) -> Result<ReconciliationReport, AppError> {
+        let repo = &self.reconciliation_repo;
+        let mut categories = Vec::new();
+        let mut upserts: Vec<GraphSyncOp> = Vec::new();
+        let mut removals: Vec<GraphSyncOp> = Vec::new();
+
+        // Mongo collections are streamed once, keeping only the keys and values being compared
+        let genres = repo.find_genres().await?;
+
+        let mut authors: HashMap<String, String> = HashMap::new();
+        repo.for_each_author(&mut |author| {
+            if let Some(id) = author.id {
+                authors.insert(id.to_hex(), author.name);
+            }
+        }).await?;
+
+        let mut works: HashMap<String, String> = HashMap::new();
+        repo.for_each_work(&mut |work| {
+            if let Some(id) = work.id {
+                works.insert(id.to_hex(), work.title);
+            }
+        }).await?;
+
+        let mut titles: HashMap<String, String> = HashMap::new();
+        let mut edition_of: HashMap<EdgeKey, String> = HashMap::new();
+        let mut referenced: HashSet<String> = HashSet::new();
+        let mut wrote: HashMap<EdgeKey, String> = HashMap::new();
+        let mut has_genre: HashMap<EdgeKey, String> = HashMap::new();
+        repo.for_each_book(&mut |book| {
+            let Some(book_id) = book.id.map(|id| id.to_hex()) else { return };
+            for author in &book.authors {
+                // `WROTE` is only created towards existing Author nodes
+                if authors.contains_key(&author.id.to_hex()) {
+                    wrote.insert((author.id.to_hex(), book_id.clone()), String::new());
+                }
+            }
+            for genre in book.genres {
+                referenced.insert(genre.name.clone());
+                has_genre.insert((book_id.clone(), genre.name), String::new());
+            }
+            if let Some(work_id) = book.work_id.map(|id| id.to_hex()).filter(|work_id| works.contains_key(work_id)) {
+                edition_of.insert((book_id.clone(), work_id), String::new());
+            }
+            titles.insert(book_id, book.title);
+        }).await?;
+
+        let mut readers: HashMap<String, String> = HashMap::new();
+        let mut shelf: HashMap<EdgeKey, String> = HashMap::new();
+        let mut shelf_ts: HashMap<EdgeKey, i64> = HashMap::new();
+        repo.for_each_reader(&mut |user| {
+            let Some(user_id) = user.id.map(|id| id.to_hex()) else { return };
+            for entry in user.shelf.unwrap_or_default() {
+                let book_id = entry.book_id.to_hex();
+                // Shelf relationships can only point at existing Book nodes
+                if titles.contains_key(&book_id) {
+                    let key = (user_id.clone(), book_id);
+                    shelf_ts.insert(key.clone(), entry.status_updated_at.timestamp_millis());
+                    shelf.insert(key, entry.status.name().to_string());
+                }
+            }
+            readers.insert(user_id, user.name);
+        }).await?;
+
+        // Genres
+        let graph_genres = repo
+            .graph_nodes("MATCH (g:Genre) WHERE g.name IS NOT NULL RETURN g.name AS key, coalesce(g.description, '') AS value")
+            .await?;
+        let mut drift = Drift::between(&genres, &graph_genres);
+        // Books merge the genres they reference, those nodes are expected even without metadata
+        drift.orphaned.retain(|name| !referenced.contains(name));
+        categories.push(DriftCategory::new("Genre", genres.len(), graph_genres.len(), &drift, String::clone));
+        for name in drift.missing.iter().chain(&drift.mismatched) {
+            upserts.push(GraphSyncOp::UpsertGenre { name: name.clone(), description: genres[name].clone() });
+        }
+        for name in drift.orphaned {
+            removals.push(GraphSyncOp::DeleteGenre { name });
+        }
+
+        // Authors
+        let graph_authors = repo
+            .graph_nodes("MATCH (a:Author) WHERE a.author_id IS NOT NULL RETURN a.author_id AS key, coalesce(a.name, '') AS value")
+            .await?;
+        let drift = Drift::between(&authors, &graph_authors);
+        categories.push(DriftCategory::new("Author", authors.len(), graph_authors.len(), &drift, String::clone));
+        for author_id in drift.missing.iter().chain(&drift.mismatched) {
+            upserts.push(GraphSyncOp::UpsertAuthor { author_id: author_id.clone(), name: authors[author_id].clone() });
+        }
+        if !drift.orphaned.is_empty() {
+            removals.push(GraphSyncOp::DeleteAuthors { author_ids: drift.orphaned });
+        }
+
+        // Readers
+        let graph_readers = repo
+            .graph_nodes("MATCH (r:Reader) WHERE r.user_id IS NOT NULL RETURN r.user_id AS key, coalesce(r.name, '') AS value")
+            .await?;
+        let drift = Drift::between(&readers, &graph_readers);
+        categories.push(DriftCategory::new("Reader", readers.len(), graph_readers.len(), &drift, String::clone));
+        for user_id in drift.missing.iter().chain(&drift.mismatched) {
+            upserts.push(GraphSyncOp::UpsertReader { user_id: user_id.clone(), name: readers[user_id].clone() });
+        }
+        if !drift.orphaned.is_empty() {
+            removals.push(GraphSyncOp::DeleteReaders { user_ids: drift.orphaned });
+        }
+
+        // Books and their catalog links, any drift rebuilds the whole book
+        let graph_books = repo
+            .graph_nodes("MATCH (b:Book) WHERE b.book_id IS NOT NULL RETURN b.book_id AS key, coalesce(b.title, '') AS value")
+            .await?;
+        let drift = Drift::between(&titles, &graph_books);
+        categories.push(DriftCategory::new("Book", titles.len(), graph_books.len(), &drift, String::clone));
+        let mut rebuild: BTreeSet<String> = drift.missing.iter().chain(&drift.mismatched).cloned().collect();
+        if !drift.orphaned.is_empty() {
+            removals.push(GraphSyncOp::DeleteBooks { book_ids: drift.orphaned });
+        }
+
+        let graph_wrote = repo
+            .graph_edges("MATCH (a:Author)-[:WROTE]->(b:Book) RETURN coalesce(a.author_id, '') AS from, coalesce(b.book_id, '') AS to, '' AS value")
+            .await?;
+        let drift = Drift::between(&wrote, &graph_wrote);
+        categories.push(DriftCategory::new("WROTE", wrote.len(), graph_wrote.len(), &drift, edge_label("WROTE")));
+        rebuild.extend(drift.missing.into_iter().chain(drift.orphaned).map(|(_, book_id)| book_id));
+
+        let graph_has_genre = repo
+            .graph_edges("MATCH (b:Book)-[:HAS_GENRE]->(g:Genre) RETURN coalesce(b.book_id, '') AS from, coalesce(g.name, '') AS to, '' AS value")
+            .await?;
+        let drift = Drift::between(&has_genre, &graph_has_genre);
+        categories.push(DriftCategory::new("HAS_GENRE", has_genre.len(), graph_has_genre.len(), &drift, edge_label("HAS_GENRE")));
+        rebuild.extend(drift.missing.into_iter().chain(drift.orphaned).map(|(book_id, _)| book_id));
+
+        // Only the drifted books are read back in full, a page at a time.
+        // Links of books deleted from Mongo go away with their node.
+        let rebuild: Vec<String> = rebuild.into_iter().filter(|book_id| titles.contains_key(book_id)).collect();
+        for page in rebuild.chunks(RECONCILE_REPAIR_CHUNK_SIZE) {
+            for book in repo.find_books_by_ids(page).await? {
+                upserts.push(GraphSyncOp::upsert_book(&book));
+            }
+        }
+
+        // Works and their editions
+        let graph_works = repo
+            .graph_nodes("MATCH (w:Work) WHERE w.work_id IS NOT NULL RETURN w.work_id AS key, coalesce(w.title, '') AS value")
+            .await?;
+        let drift = Drift::between(&works, &graph_works);
+        categories.push(DriftCategory::new("Work", works.len(), graph_works.len(), &drift, String::clone));
+        for work_id in drift.missing.iter().chain(&drift.mismatched) {
+            upserts.push(GraphSyncOp::UpsertWork { work_id: work_id.clone(), title: works[work_id].clone() });
+        }
+        if !drift.orphaned.is_empty() {
+            removals.push(GraphSyncOp::DeleteWorks { work_ids: drift.orphaned });
+        }
+
+        let graph_edition_of = repo
+            .graph_edges("MATCH (b:Book)-[:EDITION_OF]->(w:Work) RETURN coalesce(b.book_id, '') AS from, coalesce(w.work_id, '') AS to, '' AS value")
+            .await?;
+        let drift = Drift::between(&edition_of, &graph_edition_of);
+        categories.push(DriftCategory::new("EDITION_OF", edition_of.len(), graph_edition_of.len(), &drift, edge_label("EDITION_OF")));
+        for (book_id, work_id) in drift.missing {
+            upserts.push(GraphSyncOp::LinkEdition { book_id, work_id });
+        }
+        // Linking a book to its work already drops its link to another one
+        let linked: HashSet<&String> = edition_of.keys().map(|(book_id, _)| book_id).collect();
+        for (book_id, _) in drift.orphaned {
+            if !linked.contains(&book_id) {
+                removals.push(GraphSyncOp::UnlinkEdition { book_id });
+            }
+        }
+
+        // Shelves
+        let graph_shelf = repo
+            .graph_edges("MATCH (r:Reader)-[rel:ADDED_TO_SHELF]->(b:Book) RETURN coalesce(r.user_id, '') AS from, coalesce(b.book_id, '') AS to, coalesce(rel.status, '') AS value")
+            .await?;
+        let drift = Drift::between(&shelf, &graph_shelf);
+        categories.push(DriftCategory::new("ADDED_TO_SHELF", shelf.len(), graph_shelf.len(), &drift, edge_label("ADDED_TO_SHELF")));
+        for key in drift.missing.iter().chain(&drift.mismatched) {
+            let (user_id, book_id) = key.clone();
+            upserts.push(GraphSyncOp::AddToShelf { user_id, book_id, status: shelf[key].clone(), ts: shelf_ts[key] });
+        }
+        for (user_id, book_id) in drift.orphaned {
+            removals.push(GraphSyncOp::RemoveFromShelf { user_id, book_id });
+        }
+
+        // Ratings
+        let mut ratings: HashMap<EdgeKey, String> = HashMap::new();
+        let mut rating_props: HashMap<EdgeKey, (f64, i64)> = HashMap::new();
+        repo.for_each_review(&mut |review| {
+            let key = (review.user.id.to_hex(), review.book_id.to_hex());
+            if readers.contains_key(&key.0) && titles.contains_key(&key.1) {
+                let ts = review.updated_at.or(review.date_added).map_or(0, |ts| ts.timestamp_millis());
+                rating_props.insert(key.clone(), (f64::from(review.score), ts));
+                ratings.insert(key, rating_value(f64::from(review.score)));
+            }
+        }).await?;
+        let graph_ratings = repo
+            .graph_edges("MATCH (u:Reader)-[r:RATED]->(b:Book) RETURN coalesce(u.user_id, '') AS from, coalesce(b.book_id, '') AS to, coalesce(toString(r.rating), '') AS value")
+            .await?;
+        let drift = Drift::between(&ratings, &graph_ratings);
+        categories.push(DriftCategory::new("RATED", ratings.len(), graph_ratings.len(), &drift, edge_label("RATED")));
+        for key in drift.missing.iter().chain(&drift.mismatched) {
+            let (user_id, book_id) = key.clone();
+            let (rating, ts) = rating_props[key];
+            upserts.push(GraphSyncOp::UpsertRating { user_id, book_id, rating, ts });
+        }
+        for (user_id, book_id) in drift.orphaned {
+            removals.push(GraphSyncOp::RemoveRating { user_id, book_id });
+        }
+
+        // Follows
+        let mut follows: HashMap<EdgeKey, String> = HashMap::new();
+        let mut follow_ts: HashMap<EdgeKey, i64> = HashMap::new();
+        repo.for_each_follow(&mut |follow| {
+            let key = (follow.follower_id.to_hex(), follow.followee_id.to_hex());
+            if readers.contains_key(&key.0) && readers.contains_key(&key.1) {
+                follow_ts.insert(key.clone(), follow.created_at.timestamp_millis());
+                follows.insert(key, String::new());
+            }
+        }).await?;
+        let graph_follows = repo
+            .graph_edges("MATCH (f:Reader)-[:FOLLOWS]->(t:Reader) RETURN coalesce(f.user_id, '') AS from, coalesce(t.user_id, '') AS to, '' AS value")
+            .await?;
+        let drift = Drift::between(&follows, &graph_follows);
+        categories.push(DriftCategory::new("FOLLOWS", follows.len(), graph_follows.len(), &drift, edge_label("FOLLOWS")));
+        for key in &drift.missing {
+            let (follower_id, followee_id) = key.clone();
+            upserts.push(GraphSyncOp::Follow { follower_id, followee_id, ts: follow_ts[key] });
+        }
+        for (follower_id, followee_id) in drift.orphaned {
+            removals.push(GraphSyncOp::Unfollow { follower_id, followee_id });
+        }
+
+        let repair_events = if repair {
+            upserts.extend(removals);
+            repo.enqueue(upserts).await?
+        } else {
+            0
+        };
+
+        Ok(ReconciliationReport { categories, repair, repair_events })
+    }
+}
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::command::review_command::{
+    ReviewBookListCommand, ReviewCreateCommand, ReviewDeleteCommand, ReviewGetCommand, ReviewUpdateCommand, ReviewUserListCommand,
+    ReviewWorkListCommand,
+};
+use crate::dto::review_dto::ReviewResponse;
+use crate::model::review_model::Review;
+use crate::model::user_model::UserEmbed;
+use crate::repository::book_repository::{BookRepository, BookRepositoryInterface};
+use crate::repository::review_repository::{ReviewRepository, ReviewRepositoryInterface};
+use crate::repository::user_repository::{UserRepository, UserRepositoryInterface};
+use crate::repository::work_repository::{WorkRepository, WorkRepositoryInterface};
+use crate::shared::constant::{REVIEW_SCORE_MAX, REVIEW_SCORE_MIN};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait ReviewServiceInterface {
+    async fn get(&self, cmd: ReviewGetCommand) -> Result<ReviewResponse, AppError>;
+    async fn create(&self, cmd: ReviewCreateCommand) -> Result<ReviewResponse, AppError>;
+    async fn update(&self, cmd: ReviewUpdateCommand) -> Result<ReviewResponse, AppError>;
+    async fn delete(&self, cmd: ReviewDeleteCommand) -> Result<(), AppError>;
+    async fn list_by_book(&self, cmd: ReviewBookListCommand) -> Result<PaginatedResponse<ReviewResponse>, AppError>;
+    async fn list_by_user(&self, cmd: ReviewUserListCommand) -> Result<PaginatedResponse<ReviewResponse>, AppError>;
+    /// Reviews of every edition of the work.
+    async fn list_by_work(&self, cmd: ReviewWorkListCommand) -> Result<PaginatedResponse<ReviewResponse>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct ReviewService {
+    review_repo: ReviewRepository,
+    book_repo: BookRepository,
+    user_repo: UserRepository,
+    work_repo: WorkRepository,
+}
+
+impl From<&AppState> for ReviewService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            ReviewRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            BookRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            UserRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            WorkRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+        )
+    }
+}
+
+impl ReviewService {
+    pub fn new(review_repo: ReviewRepository, book_repo: BookRepository, user_repo: UserRepository, work_repo: WorkRepository) -> Self {
+        Self { review_repo, book_repo, user_repo, work_repo }
+    }
+}
+
+fn check_score(score: f32) -> Result<(), AppError> {
+    match Review::score_bucket(score) {
+        Some(_) => Ok(()),
+        None => Err(AppError::Validation(format!(
+            "Score must be a whole number from {} to {}", REVIEW_SCORE_MIN, REVIEW_SCORE_MAX
+        ))),
+    }
+}
+
+
+#[async_trait]
+impl ReviewServiceInterface for ReviewService {
+    async fn get(&self, cmd: ReviewGetCommand) -> Result<ReviewResponse, AppError> {
+        let review = self.review_repo.find_by_id(&cmd.id).await?;
+        review.map(ReviewResponse::from).ok_or_else(|| AppError::not_found("Review", &cmd.id))
+    }
+
+    async fn create(&self, cmd: ReviewCreateCommand) -> Result<ReviewResponse, AppError> {
+        check_score(cmd.score)?;
+
+        let book = match self.book_repo.find_by_id(&cmd.book_id).await? {
+            Some(book) => book,
+            None => return Err(AppError::not_found("Book", &cmd.book_id)),
+        };
+        let user = match self.user_repo.find_by_id(&cmd.user_id).await? {
+            Some(user) => user,
+            None => return Err(AppError::not_found("User", &cmd.user_id)),
+        };
+
+        if self.review_repo.find_by_book_and_user(&cmd.book_id, &cmd.user_id).await?.is_some() {
+            return Err(AppError::Conflict(format!("Book {} already reviewed by the user", cmd.book_id)));
+        }
+
+        let review = Review {
+            id: None,
+            book_id: book.id.ok_or_else(|| AppError::not_found("Book", &cmd.book_id))?,
+            user: UserEmbed::from(&user),
+            content: cmd.content,
+            score: cmd.score,
+            date_added: Some(Utc::now()),
+            updated_at: None,
+        };
+
+        let review_id = self.review_repo.insert(review).await?;
+        let review = self.review_repo.find_by_id(&review_id).await?;
+        review.map(ReviewResponse::from).ok_or_else(|| AppError::not_found("Review", &review_id))
+    }
+
+    async fn update(&self, cmd: ReviewUpdateCommand) -> Result<ReviewResponse, AppError> {
+        check_score(cmd.score)?;
+
+        let existing = match self.review_repo.find_by_id(&cmd.id).await? {
+            Some(review) => review,
+            None => return Err(AppError::not_found("Review", &cmd.id)),
+        };
+        if existing.user.id.to_hex() != cmd.user_id {
+            return Err(AppError::Forbidden("Review written by another user".to_string()));
+        }
+
+        if !self.review_repo.update(&cmd.id, &cmd.content, cmd.score).await? {
+            return Err(AppError::not_found("Review", &cmd.id));
+        }
+
+        let review = self.review_repo.find_by_id(&cmd.id).await?;
+        review.map(ReviewResponse::from).ok_or_else(|| AppError::not_found("Review", &cmd.id))
+    }
+
+    async fn delete(&self, cmd: ReviewDeleteCommand) -> Result<(), AppError> {
+        let existing = match self.review_repo.find_by_id(&cmd.id).await? {
+            Some(review) => review,
+            None => return Err(AppError::not_found("Review", &cmd.id)),
+        };
+        // Admins moderate reviews, readers only remove their own
+        if !cmd.is_admin && existing.user.id.to_hex() != cmd.user_id {
+            return Err(AppError::Forbidden("Review written by another user".to_string()));
+        }
+
+        if !self.review_repo.delete(&cmd.id).await? {
+            return Err(AppError::not_found("Review", &cmd.id));
+        }
+        Ok(())
+    }
+
+    async fn list_by_book(&self, cmd: ReviewBookListCommand) -> Result<PaginatedResponse<ReviewResponse>, AppError> {
+        if self.book_repo.find_by_id(&cmd.book_id).await?.is_none() {
+            return Err(AppError::not_found("Book", &cmd.book_id));
+        }
+
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (reviews, total) = self.review_repo.find_by_book(&cmd.book_id, &pagination).await?;
+        Ok(PaginatedResponse::new(reviews.into_iter().map(ReviewResponse::from).collect(), total, &pagination))
+    }
+
+    async fn list_by_user(&self, cmd: ReviewUserListCommand) -> Result<PaginatedResponse<ReviewResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (reviews, total) = self.review_repo.find_by_user(&cmd.user_id, &pagination).await?;
+        Ok(PaginatedResponse::new(reviews.into_iter().map(ReviewResponse::from).collect(), total, &pagination))
+    }
+
+    async fn list_by_work(&self, cmd: ReviewWorkListCommand) -> Result<PaginatedResponse<ReviewResponse>, AppError> {
+        if self.work_repo.find_by_id(&cmd.work_id).await?.is_none() {
+            return Err(AppError::not_found("Work", &cmd.work_id));
+        }
+
+        let pagination = cmd.pagination.unwrap_or_default();
+        let book_ids: Vec<_> = self.work_repo
+            .find_editions(&cmd.work_id)
+            .await?
+            .into_iter()
+            .filter_map(|edition| edition.id)
+            .collect();
+        let (reviews, total) = self.review_repo.find_by_books(&book_ids, &pagination).await?;
+        Ok(PaginatedResponse::new(reviews.into_iter().map(ReviewResponse::from).collect(), total, &pagination))
+    }
+}
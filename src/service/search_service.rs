@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+
+use crate::command::search_command::SearchCommand;
+use crate::dto::author_dto::AuthorResponse;
+use crate::dto::book_dto::BookResponse;
+use crate::dto::genre_dto::GenreResponse;
+use crate::dto::publisher_dto::PublisherResponse;
+use crate::dto::search_dto::{
+    AuthorSearchHitResponse, BookSearchHitResponse, GenreSearchHitResponse, PublisherSearchHitResponse, SearchResponse,
+};
+use crate::model::search_model::BookSearchFilter;
+use crate::repository::search_repository::{SearchRepository, SearchRepositoryInterface};
+use crate::shared::constant::SEARCH_TEXT_LENGTH_MAX;
+use crate::shared::error::AppError;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait SearchServiceInterface {
+    /// Searches books, authors, genres and publishers together; the filters only narrow the books.
+    async fn search(&self, cmd: SearchCommand) -> Result<SearchResponse, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct SearchService {
+    search_repo: SearchRepository,
+}
+
+impl From<&AppState> for SearchService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(SearchRepository::new(database))
+    }
+}
+
+impl SearchService {
+    pub fn new(search_repo: SearchRepository) -> Self {
+        Self { search_repo }
+    }
+}
+
+#[async_trait]
+impl SearchServiceInterface for SearchService {
+    async fn search(&self, cmd: SearchCommand) -> Result<SearchResponse, AppError> {
+        let text = cmd.text.trim();
+        if text.is_empty() {
+            return Err(AppError::Validation("Search text must not be empty".to_string()));
+        }
+        if text.chars().count() > SEARCH_TEXT_LENGTH_MAX {
+            return Err(AppError::Validation(format!(
+                "Search text must be at most {} characters", SEARCH_TEXT_LENGTH_MAX
+            )));
+        }
+
+        let filter = BookSearchFilter { language: cmd.language, format: cmd.format, genre: cmd.genre };
+        let (books, authors, genres, publishers) = tokio::try_join!(
+            self.search_repo.search_books(text, &filter, cmd.limit),
+            self.search_repo.search_authors(text, cmd.limit),
+            self.search_repo.search_metadata("genre", text, cmd.limit),
+            self.search_repo.search_metadata("publisher", text, cmd.limit),
+        )?;
+
+        Ok(SearchResponse {
+            query: text.to_string(),
+            books: books
+                .into_iter()
+                .map(|hit| BookSearchHitResponse { book: BookResponse::from(hit.item), score: hit.score })
+                .collect(),
+            authors: authors
+                .into_iter()
+                .map(|hit| AuthorSearchHitResponse { author: AuthorResponse::from(hit.item), score: hit.score })
+                .collect(),
+            genres: genres
+                .into_iter()
+                .map(|hit| GenreSearchHitResponse { genre: GenreResponse::from(hit.item.meta), score: hit.score })
+                .collect(),
+            publishers: publishers
+                .into_iter()
+                .map(|hit| PublisherSearchHitResponse { publisher: PublisherResponse::from(hit.item.meta), score: hit.score })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::command::shelf_command::{ShelfAddCommand, ShelfListCommand, ShelfRemoveCommand, ShelfStatusUpdateCommand};
+use crate::dto::shelf_dto::ShelfEntryResponse;
+use crate::model::book_model::ShelfEntry;
+use crate::repository::book_repository::{BookRepository, BookRepositoryInterface};
+use crate::repository::user_repository::{UserRepository, UserRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait ShelfServiceInterface {
+    async fn list(&self, cmd: ShelfListCommand) -> Result<Vec<ShelfEntryResponse>, AppError>;
+    async fn add(&self, cmd: ShelfAddCommand) -> Result<ShelfEntryResponse, AppError>;
+    async fn update_status(&self, cmd: ShelfStatusUpdateCommand) -> Result<ShelfEntryResponse, AppError>;
+    async fn remove(&self, cmd: ShelfRemoveCommand) -> Result<(), AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct ShelfService {
+    user_repo: UserRepository,
+    book_repo: BookRepository,
+}
+
+impl From<&AppState> for ShelfService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            UserRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            BookRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+        )
+    }
+}
+
+impl ShelfService {
+    pub fn new(user_repo: UserRepository, book_repo: BookRepository) -> Self {
+        Self { user_repo, book_repo }
+    }
+
+    async fn find_entry(&self, user_id: &str, book_id: &str) -> Result<Option<ShelfEntry>, AppError> {
+        let user = self.user_repo.find_by_id(user_id).await?;
+        Ok(user
+            .and_then(|user| user.shelf)
+            .and_then(|shelf| shelf.into_iter().find(|entry| entry.book_id.to_hex() == book_id)))
+    }
+}
+
+#[async_trait]
+impl ShelfServiceInterface for ShelfService {
+    async fn list(&self, cmd: ShelfListCommand) -> Result<Vec<ShelfEntryResponse>, AppError> {
+        let user = match self.user_repo.find_by_id(&cmd.user_id).await? {
+            Some(user) => user,
+            None => return Err(AppError::not_found("User", &cmd.user_id)),
+        };
+
+        Ok(user.shelf
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| cmd.status.is_none_or(|status| entry.status == status))
+            .map(ShelfEntryResponse::from)
+            .collect())
+    }
+
+    async fn add(&self, cmd: ShelfAddCommand) -> Result<ShelfEntryResponse, AppError> {
+        let book = match self.book_repo.find_by_id(&cmd.book_id).await? {
+            Some(book) => book,
+            None => return Err(AppError::not_found("Book", &cmd.book_id)),
+        };
+        if self.user_repo.find_by_id(&cmd.user_id).await?.is_none() {
+            return Err(AppError::not_found("User", &cmd.user_id));
+        }
+
+        let entry = ShelfEntry::new(&book, cmd.status, Utc::now());
+        if !self.user_repo.add_book_to_shelf(&cmd.user_id, entry.clone()).await? {
+            return Err(AppError::Conflict(format!("Book {} is already on the shelf", cmd.book_id)));
+        }
+
+        Ok(ShelfEntryResponse::from(entry))
+    }
+
+    async fn update_status(&self, cmd: ShelfStatusUpdateCommand) -> Result<ShelfEntryResponse, AppError> {
+        let mut entry = match self.find_entry(&cmd.user_id, &cmd.book_id).await? {
+            Some(entry) => entry,
+            None => return Err(AppError::not_found("Shelf entry", &cmd.book_id)),
+        };
+
+        if entry.status == cmd.status {
+            return Ok(ShelfEntryResponse::from(entry));
+        }
+        if !entry.status.can_move_to(cmd.status) {
+            return Err(AppError::Conflict(format!(
+                "Cannot move a book from {} to {}", entry.status.name(), cmd.status.name()
+            )));
+        }
+
+        let now = Utc::now();
+        if !self.user_repo.update_shelf_status(&cmd.user_id, &cmd.book_id, entry.status, cmd.status, now).await? {
+            // Either the entry is gone or a concurrent request moved it since it was read
+            return match self.find_entry(&cmd.user_id, &cmd.book_id).await? {
+                Some(current) => Err(AppError::Conflict(format!(
+                    "Book {} was moved to {} meanwhile", cmd.book_id, current.status.name()
+                ))),
+                None => Err(AppError::not_found("Shelf entry", &cmd.book_id)),
+            };
+        }
+
+        entry.status = cmd.status;
+        entry.status_updated_at = now;
+        Ok(ShelfEntryResponse::from(entry))
+    }
+
+    async fn remove(&self, cmd: ShelfRemoveCommand) -> Result<(), AppError> {
+        if !self.user_repo.remove_book_from_shelf(&cmd.user_id, &cmd.book_id).await? {
+            return Err(AppError::not_found("Shelf entry", &cmd.book_id));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+
+use crate::command::similarity_command::{SimilarAuthorsCommand, SimilarBooksCommand};
+use crate::dto::author_dto::AuthorResponse;
+use crate::dto::book_dto::BookResponse;
+use crate::dto::similarity_dto::{SimilarAuthorResponse, SimilarBookResponse};
+use crate::model::similarity_model::SimilarityKind;
+use crate::repository::author_repository::{AuthorRepository, AuthorRepositoryInterface};
+use crate::repository::book_repository::{BookRepository, BookRepositoryInterface};
+use crate::repository::similarity_repository::{SimilarityRepository, SimilarityRepositoryInterface};
+use crate::shared::constant::SIMILAR_CACHE_TTL_SECS;
+use crate::shared::database::redis::{get_key, set_key};
+use crate::shared::error::AppError;
+use crate::shared::logging::log;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait SimilarityServiceInterface {
+    async fn similar_books(&self, cmd: SimilarBooksCommand) -> Result<Vec<SimilarBookResponse>, AppError>;
+    async fn similar_authors(&self, cmd: SimilarAuthorsCommand) -> Result<Vec<SimilarAuthorResponse>, AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct SimilarityService {
+    similarity_repo: SimilarityRepository,
+    book_repo: BookRepository,
+    author_repo: AuthorRepository,
+    redis_pool: Option<Pool<RedisConnectionManager>>,
+    space_name: Option<String>,
+}
+
+impl From<&AppState> for SimilarityService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+        let space_name = app_state
+            .config
+            .database
+            .redis
+            .as_ref()
+            .and_then(|r| r.app_space_name.as_deref())
+            .unwrap_or("booknet")
+            .to_string();
+
+        Self::new(
+            SimilarityRepository::new(app_state.neo4j_client.clone()),
+            BookRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            AuthorRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+            Some(app_state.redis_pool.clone()),
+            Some(space_name),
+        )
+    }
+}
+
+impl SimilarityService {
+    pub fn new(
+        similarity_repo: SimilarityRepository,
+        book_repo: BookRepository,
+        author_repo: AuthorRepository,
+        redis_pool: Option<Pool<RedisConnectionManager>>,
+        space_name: Option<String>,
+    ) -> Self {
+        Self { similarity_repo, book_repo, author_repo, redis_pool, space_name }
+    }
+
+    // Generates: "booknet:similar:book:<book_id>:limit=10"
+    fn cache_key(&self, kind: SimilarityKind, id: &str, limit: u64) -> String {
+        let prefix = self.space_name
+            .as_deref()
+            .map(|s| format!("{s}:"))
+            .unwrap_or_default();
+        format!("{}similar:{}:{}:limit={}", prefix, kind.name(), id, limit)
+    }
+}
+
+
+#[async_trait]
+impl SimilarityServiceInterface for SimilarityService {
+    async fn similar_books(&self, cmd: SimilarBooksCommand) -> Result<Vec<SimilarBookResponse>, AppError> {
+        let cache_key = self.cache_key(SimilarityKind::Book, &cmd.book_id, cmd.limit);
+        if let Some(pool) = &self.redis_pool {
+            let cached: Option<Vec<SimilarBookResponse>> = get_key(pool, &cache_key).await?;
+            if let Some(cached) = cached {
+                return Ok(cached);
+            }
+        }
+
+        if self.book_repo.find_by_id(&cmd.book_id).await?.is_none() {
+            return Err(AppError::not_found("Book", &cmd.book_id));
+        }
+
+        let scores = self.similarity_repo.find_similar(SimilarityKind::Book, &cmd.book_id, cmd.limit).await?;
+        let mut books: HashMap<String, BookResponse> = self.book_repo
+            .find_by_ids(scores.iter().map(|s| s.id.as_str()).collect())
+            .await?
+            .into_iter()
+            .map(|book| {
+                let book = BookResponse::from(book);
+                (book.id.clone(), book)
+            })
+            .collect();
+
+        // Nodes whose document is gone are skipped until reconciliation removes them
+        let similar: Vec<SimilarBookResponse> = scores
+            .iter()
+            .filter_map(|score| books.remove(&score.id).map(|book| SimilarBookResponse::new(book, score)))
+            .collect();
+
+        if let Some(pool) = &self.redis_pool {
+            // A miss only costs the next request a recomputation
+            if let Err(e) = set_key(pool, &cache_key, &similar, Some(SIMILAR_CACHE_TTL_SECS)).await {
+                log::warning(&format!("[SIMILARITY] Unable to cache {}: {}", cache_key, e));
+            }
+        }
+        Ok(similar)
+    }
+
+    async fn similar_authors(&self, cmd: SimilarAuthorsCommand) -> Result<Vec<SimilarAuthorResponse>, AppError> {
+        let cache_key = self.cache_key(SimilarityKind::Author, &cmd.author_id, cmd.limit);
+        if let Some(pool) = &self.redis_pool {
+            let cached: Option<Vec<SimilarAuthorResponse>> = get_key(pool, &cache_key).await?;
+            if let Some(cached) = cached {
+                return Ok(cached);
+            }
+        }
+
+        if self.author_repo.find_by_id(&cmd.author_id).await?.is_none() {
+            return Err(AppError::not_found("Author", &cmd.author_id));
+        }
+
+        let scores = self.similarity_repo.find_similar(SimilarityKind::Author, &cmd.author_id, cmd.limit).await?;
+        let mut authors: HashMap<String, AuthorResponse> = self.author_repo
+            .find_by_ids(scores.iter().map(|s| s.id.as_str()).collect())
+            .await?
+            .into_iter()
+            .map(|author| {
+                let author = AuthorResponse::from(author);
+                (author.id.clone(), author)
+            })
+            .collect();
+
+        let similar: Vec<SimilarAuthorResponse> = scores
+            .iter()
+            .filter_map(|score| authors.remove(&score.id).map(|author| SimilarAuthorResponse::new(author, score)))
+            .collect();
+
+        if let Some(pool) = &self.redis_pool {
+            // A miss only costs the next request a recomputation
+            if let Err(e) = set_key(pool, &cache_key, &similar, Some(SIMILAR_CACHE_TTL_SECS)).await {
+                log::warning(&format!("[SIMILARITY] Unable to cache {}: {}", cache_key, e));
+            }
+        }
+        Ok(similar)
+    }
+}
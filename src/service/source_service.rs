@@ -1,4 +1,3 @@
-use anyhow::{Error, Result};
 use async_trait::async_trait;
 
 use crate::command::source_command::{
@@ -6,17 +5,19 @@ use crate::command::source_command::{
 };
 use crate::dto::source_dto::SourceResponse;
 use crate::service::metadata_service::{MetadataService, MetadataServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
 use crate::shared::state::AppState;
 
 
 
 #[async_trait]
 pub trait SourceServiceInterface {
-    async fn get(&self, cmd: SourceGetCommand) -> Result<Option<SourceResponse>, Error>;
-    async fn create(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, Error>;
-    async fn update(&self, cmd: SourceUpdateCommand) -> Result<Option<SourceResponse>, Error>;
-    async fn delete(&self, cmd: SourceDeleteCommand) -> Result<(), Error>;
-    async fn list(&self, cmd: SourceListCommand) -> Result<Vec<SourceResponse>, Error>;
+    async fn get(&self, cmd: SourceGetCommand) -> Result<SourceResponse, AppError>;
+    async fn create(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, AppError>;
+    async fn update(&self, cmd: SourceUpdateCommand) -> Result<SourceResponse, AppError>;
+    async fn delete(&self, cmd: SourceDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: SourceListCommand) -> Result<PaginatedResponse<SourceResponse>, AppError>;
 }
 
 
@@ -42,23 +43,23 @@ impl SourceService {
 
 #[async_trait]
 impl SourceServiceInterface for SourceService {
-    async fn get(&self, cmd: SourceGetCommand) -> Result<Option<SourceResponse>, Error> {
+    async fn get(&self, cmd: SourceGetCommand) -> Result<SourceResponse, AppError> {
         self.metadata_service.get_source(cmd).await
     }
 
-    async fn create(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, Error> {
+    async fn create(&self, cmd: SourceCreateCommand) -> Result<SourceResponse, AppError> {
         self.metadata_service.create_source(cmd).await
     }
 
-    async fn update(&self, cmd: SourceUpdateCommand) -> Result<Option<SourceResponse>, Error> {
+    async fn update(&self, cmd: SourceUpdateCommand) -> Result<SourceResponse, AppError> {
         self.metadata_service.update_source(cmd).await
     }
 
-    async fn delete(&self, cmd: SourceDeleteCommand) -> Result<(), Error> {
+    async fn delete(&self, cmd: SourceDeleteCommand) -> Result<(), AppError> {
         self.metadata_service.delete_source(cmd).await
     }
 
-    async fn list(&self, cmd: SourceListCommand) -> Result<Vec<SourceResponse>, Error> {
+    async fn list(&self, cmd: SourceListCommand) -> Result<PaginatedResponse<SourceResponse>, AppError> {
         self.metadata_service.list_sources(cmd).await
     }
 }
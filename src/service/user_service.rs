@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::command::user_command::{
+    UserCreateCommand, UserDeleteCommand, UserGetCommand, UserPasswordUpdateCommand, UserPreferenceUpdateCommand,
+    UserUpdateCommand,
+};
+use crate::dto::user_dto::UserResponse;
+use crate::model::user_model::{User, UserPreference, UserRole};
+use crate::repository::user_repository::{UserRepository, UserRepositoryInterface};
+use crate::service::credential_service::{CredentialService, CredentialServiceInterface};
+use crate::shared::error::AppError;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait UserServiceInterface {
+    async fn get(&self, cmd: UserGetCommand) -> Result<UserResponse, AppError>;
+    /// Fails with `Conflict` when the username is already taken.
+    async fn create(&self, cmd: UserCreateCommand) -> Result<UserResponse, AppError>;
+    async fn update(&self, cmd: UserUpdateCommand) -> Result<UserResponse, AppError>;
+    /// Fails with `Unauthorized` when the current password is wrong.
+    async fn update_password(&self, cmd: UserPasswordUpdateCommand) -> Result<(), AppError>;
+    async fn update_preference(&self, cmd: UserPreferenceUpdateCommand) -> Result<UserResponse, AppError>;
+    async fn delete(&self, cmd: UserDeleteCommand) -> Result<(), AppError>;
+}
+
+
+#[derive(Clone)]
+pub struct UserService {
+    user_repo: UserRepository,
+    credential_service: CredentialService,
+}
+
+impl From<&AppState> for UserService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            UserRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+            CredentialService::from(app_state),
+        )
+    }
+}
+
+impl UserService {
+    pub fn new(user_repo: UserRepository, credential_service: CredentialService) -> Self {
+        Self { user_repo, credential_service }
+    }
+}
+
+#[async_trait]
+impl UserServiceInterface for UserService {
+    async fn get(&self, cmd: UserGetCommand) -> Result<UserResponse, AppError> {
+        let user = self.user_repo.find_by_id(&cmd.id).await?;
+        user.map(UserResponse::from).ok_or_else(|| AppError::not_found("User", &cmd.id))
+    }
+
+    async fn create(&self, cmd: UserCreateCommand) -> Result<UserResponse, AppError> {
+        if self.user_repo.find_by_username(&cmd.username).await?.is_some() {
+            return Err(AppError::Conflict(format!("Username already taken: {}", cmd.username)));
+        }
+
+        let now = Utc::now();
+        let user = User {
+            id: None,
+            username: cmd.username,
+            password: self.credential_service.hash_password(&cmd.password).await?,
+            name: cmd.name,
+            image_url: cmd.image_url,
+            role: UserRole::Reader,
+            preference: None,
+            shelf: None,
+            reviews: None,
+            external_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let user_id = self.user_repo.insert(user).await?;
+        let user = self.user_repo.find_by_id(&user_id).await?;
+        user.map(UserResponse::from).ok_or_else(|| AppError::not_found("User", &user_id))
+    }
+
+    async fn update(&self, cmd: UserUpdateCommand) -> Result<UserResponse, AppError> {
+        if let Some(name) = &cmd.name {
+            self.user_repo.update_name(&cmd.id, name).await?;
+        }
+        if let Some(image_url) = &cmd.image_url {
+            self.user_repo.update_image_url(&cmd.id, image_url).await?;
+        }
+
+        let user = self.user_repo.find_by_id(&cmd.id).await?;
+        user.map(UserResponse::from).ok_or_else(|| AppError::not_found("User", &cmd.id))
+    }
+
+    async fn update_password(&self, cmd: UserPasswordUpdateCommand) -> Result<(), AppError> {
+        let user = match self.user_repo.find_by_id(&cmd.id).await? {
+            Some(user) => user,
+            None => return Err(AppError::not_found("User", &cmd.id)),
+        };
+
+        if !self.credential_service.verify_password(&user, &cmd.current_password).await? {
+            return Err(AppError::Unauthorized("Current password is incorrect".to_string()));
+        }
+
+        let password = self.credential_service.hash_password(&cmd.new_password).await?;
+        self.user_repo.update_password(&cmd.id, &password).await?;
+        Ok(())
+    }
+
+    async fn update_preference(&self, cmd: UserPreferenceUpdateCommand) -> Result<UserResponse, AppError> {
+        let preference = UserPreference {
+            authors: cmd.authors,
+            genres: cmd.genres,
+            languages: cmd.languages,
+        };
+        self.user_repo.update_preference(&cmd.id, preference).await?;
+
+        let user = self.user_repo.find_by_id(&cmd.id).await?;
+        user.map(UserResponse::from).ok_or_else(|| AppError::not_found("User", &cmd.id))
+    }
+
+    async fn delete(&self, cmd: UserDeleteCommand) -> Result<(), AppError> {
+        if !self.user_repo.delete(&cmd.id).await? {
+            return Err(AppError::not_found("User", &cmd.id));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use mongodb::bson::oid::ObjectId;
+
+use crate::command::work_command::{
+    WorkCreateCommand, WorkDeleteCommand, WorkEditionAttachCommand, WorkEditionDetachCommand, WorkGetCommand, WorkListCommand,
+    WorkUpdateCommand,
+};
+use crate::dto::work_dto::{WorkDetailResponse, WorkResponse};
+use crate::model::book_model::Book;
+use crate::model::work_model::Work;
+use crate::repository::book_repository::{BookRepository, BookRepositoryInterface};
+use crate::repository::work_repository::{WorkRepository, WorkRepositoryInterface};
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginatedResponse;
+use crate::shared::state::AppState;
+
+
+#[async_trait]
+pub trait WorkServiceInterface {
+    async fn get(&self, cmd: WorkGetCommand) -> Result<WorkDetailResponse, AppError>;
+    async fn create(&self, cmd: WorkCreateCommand) -> Result<WorkDetailResponse, AppError>;
+    async fn update(&self, cmd: WorkUpdateCommand) -> Result<WorkDetailResponse, AppError>;
+    async fn delete(&self, cmd: WorkDeleteCommand) -> Result<(), AppError>;
+    async fn list(&self, cmd: WorkListCommand) -> Result<PaginatedResponse<WorkResponse>, AppError>;
+    async fn attach_edition(&self, cmd: WorkEditionAttachCommand) -> Result<WorkDetailResponse, AppError>;
+    async fn detach_edition(&self, cmd: WorkEditionDetachCommand) -> Result<WorkDetailResponse, AppError>;
+}
+
+
+/// Groups the editions and translations of a book under one work.
+/// A book is an edition of at most one work: moving it to another one means detaching it first.
+#[derive(Clone)]
+pub struct WorkService {
+    work_repo: WorkRepository,
+    book_repo: BookRepository,
+}
+
+impl From<&AppState> for WorkService {
+    fn from(app_state: &AppState) -> Self {
+        let database = app_state.mongo_client.database("booknet").clone();
+
+        Self::new(
+            WorkRepository::new(
+                app_state.mongo_client.clone(),
+                database.clone()
+            ),
+            BookRepository::new(
+                app_state.mongo_client.clone(),
+                database
+            ),
+        )
+    }
+}
+
+impl WorkService {
+    pub fn new(work_repo: WorkRepository, book_repo: BookRepository) -> Self {
+        Self { work_repo, book_repo }
+    }
+
+    async fn find(&self, work_id: &str) -> Result<Work, AppError> {
+        self.work_repo.find_by_id(work_id).await?.ok_or_else(|| AppError::not_found("Work", work_id))
+    }
+
+    async fn detail(&self, work: Work) -> Result<WorkDetailResponse, AppError> {
+        let work_id = work.id.map(|id| id.to_hex()).unwrap_or_default();
+        let editions = self.work_repo.find_editions(&work_id).await?;
+        Ok(WorkDetailResponse::new(work, editions))
+    }
+}
+
+/// Parses the edition ids of a new work, dropping repeated ones.
+fn edition_ids(ids: &[String]) -> Result<Vec<ObjectId>, AppError> {
+    let mut edition_ids: Vec<ObjectId> = Vec::with_capacity(ids.len());
+    for id in ids {
+        let edition_id = ObjectId::parse_str(id).map_err(|_| AppError::invalid_id("book", id))?;
+        if !edition_ids.contains(&edition_id) {
+            edition_ids.push(edition_id);
+        }
+    }
+    Ok(edition_ids)
+}
+
+/// Rejects a book that is already an edition of a work other than `work_id`.
+fn check_unclaimed(book: &Book, work_id: Option<&ObjectId>) -> Result<(), AppError> {
+    match &book.work_id {
+        Some(other) if Some(other) != work_id => Err(AppError::Conflict(format!(
+            "Book {} is already an edition of work {}",
+            book.id.map(|id| id.to_hex()).unwrap_or_default(),
+            other.to_hex()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+
+#[async_trait]
+impl WorkServiceInterface for WorkService {
+    async fn get(&self, cmd: WorkGetCommand) -> Result<WorkDetailResponse, AppError> {
+        let work = self.find(&cmd.id).await?;
+        self.detail(work).await
+    }
+
+    async fn create(&self, cmd: WorkCreateCommand) -> Result<WorkDetailResponse, AppError> {
+        let edition_ids = edition_ids(&cmd.edition_ids)?;
+        if !edition_ids.is_empty() {
+            let hex_ids: Vec<String> = edition_ids.iter().map(|id| id.to_hex()).collect();
+            let books = self.book_repo.find_by_ids(hex_ids.iter().map(String::as_str).collect()).await?;
+            if let Some(missing) = edition_ids.iter().find(|id| !books.iter().any(|book| book.id.as_ref() == Some(*id))) {
+                return Err(AppError::not_found("Book", &missing.to_hex()));
+            }
+            for book in &books {
+                check_unclaimed(book, None)?;
+            }
+        }
+
+        let now = Utc::now();
+        let work = Work {
+            id: None,
+            title: cmd.title,
+            description: cmd.description,
+            original_language: cmd.original_language,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let work_id = self.work_repo.insert(work, edition_ids).await?;
+        self.get(WorkGetCommand { id: work_id }).await
+    }
+
+    async fn update(&self, cmd: WorkUpdateCommand) -> Result<WorkDetailResponse, AppError> {
+        let existing = self.find(&cmd.id).await?;
+        let work = Work {
+            id: existing.id,
+            title: cmd.title,
+            description: cmd.description,
+            original_language: cmd.original_language,
+            created_at: existing.created_at,
+            updated_at: Utc::now(),
+        };
+
+        if !self.work_repo.update(work).await? {
+            return Err(AppError::not_found("Work", &cmd.id));
+        }
+        self.get(WorkGetCommand { id: cmd.id }).await
+    }
+
+    async fn delete(&self, cmd: WorkDeleteCommand) -> Result<(), AppError> {
+        if !self.work_repo.delete(&cmd.id).await? {
+            return Err(AppError::not_found("Work", &cmd.id));
+        }
+        Ok(())
+    }
+
+    async fn list(&self, cmd: WorkListCommand) -> Result<PaginatedResponse<WorkResponse>, AppError> {
+        let pagination = cmd.pagination.unwrap_or_default();
+        let (works, total) = self.work_repo.find_all(&pagination).await?;
+        Ok(PaginatedResponse::new(works.into_iter().map(WorkResponse::from).collect(), total, &pagination))
+    }
+
+    /// Attaching an edition of this work again changes nothing.
+    async fn attach_edition(&self, cmd: WorkEditionAttachCommand) -> Result<WorkDetailResponse, AppError> {
+        let work = self.find(&cmd.id).await?;
+        let book = self.book_repo
+            .find_by_id(&cmd.book_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Book", &cmd.book_id))?;
+        check_unclaimed(&book, work.id.as_ref())?;
+
+        if book.work_id.is_none() && !self.work_repo.attach_edition(&cmd.id, &cmd.book_id).await? {
+            // Deleted or claimed since it was read: report what it is now
+            let book = self.book_repo
+                .find_by_id(&cmd.book_id)
+                .await?
+                .ok_or_else(|| AppError::not_found("Book", &cmd.book_id))?;
+            check_unclaimed(&book, work.id.as_ref())?;
+            if book.work_id.is_none() {
+                return Err(AppError::Conflict(format!("Book {} changed while being attached", cmd.book_id)));
+            }
+        }
+        self.get(WorkGetCommand { id: cmd.id }).await
+    }
+
+    async fn detach_edition(&self, cmd: WorkEditionDetachCommand) -> Result<WorkDetailResponse, AppError> {
+        let work = self.find(&cmd.id).await?;
+        if !self.work_repo.detach_edition(&cmd.id, &cmd.book_id).await? {
+            return Err(AppError::not_found("Edition", &cmd.book_id));
+        }
+        self.detail(work).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::book_model::{BookFormat, BookRating};
+
+    fn book(work_id: Option<ObjectId>) -> Book {
+        Book {
+            id: Some(ObjectId::new()),
+            isbn: String::new(),
+            isbn13: String::new(),
+            title: "Le Petit Prince".to_string(),
+            subtitle: None,
+            description: None,
+            num_pages: None,
+            published_date: None,
+            format: BookFormat::Paperback,
+            images: vec![],
+            preview: vec![],
+            genres: vec![],
+            authors: vec![],
+            publishers: vec![],
+            languages: vec![],
+            reviews: vec![],
+            rating: BookRating::default(),
+            external_id: None,
+            work_id,
+        }
+    }
+
+    #[test]
+    fn repeated_edition_ids_are_attached_once() {
+        let id = "65f0c0ffee0000000000b001".to_string();
+
+        assert_eq!(edition_ids(&[id.clone(), id]).unwrap().len(), 1);
+        assert!(matches!(edition_ids(&["nope".to_string()]), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn an_edition_of_another_work_cannot_be_claimed() {
+        let (work_id, other_id) = (ObjectId::new(), ObjectId::new());
+
+        assert!(check_unclaimed(&book(None), Some(&work_id)).is_ok());
+        assert!(check_unclaimed(&book(Some(work_id)), Some(&work_id)).is_ok());
+        assert!(matches!(check_unclaimed(&book(Some(other_id)), Some(&work_id)), Err(AppError::Conflict(_))));
+        assert!(check_unclaimed(&book(Some(other_id)), None).is_err());
+    }
+}
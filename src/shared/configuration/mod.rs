@@ -4,14 +4,21 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfigJWT {
-    pub private_secret_pem_path: Option<String>,
-    pub public_secret_pem_path: String, // HS256 secret
+    pub private_secret_pem_path: Option<String>, // RS256 private key (PEM), only needed to issue tokens
+    pub public_secret_pem_path: String, // RS256 public key (PEM)
     pub issuer: String, // "booknet"
     pub audience: String, // "all-service" or service name
     pub expires_in_minutes: i64,
     pub kid: Option<String>, // key id shown in the JWT header
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppConfigPasswordHash {
+    pub memory_kib: u32, // Argon2id m_cost
+    pub iterations: u32, // Argon2id t_cost
+    pub parallelism: u32, // Argon2id p_cost
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppDatabaseMongoDBConfig {
     pub uri: String,
@@ -51,10 +58,14 @@ pub struct AppConfig {
 
     pub jwt: AppConfigJWT,
 
+    pub password_hash: AppConfigPasswordHash,
+
     pub database: AppDatabaseConfig,
 
     pub bind_addr: String,
     pub metrics_addr: String,
+
+    pub import_dir: String, // dumps the import endpoint may read
 }
 
 
@@ -68,6 +79,7 @@ impl AppConfig {
 
         let bind_addr = get_env("BIND_ADDR")?;
         let metrics_addr = get_env("METRICS_ADDR")?;
+        let import_dir = get_env("IMPORT_DIR").ok().unwrap_or_else(|| "imports".to_string());
 
         // Use a helper function to get required env vars
         let is_prod = matches!(get_env("APP_ENV")?.to_ascii_lowercase().as_str(), "prod" | "production");
@@ -90,6 +102,20 @@ impl AppConfig {
             kid: jwt_kid,
         };
 
+        // OWASP recommended Argon2id baseline: 19 MiB, 2 iterations, 1 lane
+        let password_hash_memory_kib = get_env("PASSWORD_HASH_MEMORY_KIB").ok()
+            .map(|v| v.trim().parse::<u32>()).transpose()?.unwrap_or(19 * 1024);
+        let password_hash_iterations = get_env("PASSWORD_HASH_ITERATIONS").ok()
+            .map(|v| v.trim().parse::<u32>()).transpose()?.unwrap_or(2);
+        let password_hash_parallelism = get_env("PASSWORD_HASH_PARALLELISM").ok()
+            .map(|v| v.trim().parse::<u32>()).transpose()?.unwrap_or(1);
+
+        let password_hash = AppConfigPasswordHash {
+            memory_kib: password_hash_memory_kib,
+            iterations: password_hash_iterations,
+            parallelism: password_hash_parallelism,
+        };
+
         let mongo_url = get_env("MONGO_URL").ok();
         let mongo = match mongo_url {
             Some(url) => {
@@ -151,10 +177,14 @@ impl AppConfig {
 
             jwt,
 
+            password_hash,
+
             database,
 
             bind_addr,
             metrics_addr,
+
+            import_dir,
         })
     }
 }
@@ -1,3 +1,63 @@
 
 pub const LIMIT_DEFAULT: u64 = 10;
 pub const LIMIT_MAX: u64 = 100;
+
+pub const REVIEW_SCORE_MIN: u8 = 1;
+pub const REVIEW_SCORE_MAX: u8 = 5;
+
+pub const PASSWORD_LENGTH_MIN: usize = 8;
+pub const PASSWORD_LENGTH_MAX: usize = 128;
+
+pub const GRAPH_SYNC_POLL_INTERVAL_MS: u64 = 500;
+pub const GRAPH_SYNC_LEASE_MS: i64 = 30_000;
+pub const GRAPH_SYNC_MAX_ATTEMPTS: u32 = 8;
+pub const GRAPH_SYNC_BACKOFF_BASE_MS: i64 = 1_000;
+pub const GRAPH_SYNC_BACKOFF_MAX_MS: i64 = 300_000;
+/// Outbox events a worker looks through for one it may claim
+pub const GRAPH_SYNC_CLAIM_WINDOW: i64 = 500;
+
+pub const RECONCILE_SAMPLE_SIZE: usize = 20;
+pub const RECONCILE_REPAIR_CHUNK_SIZE: usize = 500;
+
+pub const RECOMMENDATION_LIMIT_DEFAULT: u64 = 20;
+pub const RECOMMENDATION_LIMIT_MAX: u64 = 50;
+/// Readers and books count as liked from this rating up
+pub const RECOMMENDATION_LIKED_RATING: f64 = 4.0;
+pub const RECOMMENDATION_WEIGHT_COLLABORATIVE: f64 = 0.5;
+pub const RECOMMENDATION_WEIGHT_CONTENT: f64 = 0.3;
+pub const RECOMMENDATION_WEIGHT_PREFERENCE: f64 = 0.2;
+/// Multiplier applied to books available in one of the reader's preferred languages
+pub const RECOMMENDATION_LANGUAGE_BOOST: f64 = 1.1;
+pub const RECOMMENDATION_CACHE_TTL_SECS: u64 = 10 * 60;
+
+pub const SIMILAR_LIMIT_DEFAULT: u64 = 10;
+pub const SIMILAR_LIMIT_MAX: u64 = 50;
+/// Weight of the Jaccard index over readers who rated or shelved both sides
+pub const SIMILAR_WEIGHT_READERS: f64 = 0.6;
+/// Weight of the Jaccard index over genres
+pub const SIMILAR_WEIGHT_GENRES: f64 = 0.4;
+pub const SIMILAR_CACHE_TTL_SECS: u64 = 60 * 60;
+
+pub const SEARCH_LIMIT_DEFAULT: u64 = 10;
+pub const SEARCH_LIMIT_MAX: u64 = 50;
+pub const SEARCH_TEXT_LENGTH_MAX: usize = 256;
+
+/// Values returned per facet of the book catalog
+pub const BOOK_FACET_LIMIT: i64 = 50;
+
+/// Rows written per MongoDB transaction by a catalog import
+pub const IMPORT_BATCH_SIZE: usize = 500;
+/// Row errors kept in an import report, the rest are only counted
+pub const IMPORT_ERROR_SAMPLE_SIZE: usize = 100;
+
+pub const DEDUP_LIMIT_DEFAULT: u64 = 50;
+pub const DEDUP_LIMIT_MAX: u64 = 500;
+/// Name similarity from which two records are proposed as duplicates
+pub const DEDUP_MIN_SCORE_DEFAULT: f64 = 0.85;
+/// Records sharing a name word beyond which the word is too common to compare them by
+pub const DEDUP_BLOCK_SIZE_MAX: usize = 200;
+/// Records merged into a survivor in one request
+pub const DEDUP_MERGE_MAX: usize = 50;
+
+/// Editions attached to a work when it is created
+pub const WORK_EDITIONS_MAX: usize = 100;
@@ -1,3 +1,5 @@
 pub mod redis;
 pub mod mongodb;
-pub mod neo4j;
\ No newline at end of file
+pub mod neo4j;
+pub mod neo4j_schema;
+pub mod mongodb_migration;
\ No newline at end of file
@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, to_bson, Document},
+    options::IndexOptions,
+    Database, IndexModel,
+};
+use serde::Serialize;
+use tracing::info;
+
+use crate::model::external_id_model::ExternalProvider;
+
+
+pub const MIGRATIONS_COLLECTION: &str = "schema_migrations";
+
+
+/// A single change made by a migration.
+pub enum MigrationStep {
+    CreateIndex {
+        collection: &'static str,
+        name: &'static str,
+        keys: Document,
+        unique: bool,
+        weights: Option<Document>,
+        /// Only documents matching this filter are indexed
+        partial: Option<Document>,
+    },
+    /// Pipeline update applied to every document matching `filter`.
+    UpdateMany {
+        collection: &'static str,
+        filter: Document,
+        update: Vec<Document>,
+    },
+}
+
+impl MigrationStep {
+    fn index(collection: &'static str, name: &'static str, keys: Document) -> Self {
+        Self::CreateIndex { collection, name, keys, unique: false, weights: None, partial: None }
+    }
+
+    fn unique_index(collection: &'static str, name: &'static str, keys: Document) -> Self {
+        Self::CreateIndex { collection, name, keys, unique: true, weights: None, partial: None }
+    }
+
+    fn unique_partial_index(collection: &'static str, name: &'static str, keys: Document, partial: Document) -> Self {
+        Self::CreateIndex { collection, name, keys, unique: true, weights: None, partial: Some(partial) }
+    }
+
+    fn text_index(collection: &'static str, name: &'static str, keys: Document, weights: Document) -> Self {
+        Self::CreateIndex { collection, name, keys, unique: false, weights: Some(weights), partial: None }
+    }
+
+    fn collection(&self) -> &'static str {
+        match self {
+            Self::CreateIndex { collection, .. } | Self::UpdateMany { collection, .. } => collection,
+        }
+    }
+}
+
+
+/// One versioned migration. Index creation is idempotent and data steps only match documents
+/// still needing the change, so a migration interrupted halfway can run again.
+pub struct MongoMigration {
+    pub version: i64,
+    pub name: &'static str,
+    pub steps: fn() -> Vec<MigrationStep>,
+}
+
+/// `created_at` taken from the `_id` timestamp, in the RFC 3339 format chrono reads back.
+fn backfill_timestamps() -> Vec<Document> {
+    vec![
+        doc! { "$set": { "created_at": { "$ifNull": [
+            "$created_at",
+            { "$dateToString": { "date": { "$toDate": "$_id" }, "format": "%Y-%m-%dT%H:%M:%S.%LZ" } },
+        ] } } },
+        doc! { "$set": { "updated_at": { "$ifNull": ["$updated_at", "$created_at"] } } },
+    ]
+}
+
+/// One unique index per provider over the external ids of `collection`,
+/// skipping the documents without an id from that provider.
+fn external_id_indexes(collection: &'static str) -> Vec<MigrationStep> {
+    const NAMES: [&str; 4] = ["external_id_good_reads", "external_id_amazon", "external_id_google_books", "external_id_kaggle"];
+    ExternalProvider::ALL
+        .into_iter()
+        .zip(NAMES)
+        .map(|(provider, name)| MigrationStep::unique_partial_index(
+            collection,
+            name,
+            doc! { provider.path(): 1 },
+            doc! { provider.path(): { "$type": "string" } },
+        ))
+        .collect()
+}
+
+/// Applied in order, each version at most once. Append new migrations, never edit applied ones.
+pub fn mongo_migrations() -> Vec<MongoMigration> {
+    vec![
+        MongoMigration {
+            version: 1,
+            name: "lookup indexes",
+            steps: || vec![
+                MigrationStep::unique_index("users", "username_unique", doc! { "username": 1 }),
+                MigrationStep::unique_index("metadata", "type_key", doc! { "type": 1, "key": 1 }),
+                MigrationStep::index("authors", "books_book_id", doc! { "books.book_id": 1 }),
+                MigrationStep::unique_index("reviews", "book_id_user_id", doc! { "book_id": 1, "user.id": 1 }),
+                MigrationStep::index("reviews", "user_id_date_added", doc! { "user.id": 1, "date_added": -1 }),
+            ],
+        },
+        MongoMigration {
+            version: 2,
+            name: "text search indexes",
+            steps: || vec![
+                MigrationStep::text_index(
+                    "books",
+                    "books_text",
+                    doc! { "title": "text", "subtitle": "text", "description": "text" },
+                    doc! { "title": 10, "subtitle": 5, "description": 1 },
+                ),
+                MigrationStep::text_index(
+                    "authors",
+                    "authors_text",
+                    doc! { "name": "text", "description": "text" },
+                    doc! { "name": 10, "description": 1 },
+                ),
+            ],
+        },
+        MongoMigration {
+            version: 3,
+            name: "backfill created_at and updated_at",
+            steps: || {
+                let missing = doc! { "$or": [
+                    { "created_at": { "$exists": false } },
+                    { "updated_at": { "$exists": false } },
+                ] };
+                vec![
+                    MigrationStep::UpdateMany { collection: "users", filter: missing.clone(), update: backfill_timestamps() },
+                    MigrationStep::UpdateMany { collection: "authors", filter: missing, update: backfill_timestamps() },
+                ]
+            },
+        },
+        MongoMigration {
+            version: 4,
+            name: "graph sync outbox sequence",
+            steps: || vec![
+                MigrationStep::index("graph_sync_outbox", "seq", doc! { "seq": 1, "_id": 1 }),
+            ],
+        },
+        MongoMigration {
+            version: 5,
+            name: "follow indexes",
+            steps: || vec![
+                MigrationStep::unique_index("follows", "follower_id_followee_id", doc! { "follower_id": 1, "followee_id": 1 }),
+                MigrationStep::index("follows", "followee_id_created_at", doc! { "followee_id": 1, "created_at": -1 }),
+            ],
+        },
+        MongoMigration {
+            version: 6,
+            name: "metadata text search index",
+            steps: || vec![
+                MigrationStep::text_index(
+                    "metadata",
+                    "metadata_text",
+                    doc! { "name": "text", "description": "text" },
+                    doc! { "name": 10, "description": 1 },
+                ),
+            ],
+        },
+        MongoMigration {
+            version: 7,
+            name: "user external id indexes",
+            steps: || external_id_indexes("users"),
+        },
+        MongoMigration {
+            version: 8,
+            name: "book and author external id indexes",
+            steps: || ["books", "authors"].into_iter().flat_map(external_id_indexes).collect(),
+        },
+        MongoMigration {
+            version: 9,
+            name: "book work index",
+            steps: || vec![
+                MigrationStep::index("books", "work_id", doc! { "work_id": 1 }),
+            ],
+        },
+        MongoMigration {
+            version: 10,
+            name: "graph sync outbox keys",
+            steps: || vec![
+                MigrationStep::index("graph_sync_outbox", "keys_seq", doc! { "keys": 1, "seq": 1 }),
+            ],
+        },
+    ]
+}
+
+
+/// What a step did, or would do on a dry run.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStepReport {
+    pub collection: String,
+    pub description: String,
+    /// Documents changed by a data step, or matched by it on a dry run
+    pub documents: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub version: i64,
+    pub name: String,
+    pub steps: Vec<MigrationStepReport>,
+}
+
+
+async fn applied_versions(database: &Database) -> Result<HashSet<i64>> {
+    let applied: Vec<Document> = database
+        .collection::<Document>(MIGRATIONS_COLLECTION)
+        .find(doc! {})
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(applied.iter().filter_map(|d| d.get_i64("_id").ok()).collect())
+}
+
+async fn run_step(database: &Database, step: MigrationStep, dry_run: bool) -> Result<MigrationStepReport> {
+    let collection_name = step.collection();
+    let collection = database.collection::<Document>(collection_name);
+
+    let (description, documents) = match step {
+        MigrationStep::CreateIndex { name, keys, unique, weights, partial, .. } => {
+            let mut description = format!("create {}index {} on {}", if unique { "unique " } else { "" }, name, keys);
+            if let Some(partial) = &partial {
+                description.push_str(&format!(" where {}", partial));
+            }
+            if !dry_run {
+                let options = IndexOptions::builder()
+                    .name(name.to_string())
+                    .unique(unique)
+                    .weights(weights)
+                    .partial_filter_expression(partial)
+                    .build();
+                collection
+                    .create_index(IndexModel::builder().keys(keys).options(options).build())
+                    .await
+                    .with_context(|| format!("Failed to {}; duplicated values must be merged first", description))?;
+            }
+            (description, None)
+        },
+        MigrationStep::UpdateMany { filter, update, .. } => {
+            let description = format!("update documents matching {}", filter);
+            let documents = if dry_run {
+                collection.count_documents(filter).await?
+            } else {
+                collection.update_many(filter, update).await?.modified_count
+            };
+            (description, Some(documents))
+        },
+    };
+
+    Ok(MigrationStepReport { collection: collection_name.to_string(), description, documents })
+}
+
+/// Runs the pending migrations, or on a dry run only reports what they would do.
+/// Each applied migration is recorded in `schema_migrations`.
+pub async fn migrate(database: &Database, dry_run: bool) -> Result<Vec<MigrationReport>> {
+    let applied = applied_versions(database).await?;
+    let mut reports = Vec::new();
+
+    for migration in mongo_migrations().into_iter().filter(|m| !applied.contains(&m.version)) {
+        info!("{} MongoDB migration {}: {}", if dry_run { "Planning" } else { "Applying" }, migration.version, migration.name);
+
+        let mut steps = Vec::new();
+        for step in (migration.steps)() {
+            steps.push(run_step(database, step, dry_run).await?);
+        }
+
+        if !dry_run {
+            database
+                .collection::<Document>(MIGRATIONS_COLLECTION)
+                .insert_one(doc! {
+                    "_id": migration.version,
+                    "name": migration.name,
+                    "applied_at": to_bson(&Utc::now())?,
+                })
+                .await?;
+        }
+
+        reports.push(MigrationReport { version: migration.version, name: migration.name.to_string(), steps });
+    }
+
+    Ok(reports)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_are_unique_and_increasing() {
+        let versions: Vec<i64> = mongo_migrations().iter().map(|m| m.version).collect();
+
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", versions);
+        assert_eq!(versions.first(), Some(&1));
+    }
+
+    #[test]
+    fn index_names_are_unique_per_collection() {
+        let mut seen = HashSet::new();
+        for migration in mongo_migrations() {
+            for step in (migration.steps)() {
+                if let MigrationStep::CreateIndex { collection, name, .. } = step {
+                    assert!(seen.insert((collection, name)), "{}.{} created twice", collection, name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn external_id_indexes_cover_every_provider_and_only_string_ids() {
+        let steps = external_id_indexes("users");
+
+        assert_eq!(steps.len(), ExternalProvider::ALL.len());
+        for (step, provider) in steps.iter().zip(ExternalProvider::ALL) {
+            match step {
+                MigrationStep::CreateIndex { keys, unique, partial, .. } => {
+                    assert_eq!(keys, &doc! { provider.path(): 1 });
+                    assert!(unique);
+                    assert_eq!(partial, &Some(doc! { provider.path(): { "$type": "string" } }));
+                },
+                MigrationStep::UpdateMany { .. } => panic!("expected an index"),
+            }
+        }
+    }
+}
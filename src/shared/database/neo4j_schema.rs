@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use neo4rs::{query, Graph};
+use tracing::{info, warn};
+
+
+/// One versioned step of the graph schema.
+/// Statements use `IF NOT EXISTS`, so a step interrupted halfway can simply run again.
+pub struct SchemaMigration {
+    pub version: i64,
+    pub name: &'static str,
+    /// `(label, property)` keys made unique by the step, see `merge_duplicates`
+    pub unique_keys: &'static [(&'static str, &'static str)],
+    pub statements: &'static [&'static str],
+}
+
+/// Applied in order, each version at most once. Append new steps, never edit applied ones.
+pub const NEO4J_SCHEMA: &[SchemaMigration] = &[
+    SchemaMigration {
+        version: 1,
+        name: "unique node keys",
+        unique_keys: &[
+            ("SchemaVersion", "version"),
+            ("Reader", "user_id"),
+            ("Author", "author_id"),
+            ("Genre", "name"),
+            ("Book", "book_id"),
+            ("Publisher", "name"),
+        ],
+        statements: &[
+            "CREATE CONSTRAINT schema_version_version IF NOT EXISTS FOR (v:SchemaVersion) REQUIRE v.version IS UNIQUE",
+            "CREATE CONSTRAINT reader_user_id IF NOT EXISTS FOR (r:Reader) REQUIRE r.user_id IS UNIQUE",
+            "CREATE CONSTRAINT author_author_id IF NOT EXISTS FOR (a:Author) REQUIRE a.author_id IS UNIQUE",
+            "CREATE CONSTRAINT genre_name IF NOT EXISTS FOR (g:Genre) REQUIRE g.name IS UNIQUE",
+            "CREATE CONSTRAINT book_book_id IF NOT EXISTS FOR (b:Book) REQUIRE b.book_id IS UNIQUE",
+            "CREATE CONSTRAINT publisher_name IF NOT EXISTS FOR (p:Publisher) REQUIRE p.name IS UNIQUE",
+        ],
+    },
+    SchemaMigration {
+        version: 2,
+        name: "relationship property indexes",
+        unique_keys: &[],
+        statements: &[
+            "CREATE INDEX added_to_shelf_status IF NOT EXISTS FOR ()-[rel:ADDED_TO_SHELF]-() ON (rel.status)",
+            "CREATE INDEX rated_rating IF NOT EXISTS FOR ()-[r:RATED]-() ON (r.rating)",
+        ],
+    },
+    SchemaMigration {
+        version: 3,
+        name: "unique work key",
+        unique_keys: &[("Work", "work_id")],
+        statements: &[
+            "CREATE CONSTRAINT work_work_id IF NOT EXISTS FOR (w:Work) REQUIRE w.work_id IS UNIQUE",
+        ],
+    },
+];
+
+
+async fn applied_versions(graph: &Graph) -> Result<HashSet<i64>> {
+    let mut stream = graph
+        .execute(query("MATCH (v:SchemaVersion) RETURN v.version AS version"))
+        .await?;
+
+    let mut versions = HashSet::new();
+    while let Some(row) = stream.next().await? {
+        versions.insert(row.get::<i64>("version")?);
+    }
+    Ok(versions)
+}
+
+async fn relationship_types(graph: &Graph) -> Result<Vec<String>> {
+    let mut stream = graph
+        .execute(query("CALL db.relationshipTypes() YIELD relationshipType RETURN relationshipType"))
+        .await?;
+
+    let mut types = Vec::new();
+    while let Some(row) = stream.next().await? {
+        types.push(row.get::<String>("relationshipType")?);
+    }
+    Ok(types)
+}
+
+/// Merges the nodes sharing a key into the most connected one and returns how many were deleted.
+/// Every relationship of a duplicate is first recreated on the kept node, with its properties,
+/// so no shelf, rating or catalog link is lost.
+async fn merge_duplicates(graph: &Graph, label: &str, property: &str) -> Result<i64> {
+    // The kept node only gains relationships while the others are moved, so it stays first
+    let duplicates = format!(
+        "MATCH (n:{label}) WHERE n.{property} IS NOT NULL \
+         WITH n.{property} AS key, n ORDER BY size([(n)--() | 1]) DESC, id(n) \
+         WITH key, collect(n) AS nodes WHERE size(nodes) > 1 \
+         WITH nodes[0] AS keep, nodes[1..] AS duplicates \
+         UNWIND duplicates AS duplicate"
+    );
+
+    for rel_type in relationship_types(graph).await? {
+        let rel_type = rel_type.replace('`', "``");
+        graph.run(query(&format!(
+            "{duplicates} \
+             MATCH (duplicate)-[rel:`{rel_type}`]->(other) WHERE other <> keep \
+             MERGE (keep)-[moved:`{rel_type}`]->(other) ON CREATE SET moved = properties(rel)"
+        ))).await?;
+        graph.run(query(&format!(
+            "{duplicates} \
+             MATCH (other)-[rel:`{rel_type}`]->(duplicate) WHERE other <> keep \
+             MERGE (other)-[moved:`{rel_type}`]->(keep) ON CREATE SET moved = properties(rel)"
+        ))).await?;
+    }
+
+    let mut stream = graph
+        .execute(query(&format!("{duplicates} DETACH DELETE duplicate RETURN count(*) AS removed")))
+        .await?;
+    let removed = match stream.next().await? {
+        Some(row) => row.get::<i64>("removed")?,
+        None => 0,
+    };
+    Ok(removed)
+}
+
+/// Applies the pending steps of `NEO4J_SCHEMA` and returns their versions.
+/// Each applied step is recorded as a `SchemaVersion` node.
+///
+/// A uniqueness constraint cannot be created over duplicated keys. Unless `merge_duplicated_keys`
+/// is set, which only the `neo4j-schema` command does, the step then fails and nothing is deleted.
+pub async fn migrate(graph: &Graph, merge_duplicated_keys: bool) -> Result<Vec<i64>> {
+    let applied = applied_versions(graph).await?;
+    let mut newly_applied = Vec::new();
+
+    for migration in NEO4J_SCHEMA.iter().filter(|m| !applied.contains(&m.version)) {
+        info!("Applying Neo4j schema version {}: {}", migration.version, migration.name);
+
+        if merge_duplicated_keys {
+            for (label, property) in migration.unique_keys {
+                let removed = merge_duplicates(graph, label, property)
+                    .await
+                    .with_context(|| format!("Neo4j schema version {} failed to merge duplicated {}.{}", migration.version, label, property))?;
+                if removed > 0 {
+                    warn!("Merged {} duplicated {} nodes by {}", removed, label, property);
+                }
+            }
+        }
+
+        // Schema changes cannot share a transaction with writes, every statement runs on its own
+        for statement in migration.statements {
+            graph
+                .run(query(statement))
+                .await
+                .with_context(|| format!(
+                    "Neo4j schema version {} failed on `{}`; run the `neo4j-schema` command to merge duplicated keys",
+                    migration.version, statement
+                ))?;
+        }
+
+        graph
+            .run(
+                query("MERGE (v:SchemaVersion {version:$version}) SET v.name = $name, v.applied_at = $applied_at")
+                    .param("version", migration.version)
+                    .param("name", migration.name)
+                    .param("applied_at", Utc::now().timestamp_millis()),
+            )
+            .await?;
+
+        newly_applied.push(migration.version);
+    }
+
+    Ok(newly_applied)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(label, property)` of a `CREATE CONSTRAINT ... FOR (n:Label) REQUIRE n.property IS UNIQUE` statement.
+    fn unique_key(statement: &str) -> Option<(&str, &str)> {
+        let (_, pattern) = statement.split_once("FOR (")?;
+        let (_, label) = pattern.split_once(':')?;
+        let (label, rest) = label.split_once(')')?;
+        let (_, property) = rest.split_once("REQUIRE ")?.1.split_once('.')?;
+        let property = property.strip_suffix(" IS UNIQUE")?;
+        Some((label, property))
+    }
+
+    #[test]
+    fn versions_are_unique_and_increasing() {
+        let versions: Vec<i64> = NEO4J_SCHEMA.iter().map(|m| m.version).collect();
+
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", versions);
+        assert_eq!(versions.first(), Some(&1));
+    }
+
+    #[test]
+    fn statements_can_run_again() {
+        for statement in NEO4J_SCHEMA.iter().flat_map(|m| m.statements) {
+            assert!(statement.contains("IF NOT EXISTS"), "{}", statement);
+        }
+    }
+
+    #[test]
+    fn every_uniqueness_constraint_is_deduplicated_first() {
+        for migration in NEO4J_SCHEMA {
+            let constrained: Vec<(&str, &str)> = migration.statements.iter().filter_map(|s| unique_key(s)).collect();
+
+            assert_eq!(constrained, migration.unique_keys.to_vec(), "version {}", migration.version);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use bb8::Pool;
 use bb8_redis::RedisConnectionManager;
-use redis::{AsyncCommands, RedisError};
+use redis::AsyncCommands;
 use tracing::info;
 use crate::shared::configuration::AppDatabaseRedisConfig;
 use crate::shared::logging::log::TimePrinter;
@@ -30,7 +30,7 @@ pub async fn set_key<T: serde::Serialize>(
 ) -> Result<()> {
     let timer = TimePrinter::with_message(&format!(
         "[REDIS] [SET] Key: {} ",
-        key.to_string()
+        key
     ));
 
     let mut conn = pool.get().await?;
@@ -53,7 +53,7 @@ pub async fn get_key<T: serde::de::DeserializeOwned>(
 ) -> Result<Option<T>> {
     let timer = TimePrinter::with_message(&format!(
         "[REDIS] [GET] Key: {} ",
-        key.to_string()
+        key
     ));
 
     let mut conn = pool.get().await?;
@@ -75,7 +75,7 @@ pub async fn get_key<T: serde::de::DeserializeOwned>(
 pub async fn delete_key(pool: &RedisDatabase, key: &str) -> Result<()> {
     let timer = TimePrinter::with_message(&format!(
         "[REDIS] [DELETE] Key: {} ",
-        key.to_string()
+        key
     ));
 
     let mut conn = pool.get().await?;
@@ -83,4 +83,28 @@ pub async fn delete_key(pool: &RedisDatabase, key: &str) -> Result<()> {
 
     timer.log();
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Deletes every key starting with `prefix`, used to drop all cached pages of a list.
+pub async fn delete_keys_by_prefix(pool: &RedisDatabase, prefix: &str) -> Result<()> {
+    let timer = TimePrinter::with_message(&format!(
+        "[REDIS] [DELETE PREFIX] Prefix: {} ",
+        prefix
+    ));
+
+    let mut conn = pool.get().await?;
+    let mut keys: Vec<String> = Vec::new();
+    {
+        let mut iter = conn.scan_match::<_, String>(format!("{}*", prefix)).await?;
+        while let Some(key) = iter.next_item().await {
+            keys.push(key?);
+        }
+    }
+
+    if !keys.is_empty() {
+        let _: () = conn.del(keys).await?;
+    }
+
+    timer.log();
+    Ok(())
+}
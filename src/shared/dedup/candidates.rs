@@ -0,0 +1,159 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::model::dedup_model::{DedupRecord, DuplicateCandidate, DuplicateReason};
+use crate::shared::constant::DEDUP_BLOCK_SIZE_MAX;
+use crate::shared::dedup::normalize::similarity;
+
+
+/// Groups record indexes by key, dropping the keys held by a single record.
+fn blocks<'a>(records: &'a [DedupRecord], keys: impl Fn(&'a DedupRecord) -> Vec<String>) -> Vec<Vec<usize>> {
+    let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, record) in records.iter().enumerate() {
+        for key in keys(record) {
+            let block = blocks.entry(key).or_default();
+            if block.last() != Some(&index) {
+                block.push(index);
+            }
+        }
+    }
+    blocks.into_values().filter(|block| block.len() > 1).collect()
+}
+
+fn pairs(block: &[usize]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    block.iter().enumerate().flat_map(move |(i, a)| block[i + 1..].iter().map(move |b| (*a, *b)))
+}
+
+/// Books by different authors are not the same book, whatever their titles.
+fn same_authors_possible(a: &DedupRecord, b: &DedupRecord) -> bool {
+    a.author_ids.is_empty() || b.author_ids.is_empty() || a.author_ids.iter().any(|id| b.author_ids.contains(id))
+}
+
+/// Pairs of records that may be duplicates, best score first, at most `limit` of them.
+///
+/// A shared ISBN or external id always makes a candidate. Names are only compared within
+/// records sharing a word of their key, and blocks of more than `DEDUP_BLOCK_SIZE_MAX` records
+/// are skipped, so common words do not make the comparison quadratic.
+pub fn duplicate_candidates(records: &[DedupRecord], min_score: f64, limit: usize) -> Vec<DuplicateCandidate> {
+    let mut reasons: HashMap<(usize, usize), BTreeSet<DuplicateReason>> = HashMap::new();
+
+    for block in blocks(records, |record| record.isbns.clone()) {
+        for pair in pairs(&block) {
+            reasons.entry(pair).or_default().insert(DuplicateReason::SharedIsbn);
+        }
+    }
+    let external_keys = |record: &DedupRecord| {
+        record.external_ids.iter().map(|(provider, id)| format!("{}:{}", provider.field(), id)).collect()
+    };
+    for block in blocks(records, external_keys) {
+        for pair in pairs(&block) {
+            reasons.entry(pair).or_default().insert(DuplicateReason::SharedExternalId);
+        }
+    }
+
+    let mut scores: HashMap<(usize, usize), f64> = HashMap::new();
+    let word_keys = |record: &DedupRecord| record.key.split(' ').filter(|word| !word.is_empty()).map(str::to_string).collect();
+    for block in blocks(records, word_keys).into_iter().filter(|block| block.len() <= DEDUP_BLOCK_SIZE_MAX) {
+        for (a, b) in pairs(&block) {
+            if scores.contains_key(&(a, b)) {
+                continue;
+            }
+            let score = similarity(&records[a].key, &records[b].key);
+            scores.insert((a, b), score);
+            if score >= min_score && same_authors_possible(&records[a], &records[b]) {
+                reasons.entry((a, b)).or_default().insert(DuplicateReason::SimilarName);
+            }
+        }
+    }
+
+    let mut candidates: Vec<DuplicateCandidate> = reasons
+        .into_iter()
+        .map(|((a, b), reasons)| {
+            let (a, b) = (&records[a], &records[b]);
+            let score = if reasons.iter().any(|reason| *reason != DuplicateReason::SimilarName) {
+                1.0
+            } else {
+                similarity(&a.key, &b.key)
+            };
+            DuplicateCandidate {
+                ids: [a.id, b.id],
+                names: [a.name.clone(), b.name.clone()],
+                score,
+                reasons: reasons.into_iter().collect(),
+            }
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.ids.cmp(&b.ids)));
+    candidates.truncate(limit);
+    candidates
+}
+
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::oid::ObjectId;
+
+    use super::*;
+    use crate::model::external_id_model::ExternalProvider;
+    use crate::shared::dedup::normalize::name_key;
+
+    fn author(name: &str) -> DedupRecord {
+        DedupRecord {
+            id: ObjectId::new(),
+            name: name.to_string(),
+            key: name_key(name),
+            isbns: vec![],
+            external_ids: vec![],
+            author_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn alike_names_are_proposed_and_different_ones_are_not() {
+        let records = vec![author("Fyodor Dostoevsky"), author("Dostoyevsky, Fyodor"), author("Fyodor Tyutchev")];
+
+        let candidates = duplicate_candidates(&records, 0.85, 10);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].ids, [records[0].id, records[1].id]);
+        assert_eq!(candidates[0].reasons, vec![DuplicateReason::SimilarName]);
+        assert!(candidates[0].score < 1.0);
+    }
+
+    #[test]
+    fn shared_keys_rank_first_whatever_the_names() {
+        let mut first = author("Mark Twain");
+        let mut second = author("Samuel Clemens");
+        first.external_ids.push((ExternalProvider::GoodReads, "1244".to_string()));
+        second.external_ids.push((ExternalProvider::GoodReads, "1244".to_string()));
+        let records = vec![author("Jane Austen"), author("Jane Austin"), first, second];
+
+        let candidates = duplicate_candidates(&records, 0.8, 10);
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].reasons, vec![DuplicateReason::SharedExternalId]);
+        assert_eq!(candidates[0].score, 1.0);
+        assert_eq!(candidates[1].reasons, vec![DuplicateReason::SimilarName]);
+    }
+
+    #[test]
+    fn alike_titles_by_different_authors_are_not_proposed() {
+        let (tolkien, lewis) = (ObjectId::new(), ObjectId::new());
+        let mut records = vec![author("Poems"), author("Poems"), author("Poems")];
+        records[0].author_ids = vec![tolkien];
+        records[1].author_ids = vec![lewis];
+        records[2].author_ids = vec![tolkien, lewis];
+
+        let candidates = duplicate_candidates(&records, 0.9, 10);
+
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates.iter().all(|candidate| candidate.ids.contains(&records[2].id)));
+    }
+
+    #[test]
+    fn only_the_best_candidates_are_kept() {
+        let records = vec![author("Ursula Le Guin"), author("Ursula K. Le Guin"), author("Ursula LeGuin")];
+
+        assert_eq!(duplicate_candidates(&records, 0.5, 1).len(), 1);
+    }
+}
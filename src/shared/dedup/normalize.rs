@@ -0,0 +1,159 @@
+/// Leading articles ignored when comparing titles.
+const TITLE_ARTICLES: [&str; 3] = ["the", "a", "an"];
+
+
+/// ASCII spelling of the accented Latin letters imports spell both ways.
+fn fold(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Lowercase words of `text` without accents or punctuation. Runs of single letters
+/// are joined, so "J. R. R. Tolkien" and "JRR Tolkien" give the same words.
+fn words(text: &str) -> Vec<String> {
+    let mut plain = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match fold(c) {
+            Some(folded) => plain.push_str(folded),
+            None if c.is_alphanumeric() => plain.push(c),
+            None => plain.push(' '),
+        }
+    }
+
+    let mut words: Vec<String> = Vec::new();
+    let mut initials = false;
+    for word in plain.split_whitespace() {
+        let initial = word.chars().count() == 1 && !word.starts_with(|c: char| c.is_numeric());
+        match words.last_mut() {
+            Some(last) if initial && initials => last.push_str(word),
+            _ => words.push(word.to_string()),
+        }
+        initials = initial;
+    }
+    words
+}
+
+/// Comparison key of a person's name: its words in alphabetical order,
+/// so "Tolkien, J.R.R." matches "J. R. R. Tolkien".
+pub fn name_key(name: &str) -> String {
+    let mut words = words(name);
+    words.sort();
+    words.join(" ")
+}
+
+/// Comparison key of a book title, without the trailing "(Series, #1)" part
+/// imports add and without a leading article.
+pub fn title_key(title: &str) -> String {
+    let title = title.trim();
+    let title = match title.rfind('(') {
+        Some(start) if start > 0 && title.ends_with(')') => &title[..start],
+        _ => title,
+    };
+    let mut words = words(title);
+    if words.len() > 1 && TITLE_ARTICLES.contains(&words[0].as_str()) {
+        words.remove(0);
+    }
+    words.join(" ")
+}
+
+/// ISBN-13 of an edition, converting a 10 digit ISBN so both spellings of a number compare equal.
+pub fn isbn_key(isbn: &str) -> Option<String> {
+    let isbn: String = isbn.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_uppercase();
+    match isbn.len() {
+        13 if isbn.chars().all(|c| c.is_ascii_digit()) => Some(isbn),
+        10 if isbn[..9].chars().all(|c| c.is_ascii_digit()) => {
+            let body = format!("978{}", &isbn[..9]);
+            let sum: u32 = body
+                .chars()
+                .enumerate()
+                .map(|(i, c)| c.to_digit(10).unwrap_or(0) * if i % 2 == 0 { 1 } else { 3 })
+                .sum();
+            Some(format!("{}{}", body, (10 - sum % 10) % 10))
+        },
+        _ => None,
+    }
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Similarity of two comparison keys between 0 and 1: one minus their edit distance
+/// over the length of the longer one.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_keys_ignore_case_accents_punctuation_and_word_order() {
+        assert_eq!(name_key("J. R. R. Tolkien"), "jrr tolkien");
+        assert_eq!(name_key("Tolkien, JRR"), "jrr tolkien");
+        assert_eq!(name_key("Gabriel García Márquez"), name_key("gabriel garcia marquez"));
+    }
+
+    #[test]
+    fn title_keys_drop_series_suffix_and_leading_article() {
+        assert_eq!(title_key("The Hobbit (Middle-earth Universe)"), "hobbit");
+        assert_eq!(title_key("Hobbit"), "hobbit");
+        assert_eq!(title_key("A Game of Thrones (A Song of Ice and Fire, #1)"), "game of thrones");
+        assert_eq!(title_key("(Untitled)"), "untitled");
+    }
+
+    #[test]
+    fn isbn10_and_isbn13_of_an_edition_share_a_key() {
+        assert_eq!(isbn_key("0-306-40615-2"), Some("9780306406157".to_string()));
+        assert_eq!(isbn_key("978-0-306-40615-7"), Some("9780306406157".to_string()));
+        assert_eq!(isbn_key("080442957X"), Some("9780804429573".to_string()));
+        assert_eq!(isbn_key(""), None);
+    }
+
+    #[test]
+    fn similarity_follows_the_edit_distance() {
+        assert_eq!(similarity("tolkien", "tolkien"), 1.0);
+        assert!((similarity("dostoevsky", "dostoyevsky") - 10.0 / 11.0).abs() < 1e-9);
+        assert!(similarity("austen", "orwell") < 0.5);
+    }
+}
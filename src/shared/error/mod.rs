@@ -0,0 +1,201 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use mongodb::error::{ErrorKind, WriteFailure};
+use thiserror::Error;
+
+use crate::shared::logging::log;
+use crate::shared::models::response::{ApiResponse, ValidationErrorResponse};
+use crate::shared::validation::ValidationErrors;
+
+
+const MONGO_DUPLICATE_KEY: i32 = 11000;
+
+
+/// Error returned by repositories, services and handlers.
+/// Rendered as an `ApiResponse` envelope with the matching status code.
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    /// Request body rejected by its `Validate` rules, reported field by field.
+    #[error("Request validation failed")]
+    InvalidFields(ValidationErrors),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    /// MongoDB, Neo4j, Redis or any other dependency failed.
+    /// The cause is logged but never sent to the client.
+    #[error(transparent)]
+    Upstream(anyhow::Error),
+}
+
+impl AppError {
+    pub fn invalid_id(kind: &str, id: &str) -> Self {
+        Self::Validation(format!("Invalid {} id: {}", kind, id))
+    }
+
+    pub fn not_found(kind: &str, id: &str) -> Self {
+        Self::NotFound(format!("{} not found: {}", kind, id))
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Upstream(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        if let Self::InvalidFields(errors) = self {
+            let body = ValidationErrorResponse::new("Request validation failed".to_string(), errors.into_fields());
+            return (status, Json(body)).into_response();
+        }
+
+        let message = match &self {
+            Self::Upstream(e) => {
+                log::error(&format!("[ERROR] {:#}", e));
+                "Internal server error".to_string()
+            },
+            other => other.to_string(),
+        };
+
+        (status, Json(ApiResponse::<()>::error(message))).into_response()
+    }
+}
+
+
+fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(e)) => e.code == MONGO_DUPLICATE_KEY,
+        ErrorKind::Command(e) => e.code == MONGO_DUPLICATE_KEY,
+        ErrorKind::InsertMany(e) => e.write_errors
+            .as_ref()
+            .is_some_and(|errors| errors.iter().any(|e| e.code == MONGO_DUPLICATE_KEY)),
+        _ => false,
+    }
+}
+
+impl From<mongodb::error::Error> for AppError {
+    fn from(error: mongodb::error::Error) -> Self {
+        if is_duplicate_key(&error) {
+            Self::Conflict("Resource already exists".to_string())
+        } else {
+            Self::Upstream(error.into())
+        }
+    }
+}
+
+impl From<mongodb::bson::oid::Error> for AppError {
+    fn from(error: mongodb::bson::oid::Error) -> Self {
+        Self::Validation(format!("Invalid id: {}", error))
+    }
+}
+
+impl From<mongodb::bson::ser::Error> for AppError {
+    fn from(error: mongodb::bson::ser::Error) -> Self {
+        Self::Upstream(error.into())
+    }
+}
+
+impl From<neo4rs::Error> for AppError {
+    fn from(error: neo4rs::Error) -> Self {
+        Self::Upstream(error.into())
+    }
+}
+
+impl From<neo4rs::DeError> for AppError {
+    fn from(error: neo4rs::DeError) -> Self {
+        Self::Upstream(error.into())
+    }
+}
+
+/// Keeps the typed error when it travelled through `anyhow`, everything else is an upstream failure.
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<AppError>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+        match error.downcast::<mongodb::error::Error>() {
+            Ok(error) => Self::from(error),
+            Err(error) => Self::Upstream(error),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::oid::ObjectId;
+
+    async fn body(error: AppError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[test]
+    fn each_variant_has_its_status_code() {
+        assert_eq!(AppError::not_found("Book", "1").status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(AppError::Conflict("taken".into()).status_code(), StatusCode::CONFLICT);
+        assert_eq!(AppError::invalid_id("book", "x").status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::InvalidFields(ValidationErrors::default()).status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(AppError::Unauthorized("no".into()).status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(AppError::Forbidden("no".into()).status_code(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::Upstream(anyhow::anyhow!("down")).status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn malformed_object_ids_are_validation_errors() {
+        let error = AppError::from(ObjectId::parse_str("nope").unwrap_err());
+
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn typed_errors_survive_a_trip_through_anyhow() {
+        let error = AppError::from(anyhow::Error::new(AppError::not_found("Author", "42")));
+
+        assert!(matches!(error, AppError::NotFound(message) if message == "Author not found: 42"));
+    }
+
+    #[tokio::test]
+    async fn client_errors_send_their_message() {
+        let (status, body) = body(AppError::Conflict("Resource already exists".into())).await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"], "Resource already exists");
+    }
+
+    #[tokio::test]
+    async fn upstream_causes_are_not_sent_to_the_client() {
+        let (status, body) = body(AppError::Upstream(anyhow::anyhow!("connection refused to db:27017"))).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"], "Internal server error");
+    }
+}
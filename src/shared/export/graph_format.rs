@@ -0,0 +1,208 @@
+use crate::model::export_model::{GraphEdge, GraphNode};
+
+
+/// Escapes the XML special characters of `text`, for element content and attribute values alike.
+pub fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Declares the node and edge attributes, then opens the directed graph.
+pub fn graphml_header() -> String {
+    concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n",
+        "  <key id=\"type\" for=\"edge\" attr.name=\"type\" attr.type=\"string\"/>\n",
+        "  <key id=\"status\" for=\"edge\" attr.name=\"status\" attr.type=\"string\"/>\n",
+        "  <key id=\"rating\" for=\"edge\" attr.name=\"rating\" attr.type=\"double\"/>\n",
+        "  <key id=\"ts\" for=\"edge\" attr.name=\"ts\" attr.type=\"long\"/>\n",
+        "  <graph id=\"booknet\" edgedefault=\"directed\">\n",
+    ).to_string()
+}
+
+pub fn graphml_footer() -> String {
+    "  </graph>\n</graphml>\n".to_string()
+}
+
+fn graphml_data(key: &str, value: &str) -> String {
+    format!("<data key=\"{}\">{}</data>", key, xml_escape(value))
+}
+
+pub fn graphml_node(node: &GraphNode) -> String {
+    let mut data = graphml_data("label", node.kind.label());
+    if let Some(name) = &node.name {
+        data.push_str(&graphml_data("name", name));
+    }
+    format!("    <node id=\"{}\">{}</node>\n", xml_escape(&node.kind.node_id(&node.key)), data)
+}
+
+pub fn graphml_edge(edge: &GraphEdge) -> String {
+    let relationship = edge.relationship;
+    let mut data = graphml_data("type", relationship.rel_type());
+    if let Some(status) = &edge.status {
+        data.push_str(&graphml_data("status", status));
+    }
+    if let Some(rating) = edge.rating {
+        data.push_str(&graphml_data("rating", &rating.to_string()));
+    }
+    if let Some(ts) = edge.ts {
+        data.push_str(&graphml_data("ts", &ts.to_string()));
+    }
+    format!(
+        "    <edge source=\"{}\" target=\"{}\">{}</edge>\n",
+        xml_escape(&relationship.source().node_id(&edge.source)),
+        xml_escape(&relationship.target().node_id(&edge.target)),
+        data,
+    )
+}
+
+
+/// Double-quoted Cypher string literal.
+pub fn cypher_string(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+pub fn cypher_node(node: &GraphNode) -> String {
+    let kind = node.kind;
+    let mut statement = format!("MERGE (n:{} {{{}: {}}})", kind.label(), kind.key_property(), cypher_string(&node.key));
+    if let Some(name) = node.name.as_ref().filter(|_| kind.name_property() != kind.key_property()) {
+        statement.push_str(&format!(" SET n.{} = {}", kind.name_property(), cypher_string(name)));
+    }
+    statement.push_str(";\n");
+    statement
+}
+
+pub fn cypher_edge(edge: &GraphEdge) -> String {
+    let relationship = edge.relationship;
+    let (source, target) = (relationship.source(), relationship.target());
+    let mut properties = Vec::new();
+    if let Some(status) = &edge.status {
+        properties.push(format!("r.status = {}", cypher_string(status)));
+    }
+    if let Some(rating) = edge.rating {
+        properties.push(format!("r.rating = {:?}", rating));
+    }
+    if let Some(ts) = edge.ts {
+        properties.push(format!("r.ts = {}", ts));
+    }
+
+    let mut statement = format!(
+        "MATCH (s:{} {{{}: {}}}), (t:{} {{{}: {}}}) MERGE (s)-[r:{}]->(t)",
+        source.label(), source.key_property(), cypher_string(&edge.source),
+        target.label(), target.key_property(), cypher_string(&edge.target),
+        relationship.rel_type(),
+    );
+    if !properties.is_empty() {
+        statement.push_str(" SET ");
+        statement.push_str(&properties.join(", "));
+    }
+    statement.push_str(";\n");
+    statement
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::export_model::{GraphNodeKind, GraphRelationship};
+
+    fn rating() -> GraphEdge {
+        GraphEdge {
+            relationship: GraphRelationship::Rated,
+            source: "u1".to_string(),
+            target: "b1".to_string(),
+            status: None,
+            rating: Some(4.0),
+            ts: Some(1700000000),
+        }
+    }
+
+    #[test]
+    fn graphml_nodes_are_prefixed_by_label_and_escaped() {
+        let node = GraphNode { kind: GraphNodeKind::Book, key: "b1".to_string(), name: Some("Tom & Jerry <3>".to_string()) };
+
+        assert_eq!(
+            graphml_node(&node),
+            "    <node id=\"book:b1\"><data key=\"label\">Book</data><data key=\"name\">Tom &amp; Jerry &lt;3&gt;</data></node>\n"
+        );
+    }
+
+    #[test]
+    fn graphml_edges_only_carry_the_properties_they_have() {
+        assert_eq!(
+            graphml_edge(&rating()),
+            "    <edge source=\"reader:u1\" target=\"book:b1\"><data key=\"type\">RATED</data>\
+             <data key=\"rating\">4</data><data key=\"ts\">1700000000</data></edge>\n"
+        );
+    }
+
+    #[test]
+    fn cypher_nodes_merge_on_their_key() {
+        let book = GraphNode { kind: GraphNodeKind::Book, key: "b1".to_string(), name: Some("The \"Hobbit\"".to_string()) };
+        let genre = GraphNode { kind: GraphNodeKind::Genre, key: "fantasy".to_string(), name: Some("fantasy".to_string()) };
+
+        assert_eq!(cypher_node(&book), "MERGE (n:Book {book_id: \"b1\"}) SET n.title = \"The \\\"Hobbit\\\"\";\n");
+        assert_eq!(cypher_node(&genre), "MERGE (n:Genre {name: \"fantasy\"});\n");
+    }
+
+    #[test]
+    fn works_are_exported_with_their_editions() {
+        let work = GraphNode { kind: GraphNodeKind::Work, key: "w1".to_string(), name: Some("Le Petit Prince".to_string()) };
+        let edition = GraphEdge {
+            relationship: GraphRelationship::EditionOf,
+            source: "b1".to_string(),
+            target: "w1".to_string(),
+            status: None,
+            rating: None,
+            ts: None,
+        };
+
+        assert_eq!(
+            graphml_node(&work),
+            "    <node id=\"work:w1\"><data key=\"label\">Work</data><data key=\"name\">Le Petit Prince</data></node>\n"
+        );
+        assert_eq!(
+            graphml_edge(&edition),
+            "    <edge source=\"book:b1\" target=\"work:w1\"><data key=\"type\">EDITION_OF</data></edge>\n"
+        );
+        assert_eq!(cypher_node(&work), "MERGE (n:Work {work_id: \"w1\"}) SET n.title = \"Le Petit Prince\";\n");
+        assert_eq!(
+            cypher_edge(&edition),
+            "MATCH (s:Book {book_id: \"b1\"}), (t:Work {work_id: \"w1\"}) MERGE (s)-[r:EDITION_OF]->(t);\n"
+        );
+    }
+
+    #[test]
+    fn cypher_edges_match_both_ends_then_merge() {
+        assert_eq!(
+            cypher_edge(&rating()),
+            "MATCH (s:Reader {user_id: \"u1\"}), (t:Book {book_id: \"b1\"}) MERGE (s)-[r:RATED]->(t) \
+             SET r.rating = 4.0, r.ts = 1700000000;\n"
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod record_format;
+pub mod graph_format;
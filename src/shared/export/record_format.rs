@@ -0,0 +1,108 @@
+use serde_json::{Map, Value};
+
+
+/// Separator of the items of a list held in one CSV field.
+pub const CSV_LIST_SEPARATOR: &str = "|";
+
+
+/// The record as one line of JSON Lines, newline included.
+pub fn json_line(record: &Map<String, Value>) -> String {
+    let mut line = Value::Object(record.clone()).to_string();
+    line.push('\n');
+    line
+}
+
+/// Quotes a CSV field when it holds a separator, a quote or a line break, doubling its quotes.
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Text of a value in a CSV field: nothing for null, lists of scalars joined with
+/// `CSV_LIST_SEPARATOR`, and JSON for anything more nested.
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(items) if items.iter().all(|item| !item.is_array() && !item.is_object()) => items
+            .iter()
+            .map(csv_field)
+            .collect::<Vec<_>>()
+            .join(CSV_LIST_SEPARATOR),
+        _ => value.to_string(),
+    }
+}
+
+/// One CSV line of `fields`, newline included.
+pub fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| csv_escape(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+/// The record as a CSV line with one field per column, missing ones left empty.
+pub fn csv_record<S: AsRef<str>>(record: &Map<String, Value>, columns: &[S]) -> String {
+    let fields: Vec<String> = columns
+        .iter()
+        .map(|column| record.get(column.as_ref()).map(csv_field).unwrap_or_default())
+        .collect();
+    csv_line(&fields)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn record(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(fields) => fields,
+            _ => panic!("expected an object"),
+        }
+    }
+
+    #[test]
+    fn fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a, b"), "\"a, b\"");
+        assert_eq!(csv_escape("the \"best\" book"), "\"the \"\"best\"\" book\"");
+        assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn csv_records_follow_the_columns_and_flatten_lists() {
+        let record = record(json!({
+            "title": "Dune, Part One",
+            "genres": ["science-fiction", "classics"],
+            "num_pages": 412,
+            "subtitle": null,
+            "shelf": [{ "book_id": "b1", "status": "read" }],
+        }));
+
+        let line = csv_record(&record, &["title", "subtitle", "genres", "num_pages", "missing", "shelf"]);
+
+        assert_eq!(
+            line,
+            "\"Dune, Part One\",,science-fiction|classics,412,,\"[{\"\"book_id\"\":\"\"b1\"\",\"\"status\"\":\"\"read\"\"}]\"\n"
+        );
+    }
+
+    #[test]
+    fn json_lines_hold_one_record_per_line() {
+        let line = json_line(&record(json!({ "id": "a1", "description": "one\ntwo" })));
+
+        assert_eq!(line.matches('\n').count(), 1);
+        assert!(line.ends_with('\n'));
+        assert_eq!(serde_json::from_str::<Value>(&line).unwrap(), json!({ "id": "a1", "description": "one\ntwo" }));
+    }
+}
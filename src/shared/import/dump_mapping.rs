@@ -0,0 +1,344 @@
+//! Maps dump rows to imported records. Goodreads and Kaggle dumps name the same data
+//! differently, so every field is read from the first of several known columns.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+
+use crate::model::book_model::BookFormat;
+use crate::model::external_id_model::ExternalProvider;
+use crate::model::import_model::{
+    ImportKind, ImportRecord, ImportedAuthor, ImportedAuthorRef, ImportedBook, ImportedRating,
+};
+use crate::model::review_model::Review;
+use crate::shared::import::dump_reader::DumpRow;
+use crate::shared::validation::rules;
+
+
+/// Goodreads dumps carry Goodreads ids, and the goodbooks datasets a Goodreads id next to their own.
+const GOOD_READS_BOOK_ID: &[&str] = &["goodreads_book_id", "best_book_id", "book_id", "bookID", "id"];
+/// Kaggle datasets number their books themselves, and their ratings refer to those numbers.
+const KAGGLE_BOOK_ID: &[&str] = &["book_id", "bookID", "id"];
+
+const AUTHOR_ID: &[&str] = &["author_id", "authorId", "id"];
+const AUTHOR_NAME: &[&str] = &["name", "author_name", "author"];
+const READER_ID: &[&str] = &["user_id", "userId", "reader_id"];
+const READER_NAME: &[&str] = &["user_name", "username", "reader_name"];
+
+const LIST_SEPARATORS: &[char] = &['/', ',', '|', ';'];
+
+
+fn book_id_columns(provider: ExternalProvider) -> &'static [&'static str] {
+    match provider {
+        ExternalProvider::Kaggle => KAGGLE_BOOK_ID,
+        _ => GOOD_READS_BOOK_ID,
+    }
+}
+
+pub fn parse_record(kind: ImportKind, provider: ExternalProvider, line: u64, row: &DumpRow) -> Result<ImportRecord, String> {
+    match kind {
+        ImportKind::Authors => parse_author(line, row).map(ImportRecord::Author),
+        ImportKind::Books => parse_book(provider, line, row).map(ImportRecord::Book),
+        ImportKind::Ratings => parse_rating(provider, line, row).map(ImportRecord::Rating),
+    }
+}
+
+fn parse_author(line: u64, row: &DumpRow) -> Result<ImportedAuthor, String> {
+    Ok(ImportedAuthor {
+        line,
+        external_id: row.text(AUTHOR_ID).ok_or("Missing author id")?,
+        name: row.text(AUTHOR_NAME).ok_or("Missing author name")?,
+        description: row.text(&["about", "description", "bio"]),
+        image_url: row.text(&["image_url", "image"]).filter(|url| rules::url(url).is_ok()),
+    })
+}
+
+fn parse_book(provider: ExternalProvider, line: u64, row: &DumpRow) -> Result<ImportedBook, String> {
+    let isbn = row.text(&["isbn"]).and_then(|isbn| normalize_isbn(&isbn, 10));
+    let isbn13 = row.text(&["isbn13"]).and_then(|isbn| normalize_isbn(&isbn, 13));
+    let external_id = row.text(book_id_columns(provider));
+    if external_id.is_none() && isbn.is_none() && isbn13.is_none() {
+        return Err("Missing book id and ISBN".to_string());
+    }
+
+    let mut languages: Vec<String> = row
+        .list(&["language_code", "language", "languages"], LIST_SEPARATORS)
+        .iter()
+        .filter_map(value_text)
+        .filter_map(|code| normalize_language(&code))
+        .collect();
+    languages.dedup();
+
+    Ok(ImportedBook {
+        line,
+        external_id,
+        isbn,
+        isbn13,
+        title: row.text(&["title", "original_title", "title_without_series"]).ok_or("Missing title")?,
+        description: row.text(&["description"]),
+        num_pages: row.number::<f64>(&["num_pages", "pages"])?.map(|pages| pages as i32).filter(|pages| *pages > 0),
+        published_date: published_date(row)?,
+        format: book_format(row.text(&["format"]).as_deref()),
+        authors: author_refs(row),
+        genres: row
+            .list(&["genres", "genre"], LIST_SEPARATORS)
+            .iter()
+            .filter_map(value_text)
+            .filter_map(|name| normalize_slug(&name))
+            .collect(),
+        publishers: row
+            .list(&["publisher", "publishers"], &['|', ';'])
+            .iter()
+            .filter_map(value_text)
+            .filter_map(|name| normalize_slug(&name))
+            .collect(),
+        languages,
+        image_url: row.text(&["image_url", "image", "small_image_url"]).filter(|url| rules::url(url).is_ok()),
+    })
+}
+
+fn parse_rating(provider: ExternalProvider, line: u64, row: &DumpRow) -> Result<ImportedRating, String> {
+    let score = row.number::<f32>(&["rating", "score", "stars"])?.ok_or("Missing rating")?;
+    // Goodreads exports shelved but unrated books with a rating of 0
+    if Review::score_bucket(score).is_none() {
+        return Err(format!("Invalid rating: {}", score));
+    }
+
+    let book_external_id = row.text(book_id_columns(provider));
+    let isbn = row
+        .text(&["isbn13", "isbn"])
+        .and_then(|isbn| normalize_isbn(&isbn, 13).or_else(|| normalize_isbn(&isbn, 10)));
+    if book_external_id.is_none() && isbn.is_none() {
+        return Err("Missing book id and ISBN".to_string());
+    }
+
+    Ok(ImportedRating {
+        line,
+        reader_external_id: row.text(READER_ID).ok_or("Missing user id")?,
+        reader_name: row.text(READER_NAME),
+        book_external_id,
+        isbn,
+        score,
+        rated_at: row.text(&["date_added", "rated_at", "date"]).and_then(|date| parse_date(&date)),
+    })
+}
+
+
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        // Goodreads lists shelves and genres as `{ "name": ..., "count": ... }`
+        Value::Object(fields) => fields.get("name").and_then(value_text),
+        _ => None,
+    }
+}
+
+/// Authors given as names, as ids, or as `{ "author_id": ..., "name": ... }` objects.
+fn author_refs(row: &DumpRow) -> Vec<ImportedAuthorRef> {
+    let mut refs: Vec<ImportedAuthorRef> = row
+        .list(&["authors", "author", "author_name"], LIST_SEPARATORS)
+        .iter()
+        .filter_map(|value| match value {
+            Value::Object(fields) => {
+                let external_id = fields.get("author_id").or_else(|| fields.get("id")).and_then(value_text);
+                let name = fields.get("name").and_then(value_text);
+                (external_id.is_some() || name.is_some()).then_some(ImportedAuthorRef { external_id, name })
+            },
+            other => value_text(other).map(|name| ImportedAuthorRef { external_id: None, name: Some(name) }),
+        })
+        .collect();
+
+    if refs.is_empty() {
+        refs = row
+            .list(&["author_ids", "author_id"], LIST_SEPARATORS)
+            .iter()
+            .filter_map(value_text)
+            .map(|id| ImportedAuthorRef { external_id: Some(id), name: None })
+            .collect();
+    }
+    refs
+}
+
+/// Digits of the ISBN, with a final `X` check digit kept. Spreadsheet exponents such as
+/// `9.78044E+12` have lost digits and are dropped.
+fn normalize_isbn(raw: &str, length: usize) -> Option<String> {
+    if raw.contains(['e', 'E']) {
+        return None;
+    }
+    let isbn: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'X' || *c == 'x')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    // CSV tools strip the leading zeros of ISBN-10s stored as numbers
+    let isbn = if isbn.len() < length && length == 10 { format!("{:0>10}", isbn) } else { isbn };
+    (isbn.len() == length).then_some(isbn)
+}
+
+/// `en-US` and `en` both give `en`, ISO 639-2 codes such as `eng` are kept.
+fn normalize_language(raw: &str) -> Option<String> {
+    let code = raw.split(['-', '_']).next()?.to_ascii_lowercase();
+    rules::iso639(&code).ok().map(|_| code)
+}
+
+/// Lowercase slug, the only form genre and publisher names are accepted in.
+fn normalize_slug(raw: &str) -> Option<String> {
+    let mut name = String::with_capacity(raw.len());
+    for c in raw.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let name = name.trim_end_matches('-').to_string();
+    rules::slug(&name).ok().map(|_| name)
+}
+
+fn book_format(raw: Option<&str>) -> BookFormat {
+    let format = raw.unwrap_or_default().to_lowercase();
+    if format.contains("hardcover") {
+        BookFormat::Hardcover
+    } else if format.contains("audio") {
+        BookFormat::Audiobook
+    } else if format.contains("ebook") || format.contains("kindle") {
+        BookFormat::EBook
+    } else {
+        BookFormat::Paperback
+    }
+}
+
+/// RFC 3339, `2004-09-16`, Goodreads' `9/16/2004` or a bare year.
+fn parse_date(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(raw) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let date = ["%Y-%m-%d", "%m/%d/%Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+        .or_else(|| {
+            let year = raw.parse::<f64>().ok()?;
+            NaiveDate::from_ymd_opt(year as i32, 1, 1)
+        })?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+fn published_date(row: &DumpRow) -> Result<Option<DateTime<Utc>>, String> {
+    if let Some(year) = row.number::<f64>(&["publication_year"])? {
+        let month = row.number::<u32>(&["publication_month"])?.unwrap_or(1);
+        let day = row.number::<u32>(&["publication_day"])?.unwrap_or(1);
+        return Ok(NaiveDate::from_ymd_opt(year as i32, month, day)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc()));
+    }
+    match row.text(&["publication_date", "published_date", "original_publication_year"]) {
+        Some(raw) => parse_date(&raw).map(Some).ok_or_else(|| format!("Invalid publication date: {}", raw)),
+        None => Ok(None),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(fields: Value) -> DumpRow {
+        match fields {
+            Value::Object(fields) => DumpRow::new(fields),
+            _ => panic!("expected an object"),
+        }
+    }
+
+    #[test]
+    fn isbn_keeps_digits_and_the_check_letter() {
+        assert_eq!(normalize_isbn("0-8044-2957-x", 10).as_deref(), Some("080442957X"));
+        assert_eq!(normalize_isbn("978-0-306-40615-7", 13).as_deref(), Some("9780306406157"));
+    }
+
+    #[test]
+    fn isbn10_gets_back_the_zeros_a_spreadsheet_stripped() {
+        assert_eq!(normalize_isbn("306406152", 10).as_deref(), Some("0306406152"));
+        assert_eq!(normalize_isbn("306406152", 13), None);
+    }
+
+    #[test]
+    fn isbn_in_exponent_notation_is_dropped() {
+        assert_eq!(normalize_isbn("9.78031E+12", 13), None);
+    }
+
+    #[test]
+    fn language_keeps_the_base_code() {
+        assert_eq!(normalize_language("en-US").as_deref(), Some("en"));
+        assert_eq!(normalize_language("eng").as_deref(), Some("eng"));
+        assert_eq!(normalize_language("English"), None);
+    }
+
+    #[test]
+    fn names_are_slugged() {
+        assert_eq!(normalize_slug("Penguin Books, Ltd.").as_deref(), Some("penguin-books-ltd"));
+        assert_eq!(normalize_slug("  Science Fiction ").as_deref(), Some("science-fiction"));
+        assert_eq!(normalize_slug("***"), None);
+    }
+
+    #[test]
+    fn format_is_guessed_from_its_description() {
+        assert_eq!(book_format(Some("Kindle Edition")).name(), "EBook");
+        assert_eq!(book_format(Some("Audio CD")).name(), "Audiobook");
+        assert_eq!(book_format(Some("Hardcover")).name(), "Hardcover");
+        assert_eq!(book_format(None).name(), "Paperback");
+    }
+
+    #[test]
+    fn dates_are_read_in_every_dump_format() {
+        let expected = NaiveDate::from_ymd_opt(2004, 9, 16).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        assert_eq!(parse_date("2004-09-16"), Some(expected));
+        assert_eq!(parse_date("9/16/2004"), Some(expected));
+        assert_eq!(parse_date("2004-09-16T00:00:00Z"), Some(expected));
+        assert_eq!(parse_date("2004.0").map(|date| date.to_rfc3339()), Some("2004-01-01T00:00:00+00:00".to_string()));
+        assert_eq!(parse_date("someday"), None);
+    }
+
+    #[test]
+    fn goodreads_book_row_is_mapped() {
+        let row = row(serde_json::json!({
+            "book_id": "5907",
+            "isbn": "618260307",
+            "title": "The Hobbit",
+            "authors": [{ "author_id": "656983", "name": "J.R.R. Tolkien" }],
+            "genres": "Fantasy, Young Adult",
+            "language_code": "en-GB",
+            "num_pages": "366",
+            "publication_year": "1937",
+            "publication_month": "9",
+            "publication_day": "21",
+        }));
+
+        let book = match parse_record(ImportKind::Books, ExternalProvider::GoodReads, 2, &row).unwrap() {
+            ImportRecord::Book(book) => book,
+            other => panic!("unexpected record {:?}", other),
+        };
+
+        assert_eq!(book.external_id.as_deref(), Some("5907"));
+        assert_eq!(book.isbn.as_deref(), Some("0618260307"));
+        assert_eq!(book.genres, vec!["fantasy", "young-adult"]);
+        assert_eq!(book.languages, vec!["en"]);
+        assert_eq!(book.num_pages, Some(366));
+        assert_eq!(book.authors[0].external_id.as_deref(), Some("656983"));
+        assert_eq!(book.published_date.map(|date| date.date_naive()), NaiveDate::from_ymd_opt(1937, 9, 21));
+    }
+
+    #[test]
+    fn book_without_id_or_isbn_is_rejected() {
+        let row = row(serde_json::json!({ "title": "Untitled" }));
+
+        assert_eq!(parse_record(ImportKind::Books, ExternalProvider::GoodReads, 2, &row).unwrap_err(), "Missing book id and ISBN");
+    }
+
+    #[test]
+    fn unrated_shelvings_are_not_ratings() {
+        let row = row(serde_json::json!({ "user_id": "1", "book_id": "5907", "rating": "0" }));
+
+        assert_eq!(parse_record(ImportKind::Ratings, ExternalProvider::GoodReads, 2, &row).unwrap_err(), "Invalid rating: 0");
+    }
+}
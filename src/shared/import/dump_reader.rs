@@ -0,0 +1,229 @@
+use serde_json::{Map, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, Lines};
+
+use crate::model::import_model::ImportFormat;
+
+
+/// One record of a dump, keyed by column or property name.
+#[derive(Debug, Clone, Default)]
+pub struct DumpRow(Map<String, Value>);
+
+impl DumpRow {
+    pub fn new(fields: Map<String, Value>) -> Self {
+        Self(fields)
+    }
+
+    /// First of the `keys` holding a value, as text. Numbers are formatted, blanks are skipped.
+    pub fn text(&self, keys: &[&str]) -> Option<String> {
+        keys.iter().find_map(|key| match self.0.get(*key)? {
+            Value::String(value) if !value.trim().is_empty() => Some(value.trim().to_string()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        })
+    }
+
+    /// Parsed value of the first of the `keys` holding one.
+    pub fn number<T: std::str::FromStr>(&self, keys: &[&str]) -> Result<Option<T>, String> {
+        match self.text(keys) {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("Invalid {}: {}", keys[0], value)),
+            None => Ok(None),
+        }
+    }
+
+    /// Values of the first of the `keys` present: JSON arrays item by item, with objects
+    /// kept whole, and text split on any of the `separators`.
+    pub fn list(&self, keys: &[&str], separators: &[char]) -> Vec<Value> {
+        let value = keys.iter().find_map(|key| self.0.get(*key).filter(|value| !value.is_null()));
+        match value {
+            Some(Value::Array(values)) => values.clone(),
+            Some(Value::String(text)) => text
+                .split(separators)
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+            Some(other) => vec![other.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+
+/// Streams the rows of a CSV or JSON Lines dump without loading the file.
+/// CSV follows RFC 4180: the first record names the columns, and quoted fields may hold
+/// separators, doubled quotes and line breaks.
+pub struct DumpReader<R> {
+    lines: Lines<R>,
+    format: ImportFormat,
+    headers: Option<Vec<String>>,
+    line: u64,
+}
+
+impl<R: AsyncBufRead + Unpin> DumpReader<R> {
+    pub fn new(reader: R, format: ImportFormat) -> Self {
+        Self { lines: reader.lines(), format, headers: None, line: 0 }
+    }
+
+    /// The next row with the line it starts on, or the reason it could not be parsed.
+    /// Only I/O failures end the stream with an error.
+    pub async fn next_row(&mut self) -> std::io::Result<Option<(u64, Result<DumpRow, String>)>> {
+        match self.format {
+            ImportFormat::JsonLines => self.next_json_row().await,
+            ImportFormat::Csv => self.next_csv_row().await,
+        }
+    }
+
+    async fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        let line = self.lines.next_line().await?;
+        if line.is_some() {
+            self.line += 1;
+        }
+        Ok(line)
+    }
+
+    async fn next_json_row(&mut self) -> std::io::Result<Option<(u64, Result<DumpRow, String>)>> {
+        while let Some(line) = self.next_line().await? {
+            // Tolerates a dump written as one JSON array with an object per line
+            let record = line.trim().trim_end_matches(',');
+            if record.is_empty() || record == "[" || record == "]" {
+                continue;
+            }
+            let row = match serde_json::from_str::<Value>(record) {
+                Ok(Value::Object(fields)) => Ok(DumpRow::new(fields)),
+                Ok(_) => Err("Expected a JSON object".to_string()),
+                Err(e) => Err(format!("Invalid JSON: {}", e)),
+            };
+            return Ok(Some((self.line, row)));
+        }
+        Ok(None)
+    }
+
+    /// Lines of the next CSV record, joined while a quoted field is still open.
+    async fn next_csv_record(&mut self) -> std::io::Result<Option<(u64, Vec<String>)>> {
+        let mut record = loop {
+            match self.next_line().await? {
+                Some(line) if line.trim().is_empty() => continue,
+                Some(line) => break line,
+                None => return Ok(None),
+            }
+        };
+        let start = self.line;
+
+        while record.matches('"').count() % 2 == 1 {
+            match self.next_line().await? {
+                Some(line) => {
+                    record.push('\n');
+                    record.push_str(&line);
+                },
+                None => break,
+            }
+        }
+        Ok(Some((start, split_csv_record(&record))))
+    }
+
+    async fn next_csv_row(&mut self) -> std::io::Result<Option<(u64, Result<DumpRow, String>)>> {
+        if self.headers.is_none() {
+            match self.next_csv_record().await? {
+                Some((_, headers)) => {
+                    let headers = headers.into_iter().map(|header| header.trim().trim_start_matches('\u{feff}').to_string()).collect();
+                    self.headers = Some(headers);
+                },
+                None => return Ok(None),
+            }
+        }
+
+        let (line, fields) = match self.next_csv_record().await? {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+        let headers = self.headers.as_deref().unwrap_or_default();
+        if fields.len() != headers.len() {
+            let message = format!("Expected {} fields, found {}", headers.len(), fields.len());
+            return Ok(Some((line, Err(message))));
+        }
+
+        let row = headers
+            .iter()
+            .zip(fields)
+            .map(|(header, field)| (header.clone(), Value::String(field)))
+            .collect();
+        Ok(Some((line, Ok(DumpRow::new(row)))))
+    }
+}
+
+fn split_csv_record(record: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = record.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn rows(dump: &str, format: ImportFormat) -> Vec<(u64, Result<DumpRow, String>)> {
+        let mut reader = DumpReader::new(dump.as_bytes(), format);
+        let mut rows = Vec::new();
+        while let Some(row) = reader.next_row().await.unwrap() {
+            rows.push(row);
+        }
+        rows
+    }
+
+    #[test]
+    fn csv_fields_may_quote_separators_and_quotes() {
+        assert_eq!(split_csv_record(r#"1,"Tolkien, J.R.R.","The ""Hobbit""","#), vec!["1", "Tolkien, J.R.R.", r#"The "Hobbit""#, ""]);
+    }
+
+    #[tokio::test]
+    async fn csv_rows_are_keyed_by_header_and_numbered_by_their_first_line() {
+        let rows = rows("\u{feff}id,title\n1,\"A\nmultiline title\"\n\n2,Second\n3\n", ImportFormat::Csv).await;
+
+        assert_eq!(rows.len(), 3);
+        let (line, first) = &rows[0];
+        assert_eq!(*line, 2);
+        assert_eq!(first.as_ref().unwrap().text(&["id"]).as_deref(), Some("1"));
+        assert_eq!(first.as_ref().unwrap().text(&["title"]).as_deref(), Some("A\nmultiline title"));
+        assert_eq!(rows[1].0, 5);
+        assert_eq!(rows[2].1.as_ref().unwrap_err(), "Expected 2 fields, found 1");
+    }
+
+    #[tokio::test]
+    async fn json_lines_skip_array_brackets_and_report_bad_records() {
+        let rows = rows("[\n{\"id\": 1},\n42\n{broken\n]\n", ImportFormat::JsonLines).await;
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].1.as_ref().unwrap().text(&["id"]).as_deref(), Some("1"));
+        assert_eq!(rows[1].1.as_ref().unwrap_err(), "Expected a JSON object");
+        assert!(rows[2].1.as_ref().unwrap_err().starts_with("Invalid JSON"));
+        assert_eq!(rows[2].0, 4);
+    }
+
+    #[test]
+    fn row_reads_the_first_column_present() {
+        let row = DumpRow::new(serde_json::json!({ "name": " ", "author": "Le Guin", "pages": "abc" }).as_object().unwrap().clone());
+
+        assert_eq!(row.text(&["name", "author"]).as_deref(), Some("Le Guin"));
+        assert_eq!(row.number::<u32>(&["pages"]).unwrap_err(), "Invalid pages: abc");
+        assert_eq!(row.list(&["author"], &['/']), vec![Value::String("Le Guin".to_string())]);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod dump_reader;
+pub mod dump_mapping;
\ No newline at end of file
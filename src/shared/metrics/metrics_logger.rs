@@ -2,7 +2,6 @@ use axum::{
     extract::Request,
     middleware::Next,
     response::Response,
-    http::{Method, StatusCode},
 };
 use std::time::Instant;
 
@@ -15,7 +14,6 @@ pub async fn metrics_and_logging_middleware(
 ) -> Response {
     let method = request.method().clone();
     let uri = request.uri().clone();
-    let path = uri.path().to_string();
 
     // Start timer for metrics
     let start_time = Instant::now();
@@ -31,7 +29,7 @@ pub async fn metrics_and_logging_middleware(
     let status = response.status();
 
     // Record metrics (if you have metrics setup)
-    // state.metrics.record_http_request(method.as_str(), &path, status.as_u16(), duration.as_secs_f64());
+    // state.metrics.record_http_request(method.as_str(), uri.path(), status.as_u16(), duration.as_secs_f64());
 
     // Log response with color based on status code
     match status.as_u16() {
@@ -6,4 +6,10 @@ pub mod database;
 pub mod openapi;
 pub mod logging;
 pub mod repository;
-pub mod constant;
\ No newline at end of file
+pub mod constant;
+pub mod security;
+pub mod error;
+pub mod validation;
+pub mod import;
+pub mod export;
+pub mod dedup;
\ No newline at end of file
@@ -1,15 +1,63 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::shared::constant::{LIMIT_DEFAULT, LIMIT_MAX};
+
+/// Requested number of items, `default` when absent, clamped to `1..=max`.
+pub fn limit(requested: Option<u32>, default: u64, max: u64) -> u64 {
+    requested.map(u64::from).unwrap_or(default).clamp(1, max)
+}
+
 /// A generic paginated request structure.
-#[derive(Debug, Serialize, Deserialize, ToSchema, IntoParams)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
 pub struct PaginationRequest {
+    /// 1-based page number
     #[param(example = 1)]
     pub page: Option<u32>,
+    /// Items per page, capped at `LIMIT_MAX`
     #[param(example = 10)]
     pub page_size: Option<u32>,
+    /// Case-insensitive text matched against the searchable fields of the resource
     #[param(example = "name")]
     pub search: Option<String>,
+    /// Field to sort on, prefixed with `-` for descending order
+    #[param(example = "-name")]
+    pub sort: Option<String>,
+}
+
+impl PaginationRequest {
+    pub fn page(&self) -> u64 {
+        u64::from(self.page.unwrap_or(1).max(1))
+    }
+
+    pub fn page_size(&self) -> u64 {
+        limit(self.page_size, LIMIT_DEFAULT, LIMIT_MAX)
+    }
+
+    pub fn skip(&self) -> u64 {
+        (self.page() - 1) * self.page_size()
+    }
+
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref().map(str::trim).filter(|search| !search.is_empty())
+    }
+
+    pub fn sort(&self) -> Option<&str> {
+        self.sort.as_deref().map(str::trim).filter(|sort| !sort.is_empty())
+    }
+
+    /// Stable identity of the requested page, used to key cached lists.
+    pub fn cache_key(&self) -> String {
+        format!(
+            "page={}:size={}:search={}:sort={}",
+            self.page(),
+            self.page_size(),
+            self.search().unwrap_or_default().to_lowercase(),
+            self.sort().unwrap_or_default()
+        )
+    }
 }
 
 /// A generic paginated response structure.
@@ -21,6 +69,17 @@ pub struct PaginatedResponse<T> {
     pub page_size: i64,
 }
 
+impl<T> PaginatedResponse<T> {
+    pub fn new(items: Vec<T>, total: u64, pagination: &PaginationRequest) -> Self {
+        Self {
+            items,
+            total: total as i64,
+            page: pagination.page() as i64,
+            page_size: pagination.page_size() as i64,
+        }
+    }
+}
+
 
 /// API Responses
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -32,19 +91,91 @@ pub struct ApiResponse<T> {
 
 
 impl<T> ApiResponse<T> {
-    pub fn success(data: T) -> Self {
+    pub fn error(message: String) -> Self {
         Self {
-            success: true,
-            data: Some(data),
-            error: None,
+            success: false,
+            data: None,
+            error: Some(message),
         }
     }
+}
 
-    pub fn error(message: String) -> Self {
+
+/// Body of a 422 response: the `ApiResponse` error envelope plus the messages of every rejected field.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub success: bool,
+    pub error: String,
+    /// Messages keyed by field path, e.g. `name` or `languages[1]`
+    pub fields: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrorResponse {
+    pub fn new(message: String, fields: BTreeMap<String, Vec<String>>) -> Self {
         Self {
             success: false,
-            data: None,
-            error: Some(message),
+            error: message,
+            fields,
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(page: Option<u32>, page_size: Option<u32>) -> PaginationRequest {
+        PaginationRequest { page, page_size, ..Default::default() }
+    }
+
+    #[test]
+    fn missing_page_and_size_use_the_defaults() {
+        let pagination = request(None, None);
+
+        assert_eq!(pagination.page(), 1);
+        assert_eq!(pagination.page_size(), LIMIT_DEFAULT);
+        assert_eq!(pagination.skip(), 0);
+    }
+
+    #[test]
+    fn page_size_is_clamped_to_the_limits() {
+        assert_eq!(request(None, Some(0)).page_size(), 1);
+        assert_eq!(request(None, Some(10_000)).page_size(), LIMIT_MAX);
+    }
+
+    #[test]
+    fn limit_defaults_and_is_clamped() {
+        assert_eq!(limit(None, 20, 50), 20);
+        assert_eq!(limit(Some(0), 20, 50), 1);
+        assert_eq!(limit(Some(5), 20, 50), 5);
+        assert_eq!(limit(Some(1_000), 20, 50), 50);
+    }
+
+    #[test]
+    fn skip_counts_the_previous_pages() {
+        assert_eq!(request(Some(3), Some(20)).skip(), 40);
+        assert_eq!(request(Some(0), Some(20)).skip(), 0);
+    }
+
+    #[test]
+    fn blank_search_and_sort_are_ignored() {
+        let pagination = PaginationRequest { search: Some("  ".into()), sort: Some("".into()), ..Default::default() };
+
+        assert_eq!(pagination.search(), None);
+        assert_eq!(pagination.sort(), None);
+    }
+
+    #[test]
+    fn cache_key_identifies_the_effective_page() {
+        let pagination = PaginationRequest {
+            page: Some(2),
+            page_size: Some(500),
+            search: Some(" Tolkien ".into()),
+            sort: Some("-name".into()),
+        };
+
+        assert_eq!(pagination.cache_key(), "page=2:size=100:search=tolkien:sort=-name");
+        assert_eq!(request(None, None).cache_key(), request(Some(1), Some(10)).cache_key());
+    }
+}
@@ -1,39 +1,148 @@
-use utoipa::{OpenApi};
+use utoipa::{Modify, OpenApi};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 
-use crate::controller::{genre_controller, language_controller, publisher_controller, source_controller};
-use crate::dto::{genre_dto, language_dto, publisher_dto, source_dto};
+use crate::controller::{
+    auth_controller, author_controller, book_controller, dedup_controller, export_controller, follow_controller, genre_controller, import_controller, language_controller, publisher_controller, recommendation_controller, review_controller, search_controller, shelf_controller, source_controller, user_controller, work_controller
+};
+use crate::model::{book_model, dedup_model, export_model, external_id_model, follow_model, import_model};
+use crate::shared::models::response::ValidationErrorResponse;
+use crate::dto::{
+    auth_dto, author_dto, book_dto, dedup_dto, external_id_dto, follow_dto, genre_dto, import_dto, language_dto, publisher_dto, recommendation_dto, review_dto, search_dto, shelf_dto, similarity_dto, source_dto, user_dto, work_dto
+};
 
 #[derive(OpenApi)]
 #[openapi(
     info(version = "1.0.0", title = "Book Net API", description = "Book Net API description"),
+    modifiers(&SecurityAddon),
     tags(
+        (name = "Auth", description = "Authentication API endpoints"),
+        (name = "Author", description = "Author API endpoints"),
+        (name = "Book", description = "Book API endpoints"),
+        (name = "Dedup", description = "Duplicate author and book detection and merging API endpoints"),
+        (name = "Export", description = "Catalog and reading graph export API endpoints"),
+        (name = "Follow", description = "Reader follow and activity feed API endpoints"),
         (name = "Genre", description = "Genre API endpoints"),
+        (name = "Import", description = "Catalog import API endpoints"),
         (name = "Language", description = "Language API endpoints"),
         (name = "Publisher", description = "Publisher API endpoints"),
+        (name = "Recommendation", description = "Book recommendation API endpoints"),
+        (name = "Review", description = "Review API endpoints"),
+        (name = "Search", description = "Catalog search API endpoints"),
+        (name = "Shelf", description = "Reading shelf API endpoints"),
         (name = "Source", description = "Source API endpoints"),
         (name = "User", description = "User API endpoints"),
+        (name = "Work", description = "Work and edition API endpoints"),
     ),
     paths(
 
+        auth_controller::post_login,
+
+        author_controller::get_authors, author_controller::post_author,
+        author_controller::get_author, author_controller::put_author, author_controller::delete_author,
+        author_controller::get_author_books, author_controller::get_similar_authors,
+        author_controller::get_author_by_external_id, author_controller::put_author_external_id,
+        author_controller::delete_author_external_id,
+
+        book_controller::get_books, book_controller::post_book,
+        book_controller::get_book, book_controller::put_book, book_controller::delete_book,
+        book_controller::get_similar_books,
+        book_controller::get_book_by_external_id, book_controller::put_book_external_id,
+        book_controller::delete_book_external_id,
+
+        dedup_controller::get_author_duplicates, dedup_controller::post_author_merge,
+        dedup_controller::get_book_duplicates, dedup_controller::post_book_merge,
+
+        export_controller::get_export,
+
+        follow_controller::get_my_followers, follow_controller::get_my_following,
+        follow_controller::put_following, follow_controller::delete_following, follow_controller::get_my_feed,
+        follow_controller::get_user_followers, follow_controller::get_user_following,
+
         genre_controller::get_genres, genre_controller::post_genre,
         genre_controller::get_genre, genre_controller::put_genre, genre_controller::delete_genre,
 
+        import_controller::post_import, import_controller::get_import,
+
         language_controller::get_languages, language_controller::post_language,
         language_controller::get_language, language_controller::put_language, language_controller::delete_language,
     
         publisher_controller::list_publishers, publisher_controller::post_publisher,
         publisher_controller::get_publisher, publisher_controller::put_publisher, publisher_controller::delete_publisher,
     
+        recommendation_controller::get_my_recommendations,
+
+        review_controller::get_book_reviews, review_controller::post_review, review_controller::get_user_reviews,
+        review_controller::get_work_reviews,
+        review_controller::get_review, review_controller::put_review, review_controller::delete_review,
+
+        search_controller::get_search,
+
+        shelf_controller::get_my_shelf, shelf_controller::get_user_shelf, shelf_controller::post_shelf_book,
+        shelf_controller::put_shelf_status, shelf_controller::delete_shelf_book,
+
         source_controller::get_sources, source_controller::post_source,
         source_controller::get_source, source_controller::put_source, source_controller::delete_source,
+
+        user_controller::post_user, user_controller::get_me, user_controller::put_me,
+        user_controller::put_me_password, user_controller::put_me_preference, user_controller::delete_me,
+
+        work_controller::get_works, work_controller::post_work,
+        work_controller::get_work, work_controller::put_work, work_controller::delete_work,
+        work_controller::put_work_edition, work_controller::delete_work_edition,
     ),
     components(
         schemas(
+            auth_dto::LoginRequest, auth_dto::TokenResponse,
+            author_dto::AuthorResponse, author_dto::AuthorBookResponse,
+            author_dto::AuthorCreateRequest, author_dto::AuthorUpdateRequest,
+            book_dto::BookResponse, book_dto::BookAuthorResponse, book_dto::BookMediaResponse, book_dto::BookRatingResponse,
+            book_dto::BookCreateRequest, book_dto::BookUpdateRequest, book_dto::BookMediaRequest,
+            book_dto::BookCatalogResponse, book_dto::BookFacetsResponse, book_dto::FacetCountResponse,
+            dedup_dto::DuplicateCandidateResponse, dedup_dto::DuplicateRecordResponse, dedup_model::DuplicateReason,
+            dedup_dto::MergeRequest, dedup_dto::MergeReportResponse,
+            external_id_dto::ExternalIdResponse, external_id_dto::ExternalIdAttachRequest,
+            export_model::ExportDataset, export_model::ExportFormat,
+            follow_dto::FollowResponse, follow_dto::FeedItemResponse, follow_model::FeedItemKind,
             genre_dto::GenreResponse, genre_dto::GenreCreateRequest, genre_dto::GenreUpdateRequest,
+            import_dto::ImportRequest, import_dto::ImportJobResponse, import_dto::ImportReportResponse,
+            import_dto::ImportRowErrorResponse, import_model::ImportKind, import_model::ImportFormat,
+            import_model::ImportJobStatus, external_id_model::ExternalProvider,
             language_dto::LanguageResponse, language_dto::LanguageCreateRequest, language_dto::LanguageUpdateRequest,
             publisher_dto::PublisherResponse, publisher_dto::PublisherCreateRequest, publisher_dto::PublisherUpdateRequest,
+            recommendation_dto::RecommendationResponse,
+            review_dto::ReviewResponse, review_dto::ReviewUserResponse,
+            review_dto::ReviewCreateRequest, review_dto::ReviewUpdateRequest,
+            search_dto::SearchResponse, search_dto::BookSearchHitResponse, search_dto::AuthorSearchHitResponse,
+            search_dto::GenreSearchHitResponse, search_dto::PublisherSearchHitResponse,
+            shelf_dto::ShelfEntryResponse, shelf_dto::ShelfAddRequest, shelf_dto::ShelfStatusUpdateRequest,
+            book_model::BookReadStatus,
+            similarity_dto::SimilarBookResponse, similarity_dto::SimilarAuthorResponse,
             source_dto::SourceResponse, source_dto::SourceCreateRequest, source_dto::SourceUpdateRequest,
+            user_dto::UserResponse, user_dto::UserPreferenceResponse, user_dto::UserCreateRequest,
+            user_dto::UserUpdateRequest, user_dto::UserPasswordUpdateRequest, user_dto::UserPreferenceUpdateRequest,
+            work_dto::WorkResponse, work_dto::WorkDetailResponse, work_dto::WorkEditionResponse, work_dto::WorkAvailabilityResponse,
+            work_dto::WorkCreateRequest, work_dto::WorkUpdateRequest,
+            ValidationErrorResponse,
         )
     )
 )]
 pub struct ApiDoc;
+
+
+/// Registers the `bearer_auth` scheme referenced by protected operations.
+pub struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build()
+            ),
+        );
+    }
+}
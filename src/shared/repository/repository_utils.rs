@@ -1,16 +1,163 @@
-use anyhow::{Result};
-use futures::StreamExt;
-use neo4rs::{Query, Txn};
+use anyhow::{anyhow, Result};
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document, Regex};
+use mongodb::{ClientSession, Collection};
+use serde::Serialize;
 
+use crate::model::external_id_model::ExternalProvider;
+use crate::shared::error::AppError;
+use crate::shared::models::response::PaginationRequest;
 
 
-pub async fn neo4j_count(tx: &mut Txn, q: Query) -> Result<i64> {
-    let mut stream = tx.execute(q).await?;
-    if let Some(row) = stream.next(tx).await? {
-        let n: i64 = row.get("n")?;
-        Ok(n)
-    } else {
-        Ok(0)
+/// Inserts `documents` inside the caller's transaction and returns their ids, in order.
+pub async fn insert_many_in_session<T>(collection: &Collection<T>, session: &mut ClientSession, documents: &[T]) -> Result<Vec<ObjectId>, AppError>
+where
+    T: Serialize + Send + Sync,
+{
+    if documents.is_empty() {
+        return Ok(vec![]);
     }
+    let result_insert = collection.insert_many(documents).session(session).await?;
+    (0..documents.len())
+        .map(|i| {
+            result_insert.inserted_ids
+                .get(&i)
+                .and_then(Bson::as_object_id)
+                .ok_or_else(|| AppError::Upstream(anyhow!("Inserted id is not an ObjectId")))
+        })
+        .collect()
 }
 
+/// Update pipeline setting one provider's id in the document's `external_id`, or clearing it
+/// with `None`. The `external_id` may be missing or null, which `$set` on the dotted path rejects.
+pub fn external_id_update(provider: ExternalProvider, external_id: Option<&str>) -> Vec<Document> {
+    let value = match external_id {
+        Some(external_id) => Bson::Document(doc! { "$literal": external_id }),
+        None => Bson::Null,
+    };
+    vec![doc! { "$set": {
+        "external_id": { "$mergeObjects": [{ "$ifNull": ["$external_id", {}] }, { provider.field(): value }] },
+    } }]
+}
+
+/// Escapes the regex metacharacters of `text`, so it matches literally.
+pub fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Adds a case-insensitive `$or` match of the request's search text over `fields` to `filter`.
+/// The text is escaped, so it always matches literally.
+pub fn with_search(mut filter: Document, pagination: &PaginationRequest, fields: &[&str]) -> Document {
+    if let Some(search) = pagination.search() {
+        let pattern = Regex {
+            pattern: escape_regex(search),
+            options: "i".to_string(),
+        };
+        let any_field: Vec<Bson> = fields
+            .iter()
+            .map(|field| Bson::Document(doc! { *field: pattern.clone() }))
+            .collect();
+        filter.insert("$or", any_field);
+    }
+    filter
+}
+
+/// Builds the sort document of a list request. `sort` names one of `allowed`, prefixed with `-`
+/// for descending order; `_id` is always appended so pages stay stable between requests.
+pub fn sort_document(pagination: &PaginationRequest, allowed: &[&str], default: Document) -> Result<Document, AppError> {
+    let mut sort = match pagination.sort() {
+        Some(sort) => {
+            let (field, direction) = match sort.strip_prefix('-') {
+                Some(field) => (field, -1),
+                None => (sort, 1),
+            };
+            if !allowed.contains(&field) {
+                return Err(AppError::Validation(format!(
+                    "Cannot sort on {}, expected one of: {}", field, allowed.join(", ")
+                )));
+            }
+            doc! { field: direction }
+        },
+        None => default,
+    };
+    if !sort.contains_key("_id") {
+        sort.insert("_id", 1);
+    }
+    Ok(sort)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted_by(sort: &str) -> PaginationRequest {
+        PaginationRequest { sort: Some(sort.to_string()), ..Default::default() }
+    }
+
+    #[test]
+    fn regex_metacharacters_are_escaped() {
+        assert_eq!(escape_regex("C++ (3rd ed.)"), r"C\+\+ \(3rd ed\.\)");
+        assert_eq!(escape_regex("plain"), "plain");
+    }
+
+    #[test]
+    fn search_matches_any_field_case_insensitively() {
+        let pagination = PaginationRequest { search: Some(" a.b ".into()), ..Default::default() };
+
+        let filter = with_search(doc! { "type": "genre" }, &pagination, &["name", "slug"]);
+
+        let pattern = Regex { pattern: r"a\.b".to_string(), options: "i".to_string() };
+        assert_eq!(filter, doc! {
+            "type": "genre",
+            "$or": [{ "name": pattern.clone() }, { "slug": pattern }],
+        });
+    }
+
+    #[test]
+    fn no_search_leaves_the_filter_alone() {
+        let filter = with_search(doc! { "type": "genre" }, &PaginationRequest::default(), &["name"]);
+
+        assert_eq!(filter, doc! { "type": "genre" });
+    }
+
+    #[test]
+    fn sort_defaults_and_always_ends_on_id() {
+        let sort = sort_document(&PaginationRequest::default(), &["name"], doc! { "name": 1 }).unwrap();
+
+        assert_eq!(sort, doc! { "name": 1, "_id": 1 });
+    }
+
+    #[test]
+    fn dash_prefix_sorts_descending() {
+        let sort = sort_document(&sorted_by("-created_at"), &["name", "created_at"], doc! { "name": 1 }).unwrap();
+
+        assert_eq!(sort, doc! { "created_at": -1, "_id": 1 });
+    }
+
+    #[test]
+    fn unknown_sort_fields_are_rejected() {
+        let error = sort_document(&sorted_by("password"), &["name"], doc! { "name": 1 }).unwrap_err();
+
+        assert!(matches!(error, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn external_id_update_merges_a_literal_id_and_clears_with_null() {
+        let attach = external_id_update(ExternalProvider::GoodReads, Some("$1234"));
+        let detach = external_id_update(ExternalProvider::Kaggle, None);
+
+        assert_eq!(attach, vec![doc! { "$set": {
+            "external_id": { "$mergeObjects": [{ "$ifNull": ["$external_id", {}] }, { "good_reads": { "$literal": "$1234" } }] },
+        } }]);
+        assert_eq!(detach, vec![doc! { "$set": {
+            "external_id": { "$mergeObjects": [{ "$ifNull": ["$external_id", {}] }, { "kaggle": Bson::Null }] },
+        } }]);
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::shared::error::AppError;
+use crate::shared::logging::log;
+use crate::shared::security::jwt::Claims;
+use crate::shared::state::AppState;
+
+
+/// Validates the `Authorization: Bearer <token>` header when present and stores the
+/// token `Claims` in the request extensions. Requests without the header go through
+/// anonymously; handlers that need a user ask for it with the `AuthUser` extractor.
+pub async fn jwt_auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let header = match request.headers().get(AUTHORIZATION) {
+        Some(header) => header,
+        None => return Ok(next.run(request).await),
+    };
+
+    let token = header
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or_else(|| AppError::Unauthorized("Malformed authorization header".to_string()))?;
+
+    match state.jwt_keys.verify(token) {
+        Ok(claims) => {
+            request.extensions_mut().insert(claims);
+            Ok(next.run(request).await)
+        },
+        Err(e) => {
+            log::warning(&format!("[AUTH] Invalid token: {}", e));
+            Err(AppError::Unauthorized("Invalid or expired token".to_string()))
+        }
+    }
+}
+
+
+/// Extractor for the authenticated user, rejects with 401 when the request carries no valid token.
+#[derive(Debug, Clone)]
+pub struct AuthUser(pub Claims);
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Claims>()
+            .cloned()
+            .map(AuthUser)
+            .ok_or_else(|| AppError::Unauthorized("Authentication required".to_string()))
+    }
+}
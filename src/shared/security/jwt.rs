@@ -0,0 +1,196 @@
+use std::fs;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::model::user_model::{User, UserRole};
+use crate::shared::configuration::AppConfigJWT;
+
+
+/// Claims carried by every access token issued by the API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String, // user id (hex ObjectId)
+    pub username: String,
+    pub role: UserRole,
+    pub iss: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+
+/// RS256 keys and validation rules built once from `AppConfigJWT`.
+#[derive(Clone)]
+pub struct JwtKeys {
+    encoding_key: Option<Arc<EncodingKey>>,
+    decoding_key: Arc<DecodingKey>,
+    issuer: String,
+    audience: String,
+    expires_in_minutes: i64,
+    kid: Option<String>,
+}
+
+impl JwtKeys {
+    pub fn from_config(jwt_config: &AppConfigJWT) -> Result<Self> {
+        let encoding_key = match &jwt_config.private_secret_pem_path {
+            Some(path) => {
+                let pem = fs::read(path)
+                    .map_err(|e| anyhow!("Unable to read JWT private key {}: {}", path, e))?;
+                Some(Arc::new(EncodingKey::from_rsa_pem(&pem)?))
+            },
+            None => None,
+        };
+
+        let public_pem = fs::read(&jwt_config.public_secret_pem_path)
+            .map_err(|e| anyhow!("Unable to read JWT public key {}: {}", jwt_config.public_secret_pem_path, e))?;
+        let decoding_key = Arc::new(DecodingKey::from_rsa_pem(&public_pem)?);
+
+        Ok(Self {
+            encoding_key,
+            decoding_key,
+            issuer: jwt_config.issuer.clone(),
+            audience: jwt_config.audience.clone(),
+            expires_in_minutes: jwt_config.expires_in_minutes,
+            kid: jwt_config.kid.clone(),
+        })
+    }
+
+    pub fn expires_in_seconds(&self) -> i64 {
+        self.expires_in_minutes * 60
+    }
+
+    /// Builds the claims for `user`, valid from now for the configured duration.
+    pub fn claims_for(&self, user: &User) -> Result<Claims> {
+        let user_id = user.id.as_ref().ok_or_else(|| anyhow!("User has no id"))?;
+        let now = Utc::now().timestamp();
+
+        Ok(Claims {
+            sub: user_id.to_hex(),
+            username: user.username.clone(),
+            role: user.role.clone(),
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: now,
+            exp: now + self.expires_in_seconds(),
+        })
+    }
+
+    pub fn sign(&self, claims: &Claims) -> Result<String> {
+        let encoding_key = self.encoding_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("JWT private key is not configured, tokens cannot be issued"))?;
+
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = self.kid.clone();
+
+        Ok(encode(&header, claims, encoding_key)?)
+    }
+
+    /// Checks signature, `kid`, `iss`, `aud` and `exp` and returns the token claims.
+    pub fn verify(&self, token: &str) -> Result<Claims> {
+        let header = decode_header(token)?;
+        if header.alg != Algorithm::RS256 {
+            return Err(anyhow!("Unexpected JWT algorithm: {:?}", header.alg));
+        }
+        if let Some(kid) = &self.kid
+            && header.kid.as_deref() != Some(kid.as_str())
+        {
+            return Err(anyhow!("Unknown JWT key id: {:?}", header.kid));
+        }
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[self.issuer.as_str()]);
+        validation.set_audience(&[self.audience.as_str()]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let data = decode::<Claims>(token, &self.decoding_key, &validation)?;
+        Ok(data.claims)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_PEM: &[u8] = include_bytes!("testdata/jwt_test_private.pem");
+    const PUBLIC_PEM: &[u8] = include_bytes!("testdata/jwt_test_public.pem");
+
+    fn keys(kid: Option<&str>) -> JwtKeys {
+        JwtKeys {
+            encoding_key: Some(Arc::new(EncodingKey::from_rsa_pem(PRIVATE_PEM).unwrap())),
+            decoding_key: Arc::new(DecodingKey::from_rsa_pem(PUBLIC_PEM).unwrap()),
+            issuer: "booknet".to_string(),
+            audience: "all-service".to_string(),
+            expires_in_minutes: 15,
+            kid: kid.map(str::to_string),
+        }
+    }
+
+    fn claims(keys: &JwtKeys) -> Claims {
+        let now = Utc::now().timestamp();
+        Claims {
+            sub: "65f0c0ffee0000000000beef".to_string(),
+            username: "reader".to_string(),
+            role: UserRole::Reader,
+            iss: keys.issuer.clone(),
+            aud: keys.audience.clone(),
+            iat: now,
+            exp: now + keys.expires_in_seconds(),
+        }
+    }
+
+    #[test]
+    fn signed_token_verifies_to_the_same_claims() {
+        let keys = keys(Some("k1"));
+        let token = keys.sign(&claims(&keys)).unwrap();
+
+        let verified = keys.verify(&token).unwrap();
+        assert_eq!(verified.sub, "65f0c0ffee0000000000beef");
+        assert_eq!(verified.username, "reader");
+        assert!(matches!(verified.role, UserRole::Reader));
+    }
+
+    #[test]
+    fn rejects_a_token_for_another_audience_or_issuer() {
+        let keys = keys(None);
+
+        let mut other_audience = claims(&keys);
+        other_audience.aud = "other-service".to_string();
+        assert!(keys.verify(&keys.sign(&other_audience).unwrap()).is_err());
+
+        let mut other_issuer = claims(&keys);
+        other_issuer.iss = "someone-else".to_string();
+        assert!(keys.verify(&keys.sign(&other_issuer).unwrap()).is_err());
+    }
+
+    #[test]
+    fn rejects_an_expired_token() {
+        let keys = keys(None);
+        let mut expired = claims(&keys);
+        expired.iat -= 7200;
+        expired.exp = expired.iat + 60;
+
+        assert!(keys.verify(&keys.sign(&expired).unwrap()).is_err());
+    }
+
+    #[test]
+    fn rejects_a_token_signed_under_another_key_id() {
+        let signer = keys(Some("old"));
+        let token = signer.sign(&claims(&signer)).unwrap();
+
+        assert!(keys(Some("new")).verify(&token).is_err());
+    }
+
+    #[test]
+    fn cannot_sign_without_a_private_key() {
+        let mut keys = keys(None);
+        keys.encoding_key = None;
+
+        assert!(keys.sign(&claims(&keys)).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod jwt;
+pub mod auth_middleware;
+pub mod password;
+pub mod role_guard;
\ No newline at end of file
@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Result};
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+
+use crate::shared::configuration::AppConfigPasswordHash;
+
+
+/// Stored instead of a hash for accounts nobody can log into, such as readers brought in by an import.
+/// Not a PHC string, and rejected by `verify` whatever the password.
+pub const UNUSABLE_PASSWORD: &str = "!";
+
+
+/// Outcome of checking a password against the stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordCheck {
+    Invalid,
+    /// Password matches; `needs_rehash` is set when the stored value is a legacy
+    /// plain-text password or an Argon2 hash weaker than the current configuration.
+    Valid { needs_rehash: bool },
+}
+
+
+/// Argon2id hasher configured from `AppConfigPasswordHash`.
+#[derive(Clone)]
+pub struct PasswordHasher {
+    params: Params,
+    /// Hash of no real password, verified when the user does not exist so the
+    /// response time does not reveal which usernames are taken.
+    dummy_hash: String,
+}
+
+impl PasswordHasher {
+    pub fn from_config(config: &AppConfigPasswordHash) -> Result<Self> {
+        let params = Params::new(config.memory_kib, config.iterations, config.parallelism, None)
+            .map_err(|e| anyhow!("Invalid Argon2 parameters: {}", e))?;
+        let mut hasher = Self { params, dummy_hash: String::new() };
+        hasher.dummy_hash = hasher.hash("booknet-dummy-password")?;
+        Ok(hasher)
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    /// Hashes `password` into a PHC string (`$argon2id$v=19$m=...,t=...,p=...$salt$hash`).
+    pub fn hash(&self, password: &str) -> Result<String> {
+        let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+            .map_err(|e| anyhow!("Unable to generate salt: {}", e))?;
+
+        let hash = self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| anyhow!("Unable to hash password: {}", e))?;
+
+        Ok(hash.to_string())
+    }
+
+    pub fn verify(&self, password: &str, stored: &str) -> PasswordCheck {
+        if stored == UNUSABLE_PASSWORD {
+            return PasswordCheck::Invalid;
+        }
+
+        let parsed = match PasswordHash::new(stored) {
+            Ok(parsed) => parsed,
+            // Not a PHC string: account created before hashing was introduced
+            Err(_) => {
+                return if constant_time_eq(password.as_bytes(), stored.as_bytes()) {
+                    PasswordCheck::Valid { needs_rehash: true }
+                } else {
+                    PasswordCheck::Invalid
+                };
+            }
+        };
+
+        // Verify with the algorithm and parameters encoded in the hash itself
+        let verified = Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok();
+
+        if !verified {
+            return PasswordCheck::Invalid;
+        }
+
+        PasswordCheck::Valid { needs_rehash: self.is_weaker(&parsed) }
+    }
+
+    /// Spends the same time as `verify` against a stored hash, without matching any password.
+    pub fn verify_dummy(&self, password: &str) {
+        let _ = self.verify(password, &self.dummy_hash);
+    }
+
+    fn is_weaker(&self, parsed: &PasswordHash) -> bool {
+        if parsed.algorithm != Algorithm::Argon2id.ident() {
+            return true;
+        }
+        if parsed.version != Some(Version::V0x13.into()) {
+            return true;
+        }
+
+        match Params::try_from(parsed) {
+            Ok(params) => {
+                params.m_cost() < self.params.m_cost()
+                    || params.t_cost() < self.params.t_cost()
+                    || params.p_cost() < self.params.p_cost()
+            },
+            Err(_) => true,
+        }
+    }
+}
+
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hasher(memory_kib: u32) -> PasswordHasher {
+        PasswordHasher::from_config(&AppConfigPasswordHash { memory_kib, iterations: 1, parallelism: 1 }).unwrap()
+    }
+
+    #[test]
+    fn hash_is_a_phc_string_verified_only_by_its_password() {
+        let hasher = hasher(1024);
+        let hash = hasher.hash("correct horse").unwrap();
+
+        assert!(hash.starts_with("$argon2id$v=19$"));
+        assert_eq!(hasher.verify("correct horse", &hash), PasswordCheck::Valid { needs_rehash: false });
+        assert_eq!(hasher.verify("wrong horse", &hash), PasswordCheck::Invalid);
+    }
+
+    #[test]
+    fn hash_weaker_than_the_configuration_needs_a_rehash() {
+        let hash = hasher(1024).hash("correct horse").unwrap();
+
+        assert_eq!(hasher(2048).verify("correct horse", &hash), PasswordCheck::Valid { needs_rehash: true });
+    }
+
+    #[test]
+    fn legacy_plain_text_password_is_accepted_once_then_rehashed() {
+        let hasher = hasher(1024);
+
+        assert_eq!(hasher.verify("secret", "secret"), PasswordCheck::Valid { needs_rehash: true });
+        assert_eq!(hasher.verify("Secret", "secret"), PasswordCheck::Invalid);
+    }
+
+    #[test]
+    fn dummy_hash_costs_a_configured_verification_and_matches_nothing() {
+        let hasher = hasher(1024);
+
+        assert!(hasher.dummy_hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert_eq!(hasher.verify("", &hasher.dummy_hash), PasswordCheck::Invalid);
+    }
+
+    #[test]
+    fn unusable_password_never_verifies() {
+        let hasher = hasher(1024);
+
+        assert_eq!(hasher.verify(UNUSABLE_PASSWORD, UNUSABLE_PASSWORD), PasswordCheck::Invalid);
+        assert_eq!(hasher.verify("", UNUSABLE_PASSWORD), PasswordCheck::Invalid);
+    }
+}
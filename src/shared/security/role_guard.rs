@@ -0,0 +1,105 @@
+use std::marker::PhantomData;
+
+use axum::{
+    extract::FromRequestParts,
+    http::request::Parts,
+};
+
+use crate::model::user_model::UserRole;
+use crate::shared::error::AppError;
+use crate::shared::security::auth_middleware::AuthUser;
+
+
+/// A role a route can require through `RequireRole<R>`.
+pub trait RoleRequirement {
+    fn allows(role: &UserRole) -> bool;
+}
+
+/// Only administrators.
+pub struct Admin;
+
+impl RoleRequirement for Admin {
+    fn allows(role: &UserRole) -> bool {
+        role.is_admin()
+    }
+}
+
+
+/// Extractor guarding a handler by role: 401 without a valid token, 403 when the
+/// token's role does not satisfy `R`.
+///
+/// ```ignore
+/// pub async fn post_genre(_: RequireRole<Admin>, ...) -> ... { }
+/// ```
+/// Handlers needing the caller's claims as well also take an `AuthUser`.
+pub struct RequireRole<R: RoleRequirement>(PhantomData<R>);
+
+impl<S, R> FromRequestParts<S> for RequireRole<R>
+where
+    S: Send + Sync,
+    R: RoleRequirement,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(claims) = AuthUser::from_request_parts(parts, state).await?;
+
+        if R::allows(&claims.role) {
+            Ok(RequireRole(PhantomData))
+        } else {
+            Err(AppError::Forbidden("Insufficient role".to_string()))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Request;
+
+    use super::*;
+    use crate::shared::security::jwt::Claims;
+
+    fn parts(role: Option<UserRole>) -> Parts {
+        let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+        if let Some(role) = role {
+            parts.extensions.insert(Claims {
+                sub: "65f0c0ffee0000000000beef".to_string(),
+                username: "someone".to_string(),
+                role,
+                iss: "booknet".to_string(),
+                aud: "all-service".to_string(),
+                iat: 0,
+                exp: 0,
+            });
+        }
+        parts
+    }
+
+    #[test]
+    fn admin_requirement_only_allows_admins() {
+        assert!(Admin::allows(&UserRole::Admin));
+        assert!(!Admin::allows(&UserRole::Reader));
+    }
+
+    #[tokio::test]
+    async fn admin_passes_the_guard() {
+        let result = RequireRole::<Admin>::from_request_parts(&mut parts(Some(UserRole::Admin)), &()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn reader_is_forbidden() {
+        let result = RequireRole::<Admin>::from_request_parts(&mut parts(Some(UserRole::Reader)), &()).await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn anonymous_request_is_unauthorized() {
+        let result = RequireRole::<Admin>::from_request_parts(&mut parts(None), &()).await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+}
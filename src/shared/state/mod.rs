@@ -8,6 +8,8 @@ use crate::shared::configuration::AppConfig;
 use crate::shared::database::mongodb as my_mongodb;
 use crate::shared::database::neo4j as my_neo4j;
 use crate::shared::database::redis as my_redis;
+use crate::shared::security::jwt::JwtKeys;
+use crate::shared::security::password::PasswordHasher;
 // use crate::shared::metrics::prometheus::Metrics;
 
 #[derive(Clone)]
@@ -16,6 +18,8 @@ pub struct AppState {
     pub mongo_client: Client,
     pub neo4j_client: Graph,
     pub redis_pool: Pool<RedisConnectionManager>,
+    pub jwt_keys: JwtKeys,
+    pub password_hasher: PasswordHasher,
 
     // pub metrics: Metrics,
 }
@@ -29,6 +33,8 @@ impl AppState {
         let redis_pool = my_redis::connect(&config_clone.database.redis.unwrap()).await?;
         let mongo_client = my_mongodb::connect(&config_clone.database.mongo.unwrap()).await?;
         let neo4j_client = my_neo4j::connect(&config_clone.database.neo4j.unwrap()).await?;
+        let jwt_keys = JwtKeys::from_config(&config_clone.jwt)?;
+        let password_hasher = PasswordHasher::from_config(&config_clone.password_hash)?;
         // let metrics = Metrics::new();
 
         info!("Application state initialized successfully!");
@@ -38,6 +44,8 @@ impl AppState {
             mongo_client,
             neo4j_client,
             redis_pool,
+            jwt_keys,
+            password_hasher,
             // metrics,
         })
     }
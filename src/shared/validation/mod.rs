@@ -0,0 +1,106 @@
+pub mod rules;
+pub mod validated_json;
+
+use std::collections::BTreeMap;
+
+
+/// Field-level errors of a rejected request, keyed by field path (`name`, `languages[1]`).
+#[derive(Debug, Default, Clone)]
+pub struct ValidationErrors(BTreeMap<String, Vec<String>>);
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.entry(field.into()).or_default().push(message.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_fields(self) -> BTreeMap<String, Vec<String>> {
+        self.0
+    }
+}
+
+
+/// Implemented by request bodies taken through `ValidatedJson`.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+
+/// Collects the outcome of the `rules` checked against each field of a request.
+///
+/// ```ignore
+/// Validator::new()
+///     .field("name", rules::length(&self.name, 1, 64))
+///     .field("name", rules::slug(&self.name))
+///     .each("languages", &self.languages, |code| rules::iso639(code))
+///     .finish()
+/// ```
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: ValidationErrors,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, field: &str, check: Result<(), String>) -> Self {
+        if let Err(message) = check {
+            self.errors.add(field, message);
+        }
+        self
+    }
+
+    pub fn each<T>(mut self, field: &str, items: &[T], rule: impl Fn(&T) -> Result<(), String>) -> Self {
+        for (i, item) in items.iter().enumerate() {
+            if let Err(message) = rule(item) {
+                self.errors.add(format!("{}[{}]", field, i), message);
+            }
+        }
+        self
+    }
+
+    pub fn finish(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passing_checks_finish_without_errors() {
+        let result = Validator::new()
+            .field("name", Ok(()))
+            .each("languages", &["en", "fr"], |_| Ok(()))
+            .finish();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn errors_are_collected_per_field_and_item_index() {
+        let errors = Validator::new()
+            .field("name", Err("too short".to_string()))
+            .field("name", Err("not a slug".to_string()))
+            .each("languages", &["en", "xx", "yy"], |code| if *code == "en" { Ok(()) } else { Err(format!("{} is unknown", code)) })
+            .finish()
+            .unwrap_err()
+            .into_fields();
+
+        assert_eq!(errors["name"], vec!["too short", "not a slug"]);
+        assert_eq!(errors["languages[1]"], vec!["xx is unknown"]);
+        assert_eq!(errors["languages[2]"], vec!["yy is unknown"]);
+        assert!(!errors.contains_key("languages[0]"));
+    }
+}
@@ -0,0 +1,269 @@
+//! Field rules used by `Validate` implementations. Each returns the message
+//! reported for the field when the value is rejected.
+
+
+/// ISO 639-1 two-letter language codes.
+const ISO_639_1: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bi",
+    "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de",
+    "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy",
+    "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz", "ia",
+    "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj", "kk",
+    "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln", "lo",
+    "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb", "nd",
+    "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl",
+    "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl",
+    "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti", "tk",
+    "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa",
+    "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+
+/// ISO 639-2 three-letter language codes, bibliographic and terminology forms alike.
+const ISO_639_2: &[&str] = &[
+    "aar", "abk", "ace", "ach", "ada", "ady", "afa", "afh", "afr", "ain", "aka", "akk", "alb", "ale",
+    "alg", "alt", "amh", "ang", "anp", "apa", "ara", "arc", "arg", "arm", "arn", "arp", "art", "arw",
+    "asm", "ast", "ath", "aus", "ava", "ave", "awa", "aym", "aze", "bad", "bai", "bak", "bal", "bam",
+    "ban", "baq", "bas", "bat", "bej", "bel", "bem", "ben", "ber", "bho", "bih", "bik", "bin", "bis",
+    "bla", "bnt", "bod", "bos", "bra", "bre", "btk", "bua", "bug", "bul", "bur", "byn", "cad", "cai",
+    "car", "cat", "cau", "ceb", "cel", "ces", "cha", "chb", "che", "chg", "chi", "chk", "chm", "chn",
+    "cho", "chp", "chr", "chu", "chv", "chy", "cmc", "cnr", "cop", "cor", "cos", "cpe", "cpf", "cpp",
+    "cre", "crh", "crp", "csb", "cus", "cym", "cze", "dak", "dan", "dar", "day", "del", "den", "deu",
+    "dgr", "din", "div", "doi", "dra", "dsb", "dua", "dum", "dut", "dyu", "dzo", "efi", "egy", "eka",
+    "ell", "elx", "eng", "enm", "epo", "est", "eus", "ewe", "ewo", "fan", "fao", "fas", "fat", "fij",
+    "fil", "fin", "fiu", "fon", "fra", "fre", "frm", "fro", "frr", "frs", "fry", "ful", "fur", "gaa",
+    "gay", "gba", "gem", "geo", "ger", "gez", "gil", "gla", "gle", "glg", "glv", "gmh", "goh", "gon",
+    "gor", "got", "grb", "grc", "gre", "grn", "gsw", "guj", "gwi", "hai", "hat", "hau", "haw", "heb",
+    "her", "hil", "him", "hin", "hit", "hmn", "hmo", "hrv", "hsb", "hun", "hup", "hye", "iba", "ibo",
+    "ice", "ido", "iii", "ijo", "iku", "ile", "ilo", "ina", "inc", "ind", "ine", "inh", "ipk", "ira",
+    "iro", "isl", "ita", "jav", "jbo", "jpn", "jpr", "jrb", "kaa", "kab", "kac", "kal", "kam", "kan",
+    "kar", "kas", "kat", "kau", "kaw", "kaz", "kbd", "kha", "khi", "khm", "kho", "kik", "kin", "kir",
+    "kmb", "kok", "kom", "kon", "kor", "kos", "kpe", "krc", "krl", "kro", "kru", "kua", "kum", "kur",
+    "kut", "lad", "lah", "lam", "lao", "lat", "lav", "lez", "lim", "lin", "lit", "lol", "loz", "ltz",
+    "lua", "lub", "lug", "lui", "lun", "luo", "lus", "mac", "mad", "mag", "mah", "mai", "mak", "mal",
+    "man", "mao", "map", "mar", "mas", "may", "mdf", "mdr", "men", "mga", "mic", "min", "mis", "mkd",
+    "mkh", "mlg", "mlt", "mnc", "mni", "mno", "moh", "mon", "mos", "mri", "msa", "mul", "mun", "mus",
+    "mwl", "mwr", "mya", "myn", "myv", "nah", "nai", "nap", "nau", "nav", "nbl", "nde", "ndo", "nds",
+    "nep", "new", "nia", "nic", "niu", "nld", "nno", "nob", "nog", "non", "nor", "nqo", "nso", "nub",
+    "nwc", "nya", "nym", "nyn", "nyo", "nzi", "oci", "oji", "ori", "orm", "osa", "oss", "ota", "oto",
+    "paa", "pag", "pal", "pam", "pan", "pap", "pau", "peo", "per", "phi", "phn", "pli", "pol", "pon",
+    "por", "pra", "pro", "pus", "que", "raj", "rap", "rar", "roa", "roh", "rom", "ron", "rum", "run",
+    "rup", "rus", "sad", "sag", "sah", "sai", "sal", "sam", "san", "sas", "sat", "scn", "sco", "sel",
+    "sem", "sga", "sgn", "shn", "sid", "sin", "sio", "sit", "sla", "slk", "slo", "slv", "sma", "sme",
+    "smi", "smj", "smn", "smo", "sms", "sna", "snd", "snk", "sog", "som", "son", "sot", "spa", "sqi",
+    "srd", "srn", "srp", "srr", "ssa", "ssw", "suk", "sun", "sus", "sux", "swa", "swe", "syc", "syr",
+    "tah", "tai", "tam", "tat", "tel", "tem", "ter", "tet", "tgk", "tgl", "tha", "tib", "tig", "tir",
+    "tiv", "tkl", "tlh", "tli", "tmh", "tog", "ton", "tpi", "tsi", "tsn", "tso", "tuk", "tum", "tup",
+    "tur", "tut", "tvl", "twi", "tyv", "udm", "uga", "uig", "ukr", "umb", "und", "urd", "uzb", "vai",
+    "ven", "vie", "vol", "vot", "wak", "wal", "war", "was", "wel", "wen", "wln", "wol", "xal", "xho",
+    "yao", "yap", "yid", "yor", "ypk", "zap", "zbl", "zen", "zgh", "zha", "zho", "znd", "zul", "zun",
+    "zxx", "zza",
+];
+
+
+/// Between `min` and `max` characters, not counting surrounding whitespace.
+pub fn length(value: &str, min: usize, max: usize) -> Result<(), String> {
+    let len = value.trim().chars().count();
+    if min > 0 && len == 0 {
+        Err("must not be blank".to_string())
+    } else if len < min || len > max {
+        Err(format!("must be between {} and {} characters", min, max))
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs `rule` only when the optional value is present.
+pub fn optional<T: ?Sized>(value: Option<&T>, rule: impl Fn(&T) -> Result<(), String>) -> Result<(), String> {
+    value.map_or(Ok(()), rule)
+}
+
+/// Key safe to embed in ids such as `genre:<name>`: ASCII letters and digits,
+/// optionally separated by single `-`, `_` or `.`, so no `:` and no whitespace.
+pub fn slug(value: &str) -> Result<(), String> {
+    let message = "must contain only letters, digits and single '-', '_' or '.' separators";
+    let mut previous_separator = true;
+    for c in value.chars() {
+        let separator = matches!(c, '-' | '_' | '.');
+        if !(c.is_ascii_alphanumeric() || separator) || (separator && previous_separator) {
+            return Err(message.to_string());
+        }
+        previous_separator = separator;
+    }
+    if value.is_empty() || previous_separator {
+        return Err(message.to_string());
+    }
+    Ok(())
+}
+
+/// Lowercase ISO 639-1 code, or ISO 639-2 code such as `eng` or `fre`.
+pub fn iso639(code: &str) -> Result<(), String> {
+    let valid = match code.len() {
+        2 => ISO_639_1.contains(&code),
+        3 => ISO_639_2.contains(&code),
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{} is not an ISO 639 language code", code))
+    }
+}
+
+/// Absolute `http` or `https` URL with a host and no whitespace.
+pub fn url(value: &str) -> Result<(), String> {
+    let rest = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"));
+    let host = rest.map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+    match host {
+        Some(host) if !host.is_empty() && !value.chars().any(char::is_whitespace) => Ok(()),
+        _ => Err("must be an absolute http(s) URL".to_string()),
+    }
+}
+
+/// 24 character hexadecimal MongoDB ObjectId.
+pub fn object_id(value: &str) -> Result<(), String> {
+    if value.len() == 24 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(format!("{} is not a valid id", value))
+    }
+}
+
+pub fn range<T: PartialOrd + std::fmt::Display>(value: T, min: T, max: T) -> Result<(), String> {
+    if value < min || value > max {
+        Err(format!("must be between {} and {}", min, max))
+    } else {
+        Ok(())
+    }
+}
+
+fn isbn_digits(value: &str) -> Vec<char> {
+    value.chars().filter(|c| *c != '-' && *c != ' ').collect()
+}
+
+/// ISBN-10, hyphens allowed: weighted sum of the digits (`X` = 10 as check digit) divisible by 11.
+pub fn isbn10(value: &str) -> Result<(), String> {
+    let digits = isbn_digits(value);
+    let message = "must be a valid ISBN-10";
+    if digits.len() != 10 {
+        return Err(message.to_string());
+    }
+
+    let mut sum = 0;
+    for (i, c) in digits.iter().enumerate() {
+        let digit = match (c, i) {
+            ('X' | 'x', 9) => 10,
+            _ => c.to_digit(10).ok_or_else(|| message.to_string())?,
+        };
+        sum += digit * (10 - i as u32);
+    }
+
+    if sum % 11 == 0 { Ok(()) } else { Err(message.to_string()) }
+}
+
+/// ISBN-13, hyphens allowed: digits weighted 1 and 3 alternately sum to a multiple of 10.
+pub fn isbn13(value: &str) -> Result<(), String> {
+    let digits = isbn_digits(value);
+    let message = "must be a valid ISBN-13";
+    if digits.len() != 13 {
+        return Err(message.to_string());
+    }
+
+    let mut sum = 0;
+    for (i, c) in digits.iter().enumerate() {
+        let digit = c.to_digit(10).ok_or_else(|| message.to_string())?;
+        sum += if i % 2 == 0 { digit } else { digit * 3 };
+    }
+
+    if sum % 10 == 0 { Ok(()) } else { Err(message.to_string()) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_trims_and_rejects_blank_required_values() {
+        assert!(length(" ab ", 1, 2).is_ok());
+        assert_eq!(length("   ", 1, 10).unwrap_err(), "must not be blank");
+        assert!(length("abc", 1, 2).is_err());
+        assert!(length("", 0, 2).is_ok());
+    }
+
+    #[test]
+    fn optional_only_checks_present_values() {
+        assert!(optional(None, slug).is_ok());
+        assert!(optional(Some("not a slug"), slug).is_err());
+    }
+
+    #[test]
+    fn slug_accepts_single_separators_between_alphanumerics() {
+        for value in ["fantasy", "science-fiction", "sci_fi", "vol.2", "A1"] {
+            assert!(slug(value).is_ok(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn slug_rejects_colons_spaces_and_stray_separators() {
+        for value in ["", "genre:fantasy", "science fiction", "-lead", "trail-", "double--dash", "é"] {
+            assert!(slug(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn iso639_accepts_known_two_and_three_letter_codes_only() {
+        assert!(iso639("en").is_ok());
+        assert!(iso639("fra").is_ok());
+        assert!(iso639("xx").is_err());
+        assert!(iso639("ENG").is_err());
+        assert!(iso639("english").is_err());
+        assert!(iso639("zzz").is_err());
+        assert!(iso639("abc").is_err());
+    }
+
+    #[test]
+    fn url_requires_an_http_scheme_and_a_host() {
+        assert!(url("https://example.com/cover.jpg").is_ok());
+        assert!(url("http://example.com").is_ok());
+        assert!(url("ftp://example.com/cover.jpg").is_err());
+        assert!(url("https:///cover.jpg").is_err());
+        assert!(url("https://example.com/a cover.jpg").is_err());
+    }
+
+    #[test]
+    fn object_id_is_24_hex_characters() {
+        assert!(object_id("64b7f0c2a1b2c3d4e5f60718").is_ok());
+        assert!(object_id("64b7f0c2a1b2c3d4e5f6071").is_err());
+        assert!(object_id("64b7f0c2a1b2c3d4e5f6071z").is_err());
+    }
+
+    #[test]
+    fn range_is_inclusive() {
+        assert!(range(1, 1, 5).is_ok());
+        assert!(range(5, 1, 5).is_ok());
+        assert!(range(0, 1, 5).is_err());
+        assert!(range(5.5, 1.0, 5.0).is_err());
+    }
+
+    #[test]
+    fn isbn10_checks_the_weighted_sum() {
+        assert!(isbn10("0306406152").is_ok());
+        assert!(isbn10("0-306-40615-2").is_ok());
+        assert!(isbn10("080442957X").is_ok());
+        assert!(isbn10("0306406153").is_err());
+        assert!(isbn10("X306406152").is_err());
+        assert!(isbn10("030640615").is_err());
+    }
+
+    #[test]
+    fn isbn13_checks_the_alternating_weights() {
+        assert!(isbn13("9780306406157").is_ok());
+        assert!(isbn13("978-0-306-40615-7").is_ok());
+        assert!(isbn13("9780306406158").is_err());
+        assert!(isbn13("978030640615X").is_err());
+        assert!(isbn13("0306406152").is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use axum::{
+    extract::{FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+
+use crate::shared::error::AppError;
+use crate::shared::validation::Validate;
+
+
+/// `Json` extractor that also runs the body's `Validate` rules.
+/// Malformed JSON is rejected with 400, rule violations with 422 and the offending fields.
+pub struct ValidatedJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ValidatedJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state)
+            .await
+            .map_err(|e| AppError::Validation(e.body_text()))?;
+
+        value.validate().map_err(AppError::InvalidFields)?;
+        Ok(ValidatedJson(value))
+    }
+}